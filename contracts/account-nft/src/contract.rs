@@ -1,17 +1,18 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult,
 };
 use cw2::set_contract_version;
-use cw721_base::Cw721Contract;
-use mars_types::account_nft::{ExecuteMsg, InstantiateMsg, NftConfig, QueryMsg};
+use cw721_base::{Cw721Contract, ExecuteMsg as ParentExecuteMsg};
+use mars_types::account_nft::{ExecuteMsg, InstantiateMsg, NftConfig, QueryMsg, TransferPolicy};
 
 use crate::{
     error::ContractError,
-    execute::{burn, mint, update_config},
+    execute::{burn, mint, transfer, update_config},
     migrations,
-    query::{query_config, query_next_id},
+    query::{query_account_summary, query_config, query_next_id},
     state::{CONFIG, NEXT_ID},
 };
 
@@ -45,6 +46,7 @@ pub fn instantiate(
             max_value_for_burn: msg.max_value_for_burn,
             health_contract_addr,
             credit_manager_contract_addr,
+            transfer_policy: TransferPolicy::default(),
         },
     )?;
 
@@ -68,6 +70,34 @@ pub fn execute(
         ExecuteMsg::Burn {
             token_id,
         } => burn(deps, env, info, token_id),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => transfer(
+            deps,
+            env,
+            info,
+            &token_id.clone(),
+            ParentExecuteMsg::TransferNft {
+                recipient,
+                token_id,
+            },
+        ),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => transfer(
+            deps,
+            env,
+            info,
+            &token_id.clone(),
+            ParentExecuteMsg::SendNft {
+                contract,
+                token_id,
+                msg,
+            },
+        ),
         _ => Parent::default().execute(deps, env, info, msg.try_into()?).map_err(Into::into),
     }
 }
@@ -77,6 +107,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::NextId {} => to_json_binary(&query_next_id(deps)?),
+        QueryMsg::AccountSummary {
+            token_id,
+        } => to_json_binary(
+            &query_account_summary(deps, env, token_id)
+                .map_err(|e| StdError::generic_err(e.to_string()))?,
+        ),
         _ => Parent::default().query(deps, env, msg.try_into()?),
     }
 }
@@ -18,10 +18,19 @@ pub enum ContractError {
         reason: String,
     },
 
-    #[error("Health contract should be added to config before burns are allowed")]
+    #[error("{reason:?}")]
+    TransferNotAllowed {
+        reason: String,
+    },
+
+    #[error(
+        "Health contract should be added to config before burns or transfer checks are allowed"
+    )]
     HealthContractNotSet,
 
-    #[error("Credit manager contract should be added to config before burns are allowed")]
+    #[error(
+        "Credit manager contract should be added to config before burns or transfer checks are allowed"
+    )]
     CreditManagerContractNotSet,
 
     #[error("{0}")]
@@ -1,23 +1,16 @@
-use cosmwasm_std::{
-    to_json_binary, DepsMut, Empty, Env, MessageInfo, QueryRequest, Response, WasmQuery,
-};
-use cw721::Cw721Execute;
+use cosmwasm_std::{Deps, DepsMut, Empty, Env, Event, MessageInfo, Response};
+use cw721::{Cw721Execute, Cw721Query};
 use cw721_base::{
     ContractError::Ownership,
+    ExecuteMsg as ParentExecuteMsg,
     OwnershipError::{NoOwner, NotOwner},
 };
-use mars_types::{
-    account_nft::NftConfigUpdates,
-    credit_manager::QueryMsg,
-    health::{AccountKind, HealthValuesResponse, QueryMsg::HealthValues},
-    oracle::ActionKind,
-};
+use mars_types::account_nft::{NftConfig, NftConfigUpdates};
 
 use crate::{
     contract::Parent,
-    error::ContractError::{
-        self, BaseError, BurnNotAllowed, CreditManagerContractNotSet, HealthContractNotSet,
-    },
+    error::ContractError::{self, BaseError, BurnNotAllowed, TransferNotAllowed},
+    query::{query_health_values, query_positions, query_transfer_blocked_reason},
     state::{CONFIG, NEXT_ID},
 };
 
@@ -39,29 +32,7 @@ pub fn burn(
     token_id: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let Some(health_contract_addr) = config.health_contract_addr else {
-        return Err(HealthContractNotSet);
-    };
-    let Some(cm_contract_addr) = config.credit_manager_contract_addr else {
-        return Err(CreditManagerContractNotSet);
-    };
-
-    let acc_kind: AccountKind = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: cm_contract_addr.into(),
-        msg: to_json_binary(&QueryMsg::AccountKind {
-            account_id: token_id.clone(),
-        })?,
-    }))?;
-
-    let response: HealthValuesResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: health_contract_addr.into(),
-            msg: to_json_binary(&HealthValues {
-                account_id: token_id.clone(),
-                kind: acc_kind,
-                action: ActionKind::Default,
-            })?,
-        }))?;
+    let response = query_health_values(deps.as_ref(), &config, &token_id)?;
 
     if !response.total_debt_value.is_zero() {
         return Err(BurnNotAllowed {
@@ -81,6 +52,62 @@ pub fn burn(
     Parent::default().burn(deps, env, info, token_id).map_err(Into::into)
 }
 
+/// Transfers (`TransferNft`) or sends (`SendNft`) an account once it passes the checks of the
+/// transfer policy
+pub fn transfer(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_id: &str,
+    msg: ParentExecuteMsg<Empty, Empty>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if let Some(reason) = query_transfer_blocked_reason(deps.as_ref(), &env, &config, token_id)? {
+        return Err(TransferNotAllowed {
+            reason,
+        });
+    }
+
+    // Taken before the transfer to record the previous owner
+    let debt_snapshot = if config.transfer_policy.emit_debt_snapshot {
+        Some(debt_snapshot_event(deps.as_ref(), &env, &config, token_id)?)
+    } else {
+        None
+    };
+
+    let response = Parent::default().execute(deps, env, info, msg)?;
+
+    Ok(match debt_snapshot {
+        Some(event) => response.add_event(event),
+        None => response,
+    })
+}
+
+fn debt_snapshot_event(
+    deps: Deps,
+    env: &Env,
+    config: &NftConfig,
+    token_id: &str,
+) -> Result<Event, ContractError> {
+    let owner = Parent::default().owner_of(deps, env.clone(), token_id.to_string(), false)?.owner;
+    let positions = query_positions(deps, config, token_id)?;
+    let health = query_health_values(deps, config, token_id)?;
+
+    let debts = positions
+        .debts
+        .iter()
+        .map(|debt| format!("{}{}", debt.amount, debt.denom))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(Event::new("credit_account_transfer")
+        .add_attribute("token_id", token_id)
+        .add_attribute("from", owner)
+        .add_attribute("debts", debts)
+        .add_attribute("total_debt_value", health.total_debt_value.to_string()))
+}
+
 pub fn update_config(
     deps: DepsMut,
     info: MessageInfo,
@@ -119,6 +146,13 @@ pub fn update_config(
             .add_attribute("value", max.to_string());
     }
 
+    if let Some(policy) = updates.transfer_policy {
+        response = response
+            .add_attribute("key", "transfer_policy")
+            .add_attribute("value", format!("{policy:?}"));
+        config.transfer_policy = policy;
+    }
+
     CONFIG.save(deps.storage, &config)?;
 
     Ok(response)
@@ -1,7 +1,17 @@
-use cosmwasm_std::{Deps, StdResult};
-use mars_types::account_nft::UncheckedNftConfig;
+use cosmwasm_std::{to_json_binary, Deps, Env, QueryRequest, StdResult, WasmQuery};
+use cw721::Cw721Query;
+use mars_types::{
+    account_nft::{AccountSummaryResponse, NftConfig, UncheckedNftConfig},
+    credit_manager::{Positions, QueryMsg},
+    health::{AccountKind, HealthValuesResponse, QueryMsg::HealthValues},
+    oracle::ActionKind,
+};
 
-use crate::state::{CONFIG, NEXT_ID};
+use crate::{
+    contract::Parent,
+    error::ContractError::{self, CreditManagerContractNotSet, HealthContractNotSet},
+    state::{CONFIG, NEXT_ID},
+};
 
 pub fn query_config(deps: Deps) -> StdResult<UncheckedNftConfig> {
     Ok(CONFIG.load(deps.storage)?.into())
@@ -10,3 +20,117 @@ pub fn query_config(deps: Deps) -> StdResult<UncheckedNftConfig> {
 pub fn query_next_id(deps: Deps) -> StdResult<String> {
     Ok(NEXT_ID.load(deps.storage)?.to_string())
 }
+
+pub fn query_account_summary(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+) -> Result<AccountSummaryResponse, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner = Parent::default().owner_of(deps, env.clone(), token_id.clone(), false)?.owner;
+
+    Ok(AccountSummaryResponse {
+        owner,
+        kind: query_account_kind(deps, &config, &token_id)?,
+        positions: query_positions(deps, &config, &token_id)?,
+        health: query_health_values(deps, &config, &token_id)?,
+        transfer_blocked_reason: query_transfer_blocked_reason(deps, &env, &config, &token_id)?,
+    })
+}
+
+/// Checks the account against the transfer policy of the config, returning why it can't be
+/// transferred (if so)
+pub fn query_transfer_blocked_reason(
+    deps: Deps,
+    env: &Env,
+    config: &NftConfig,
+    token_id: &str,
+) -> Result<Option<String>, ContractError> {
+    let policy = &config.transfer_policy;
+
+    if policy.borrow_cooldown_blocks > 0 {
+        let Some(cm_contract_addr) = &config.credit_manager_contract_addr else {
+            return Err(CreditManagerContractNotSet);
+        };
+        let last_borrow_height: Option<u64> =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: cm_contract_addr.into(),
+                msg: to_json_binary(&QueryMsg::LastBorrowHeight {
+                    account_id: token_id.to_string(),
+                })?,
+            }))?;
+
+        if let Some(height) = last_borrow_height {
+            let unlocked_at = height.saturating_add(policy.borrow_cooldown_blocks);
+            if env.block.height < unlocked_at {
+                return Ok(Some(format!(
+                    "Account borrowed at block {height}. Transfers are blocked until block {unlocked_at}."
+                )));
+            }
+        }
+    }
+
+    if policy.block_liquidatable {
+        let health = query_health_values(deps, config, token_id)?;
+        if health.liquidatable {
+            return Ok(Some("Account is liquidatable.".to_string()));
+        }
+    }
+
+    Ok(None)
+}
+
+pub fn query_health_values(
+    deps: Deps,
+    config: &NftConfig,
+    token_id: &str,
+) -> Result<HealthValuesResponse, ContractError> {
+    let Some(health_contract_addr) = &config.health_contract_addr else {
+        return Err(HealthContractNotSet);
+    };
+
+    let kind = query_account_kind(deps, config, token_id)?;
+
+    Ok(deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: health_contract_addr.into(),
+        msg: to_json_binary(&HealthValues {
+            account_id: token_id.to_string(),
+            kind,
+            action: ActionKind::Default,
+        })?,
+    }))?)
+}
+
+pub fn query_account_kind(
+    deps: Deps,
+    config: &NftConfig,
+    token_id: &str,
+) -> Result<AccountKind, ContractError> {
+    let Some(cm_contract_addr) = &config.credit_manager_contract_addr else {
+        return Err(CreditManagerContractNotSet);
+    };
+
+    Ok(deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: cm_contract_addr.into(),
+        msg: to_json_binary(&QueryMsg::AccountKind {
+            account_id: token_id.to_string(),
+        })?,
+    }))?)
+}
+
+pub fn query_positions(
+    deps: Deps,
+    config: &NftConfig,
+    token_id: &str,
+) -> Result<Positions, ContractError> {
+    let Some(cm_contract_addr) = &config.credit_manager_contract_addr else {
+        return Err(CreditManagerContractNotSet);
+    };
+
+    Ok(deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: cm_contract_addr.into(),
+        msg: to_json_binary(&QueryMsg::Positions {
+            account_id: token_id.to_string(),
+        })?,
+    }))?)
+}
@@ -7,12 +7,16 @@ use cw721_base::{
     Ownership,
 };
 use cw_multi_test::{App, AppResponse, BasicApp, Executor};
-use mars_mock_credit_manager::msg::ExecuteMsg::SetAccountKindResponse;
+use mars_mock_credit_manager::msg::ExecuteMsg::{
+    SetAccountKindResponse, SetLastBorrowHeight, SetPositionsResponse,
+};
 use mars_mock_rover_health::msg::ExecuteMsg::SetHealthResponse;
 use mars_types::{
     account_nft::{
-        ExecuteMsg, ExecuteMsg::UpdateConfig, NftConfigUpdates, QueryMsg, UncheckedNftConfig,
+        AccountSummaryResponse, ExecuteMsg, ExecuteMsg::UpdateConfig, NftConfigUpdates, QueryMsg,
+        UncheckedNftConfig,
     },
+    credit_manager::Positions,
     health::{AccountKind, HealthValuesResponse},
 };
 
@@ -121,6 +125,55 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn set_positions_response(&mut self, sender: &Addr, positions: &Positions) -> AppResponse {
+        let config = self.query_config();
+
+        self.app
+            .execute_contract(
+                sender.clone(),
+                Addr::unchecked(config.credit_manager_contract_addr.unwrap()),
+                &SetPositionsResponse {
+                    account_id: positions.account_id.clone(),
+                    positions: positions.clone(),
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn set_last_borrow_height(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+        height: u64,
+    ) -> AppResponse {
+        let config = self.query_config();
+
+        self.app
+            .execute_contract(
+                sender.clone(),
+                Addr::unchecked(config.credit_manager_contract_addr.unwrap()),
+                &SetLastBorrowHeight {
+                    account_id: account_id.to_string(),
+                    height,
+                },
+                &[],
+            )
+            .unwrap()
+    }
+
+    pub fn query_account_summary(&mut self, token_id: &str) -> AccountSummaryResponse {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.nft_contract.clone(),
+                &QueryMsg::AccountSummary {
+                    token_id: token_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn mint(&mut self, token_owner: &Addr) -> AnyResult<String> {
         let res = self.app.execute_contract(
             self.minter.clone(),
@@ -154,6 +207,23 @@ impl MockEnv {
         )
     }
 
+    pub fn transfer(
+        &mut self,
+        sender: &Addr,
+        recipient: &Addr,
+        token_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.nft_contract.clone(),
+            &ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: token_id.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn propose_new_minter(
         &mut self,
         sender: &Addr,
@@ -153,6 +153,10 @@ impl MockEnvBuilder {
                         zapper: "n/a".to_string(),
                         health_contract: "n/a".to_string(),
                         rewards_collector: None,
+                        perps: None,
+                        keeper_fee_config: None,
+                        account_stats_enabled: None,
+                        pyth: None,
                     },
                 },
                 &[],
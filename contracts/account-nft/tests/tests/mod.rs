@@ -5,4 +5,5 @@ mod test_instantiate;
 mod test_migration_v2;
 mod test_mint;
 mod test_proposed_minter;
+mod test_transfer_policy;
 mod test_update_config;
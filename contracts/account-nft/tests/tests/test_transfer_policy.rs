@@ -0,0 +1,195 @@
+use cosmwasm_std::{coin, Addr, Uint128};
+use mars_account_nft::error::ContractError::{self, TransferNotAllowed};
+use mars_types::{
+    account_nft::{NftConfigUpdates, TransferPolicy},
+    credit_manager::{DebtAmount, Positions},
+    health::{AccountKind, HealthValuesResponse},
+};
+
+use super::helpers::{generate_health_response, MockEnv};
+
+#[test]
+fn transfers_allowed_by_default() {
+    let mut mock = MockEnv::new().build().unwrap();
+
+    let user = Addr::unchecked("user");
+    let buyer = Addr::unchecked("buyer");
+    let token_id = mock.mint(&user).unwrap();
+
+    mock.transfer(&user, &buyer, &token_id).unwrap();
+    mock.assert_owner_is_correct(&buyer, &token_id);
+}
+
+#[test]
+fn liquidatable_account_cannot_be_transferred() {
+    let mut mock = MockEnv::new().build().unwrap();
+    set_transfer_policy(
+        &mut mock,
+        TransferPolicy {
+            block_liquidatable: true,
+            ..Default::default()
+        },
+    );
+
+    let user = Addr::unchecked("user");
+    let buyer = Addr::unchecked("buyer");
+    let token_id = mock.mint(&user).unwrap();
+    mock.set_health_response(&user, &token_id, AccountKind::Default, &liquidatable_response());
+
+    let res = mock.transfer(&user, &buyer, &token_id);
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        TransferNotAllowed {
+            reason: "Account is liquidatable.".to_string(),
+        }
+    );
+
+    mock.set_health_response(
+        &user,
+        &token_id,
+        AccountKind::Default,
+        &generate_health_response(100, 1000),
+    );
+    mock.transfer(&user, &buyer, &token_id).unwrap();
+    mock.assert_owner_is_correct(&buyer, &token_id);
+}
+
+#[test]
+fn account_cannot_be_transferred_right_after_borrow() {
+    let mut mock = MockEnv::new().build().unwrap();
+    set_transfer_policy(
+        &mut mock,
+        TransferPolicy {
+            borrow_cooldown_blocks: 10,
+            ..Default::default()
+        },
+    );
+
+    let user = Addr::unchecked("user");
+    let buyer = Addr::unchecked("buyer");
+    let token_id = mock.mint(&user).unwrap();
+
+    let height = mock.app.block_info().height;
+    mock.set_last_borrow_height(&user, &token_id, height);
+
+    let res = mock.transfer(&user, &buyer, &token_id);
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    assert_eq!(
+        err,
+        TransferNotAllowed {
+            reason: format!(
+                "Account borrowed at block {height}. Transfers are blocked until block {}.",
+                height + 10
+            ),
+        }
+    );
+
+    mock.app.update_block(|block| block.height += 10);
+
+    mock.transfer(&user, &buyer, &token_id).unwrap();
+    mock.assert_owner_is_correct(&buyer, &token_id);
+}
+
+#[test]
+fn transfer_emits_debt_snapshot() {
+    let mut mock = MockEnv::new().build().unwrap();
+    set_transfer_policy(
+        &mut mock,
+        TransferPolicy {
+            emit_debt_snapshot: true,
+            ..Default::default()
+        },
+    );
+
+    let user = Addr::unchecked("user");
+    let buyer = Addr::unchecked("buyer");
+    let token_id = mock.mint(&user).unwrap();
+    mock.set_positions_response(&user, &positions_with_debt(&token_id));
+    mock.set_health_response(
+        &user,
+        &token_id,
+        AccountKind::Default,
+        &generate_health_response(520, 2000),
+    );
+
+    let res = mock.transfer(&user, &buyer, &token_id).unwrap();
+
+    let event = res.events.iter().find(|e| e.ty == "wasm-credit_account_transfer").unwrap();
+    let attr = |key: &str| {
+        event.attributes.iter().find(|a| a.key == key).map(|a| a.value.clone()).unwrap()
+    };
+    assert_eq!(attr("token_id"), token_id);
+    assert_eq!(attr("from"), user.to_string());
+    assert_eq!(attr("debts"), "500uatom,20uosmo");
+    assert_eq!(attr("total_debt_value"), "520");
+}
+
+#[test]
+fn account_summary_shows_positions_and_transferability() {
+    let mut mock = MockEnv::new().build().unwrap();
+    set_transfer_policy(
+        &mut mock,
+        TransferPolicy {
+            block_liquidatable: true,
+            ..Default::default()
+        },
+    );
+
+    let user = Addr::unchecked("user");
+    let token_id = mock.mint(&user).unwrap();
+    let positions = positions_with_debt(&token_id);
+    mock.set_positions_response(&user, &positions);
+    mock.set_health_response(&user, &token_id, AccountKind::Default, &liquidatable_response());
+
+    let summary = mock.query_account_summary(&token_id);
+    assert_eq!(summary.owner, user.to_string());
+    assert_eq!(summary.kind, AccountKind::Default);
+    assert_eq!(summary.positions, positions);
+    assert_eq!(summary.health, liquidatable_response());
+    assert_eq!(summary.transfer_blocked_reason, Some("Account is liquidatable.".to_string()));
+}
+
+fn set_transfer_policy(mock: &mut MockEnv, policy: TransferPolicy) {
+    mock.update_config(
+        &mock.minter.clone(),
+        &NftConfigUpdates {
+            max_value_for_burn: None,
+            health_contract_addr: None,
+            credit_manager_contract_addr: None,
+            transfer_policy: Some(policy),
+        },
+    )
+    .unwrap();
+}
+
+fn liquidatable_response() -> HealthValuesResponse {
+    HealthValuesResponse {
+        liquidatable: true,
+        ..generate_health_response(1000, 1000)
+    }
+}
+
+fn positions_with_debt(token_id: &str) -> Positions {
+    Positions {
+        account_id: token_id.to_string(),
+        account_kind: AccountKind::Default,
+        deposits: vec![coin(2000, "uatom")],
+        debts: vec![
+            DebtAmount {
+                denom: "uatom".to_string(),
+                shares: Uint128::new(500_000_000),
+                amount: Uint128::new(500),
+            },
+            DebtAmount {
+                denom: "uosmo".to_string(),
+                shares: Uint128::new(20_000_000),
+                amount: Uint128::new(20),
+            },
+        ],
+        lends: vec![],
+        vaults: vec![],
+        staked_astro_lps: vec![],
+        perps: vec![],
+    }
+}
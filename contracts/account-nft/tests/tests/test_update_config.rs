@@ -14,6 +14,7 @@ fn only_minter_can_update_config() {
             max_value_for_burn: None,
             health_contract_addr: None,
             credit_manager_contract_addr: None,
+            transfer_policy: None,
         },
     );
 
@@ -34,6 +35,7 @@ fn minter_can_update_config() {
         max_value_for_burn: Some(new_max_burn_val),
         health_contract_addr: Some(new_health_contract.clone()),
         credit_manager_contract_addr: Some(new_cm_contract.clone()),
+        transfer_policy: None,
     };
 
     mock.update_config(&mock.minter.clone(), &updates).unwrap();
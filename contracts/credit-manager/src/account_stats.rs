@@ -0,0 +1,158 @@
+use cosmwasm_std::{Coin, DepsMut, Storage, Uint128};
+use mars_rover_health_computer::add_coin;
+use mars_types::{credit_manager::AccountStats, oracle::ActionKind};
+
+use crate::{
+    error::ContractResult,
+    state::{ACCOUNT_STATS, ACCOUNT_STATS_ENABLED, DEBT_SHARES, ORACLE},
+    utils::debt_shares_to_amount,
+};
+
+pub fn is_account_stats_enabled(storage: &dyn Storage) -> ContractResult<bool> {
+    Ok(ACCOUNT_STATS_ENABLED.may_load(storage)?.unwrap_or_default())
+}
+
+/// Applies the update to the stats of the account, if stats recording is enabled
+fn update_stats(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    update: impl FnOnce(&mut AccountStats) -> ContractResult<()>,
+) -> ContractResult<()> {
+    if !is_account_stats_enabled(storage)? {
+        return Ok(());
+    }
+
+    let mut stats = ACCOUNT_STATS.may_load(storage, account_id)?.unwrap_or_default();
+    update(&mut stats)?;
+    ACCOUNT_STATS.save(storage, account_id, &stats)?;
+
+    Ok(())
+}
+
+pub fn record_deposit(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    coin: &Coin,
+) -> ContractResult<()> {
+    update_stats(storage, account_id, |stats| Ok(add_coin(&mut stats.deposited, coin)?))
+}
+
+pub fn record_withdrawal(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    coin: &Coin,
+) -> ContractResult<()> {
+    update_stats(storage, account_id, |stats| Ok(add_coin(&mut stats.withdrawn, coin)?))
+}
+
+pub fn record_swap_fee(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    coin: &Coin,
+) -> ContractResult<()> {
+    update_stats(storage, account_id, |stats| Ok(add_coin(&mut stats.swap_fees_paid, coin)?))
+}
+
+pub fn record_rewards_claimed(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    rewards: &[Coin],
+) -> ContractResult<()> {
+    update_stats(storage, account_id, |stats| {
+        for reward in rewards {
+            add_coin(&mut stats.rewards_claimed, reward)?;
+        }
+        Ok(())
+    })
+}
+
+/// Records the value of the coin seized from the liquidatee in excess of the repaid debt.
+/// `seized` has to be of a denom priced by the oracle.
+pub fn record_liquidation_loss(
+    deps: &mut DepsMut,
+    account_id: &str,
+    debt: &Coin,
+    seized: &Coin,
+) -> ContractResult<()> {
+    if !is_account_stats_enabled(deps.storage)? {
+        return Ok(());
+    }
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let debt_value = oracle.query_value(&deps.querier, debt, ActionKind::Liquidation)?;
+    let seized_value = oracle.query_value(&deps.querier, seized, ActionKind::Liquidation)?;
+
+    update_stats(deps.storage, account_id, |stats| {
+        stats.liquidation_losses =
+            stats.liquidation_losses.checked_add(seized_value.saturating_sub(debt_value))?;
+        Ok(())
+    })
+}
+
+/// Adds the borrowed coin to the principal. Has to be called before the debt shares of the
+/// borrow are added: the current debt of an account without recorded principal (borrowed before
+/// recording started) becomes part of the principal.
+pub fn record_borrow(deps: &mut DepsMut, account_id: &str, coin: &Coin) -> ContractResult<()> {
+    if !is_account_stats_enabled(deps.storage)? {
+        return Ok(());
+    }
+
+    let mut stats = ACCOUNT_STATS.may_load(deps.storage, account_id)?.unwrap_or_default();
+    match stats.debt_principal.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(principal) => principal.amount = principal.amount.checked_add(coin.amount)?,
+        None => {
+            let prev_debt = match DEBT_SHARES.may_load(deps.storage, (account_id, &coin.denom))? {
+                Some(shares) => debt_shares_to_amount(deps.as_ref(), &coin.denom, shares)?.amount,
+                None => Uint128::zero(),
+            };
+            stats.debt_principal.push(Coin {
+                denom: coin.denom.clone(),
+                amount: prev_debt.checked_add(coin.amount)?,
+            });
+        }
+    }
+    ACCOUNT_STATS.save(deps.storage, account_id, &stats)?;
+
+    Ok(())
+}
+
+/// Splits the repaid coin into principal and interest. `total_debt` is the value of the debt
+/// shares before the repayment; its growth above the recorded principal is the accrued interest,
+/// which is repaid first. Without recorded principal the whole debt is considered principal.
+pub fn record_repay(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    total_debt: Uint128,
+    repaid: &Coin,
+) -> ContractResult<()> {
+    update_stats(storage, account_id, |stats| {
+        let principal =
+            stats.debt_principal.iter().find(|c| c.denom == repaid.denom).map(|c| c.amount);
+        let principal = principal.unwrap_or(total_debt).min(total_debt);
+
+        let accrued_interest = total_debt.checked_sub(principal)?;
+        let interest_repaid = repaid.amount.min(accrued_interest);
+        let principal_repaid = repaid.amount.checked_sub(interest_repaid)?.min(principal);
+        let remaining_principal = principal.checked_sub(principal_repaid)?;
+
+        stats.debt_principal.retain(|c| c.denom != repaid.denom);
+        if !remaining_principal.is_zero() {
+            stats.debt_principal.push(Coin {
+                denom: repaid.denom.clone(),
+                amount: remaining_principal,
+            });
+        }
+
+        if interest_repaid.is_zero() {
+            return Ok(());
+        }
+
+        Ok(add_coin(
+            &mut stats.interest_paid,
+            &Coin {
+                denom: repaid.denom.clone(),
+                amount: interest_repaid,
+            },
+        )?)
+    })
+}
@@ -0,0 +1,280 @@
+use std::{cmp::min, collections::BTreeMap};
+
+use cosmwasm_std::{Coin, Coins, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw_utils::nonpayable;
+use mars_types::{
+    credit_manager::{ActionAmount, ActionCoin, AutoDeleveragePolicy, CallbackMsg},
+    health::{AccountKind, HealthValuesResponse},
+    oracle::ActionKind,
+};
+
+use crate::{
+    deposit::update_or_reset_denom_deposits,
+    error::{ContractError, ContractResult},
+    execute::add_callbacks,
+    health::{query_health_state, query_health_values},
+    repay::repay,
+    state::{
+        AUTO_DELEVERAGE_POLICIES, COIN_BALANCES, DEBT_SHARES, KEEPER_FEE_CONFIG, ORACLE, PARAMS,
+        REENTRANCY_GUARD, SWAP_FEE,
+    },
+    utils::{assert_slippage, debt_shares_to_amount, get_account_kind},
+};
+
+pub fn set_auto_deleverage_policy(
+    deps: DepsMut,
+    account_id: &str,
+    policy: Option<AutoDeleveragePolicy>,
+) -> ContractResult<Response> {
+    let response = Response::new()
+        .add_attribute("action", "set_auto_deleverage_policy")
+        .add_attribute("account_id", account_id);
+
+    let Some(policy) = policy else {
+        AUTO_DELEVERAGE_POLICIES.remove(deps.storage, account_id);
+        return Ok(response);
+    };
+
+    if policy.target_health_factor <= policy.trigger_health_factor {
+        return Err(ContractError::InvalidAutoDeleveragePolicy {
+            reason: "target health factor has to be greater than the trigger health factor"
+                .to_string(),
+        });
+    }
+
+    if policy.collateral_denom == policy.debt_denom {
+        return Err(ContractError::InvalidAutoDeleveragePolicy {
+            reason: "collateral and debt denoms have to be different".to_string(),
+        });
+    }
+
+    assert_slippage(deps.storage, policy.slippage)?;
+
+    AUTO_DELEVERAGE_POLICIES.save(deps.storage, account_id, &policy)?;
+
+    Ok(response
+        .add_attribute("trigger_health_factor", policy.trigger_health_factor.to_string())
+        .add_attribute("target_health_factor", policy.target_health_factor.to_string()))
+}
+
+/// Sells collateral of the account for the debt denom of its policy and repays the debt.
+/// Can be called by anyone once the liquidation health factor dropped below the policy's trigger;
+/// the caller receives a share of the sold collateral as keeper fee. The fee is paid after the
+/// debt is repaid and is covered by the same max LTV check as the swap and repay.
+pub fn execute_auto_deleverage(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+) -> ContractResult<Response> {
+    nonpayable(&info)?;
+
+    let policy = AUTO_DELEVERAGE_POLICIES
+        .may_load(deps.storage, account_id)?
+        .ok_or_else(|| ContractError::AutoDeleveragePolicyNotFound(account_id.to_string()))?;
+
+    let health = query_health_values(deps.as_ref(), account_id, ActionKind::Default)?;
+    let triggered =
+        health.liquidation_health_factor.map_or(false, |hf| hf < policy.trigger_health_factor);
+    if !triggered {
+        return Err(ContractError::AutoDeleverageNotTriggered {
+            account_id: account_id.to_string(),
+            trigger_health_factor: policy.trigger_health_factor,
+        });
+    }
+
+    let fee_rate = KEEPER_FEE_CONFIG
+        .may_load(deps.storage)?
+        .map(|c| c.auto_deleverage_fee_rate)
+        .unwrap_or_default();
+    let swap_fee = SWAP_FEE.load(deps.storage)?;
+
+    // Share of the value of the sold collateral which ends up repaying debt
+    let repaid_share = Decimal::one()
+        .checked_sub(fee_rate)?
+        .checked_mul(Decimal::one().checked_sub(swap_fee)?)?
+        .checked_mul(Decimal::one().checked_sub(policy.slippage)?)?;
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let collateral_price =
+        oracle.query_price(&deps.querier, &policy.collateral_denom, ActionKind::Default)?.price;
+    let debt_price =
+        oracle.query_price(&deps.querier, &policy.debt_denom, ActionKind::Default)?.price;
+
+    let amount = query_collateral_to_sell(
+        deps.as_ref(),
+        account_id,
+        &policy,
+        &health,
+        repaid_share,
+        collateral_price,
+        debt_price,
+    )?;
+
+    let keeper_fee = Coin {
+        denom: policy.collateral_denom.clone(),
+        amount: amount.checked_mul_floor(fee_rate)?,
+    };
+    let coin_in = amount.checked_sub(keeper_fee.amount)?;
+
+    // Output valued at oracle prices, less the swap fee and the slippage allowed by the policy
+    let min_receive = coin_in
+        .checked_mul_floor(Decimal::one().checked_sub(swap_fee)?)?
+        .checked_mul_floor(collateral_price.checked_div(debt_price)?)?
+        .checked_mul_floor(Decimal::one().checked_sub(policy.slippage)?)?;
+
+    REENTRANCY_GUARD.try_lock(deps.storage)?;
+
+    // Taken before the keeper fee, which is checked against it together with the swap and repay
+    let prev_health_state = query_health_state(deps.as_ref(), account_id, ActionKind::Default)?;
+
+    let previous_balance =
+        COIN_BALANCES.may_load(deps.storage, (account_id, &policy.debt_denom))?.unwrap_or_default();
+
+    let mut callbacks = vec![
+        CallbackMsg::SwapExactIn {
+            account_id: account_id.to_string(),
+            coin_in: ActionCoin {
+                denom: policy.collateral_denom,
+                amount: ActionAmount::Exact(coin_in),
+            },
+            denom_out: policy.debt_denom.clone(),
+            min_receive,
+            route: policy.route,
+        },
+        CallbackMsg::RepayReceived {
+            account_id: account_id.to_string(),
+            denom: policy.debt_denom.clone(),
+            previous_balance,
+        },
+    ];
+
+    if !keeper_fee.amount.is_zero() {
+        callbacks.push(CallbackMsg::Withdraw {
+            account_id: account_id.to_string(),
+            coin: ActionCoin::from(&keeper_fee),
+            recipient: info.sender.clone(),
+        });
+    }
+
+    if get_account_kind(deps.storage, account_id)? == AccountKind::HighLeveredStrategy {
+        callbacks.push(CallbackMsg::AssertHlsRules {
+            account_id: account_id.to_string(),
+        });
+    }
+
+    callbacks.push(CallbackMsg::AssertMaxLTV {
+        account_id: account_id.to_string(),
+        prev_health_state,
+    });
+
+    // Only the part of the swap output left after repaying the whole debt stays deposited
+    let mut denoms_for_cap_check = BTreeMap::new();
+    update_or_reset_denom_deposits(
+        deps.as_ref(),
+        &mut denoms_for_cap_check,
+        &policy.debt_denom,
+        &Coins::default(),
+        false,
+    )?;
+    callbacks.push(CallbackMsg::AssertDepositCaps {
+        denoms: denoms_for_cap_check,
+    });
+
+    Ok(add_callbacks(&env, Response::new(), callbacks)?
+        .add_attribute("action", "auto_deleverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("keeper", info.sender.to_string())
+        .add_attribute("collateral_sold", amount.to_string())
+        .add_attribute("keeper_fee", keeper_fee.to_string()))
+}
+
+/// Repays the debt with the amount of `denom` received since `previous_balance`, leaving the
+/// rest of the balance deposited
+pub fn repay_received(
+    deps: DepsMut,
+    account_id: &str,
+    denom: &str,
+    previous_balance: Uint128,
+) -> ContractResult<Response> {
+    let balance = COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or_default();
+    let received = balance.saturating_sub(previous_balance);
+    if received.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    repay(
+        deps,
+        account_id,
+        &ActionCoin {
+            denom: denom.to_string(),
+            amount: ActionAmount::Exact(received),
+        },
+    )
+}
+
+/// Amount of collateral to sell for the liquidation health factor to get back to the target.
+/// Capped by the deposited collateral and by the amount needed to repay the whole debt.
+fn query_collateral_to_sell(
+    deps: Deps,
+    account_id: &str,
+    policy: &AutoDeleveragePolicy,
+    health: &HealthValuesResponse,
+    repaid_share: Decimal,
+    collateral_price: Decimal,
+    debt_price: Decimal,
+) -> ContractResult<Uint128> {
+    let balance = COIN_BALANCES
+        .may_load(deps.storage, (account_id, &policy.collateral_denom))?
+        .unwrap_or_default();
+    if balance.is_zero() {
+        return Err(ContractError::InvalidAutoDeleveragePolicy {
+            reason: format!("account has no {} deposited", policy.collateral_denom),
+        });
+    }
+
+    let debt_shares =
+        DEBT_SHARES.may_load(deps.storage, (account_id, &policy.debt_denom))?.unwrap_or_default();
+    if debt_shares.is_zero() {
+        return Err(ContractError::NoDebt);
+    }
+    let debt = debt_shares_to_amount(deps, &policy.debt_denom, debt_shares)?;
+
+    let params = PARAMS
+        .load(deps.storage)?
+        .query_asset_params(&deps.querier, &policy.collateral_denom)?
+        .ok_or_else(|| ContractError::NotWhitelisted(policy.collateral_denom.clone()))?;
+    let liquidation_threshold = match get_account_kind(deps.storage, account_id)? {
+        AccountKind::HighLeveredStrategy => params
+            .credit_manager
+            .hls
+            .as_ref()
+            .map_or(params.liquidation_threshold, |hls| hls.liquidation_threshold),
+        _ => params.liquidation_threshold,
+    };
+
+    // Selling collateral of value V changes the liquidation health factor to:
+    //      hf = (liquidation threshold adjusted collateral - V * liquidation threshold) / (debt value - V * repaid share)
+    // Re-arranging this to isolate V for hf = target renders:
+    //      V = (target * debt value - liquidation threshold adjusted collateral) / (target * repaid share - liquidation threshold)
+    // If selling collateral can't bring the health factor up to the target, all of it is sold.
+    let target = policy.target_health_factor;
+    let target_repaid_share = target.checked_mul(repaid_share)?;
+    let amount = if target_repaid_share > liquidation_threshold {
+        health
+            .total_debt_value
+            .checked_mul_ceil(target)?
+            .saturating_sub(health.liquidation_threshold_adjusted_collateral)
+            .checked_div_ceil(target_repaid_share.checked_sub(liquidation_threshold)?)?
+            .checked_div_ceil(collateral_price)?
+    } else {
+        balance
+    };
+
+    let max_amount = debt
+        .amount
+        .checked_mul_ceil(debt_price)?
+        .checked_div_ceil(collateral_price.checked_mul(repaid_share)?)?;
+
+    Ok(min(amount, min(max_amount, balance)))
+}
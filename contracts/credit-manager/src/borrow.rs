@@ -1,6 +1,7 @@
-use cosmwasm_std::{Coin, DepsMut, Response, Uint128};
+use cosmwasm_std::{Coin, Deps, DepsMut, Response, Uint128};
 
 use crate::{
+    account_stats::record_borrow,
     error::{ContractError, ContractResult},
     state::{DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
     utils::{assert_coin_is_whitelisted, increment_coin_balance},
@@ -20,22 +21,15 @@ pub fn borrow(mut deps: DepsMut, account_id: &str, coin: Coin) -> ContractResult
 
     assert_coin_is_whitelisted(&mut deps, &coin.denom)?;
 
-    let red_bank = RED_BANK.load(deps.storage)?;
-    let total_debt_amount = red_bank.query_debt(&deps.querier, &coin.denom)?;
-
-    let debt_shares_to_add = if total_debt_amount.is_zero() {
-        coin.amount.checked_mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED)?
-    } else {
-        TOTAL_DEBT_SHARES
-            .load(deps.storage, &coin.denom)?
-            .checked_multiply_ratio(coin.amount, total_debt_amount)?
-    };
+    let debt_shares_to_add = query_debt_shares_to_add(deps.as_ref(), &coin)?;
 
     // It shouldn't happen but just in case
     if debt_shares_to_add.is_zero() {
         return Err(ContractError::ZeroDebtShares);
     }
 
+    record_borrow(&mut deps, account_id, &coin)?;
+
     TOTAL_DEBT_SHARES.update(deps.storage, &coin.denom, |shares| {
         shares
             .unwrap_or_else(Uint128::zero)
@@ -53,9 +47,22 @@ pub fn borrow(mut deps: DepsMut, account_id: &str, coin: Coin) -> ContractResult
     increment_coin_balance(deps.storage, account_id, &coin)?;
 
     Ok(Response::new()
-        .add_message(red_bank.borrow_msg(&coin)?)
+        .add_message(RED_BANK.load(deps.storage)?.borrow_msg(&coin)?)
         .add_attribute("action", "borrow")
         .add_attribute("account_id", account_id)
         .add_attribute("debt_shares_added", debt_shares_to_add)
         .add_attribute("coin_borrowed", coin.to_string()))
 }
+
+pub fn query_debt_shares_to_add(deps: Deps, coin: &Coin) -> ContractResult<Uint128> {
+    let total_debt_amount = RED_BANK.load(deps.storage)?.query_debt(&deps.querier, &coin.denom)?;
+
+    let debt_shares_to_add = if total_debt_amount.is_zero() {
+        coin.amount.checked_mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED)?
+    } else {
+        TOTAL_DEBT_SHARES
+            .load(deps.storage, &coin.denom)?
+            .checked_multiply_ratio(coin.amount, total_debt_amount)?
+    };
+    Ok(debt_shares_to_add)
+}
@@ -2,6 +2,7 @@ use cosmwasm_std::{DepsMut, Response};
 use mars_types::traits::Stringify;
 
 use crate::{
+    account_stats::record_rewards_claimed,
     error::{ContractError, ContractResult},
     state::INCENTIVES,
     utils::increment_coin_balance,
@@ -23,6 +24,7 @@ pub fn claim_lp_rewards(
     for reward in rewards.iter() {
         increment_coin_balance(deps.storage, account_id, reward)?;
     }
+    record_rewards_claimed(deps.storage, account_id, &rewards)?;
 
     let claim_rewards_msg = incentives.claim_staked_astro_lp_rewards_msg(account_id, lp_denom)?;
     let res = Response::new()
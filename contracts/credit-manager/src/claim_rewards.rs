@@ -2,6 +2,7 @@ use cosmwasm_std::{DepsMut, Response};
 use mars_types::traits::Stringify;
 
 use crate::{
+    account_stats::record_rewards_claimed,
     error::{ContractError, ContractResult},
     state::INCENTIVES,
     utils::increment_coin_balance,
@@ -18,6 +19,7 @@ pub fn claim_rewards(deps: DepsMut, account_id: &str) -> ContractResult<Response
     for reward in unclaimed_rewards.iter() {
         increment_coin_balance(deps.storage, account_id, reward)?;
     }
+    record_rewards_claimed(deps.storage, account_id, &unclaimed_rewards)?;
 
     Ok(Response::new()
         .add_message(incentives.claim_rewards_msg(account_id)?)
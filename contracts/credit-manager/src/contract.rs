@@ -8,17 +8,28 @@ use mars_types::{
 };
 
 use crate::{
+    auto_deleverage::execute_auto_deleverage,
+    delegation::{grant_delegation, revoke_delegation},
     error::{ContractError, ContractResult},
-    execute::{create_credit_account, dispatch_actions, execute_callback},
+    estimate_liquidation::estimate_liquidation,
+    execute::{
+        create_credit_account, dispatch_actions, dispatch_actions_for_accounts, execute_callback,
+    },
     instantiate::store_config,
     migrations,
+    perp::update_balance_after_deleverage,
     query::{
-        query_accounts, query_all_coin_balances, query_all_debt_shares,
-        query_all_total_debt_shares, query_all_vault_positions, query_all_vault_utilizations,
-        query_config, query_positions, query_swap_fee, query_total_debt_shares,
-        query_vault_bindings, query_vault_position_value, query_vault_utilization,
+        query_account_delegations, query_account_stats, query_account_trigger_orders,
+        query_accounts, query_all_auto_deleverage_policies, query_all_coin_balances,
+        query_all_debt_shares, query_all_total_debt_shares, query_all_trigger_orders,
+        query_all_vault_positions, query_all_vault_utilizations, query_auto_deleverage_policy,
+        query_config, query_delegation, query_last_borrow_height, query_positions, query_swap_fee,
+        query_total_debt_shares, query_vault_bindings, query_vault_position_value,
+        query_vault_utilization,
     },
     repay::repay_from_wallet,
+    simulate::simulate_actions,
+    trigger::execute_trigger_order,
     update_config::{update_config, update_nft_config, update_owner},
     utils::get_account_kind,
     vault::handle_unlock_request_reply,
@@ -65,9 +76,32 @@ pub fn execute(
             account_kind,
             actions,
         } => dispatch_actions(deps, env, info, account_id, account_kind, actions),
+        ExecuteMsg::UpdateCreditAccounts {
+            updates,
+        } => dispatch_actions_for_accounts(deps, env, info, updates),
         ExecuteMsg::RepayFromWallet {
             account_id,
         } => repay_from_wallet(deps, env, info, account_id),
+        ExecuteMsg::GrantDelegation {
+            account_id,
+            delegate,
+            delegation,
+        } => grant_delegation(deps, env, info, &account_id, &delegate, delegation),
+        ExecuteMsg::RevokeDelegation {
+            account_id,
+            delegate,
+        } => revoke_delegation(deps, info, &account_id, &delegate),
+        ExecuteMsg::UpdateBalanceAfterDeleverage {
+            account_id,
+            pnl,
+        } => update_balance_after_deleverage(deps, info, account_id, pnl),
+        ExecuteMsg::ExecuteTriggerOrder {
+            account_id,
+            trigger_order_id,
+        } => execute_trigger_order(deps, env, info, &account_id, &trigger_order_id),
+        ExecuteMsg::AutoDeleverage {
+            account_id,
+        } => execute_auto_deleverage(deps, env, info, &account_id),
     }
 }
 
@@ -85,6 +119,12 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::AccountKind {
             account_id,
         } => to_json_binary(&get_account_kind(deps.storage, &account_id)?),
+        QueryMsg::LastBorrowHeight {
+            account_id,
+        } => to_json_binary(&query_last_borrow_height(deps, &account_id)?),
+        QueryMsg::AccountStats {
+            account_id,
+        } => to_json_binary(&query_account_stats(deps, &account_id)?),
         QueryMsg::Accounts {
             owner,
             start_after,
@@ -133,6 +173,42 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             limit,
         } => to_json_binary(&query_vault_bindings(deps, start_after, limit)?),
         QueryMsg::SwapFeeRate {} => to_json_binary(&query_swap_fee(deps)?),
+        QueryMsg::AccountTriggerOrders {
+            account_id,
+            start_after,
+            limit,
+        } => to_json_binary(&query_account_trigger_orders(deps, &account_id, start_after, limit)?),
+        QueryMsg::AllTriggerOrders {
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_trigger_orders(deps, start_after, limit)?),
+        QueryMsg::AutoDeleveragePolicy {
+            account_id,
+        } => to_json_binary(&query_auto_deleverage_policy(deps, &account_id)?),
+        QueryMsg::AllAutoDeleveragePolicies {
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_auto_deleverage_policies(deps, start_after, limit)?),
+        QueryMsg::Delegation {
+            account_id,
+            delegate,
+        } => to_json_binary(&query_delegation(deps, env, &account_id, &delegate)?),
+        QueryMsg::AccountDelegations {
+            account_id,
+            start_after,
+            limit,
+        } => {
+            to_json_binary(&query_account_delegations(deps, env, &account_id, start_after, limit)?)
+        }
+        QueryMsg::SimulateActions {
+            account_id,
+            actions,
+        } => to_json_binary(&simulate_actions(deps, &env, &account_id, actions)?),
+        QueryMsg::EstimateLiquidation {
+            account_id,
+            debt_coin,
+            request,
+        } => to_json_binary(&estimate_liquidation(deps, &account_id, debt_coin, request)?),
     };
     res.map_err(Into::into)
 }
@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_utils::nonpayable;
+use mars_types::credit_manager::{AccountPermission, Action, ActionAmount, Delegation};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    state::{DELEGATIONS, KEEPER_FEE_CONFIG},
+    utils::{assert_is_token_owner, query_nft_token_owner},
+};
+
+pub fn grant_delegation(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+    delegate: &str,
+    delegation: Delegation,
+) -> ContractResult<Response> {
+    nonpayable(&info)?;
+    assert_is_token_owner(&deps, &info.sender, account_id)?;
+
+    let delegate = deps.api.addr_validate(delegate)?;
+    if delegate == info.sender {
+        return Err(ContractError::InvalidDelegation {
+            reason: "delegate can not be the account owner".to_string(),
+        });
+    }
+
+    if delegation.permissions.is_empty() {
+        return Err(ContractError::InvalidDelegation {
+            reason: "at least one permission is required".to_string(),
+        });
+    }
+
+    if let Some(expires_at) = delegation.expires_at {
+        if expires_at <= env.block.time.seconds() {
+            return Err(ContractError::InvalidDelegation {
+                reason: "expiration has to be in the future".to_string(),
+            });
+        }
+    }
+
+    let mut denoms = HashSet::new();
+    if !delegation.denom_limits.iter().all(|limit| denoms.insert(&limit.denom)) {
+        return Err(ContractError::InvalidDelegation {
+            reason: "denom limits have to be unique".to_string(),
+        });
+    }
+
+    DELEGATIONS.save(
+        deps.storage,
+        (account_id, delegate.as_str()),
+        &(info.sender.to_string(), delegation.clone()),
+    )?;
+
+    let permissions =
+        delegation.permissions.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_delegation")
+        .add_attribute("account_id", account_id)
+        .add_attribute("delegate", delegate.to_string())
+        .add_attribute("permissions", permissions))
+}
+
+pub fn revoke_delegation(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: &str,
+    delegate: &str,
+) -> ContractResult<Response> {
+    nonpayable(&info)?;
+
+    // The delegate can give up its delegation at any time
+    if info.sender != delegate {
+        assert_is_token_owner(&deps, &info.sender, account_id)?;
+    }
+
+    if !DELEGATIONS.has(deps.storage, (account_id, delegate)) {
+        return Err(ContractError::InvalidDelegation {
+            reason: format!("{delegate} has no delegation on account {account_id}"),
+        });
+    }
+
+    DELEGATIONS.remove(deps.storage, (account_id, delegate));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_delegation")
+        .add_attribute("account_id", account_id)
+        .add_attribute("delegate", delegate))
+}
+
+/// A delegation can only be used before it expires and while the account is held by the owner
+/// who granted it
+pub fn is_delegation_active(
+    env: &Env,
+    owner: &str,
+    granter: &str,
+    delegation: &Delegation,
+) -> bool {
+    granter == owner
+        && delegation.expires_at.map_or(true, |expires_at| env.block.time.seconds() < expires_at)
+}
+
+/// Checks that the actions are allowed by the active delegation of `delegate` and reduces its
+/// denom limits by the amounts used.
+pub fn assert_delegated_actions(
+    deps: &mut DepsMut,
+    env: &Env,
+    delegate: &Addr,
+    owner: &str,
+    account_id: &str,
+    actions: &[Action],
+) -> ContractResult<()> {
+    let not_owner = || ContractError::NotTokenOwner {
+        user: delegate.to_string(),
+        account_id: account_id.to_string(),
+    };
+
+    let Some((granter, mut delegation)) =
+        DELEGATIONS.may_load(deps.storage, (account_id, delegate.as_str()))?
+    else {
+        return Err(not_owner());
+    };
+    if !is_delegation_active(env, owner, &granter, &delegation) {
+        return Err(not_owner());
+    }
+
+    for action in actions {
+        use_delegation(deps.as_ref(), &mut delegation, delegate, owner, account_id, action)?;
+    }
+
+    DELEGATIONS.save(deps.storage, (account_id, delegate.as_str()), &(granter, delegation))?;
+
+    Ok(())
+}
+
+fn use_delegation(
+    deps: Deps,
+    delegation: &mut Delegation,
+    delegate: &Addr,
+    owner: &str,
+    account_id: &str,
+    action: &Action,
+) -> ContractResult<()> {
+    let permission = action.permission();
+    assert_permission(delegation, delegate, account_id, permission)?;
+
+    // Repaying the debt of an account held by someone else moves funds out of the owner's
+    // accounts, the same as a withdrawal
+    if let Action::Repay {
+        recipient_account_id: Some(recipient_account_id),
+        ..
+    } = action
+    {
+        if recipient_account_id != account_id
+            && query_nft_token_owner(deps, recipient_account_id)? != owner
+        {
+            assert_permission(delegation, delegate, account_id, AccountPermission::Withdraw)?;
+        }
+    }
+
+    // The keeper fee is paid out to whoever executes the order, anything above the minimum fee
+    // could be collected by the delegate itself
+    if let Action::CreateTriggerOrder {
+        keeper_fee,
+        ..
+    } = action
+    {
+        let min_fee = KEEPER_FEE_CONFIG.may_load(deps.storage)?.map(|config| config.min_fee);
+        if min_fee.map_or(true, |min_fee| keeper_fee.amount > min_fee.amount) {
+            assert_permission(delegation, delegate, account_id, AccountPermission::Withdraw)?;
+        }
+    }
+
+    for coin in action.spent_coins() {
+        let Some(limit) =
+            delegation.denom_limits.iter_mut().find(|limit| limit.denom == coin.denom)
+        else {
+            continue;
+        };
+
+        // The account balance is only known once the action is executed, an exact amount is
+        // required for limited denoms
+        match coin.amount {
+            ActionAmount::Exact(amount) if amount <= limit.amount => {
+                limit.amount = limit.amount.checked_sub(amount)?;
+            }
+            ActionAmount::Exact(amount) => {
+                return Err(ContractError::DelegationLimitExceeded {
+                    delegate: delegate.to_string(),
+                    account_id: account_id.to_string(),
+                    denom: coin.denom,
+                    requested: amount.to_string(),
+                    remaining: limit.amount,
+                })
+            }
+            ActionAmount::AccountBalance => {
+                return Err(ContractError::DelegationLimitExceeded {
+                    delegate: delegate.to_string(),
+                    account_id: account_id.to_string(),
+                    denom: coin.denom,
+                    requested: "account_balance".to_string(),
+                    remaining: limit.amount,
+                })
+            }
+        }
+    }
+
+    // Actions of a trigger order are dispatched later on, without the delegate
+    if let Action::CreateTriggerOrder {
+        actions,
+        ..
+    } = action
+    {
+        for action in actions {
+            use_delegation(deps, delegation, delegate, owner, account_id, action)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn assert_permission(
+    delegation: &Delegation,
+    delegate: &Addr,
+    account_id: &str,
+    permission: AccountPermission,
+) -> ContractResult<()> {
+    if !delegation.permissions.contains(&permission) {
+        return Err(ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("{permission} on account {account_id}"),
+        });
+    }
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use cosmwasm_std::{Coin, Coins, Deps, DepsMut, Response, Uint128};
 use mars_types::params::TotalDepositResponse;
 
 use crate::{
+    account_stats::record_deposit,
     error::{ContractError, ContractResult},
     state::PARAMS,
     utils::increment_coin_balance,
@@ -25,6 +26,7 @@ pub fn deposit(
     received_coins.sub(coin.clone())?;
 
     increment_coin_balance(deps.storage, account_id, coin)?;
+    record_deposit(deps.storage, account_id, coin)?;
 
     Ok(response
         .add_attribute("action", "callback/deposit")
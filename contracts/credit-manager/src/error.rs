@@ -1,12 +1,16 @@
 use cosmwasm_std::{
     CheckedFromRatioError, CheckedMultiplyFractionError, CheckedMultiplyRatioError, Coin, Coins,
-    CoinsError, Decimal, DecimalRangeExceeded, OverflowError, StdError, Uint128,
+    CoinsError, ConversionOverflowError, Decimal, DecimalRangeExceeded, OverflowError, StdError,
+    Uint128,
 };
 use cw2::VersionError;
 use cw_utils::PaymentError;
 use mars_liquidation::error::LiquidationError;
 use mars_owner::OwnerError;
-use mars_types::adapters::{oracle::OracleError, vault::VaultError};
+use mars_types::{
+    adapters::{oracle::OracleError, vault::VaultError},
+    health::HealthError,
+};
 use mars_utils::error::GuardError;
 use thiserror::Error;
 
@@ -59,6 +63,9 @@ pub enum ContractError {
     #[error("{0}")]
     DecimalRangeExceeded(#[from] DecimalRangeExceeded),
 
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
     #[error("New unlocking positions: {new_amount:?}. Maximum: {maximum:?}.")]
     ExceedsMaxUnlockingPositions {
         new_amount: Uint128,
@@ -119,6 +126,9 @@ pub enum ContractError {
     #[error("No Astro LP available")]
     NoAstroLp,
 
+    #[error("No {0} perp position available")]
+    NoPerpPosition(String),
+
     #[error(
         "{account_id:?} is not a liquidatable credit account. Health factor: {lqdt_health_factor:?}."
     )]
@@ -142,6 +152,9 @@ pub enum ContractError {
     #[error("No more than one vault positions is allowed")]
     OnlyOneVaultPositionAllowed,
 
+    #[error("{0}")]
+    Health(#[from] HealthError),
+
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
@@ -201,4 +214,75 @@ pub enum ContractError {
 
     #[error("{0} asset params not found")]
     AssetParamsNotFound(String),
+
+    #[error("Trigger order {order_id:?} not found for account {account_id:?}")]
+    TriggerOrderNotFound {
+        account_id: String,
+        order_id: String,
+    },
+
+    #[error("Trigger order {0:?} has expired")]
+    TriggerOrderExpired(String),
+
+    #[error("Conditions of trigger order {0:?} are not met")]
+    TriggerConditionsNotMet(String),
+
+    #[error("Invalid trigger order: {reason}")]
+    InvalidTriggerOrder {
+        reason: String,
+    },
+
+    #[error("Auto-deleverage policy not found for account {0:?}")]
+    AutoDeleveragePolicyNotFound(String),
+
+    #[error("Liquidation health factor of account {account_id:?} is not below the auto-deleverage trigger of {trigger_health_factor}")]
+    AutoDeleverageNotTriggered {
+        account_id: String,
+        trigger_health_factor: Decimal,
+    },
+
+    #[error("Invalid auto-deleverage policy: {reason}")]
+    InvalidAutoDeleveragePolicy {
+        reason: String,
+    },
+
+    #[error("Invalid delegation: {reason}")]
+    InvalidDelegation {
+        reason: String,
+    },
+
+    #[error("{delegate:?} can use {remaining} more {denom} of account {account_id:?}, requested: {requested}")]
+    DelegationLimitExceeded {
+        delegate: String,
+        account_id: String,
+        denom: String,
+        requested: String,
+        remaining: Uint128,
+    },
+
+    #[error("Invalid liquidation estimate: {reason}")]
+    InvalidLiquidationEstimate {
+        reason: String,
+    },
+
+    #[error("Flash liquidation profit of {profit} is below the minimum of {min_profit}")]
+    FlashLiquidationProfitTooLow {
+        min_profit: Uint128,
+        profit: Uint128,
+    },
+
+    #[error("Invalid account updates: {reason}")]
+    InvalidAccountUpdates {
+        reason: String,
+    },
+
+    #[error("Invalid transfer: {reason}")]
+    InvalidTransfer {
+        reason: String,
+    },
+
+    #[error("Invalid Pyth price update: {reason}")]
+    InvalidPythPriceUpdate {
+        reason: String,
+    },
 }
@@ -0,0 +1,206 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Coin, Decimal, Deps};
+use mars_rover_health_computer::sub_coin;
+use mars_types::{
+    adapters::vault::{
+        UnlockingChange, UpdateType, VaultError, VaultPositionAmount, VaultPositionType,
+        VaultPositionUpdate, VaultUnchecked,
+    },
+    credit_manager::{LiquidateRequest, Positions},
+    liquidation::EstimateLiquidationResponse,
+    oracle::ActionKind,
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    liquidate::compute_liquidation,
+    query::query_positions,
+    state::{COIN_BALANCES, HEALTH_CONTRACT, INCENTIVES, RED_BANK, VAULT_POSITIONS},
+    vault::to_vault_coin,
+};
+
+/// Computes the liquidation the same way the `Liquidate` action would and the health of the
+/// liquidatee once its positions are reduced accordingly
+pub fn estimate_liquidation(
+    deps: Deps,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    request: LiquidateRequest<VaultUnchecked>,
+) -> ContractResult<EstimateLiquidationResponse> {
+    let mut positions = query_positions(deps, liquidatee_account_id)?;
+
+    let ((debt, liquidator_request, liquidatee_request), liquidation_bonus) = match request {
+        LiquidateRequest::Deposit(denom) => {
+            let balance = COIN_BALANCES
+                .load(deps.storage, (liquidatee_account_id, denom.as_str()))
+                .map_err(|_| ContractError::CoinNotAvailable(denom.clone()))?;
+            let (coins, liquidation_bonus) =
+                compute_liquidation(deps, liquidatee_account_id, &debt_coin, &denom, balance)?;
+            sub_coin(&mut positions.deposits, &coins.2)?;
+            (coins, liquidation_bonus)
+        }
+        LiquidateRequest::Lend(denom) => {
+            let lent_amount = RED_BANK.load(deps.storage)?.query_lent(
+                &deps.querier,
+                liquidatee_account_id,
+                &denom,
+            )?;
+            if lent_amount.is_zero() {
+                return Err(ContractError::NoneLent);
+            }
+            let (coins, liquidation_bonus) =
+                compute_liquidation(deps, liquidatee_account_id, &debt_coin, &denom, lent_amount)?;
+            sub_coin(&mut positions.lends, &coins.2)?;
+            (coins, liquidation_bonus)
+        }
+        LiquidateRequest::StakedAstroLp(denom) => {
+            let lp_amount = INCENTIVES
+                .load(deps.storage)?
+                .query_staked_astro_lp_position(&deps.querier, liquidatee_account_id, &denom)?
+                .lp_coin
+                .amount;
+            if lp_amount.is_zero() {
+                return Err(ContractError::NoAstroLp);
+            }
+            let (coins, liquidation_bonus) =
+                compute_liquidation(deps, liquidatee_account_id, &debt_coin, &denom, lp_amount)?;
+            sub_coin(&mut positions.staked_astro_lps, &coins.2)?;
+            (coins, liquidation_bonus)
+        }
+        LiquidateRequest::Vault {
+            request_vault,
+            position_type,
+        } => estimate_vault_liquidation(
+            deps,
+            &mut positions,
+            &debt_coin,
+            request_vault,
+            position_type,
+        )?,
+        LiquidateRequest::Perp(_) => {
+            return Err(ContractError::InvalidLiquidationEstimate {
+                reason: "perp positions can not be estimated".to_string(),
+            })
+        }
+    };
+
+    repay(&mut positions, &debt)?;
+    let health = HEALTH_CONTRACT.load(deps.storage)?.query_health_values_for_positions(
+        &deps.querier,
+        &positions,
+        positions.account_kind.clone(),
+        ActionKind::Liquidation,
+    )?;
+
+    let protocol_fee = Coin {
+        denom: liquidatee_request.denom.clone(),
+        amount: liquidatee_request.amount.checked_sub(liquidator_request.amount)?,
+    };
+
+    Ok(EstimateLiquidationResponse {
+        debt_repaid: debt,
+        collateral_liquidated: liquidatee_request,
+        collateral_received: liquidator_request,
+        protocol_fee,
+        liquidation_bonus,
+        health_factor_after: health.liquidation_health_factor,
+    })
+}
+
+/// Same as `liquidate_vault`, locked and unlocked positions are liquidated in vault coins,
+/// unlocking positions in base coins
+fn estimate_vault_liquidation(
+    deps: Deps,
+    positions: &mut Positions,
+    debt_coin: &Coin,
+    request_vault: VaultUnchecked,
+    position_type: VaultPositionType,
+) -> ContractResult<((Coin, Coin, Coin), Decimal)> {
+    let request_vault = request_vault.check(deps.api)?;
+    let liquidatee_account_id = positions.account_id.clone();
+    let vault_position = VAULT_POSITIONS
+        .load(deps.storage, (&liquidatee_account_id, request_vault.address.clone()))?;
+    let vault_info = request_vault.query_info(&deps.querier)?;
+
+    let amount = match (&vault_position, &position_type) {
+        (VaultPositionAmount::Unlocked(a), VaultPositionType::UNLOCKED) => a.total(),
+        (VaultPositionAmount::Locking(a), VaultPositionType::LOCKED) => a.locked.total(),
+        (VaultPositionAmount::Locking(_), VaultPositionType::UNLOCKING) => {
+            vault_position.unlocking().total()
+        }
+        _ => return Err(VaultError::MismatchedVaultType.into()),
+    };
+
+    let position =
+        positions.vaults.iter_mut().find(|p| p.vault.address == request_vault.address).ok_or_else(
+            || ContractError::InvalidLiquidationEstimate {
+                reason: format!("no position in vault {}", request_vault.address),
+            },
+        )?;
+
+    if position_type == VaultPositionType::UNLOCKING {
+        let (coins, liquidation_bonus) = compute_liquidation(
+            deps,
+            &liquidatee_account_id,
+            debt_coin,
+            &vault_info.base_token,
+            amount,
+        )?;
+
+        let mut total_to_liquidate = coins.2.amount;
+        for u in vault_position.unlocking().positions() {
+            let amount = min(u.coin.amount, total_to_liquidate);
+            if amount.is_zero() {
+                break;
+            }
+            position.amount.update(VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                id: u.id,
+                amount,
+            }))?;
+            total_to_liquidate = total_to_liquidate.checked_sub(amount)?;
+        }
+
+        return Ok((coins, liquidation_bonus));
+    }
+
+    let total_underlying = request_vault.query_preview_redeem(&deps.querier, amount)?;
+    let ((debt, liquidator_request, liquidatee_request), liquidation_bonus) = compute_liquidation(
+        deps,
+        &liquidatee_account_id,
+        debt_coin,
+        &vault_info.base_token,
+        total_underlying,
+    )?;
+    let liquidator_request =
+        to_vault_coin(liquidator_request, amount, total_underlying, &vault_info)?;
+    let liquidatee_request =
+        to_vault_coin(liquidatee_request, amount, total_underlying, &vault_info)?;
+
+    let update = UpdateType::Decrement(liquidatee_request.amount);
+    position.amount.update(if position_type == VaultPositionType::LOCKED {
+        VaultPositionUpdate::Locked(update)
+    } else {
+        VaultPositionUpdate::Unlocked(update)
+    })?;
+
+    Ok(((debt, liquidator_request, liquidatee_request), liquidation_bonus))
+}
+
+fn repay(positions: &mut Positions, debt: &Coin) -> ContractResult<()> {
+    let Some(index) = positions.debts.iter().position(|d| d.denom == debt.denom) else {
+        return Err(ContractError::NoDebt);
+    };
+
+    let debt_amount = &mut positions.debts[index];
+    if debt.amount >= debt_amount.amount {
+        positions.debts.remove(index);
+    } else {
+        debt_amount.shares = debt_amount
+            .shares
+            .checked_sub(debt_amount.shares.multiply_ratio(debt.amount, debt_amount.amount))?;
+        debt_amount.amount = debt_amount.amount.checked_sub(debt.amount)?;
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 
 use cosmwasm_std::{
     to_json_binary, Addr, Coins, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult,
@@ -13,11 +13,16 @@ use mars_types::{
 use mars_vault::msg::{ExecuteMsg, ExtensionExecuteMsg};
 
 use crate::{
+    auto_deleverage::{repay_received, set_auto_deleverage_policy},
     borrow::borrow,
     claim_astro_lp_rewards::claim_lp_rewards,
     claim_rewards::claim_rewards,
+    delegation::assert_delegated_actions,
     deposit::{assert_deposit_caps, deposit, update_or_reset_denom_deposits},
     error::{ContractError, ContractResult},
+    flash_liquidate::{
+        assert_flash_liquidation_profit, flash_liquidate, swap_liquidated_collateral,
+    },
     health::{assert_max_ltv, query_health_state},
     hls::assert_hls_rules,
     lend::lend,
@@ -25,15 +30,21 @@ use crate::{
     liquidate_astro_lp::liquidate_astro_lp,
     liquidate_deposit::liquidate_deposit,
     liquidate_lend::liquidate_lend,
+    liquidate_perp::liquidate_perp,
+    perp::execute_perp_order,
+    perp_vault::{deposit_to_perp_vault, unlock_from_perp_vault, withdraw_from_perp_vault},
+    pyth::{take_pyth_price_updates, update_pyth_prices},
     reclaim::reclaim,
     refund::refund_coin_balances,
     repay::{repay, repay_for_recipient},
     stake_astro_lp::stake_lp,
-    state::{ACCOUNT_KINDS, ACCOUNT_NFT, REENTRANCY_GUARD, VAULTS},
+    state::{ACCOUNT_KINDS, ACCOUNT_NFT, LAST_BORROW_HEIGHTS, REENTRANCY_GUARD, VAULTS},
     swap::swap_exact_in,
+    transfer::{assert_transfer_recipient, transfer_to_account},
+    trigger::{create_trigger_order, delete_trigger_order},
     unstake_astro_lp::unstake_lp,
     update_coin_balances::{update_coin_balance, update_coin_balance_after_vault_liquidation},
-    utils::{assert_is_token_owner, get_account_kind},
+    utils::{assert_is_token_owner, get_account_kind, query_nft_token_owner},
     vault::{
         enter_vault, exit_vault, exit_vault_unlocked, liquidate_vault, request_vault_unlock,
         update_vault_coin_balance,
@@ -104,7 +115,7 @@ pub fn dispatch_actions(
 
     let account_id = match account_id {
         Some(acc_id) => {
-            validate_account(&deps, &info, &acc_id, &actions)?;
+            validate_account(&mut deps, &env, &info, &acc_id, &actions)?;
             acc_id
         }
         None => {
@@ -117,12 +128,137 @@ pub fn dispatch_actions(
             acc_id
         }
     };
-    let account_id = &account_id;
 
+    // Prices are pushed first, the other actions are dispatched once they are fresh
+    let (vaa_data, actions) = take_pyth_price_updates(actions);
+    if !vaa_data.is_empty() {
+        return update_pyth_prices(deps, &env, info, &account_id, vaa_data, actions, response);
+    }
+
+    dispatch_account_actions(deps, env, info, &account_id, actions, response)
+}
+
+/// Dispatches the actions of an existing account. The sender must have been authorized already.
+pub fn dispatch_account_actions(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+    actions: Vec<Action>,
+    response: Response,
+) -> ContractResult<Response> {
     REENTRANCY_GUARD.try_lock(deps.storage)?;
 
+    let mut received_coins = Coins::try_from(info.funds.clone())?;
+    let (response, mut callbacks, assertions) = prepare_account_actions(
+        &mut deps,
+        &env,
+        &info,
+        account_id,
+        actions,
+        &mut received_coins,
+        response,
+    )?;
+
+    // after all deposits have been handled, we assert that the `received_natives` list is empty
+    // this way, we ensure that the user does not send any extra fund which will get lost in the contract
+    if !received_coins.is_empty() {
+        return Err(ContractError::ExtraFundsReceived(received_coins));
+    }
+
+    callbacks.extend(assertions);
+
+    Ok(add_callbacks(&env, response, callbacks)?
+        .add_attribute("action", "rover/execute/update_credit_account")
+        .add_attribute("account_id", account_id.to_string()))
+}
+
+/// Dispatches the actions of several accounts of the sender under one reentrancy guard.
+/// The assertions of every account (HLS rules, max LTV, deposit caps) run after the actions of
+/// all accounts have been executed, so coins moved between the accounts are accounted for.
+pub fn dispatch_actions_for_accounts(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    updates: Vec<(String, Vec<Action>)>,
+) -> ContractResult<Response> {
+    if updates.is_empty() {
+        return Err(ContractError::InvalidAccountUpdates {
+            reason: "at least one account update is required".to_string(),
+        });
+    }
+
+    let mut account_ids = HashSet::new();
+    if let Some((account_id, _)) = updates.iter().find(|(id, _)| !account_ids.insert(id)) {
+        return Err(ContractError::InvalidAccountUpdates {
+            reason: format!("account {account_id} is updated more than once"),
+        });
+    }
+
+    REENTRANCY_GUARD.try_lock(deps.storage)?;
+
+    let mut received_coins = Coins::try_from(info.funds.clone())?;
+    let mut response =
+        Response::new().add_attribute("action", "rover/execute/update_credit_accounts");
+    let mut callbacks = vec![];
+    let mut assertions = vec![];
+
+    for (account_id, actions) in updates {
+        validate_account(&mut deps, &env, &info, &account_id, &actions)?;
+
+        let (res, account_callbacks, account_assertions) = prepare_account_actions(
+            &mut deps,
+            &env,
+            &info,
+            &account_id,
+            actions,
+            &mut received_coins,
+            response,
+        )?;
+        response = res.add_attribute("account_id", account_id);
+        callbacks.extend(account_callbacks);
+        assertions.extend(account_assertions);
+    }
+
+    if !received_coins.is_empty() {
+        return Err(ContractError::ExtraFundsReceived(received_coins));
+    }
+
+    callbacks.extend(assertions);
+
+    add_callbacks(&env, response, callbacks)
+}
+
+/// Adds the callbacks to the response, followed by the removal of the reentrancy guard
+pub fn add_callbacks(
+    env: &Env,
+    response: Response,
+    mut callbacks: Vec<CallbackMsg>,
+) -> ContractResult<Response> {
+    // Removes guard so that subsequent action dispatches can be made
+    callbacks.push(CallbackMsg::RemoveReentrancyGuard {});
+
+    let callback_msgs = callbacks
+        .iter()
+        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(response.add_messages(callback_msgs))
+}
+
+/// Converts the actions of an account into callbacks. Returns the callbacks executing the actions
+/// and the ones asserting the state of the account afterwards.
+fn prepare_account_actions(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    account_id: &str,
+    actions: Vec<Action>,
+    received_coins: &mut Coins,
+    mut response: Response,
+) -> ContractResult<(Response, Vec<CallbackMsg>, Vec<CallbackMsg>)> {
     let mut callbacks: Vec<CallbackMsg> = vec![];
-    let mut received_coins = Coins::try_from(info.funds)?;
+    let mut assertions: Vec<CallbackMsg> = vec![];
 
     // deposit / repay actions don't require health check.
     // It allows users to save some positions in cases of extreme volatility.
@@ -170,13 +306,13 @@ pub fn dispatch_actions(
     for action in actions {
         match action {
             Action::Deposit(coin) => {
-                response = deposit(&mut deps, response, account_id, &coin, &mut received_coins)?;
+                response = deposit(deps, response, account_id, &coin, received_coins)?;
                 // add the denom to the map to check the deposit cap in the end of the TX
                 update_or_reset_denom_deposits(
                     deps.as_ref(),
                     &mut denoms_for_cap_check,
                     &coin.denom,
-                    &received_coins,
+                    received_coins,
                     true,
                 )?;
             }
@@ -193,10 +329,35 @@ pub fn dispatch_actions(
                 coin,
                 recipient: deps.api.addr_validate(&recipient)?,
             }),
-            Action::Borrow(coin) => callbacks.push(CallbackMsg::Borrow {
-                account_id: account_id.to_string(),
+            Action::TransferToAccount {
+                account_id: recipient_account_id,
                 coin,
-            }),
+            } => {
+                assert_transfer_recipient(deps.as_ref(), account_id, &recipient_account_id)?;
+
+                // Receiving coins can't make an account unhealthy, but it can break the HLS rules
+                if get_account_kind(deps.storage, &recipient_account_id)?
+                    == AccountKind::HighLeveredStrategy
+                {
+                    assertions.push(CallbackMsg::AssertHlsRules {
+                        account_id: recipient_account_id.clone(),
+                    });
+                }
+
+                callbacks.push(CallbackMsg::TransferToAccount {
+                    account_id: account_id.to_string(),
+                    recipient_account_id,
+                    coin,
+                })
+            }
+            Action::Borrow(coin) => {
+                // Used by the account-nft to block transfers of accounts that just borrowed
+                LAST_BORROW_HEIGHTS.save(deps.storage, account_id, &env.block.height)?;
+                callbacks.push(CallbackMsg::Borrow {
+                    account_id: account_id.to_string(),
+                    coin,
+                })
+            }
             Action::Repay {
                 recipient_account_id,
                 coin,
@@ -270,7 +431,41 @@ pub fn dispatch_actions(
                         request: LiquidateRequest::StakedAstroLp(lp_denom),
                     })
                 }
+                LiquidateRequest::Perp(denom) => callbacks.push(CallbackMsg::Liquidate {
+                    liquidator_account_id: account_id.to_string(),
+                    liquidatee_account_id: liquidatee_account_id.to_string(),
+                    debt_coin,
+                    request: LiquidateRequest::Perp(denom),
+                }),
             },
+            Action::FlashLiquidate {
+                liquidatee_account_id,
+                debt_coin,
+                request,
+                route,
+                min_profit,
+            } => callbacks.push(CallbackMsg::FlashLiquidate {
+                liquidator_account_id: account_id.to_string(),
+                liquidatee_account_id,
+                debt_coin,
+                request: match request {
+                    LiquidateRequest::Deposit(denom) => LiquidateRequest::Deposit(denom),
+                    LiquidateRequest::Lend(denom) => LiquidateRequest::Lend(denom),
+                    LiquidateRequest::Vault {
+                        request_vault,
+                        position_type,
+                    } => LiquidateRequest::Vault {
+                        request_vault: request_vault.check(deps.api)?,
+                        position_type,
+                    },
+                    LiquidateRequest::StakedAstroLp(lp_denom) => {
+                        LiquidateRequest::StakedAstroLp(lp_denom)
+                    }
+                    LiquidateRequest::Perp(denom) => LiquidateRequest::Perp(denom),
+                },
+                route,
+                min_profit,
+            }),
             Action::SwapExactIn {
                 coin_in,
                 denom_out,
@@ -289,7 +484,7 @@ pub fn dispatch_actions(
                     deps.as_ref(),
                     &mut denoms_for_cap_check,
                     &denom_out,
-                    &received_coins,
+                    received_coins,
                     false,
                 )?;
             }
@@ -334,7 +529,7 @@ pub fn dispatch_actions(
                     deps.as_ref(),
                     &mut denoms_for_cap_check,
                     &lp_token_out,
-                    &received_coins,
+                    received_coins,
                     false,
                 )?;
             }
@@ -369,19 +564,74 @@ pub fn dispatch_actions(
                     account_id: account_id.to_string(),
                 })
             }
+            Action::DepositToPerpVault {
+                coin,
+                max_receivable_shares,
+            } => callbacks.push(CallbackMsg::DepositToPerpVault {
+                account_id: account_id.to_string(),
+                coin,
+                max_receivable_shares,
+            }),
+            Action::UnlockFromPerpVault {
+                shares,
+            } => callbacks.push(CallbackMsg::UnlockFromPerpVault {
+                account_id: account_id.to_string(),
+                shares,
+            }),
+            Action::WithdrawFromPerpVault {
+                min_receive,
+            } => callbacks.push(CallbackMsg::WithdrawFromPerpVault {
+                account_id: account_id.to_string(),
+                min_receive,
+            }),
+            Action::ExecutePerpOrder {
+                denom,
+                order_size,
+                reduce_only,
+            } => callbacks.push(CallbackMsg::ExecutePerpOrder {
+                account_id: account_id.to_string(),
+                denom,
+                order_size,
+                reduce_only,
+            }),
+            Action::CreateTriggerOrder {
+                actions,
+                conditions,
+                keeper_fee,
+                expires_at,
+            } => callbacks.push(CallbackMsg::CreateTriggerOrder {
+                account_id: account_id.to_string(),
+                actions,
+                conditions,
+                keeper_fee,
+                expires_at,
+            }),
+            Action::DeleteTriggerOrder {
+                trigger_order_id,
+            } => callbacks.push(CallbackMsg::DeleteTriggerOrder {
+                account_id: account_id.to_string(),
+                trigger_order_id,
+            }),
+            Action::SetAutoDeleveragePolicy {
+                policy,
+            } => callbacks.push(CallbackMsg::SetAutoDeleveragePolicy {
+                account_id: account_id.to_string(),
+                policy,
+            }),
+            Action::UpdatePythPrices {
+                ..
+            } => {
+                return Err(ContractError::InvalidPythPriceUpdate {
+                    reason: "prices can only be updated through UpdateCreditAccount".to_string(),
+                })
+            }
         }
     }
 
-    // after all deposits have been handled, we assert that the `received_natives` list is empty
-    // this way, we ensure that the user does not send any extra fund which will get lost in the contract
-    if !received_coins.is_empty() {
-        return Err(ContractError::ExtraFundsReceived(received_coins));
-    }
-
     // Ensures the account state abides by the rules of the HLS account kind
     let kind = get_account_kind(deps.storage, account_id)?;
     if kind == AccountKind::HighLeveredStrategy {
-        callbacks.push(CallbackMsg::AssertHlsRules {
+        assertions.push(CallbackMsg::AssertHlsRules {
             account_id: account_id.to_string(),
         });
     }
@@ -391,35 +641,24 @@ pub fn dispatch_actions(
         // - Healthy, if prior to actions MaxLTV health factor >= 1 or None
         // - Not further weakened, if prior to actions MaxLTV health factor < 1
         // Else, throw error and revert all actions
-        callbacks.push(CallbackMsg::AssertMaxLTV {
+        assertions.push(CallbackMsg::AssertMaxLTV {
             account_id: account_id.to_string(),
             prev_health_state: phs,
         });
     }
 
-    callbacks.extend([
-        // After user selected actions, we assert that the relevant deposit caps
-        // are not exceeded.
-        CallbackMsg::AssertDepositCaps {
-            denoms: denoms_for_cap_check,
-        },
-        // Removes guard so that subsequent action dispatches can be made
-        CallbackMsg::RemoveReentrancyGuard {},
-    ]);
-
-    let callback_msgs = callbacks
-        .iter()
-        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
-        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+    // After user selected actions, we assert that the relevant deposit caps
+    // are not exceeded.
+    assertions.push(CallbackMsg::AssertDepositCaps {
+        denoms: denoms_for_cap_check,
+    });
 
-    Ok(response
-        .add_messages(callback_msgs)
-        .add_attribute("action", "rover/execute/update_credit_account")
-        .add_attribute("account_id", account_id.to_string()))
+    Ok((response, callbacks, assertions))
 }
 
 fn validate_account(
-    deps: &DepsMut,
+    deps: &mut DepsMut,
+    env: &Env,
     info: &MessageInfo,
     acc_id: &String,
     actions: &[Action],
@@ -440,12 +679,13 @@ fn validate_account(
                         | Action::Withdraw(..)
                         | Action::RefundAllCoinBalances {}
                         | Action::WithdrawToWallet { .. }
+                        | Action::TransferToAccount { .. }
                 )
             });
             if actions_not_allowed {
                 return Err(ContractError::Unauthorized {
                     user: acc_id.to_string(),
-                    action: "deposit, withdraw, refund_all_coin_balances, withdraw_to_wallet"
+                    action: "deposit, withdraw, refund_all_coin_balances, withdraw_to_wallet, transfer_to_account"
                         .to_string(),
                 });
             }
@@ -455,8 +695,12 @@ fn validate_account(
         AccountKind::FundManager {
             ..
         } => {}
+        // Delegates of the owner can dispatch the actions allowed by their delegation
         AccountKind::Default | AccountKind::HighLeveredStrategy => {
-            assert_is_token_owner(deps, &info.sender, acc_id)?
+            let owner = query_nft_token_owner(deps.as_ref(), acc_id)?;
+            if info.sender != owner {
+                assert_delegated_actions(deps, env, &info.sender, &owner, acc_id, actions)?;
+            }
         }
     }
 
@@ -486,11 +730,21 @@ pub fn execute_callback(
             account_id,
             coin,
         } => repay(deps, &account_id, &coin),
+        CallbackMsg::TransferToAccount {
+            account_id,
+            recipient_account_id,
+            coin,
+        } => transfer_to_account(deps, &account_id, &recipient_account_id, &coin),
         CallbackMsg::RepayForRecipient {
             benefactor_account_id,
             recipient_account_id,
             coin,
         } => repay_for_recipient(deps, env, &benefactor_account_id, &recipient_account_id, coin),
+        CallbackMsg::RepayReceived {
+            account_id,
+            denom,
+            previous_balance,
+        } => repay_received(deps, &account_id, &denom, previous_balance),
         CallbackMsg::Lend {
             account_id,
             coin,
@@ -569,8 +823,60 @@ pub fn execute_callback(
                     debt_coin,
                     &request_coin_denom,
                 ),
+                LiquidateRequest::Perp(perp_denom) => liquidate_perp(
+                    deps,
+                    env,
+                    &liquidator_account_id,
+                    &liquidatee_account_id,
+                    debt_coin,
+                    &perp_denom,
+                ),
             }
         }
+        CallbackMsg::FlashLiquidate {
+            liquidator_account_id,
+            liquidatee_account_id,
+            debt_coin,
+            request,
+            route,
+            min_profit,
+        } => flash_liquidate(
+            deps,
+            env,
+            &liquidator_account_id,
+            &liquidatee_account_id,
+            debt_coin,
+            request,
+            route,
+            min_profit,
+        ),
+        CallbackMsg::SwapLiquidatedCollateral {
+            account_id,
+            denom_in,
+            previous_balance,
+            denom_out,
+            route,
+        } => swap_liquidated_collateral(
+            deps,
+            env,
+            &account_id,
+            &denom_in,
+            previous_balance,
+            &denom_out,
+            route,
+        ),
+        CallbackMsg::AssertFlashLiquidationProfit {
+            account_id,
+            previous_balance,
+            previous_debt,
+            min_profit,
+        } => assert_flash_liquidation_profit(
+            deps.as_ref(),
+            &account_id,
+            &previous_balance,
+            previous_debt,
+            min_profit,
+        ),
         CallbackMsg::SwapExactIn {
             account_id,
             coin_in,
@@ -642,5 +948,67 @@ pub fn execute_callback(
             account_id,
             lp_denom,
         } => claim_lp_rewards(deps, &account_id, &lp_denom),
+        CallbackMsg::DepositToPerpVault {
+            account_id,
+            coin,
+            max_receivable_shares,
+        } => deposit_to_perp_vault(deps, &account_id, &coin, max_receivable_shares),
+        CallbackMsg::UnlockFromPerpVault {
+            account_id,
+            shares,
+        } => unlock_from_perp_vault(deps, &account_id, shares),
+        CallbackMsg::WithdrawFromPerpVault {
+            account_id,
+            min_receive,
+        } => withdraw_from_perp_vault(deps, env, &account_id, min_receive),
+        CallbackMsg::ExecutePerpOrder {
+            account_id,
+            denom,
+            order_size,
+            reduce_only,
+        } => execute_perp_order(deps, &account_id, &denom, order_size, reduce_only),
+        CallbackMsg::CreateTriggerOrder {
+            account_id,
+            actions,
+            conditions,
+            keeper_fee,
+            expires_at,
+        } => create_trigger_order(
+            deps,
+            env,
+            &account_id,
+            actions,
+            conditions,
+            keeper_fee,
+            expires_at,
+        ),
+        CallbackMsg::DeleteTriggerOrder {
+            account_id,
+            trigger_order_id,
+        } => delete_trigger_order(deps, &account_id, &trigger_order_id),
+        CallbackMsg::SetAutoDeleveragePolicy {
+            account_id,
+            policy,
+        } => set_auto_deleverage_policy(deps, &account_id, policy),
+        CallbackMsg::DispatchActions {
+            account_id,
+            sender,
+            actions,
+            funds,
+        } => {
+            // The guard taken when pushing the Pyth prices is handed over to the dispatch
+            REENTRANCY_GUARD.try_unlock(deps.storage)?;
+            dispatch_account_actions(
+                deps,
+                env,
+                MessageInfo {
+                    sender,
+                    funds,
+                },
+                &account_id,
+                actions,
+                Response::new(),
+            )
+        }
     }
 }
@@ -0,0 +1,182 @@
+use cosmwasm_std::{Coin, CosmosMsg, Deps, DepsMut, Env, Response, StdResult, Uint128};
+use mars_types::{
+    adapters::vault::Vault,
+    credit_manager::{ActionAmount, ActionCoin, CallbackMsg, LiquidateRequest},
+    swapper::SwapperRoute,
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    liquidate::assert_not_self_liquidation,
+    state::{COIN_BALANCES, DEBT_SHARES, PERPS},
+    swap::swap_exact_in,
+    utils::debt_shares_to_amount,
+};
+
+/// Borrows the debt coin, liquidates the liquidatee with it, swaps the seized collateral back to
+/// the debt denom and repays the borrowed amount. The balances before the liquidation are
+/// recorded so that only the seized collateral is swapped and the profit can be asserted in the end.
+pub fn flash_liquidate(
+    deps: DepsMut,
+    env: Env,
+    liquidator_account_id: &str,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    request: LiquidateRequest<Vault>,
+    route: Option<SwapperRoute>,
+    min_profit: Uint128,
+) -> ContractResult<Response> {
+    assert_not_self_liquidation(liquidator_account_id, liquidatee_account_id)?;
+
+    let collateral_denom = query_liquidated_collateral_denom(deps.as_ref(), &request)?;
+    let previous_debt_balance =
+        coin_balance(deps.as_ref(), liquidator_account_id, &debt_coin.denom)?;
+    let previous_collateral_balance =
+        coin_balance(deps.as_ref(), liquidator_account_id, &collateral_denom)?;
+    let previous_debt = current_debt(deps.as_ref(), liquidator_account_id, &debt_coin.denom)?;
+
+    let mut callbacks = vec![
+        CallbackMsg::Borrow {
+            account_id: liquidator_account_id.to_string(),
+            coin: debt_coin.clone(),
+        },
+        CallbackMsg::Liquidate {
+            liquidator_account_id: liquidator_account_id.to_string(),
+            liquidatee_account_id: liquidatee_account_id.to_string(),
+            debt_coin: debt_coin.clone(),
+            request,
+        },
+    ];
+
+    // Collateral already in the debt denom can be used for the repayment as is
+    if collateral_denom != debt_coin.denom {
+        callbacks.push(CallbackMsg::SwapLiquidatedCollateral {
+            account_id: liquidator_account_id.to_string(),
+            denom_in: collateral_denom.clone(),
+            previous_balance: previous_collateral_balance,
+            denom_out: debt_coin.denom.clone(),
+            route,
+        });
+    }
+
+    callbacks.extend([
+        CallbackMsg::Repay {
+            account_id: liquidator_account_id.to_string(),
+            coin: ActionCoin {
+                denom: debt_coin.denom.clone(),
+                amount: ActionAmount::Exact(debt_coin.amount),
+            },
+        },
+        CallbackMsg::AssertFlashLiquidationProfit {
+            account_id: liquidator_account_id.to_string(),
+            previous_balance: Coin {
+                denom: debt_coin.denom.clone(),
+                amount: previous_debt_balance,
+            },
+            previous_debt,
+            min_profit,
+        },
+    ]);
+
+    let callback_msgs = callbacks
+        .iter()
+        .map(|callback| callback.into_cosmos_msg(&env.contract.address))
+        .collect::<StdResult<Vec<CosmosMsg>>>()?;
+
+    Ok(Response::new()
+        .add_messages(callback_msgs)
+        .add_attribute("action", "flash_liquidate")
+        .add_attribute("account_id", liquidator_account_id)
+        .add_attribute("liquidatee_account_id", liquidatee_account_id)
+        .add_attribute("debt_coin", debt_coin.to_string())
+        .add_attribute("collateral_denom", collateral_denom))
+}
+
+/// Swaps the collateral received by the liquidator (balance increase since `previous_balance`)
+pub fn swap_liquidated_collateral(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    denom_in: &str,
+    previous_balance: Uint128,
+    denom_out: &str,
+    route: Option<SwapperRoute>,
+) -> ContractResult<Response> {
+    let current_balance = coin_balance(deps.as_ref(), account_id, denom_in)?;
+    let amount = current_balance.saturating_sub(previous_balance);
+    if amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    // Slippage is covered by the profit assertion at the end of the flash liquidation
+    swap_exact_in(
+        deps,
+        env,
+        account_id,
+        &ActionCoin {
+            denom: denom_in.to_string(),
+            amount: ActionAmount::Exact(amount),
+        },
+        denom_out,
+        Uint128::one(),
+        route,
+    )
+}
+
+/// The profit is the increase of the debt denom balance minus any debt left over from the loan
+pub fn assert_flash_liquidation_profit(
+    deps: Deps,
+    account_id: &str,
+    previous_balance: &Coin,
+    previous_debt: Uint128,
+    min_profit: Uint128,
+) -> ContractResult<Response> {
+    let current_balance = coin_balance(deps, account_id, &previous_balance.denom)?;
+    let debt_increase =
+        current_debt(deps, account_id, &previous_balance.denom)?.saturating_sub(previous_debt);
+
+    let profit =
+        current_balance.saturating_sub(previous_balance.amount.checked_add(debt_increase)?);
+    if profit < min_profit {
+        return Err(ContractError::FlashLiquidationProfitTooLow {
+            min_profit,
+            profit,
+        });
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "assert_flash_liquidation_profit")
+        .add_attribute("account_id", account_id)
+        .add_attribute("profit", profit))
+}
+
+/// The denom credited to the liquidator's coin balance for the requested position
+fn query_liquidated_collateral_denom(
+    deps: Deps,
+    request: &LiquidateRequest<Vault>,
+) -> ContractResult<String> {
+    let denom = match request {
+        LiquidateRequest::Deposit(denom)
+        | LiquidateRequest::Lend(denom)
+        | LiquidateRequest::StakedAstroLp(denom) => denom.clone(),
+        LiquidateRequest::Vault {
+            request_vault,
+            ..
+        } => request_vault.query_info(&deps.querier)?.base_token,
+        LiquidateRequest::Perp(_) => {
+            PERPS.load(deps.storage)?.query_config(&deps.querier)?.base_denom
+        }
+    };
+    Ok(denom)
+}
+
+fn coin_balance(deps: Deps, account_id: &str, denom: &str) -> ContractResult<Uint128> {
+    Ok(COIN_BALANCES.may_load(deps.storage, (account_id, denom))?.unwrap_or_default())
+}
+
+fn current_debt(deps: Deps, account_id: &str, denom: &str) -> ContractResult<Uint128> {
+    match DEBT_SHARES.may_load(deps.storage, (account_id, denom))? {
+        Some(shares) => Ok(debt_shares_to_amount(deps, denom, shares)?.amount),
+        None => Ok(Uint128::zero()),
+    }
+}
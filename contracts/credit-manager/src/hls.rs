@@ -8,17 +8,29 @@ use crate::{
 };
 
 pub fn assert_hls_rules(deps: Deps, account_id: &str) -> ContractResult<Response> {
+    let positions = query_positions(deps, account_id)?;
+    assert_hls_positions(deps, positions)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "callback/assert_hls_rules")
+        .add_attribute("account_id", account_id)
+        .add_attribute("account_kind", AccountKind::HighLeveredStrategy.to_string()))
+}
+
+/// Asserts that the positions abide by the rules of the HLS account kind
+pub fn assert_hls_positions(deps: Deps, positions: Positions) -> ContractResult<()> {
     // Rule #1 - There can only be 0 or 1 debt denom in the account
     let Positions {
         // destruct Positions so whenever we add new positions we don't forget to add them here
-        account_id,
+        account_id: _,
         account_kind: _,
         deposits: _,
         debts,
         lends,
         vaults,
         staked_astro_lps,
-    } = query_positions(deps, account_id)?;
+        perps,
+    } = positions;
 
     if debts.len() > 1 {
         return Err(ContractError::HLS {
@@ -99,8 +111,12 @@ pub fn assert_hls_rules(deps: Deps, account_id: &str) -> ContractResult<Response
         }
     }
 
-    Ok(Response::new()
-        .add_attribute("action", "callback/assert_hls_rules")
-        .add_attribute("account_id", account_id)
-        .add_attribute("account_kind", AccountKind::HighLeveredStrategy.to_string()))
+    // Rule #4 - Perp positions are not correlated to any debt, HLS accounts can't hold them
+    if !perps.is_empty() {
+        return Err(ContractError::HLS {
+            reason: "Account has perp positions".to_string(),
+        });
+    }
+
+    Ok(())
 }
@@ -1,10 +1,15 @@
+pub mod account_stats;
+pub mod auto_deleverage;
 pub mod borrow;
 pub mod claim_astro_lp_rewards;
 pub mod claim_rewards;
 pub mod contract;
+pub mod delegation;
 pub mod deposit;
 pub mod error;
+pub mod estimate_liquidation;
 pub mod execute;
+pub mod flash_liquidate;
 pub mod health;
 pub mod hls;
 pub mod instantiate;
@@ -13,14 +18,21 @@ pub mod liquidate;
 pub mod liquidate_astro_lp;
 pub mod liquidate_deposit;
 pub mod liquidate_lend;
+pub mod liquidate_perp;
 pub mod migrations;
+pub mod perp;
+pub mod perp_vault;
+pub mod pyth;
 pub mod query;
 pub mod reclaim;
 pub mod refund;
 pub mod repay;
+pub mod simulate;
 pub mod stake_astro_lp;
 pub mod state;
 pub mod swap;
+pub mod transfer;
+pub mod trigger;
 pub mod unstake_astro_lp;
 pub mod update_coin_balances;
 pub mod update_config;
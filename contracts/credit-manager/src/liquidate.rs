@@ -1,5 +1,5 @@
-use cosmwasm_std::{Coin, DepsMut, QuerierWrapper, Uint128};
-use mars_liquidation::liquidation::calculate_liquidation_amounts;
+use cosmwasm_std::{Coin, Decimal, Deps, DepsMut, QuerierWrapper, Uint128};
+use mars_liquidation::liquidation;
 use mars_types::{adapters::oracle::Oracle, oracle::ActionKind, traits::Stringify};
 
 use crate::{
@@ -24,9 +24,27 @@ pub fn calculate_liquidation(
     request_coin: &str,
     request_coin_balance: Uint128,
 ) -> ContractResult<(Coin, Coin, Coin)> {
+    let (result, _) = compute_liquidation(
+        deps.as_ref(),
+        liquidatee_account_id,
+        debt_coin,
+        request_coin,
+        request_coin_balance,
+    )?;
+
+    Ok(result)
+}
+
+/// Same as [`calculate_liquidation`], also returning the liquidation bonus applied
+pub fn compute_liquidation(
+    deps: Deps,
+    liquidatee_account_id: &str,
+    debt_coin: &Coin,
+    request_coin: &str,
+    request_coin_balance: Uint128,
+) -> ContractResult<((Coin, Coin, Coin), Decimal)> {
     // Assert the liquidatee's credit account is liquidatable
-    let health =
-        query_health_values(deps.as_ref(), liquidatee_account_id, ActionKind::Liquidation)?;
+    let health = query_health_values(deps, liquidatee_account_id, ActionKind::Liquidation)?;
     if !health.liquidatable {
         return Err(ContractError::NotLiquidatable {
             account_id: liquidatee_account_id.to_string(),
@@ -36,7 +54,7 @@ pub fn calculate_liquidation(
 
     // Ensure debt repaid does not exceed liquidatee's total debt for denom
     let (total_debt_amount, _) =
-        current_debt_for_denom(deps.as_ref(), liquidatee_account_id, &debt_coin.denom)?;
+        current_debt_for_denom(deps, liquidatee_account_id, &debt_coin.denom)?;
 
     let params = PARAMS.load(deps.storage)?;
     let target_health_factor = params.query_target_health_factor(&deps.querier)?;
@@ -50,37 +68,36 @@ pub fn calculate_liquidation(
     let request_coin_price =
         oracle.query_price(&deps.querier, request_coin, ActionKind::Liquidation)?.price;
 
-    let (debt_amount_to_repay, request_amount_to_liquidate, request_amount_received_by_liquidator) =
-        calculate_liquidation_amounts(
-            request_coin_balance,
-            request_coin_price,
-            &request_coin_params,
-            total_debt_amount,
-            debt_coin.amount,
-            debt_coin_price,
-            target_health_factor,
-            &health.into(),
-        )?;
+    let amounts = liquidation::calculate_liquidation(
+        request_coin_balance,
+        request_coin_price,
+        &request_coin_params,
+        total_debt_amount,
+        debt_coin.amount,
+        debt_coin_price,
+        target_health_factor,
+        &health.into(),
+    )?;
 
     // (Debt Coin, Liquidator Request Coin, Liquidatee Request Coin)
     let result = (
         Coin {
             denom: debt_coin.denom.clone(),
-            amount: debt_amount_to_repay,
+            amount: amounts.debt_amount_to_repay,
         },
         Coin {
             denom: request_coin.to_string(),
-            amount: request_amount_received_by_liquidator,
+            amount: amounts.collateral_amount_received_by_liquidator,
         },
         Coin {
             denom: request_coin.to_string(),
-            amount: request_amount_to_liquidate,
+            amount: amounts.collateral_amount_to_liquidate,
         },
     );
 
     assert_liquidation_profitable(&deps.querier, &oracle, result.clone())?;
 
-    Ok(result)
+    Ok((result, amounts.liquidation_bonus))
 }
 
 /// In scenarios with small amounts or large gap between coin prices, there is a possibility
@@ -9,7 +9,7 @@ use crate::{
 };
 
 pub fn liquidate_astro_lp(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -48,8 +48,14 @@ pub fn liquidate_astro_lp(
     }
 
     // Liquidator pays down debt on behalf of liquidatee
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    let repay_msg = repay_debt(
+        &mut deps,
+        &env,
+        liquidator_account_id,
+        liquidatee_account_id,
+        &debt,
+        &liquidatee_request,
+    )?;
 
     // Liquidatee's LP coin withdrawn from Astro
     let withdraw_from_liquidatee_msg =
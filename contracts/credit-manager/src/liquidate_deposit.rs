@@ -1,7 +1,8 @@
-use cosmwasm_std::{Coin, CosmosMsg, DepsMut, Env, Response, Storage};
+use cosmwasm_std::{Coin, CosmosMsg, DepsMut, Env, Response};
 use mars_types::credit_manager::CallbackMsg;
 
 use crate::{
+    account_stats::record_liquidation_loss,
     error::{ContractError, ContractResult},
     liquidate::calculate_liquidation,
     state::{COIN_BALANCES, REWARDS_COLLECTOR},
@@ -9,7 +10,7 @@ use crate::{
 };
 
 pub fn liquidate_deposit(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -28,8 +29,14 @@ pub fn liquidate_deposit(
         request_coin_balance,
     )?;
 
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    let repay_msg = repay_debt(
+        &mut deps,
+        &env,
+        liquidator_account_id,
+        liquidatee_account_id,
+        &debt,
+        &liquidatee_request,
+    )?;
 
     // Transfer requested coin from liquidatee to liquidator
     decrement_coin_balance(deps.storage, liquidatee_account_id, &liquidatee_request)?;
@@ -57,17 +64,21 @@ pub fn liquidate_deposit(
         ))
 }
 
+/// Records the liquidatee's loss on the liquidation, `seized` being the coin taken from it in a
+/// denom priced by the oracle
 pub fn repay_debt(
-    storage: &mut dyn Storage,
+    deps: &mut DepsMut,
     env: &Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
     debt: &Coin,
+    seized: &Coin,
 ) -> ContractResult<CosmosMsg> {
     // Transfer debt coin from liquidator's coin balance to liquidatee
     // Will be used to pay off the debt via CallbackMsg::Repay {}
-    decrement_coin_balance(storage, liquidator_account_id, debt)?;
-    increment_coin_balance(storage, liquidatee_account_id, debt)?;
+    decrement_coin_balance(deps.storage, liquidator_account_id, debt)?;
+    increment_coin_balance(deps.storage, liquidatee_account_id, debt)?;
+    record_liquidation_loss(deps, liquidatee_account_id, debt, seized)?;
     let msg = (CallbackMsg::Repay {
         account_id: liquidatee_account_id.to_string(),
         coin: debt.into(),
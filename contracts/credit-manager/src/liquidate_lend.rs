@@ -9,7 +9,7 @@ use crate::{
 };
 
 pub fn liquidate_lend(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -36,8 +36,14 @@ pub fn liquidate_lend(
     )?;
 
     // Liquidator pays down debt on behalf of liquidatee
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    let repay_msg = repay_debt(
+        &mut deps,
+        &env,
+        liquidator_account_id,
+        liquidatee_account_id,
+        &debt,
+        &liquidatee_request,
+    )?;
 
     // Liquidatee's lent coin reclaimed from Red Bank
     let red_bank = RED_BANK.load(deps.storage)?;
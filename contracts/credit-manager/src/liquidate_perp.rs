@@ -0,0 +1,131 @@
+use cosmwasm_std::{Coin, DepsMut, Env, Int128, Response, Uint128};
+use mars_types::oracle::ActionKind;
+
+use crate::{
+    error::{ContractError, ContractResult},
+    health::query_health_values,
+    liquidate::calculate_liquidation,
+    liquidate_deposit::repay_debt,
+    perp::payable_profit,
+    state::{COIN_BALANCES, PERPS, REWARDS_COLLECTOR},
+    utils::{decrement_coin_balance, increment_coin_balance},
+};
+
+pub fn liquidate_perp(
+    mut deps: DepsMut,
+    env: Env,
+    liquidator_account_id: &str,
+    liquidatee_account_id: &str,
+    debt_coin: Coin,
+    perp_denom: &str,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+    let base_denom = perps.query_config(&deps.querier)?.base_denom;
+
+    // Without an order size, the unrealized PnL is the amount realized by closing the position
+    let position = perps
+        .query_position(&deps.querier, liquidatee_account_id, perp_denom, None, None)?
+        .position
+        .ok_or_else(|| ContractError::NoPerpPosition(perp_denom.to_string()))?;
+    let pnl = position.unrealized_pnl.pnl;
+    let mut pnl_coin = Coin {
+        denom: base_denom.clone(),
+        amount: Uint128::new(pnl.i128().unsigned_abs()),
+    };
+    if pnl > Int128::zero() {
+        pnl_coin.amount = payable_profit(&deps.querier, &perps, &pnl_coin)?;
+    }
+
+    // Base denom balance of the liquidatee once the position is closed
+    let base_denom_balance = COIN_BALANCES
+        .may_load(deps.storage, (liquidatee_account_id, base_denom.as_str()))?
+        .unwrap_or_default();
+    let base_denom_balance = if pnl < Int128::zero() {
+        base_denom_balance.checked_sub(pnl_coin.amount).map_err(|_| {
+            ContractError::InsufficientFunds {
+                requested: pnl_coin.amount,
+                available: base_denom_balance,
+            }
+        })?
+    } else {
+        base_denom_balance.checked_add(pnl_coin.amount)?
+    };
+
+    // Health has to be checked before any balance is updated
+    let liquidation = if debt_coin.amount.is_zero() {
+        let health =
+            query_health_values(deps.as_ref(), liquidatee_account_id, ActionKind::Liquidation)?;
+        if !health.liquidatable {
+            return Err(ContractError::NotLiquidatable {
+                account_id: liquidatee_account_id.to_string(),
+                lqdt_health_factor: health.liquidation_health_factor.to_string(),
+            });
+        }
+        None
+    } else {
+        Some(calculate_liquidation(
+            &deps,
+            liquidatee_account_id,
+            &debt_coin,
+            &base_denom,
+            base_denom_balance,
+        )?)
+    };
+
+    // Close the position, settling the realized PnL with the liquidatee's balance
+    let mut funds = vec![];
+    if pnl < Int128::zero() {
+        decrement_coin_balance(deps.storage, liquidatee_account_id, &pnl_coin)?;
+        funds.push(pnl_coin);
+    } else if pnl > Int128::zero() {
+        increment_coin_balance(deps.storage, liquidatee_account_id, &pnl_coin)?;
+    }
+    let close_msg = perps.execute_perp_order_msg(
+        liquidatee_account_id,
+        perp_denom,
+        Int128::zero().checked_sub(position.size)?,
+        Some(true),
+        funds,
+    )?;
+
+    let mut response = Response::new()
+        .add_message(close_msg)
+        .add_attribute("action", "liquidate_perp")
+        .add_attribute("account_id", liquidator_account_id)
+        .add_attribute("liquidatee_account_id", liquidatee_account_id)
+        .add_attribute("perp_denom", perp_denom)
+        .add_attribute("realized_pnl", pnl.to_string());
+
+    let Some((debt, liquidator_request, liquidatee_request)) = liquidation else {
+        return Ok(response);
+    };
+
+    let repay_msg = repay_debt(
+        &mut deps,
+        &env,
+        liquidator_account_id,
+        liquidatee_account_id,
+        &debt,
+        &liquidatee_request,
+    )?;
+
+    // Transfer base denom from liquidatee to liquidator
+    decrement_coin_balance(deps.storage, liquidatee_account_id, &liquidatee_request)?;
+    increment_coin_balance(deps.storage, liquidator_account_id, &liquidator_request)?;
+
+    // Transfer protocol fee to rewards-collector account
+    let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
+    let protocol_fee_coin = Coin {
+        denom: base_denom,
+        amount: liquidatee_request.amount.checked_sub(liquidator_request.amount)?,
+    };
+    increment_coin_balance(deps.storage, &rewards_collector_account, &protocol_fee_coin)?;
+
+    response = response
+        .add_message(repay_msg)
+        .add_attribute("coin_debt_repaid", debt.to_string())
+        .add_attribute("coin_liquidated", liquidatee_request.to_string())
+        .add_attribute("protocol_fee_coin", protocol_fee_coin.to_string());
+
+    Ok(response)
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Coin, DepsMut, Int128, MessageInfo, QuerierWrapper, Response, Uint128};
+use cw_utils::must_pay;
+use mars_types::adapters::perps::Perps;
+
+use crate::{
+    error::{ContractError, ContractResult},
+    state::PERPS,
+    utils::{decrement_coin_balance, increment_coin_balance},
+};
+
+/// Execute a perp order for the account.
+///
+/// The PnL realized by the order is queried from the perps contract beforehand (within the same
+/// block the query and the execution give the same result). A loss is paid from the account
+/// balance and sent along with the order, a profit is credited to the account balance right
+/// away and sent back by the perps contract during the execution. The perps contract caps the
+/// payout at its base denom balance, so the same cap is applied here.
+pub fn execute_perp_order(
+    deps: DepsMut,
+    account_id: &str,
+    denom: &str,
+    order_size: Int128,
+    reduce_only: Option<bool>,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+    let base_denom = perps.query_config(&deps.querier)?.base_denom;
+
+    let position =
+        perps.query_position(&deps.querier, account_id, denom, Some(order_size), reduce_only)?;
+    let pnl = match position.position {
+        Some(position) => position.unrealized_pnl.pnl,
+        // opening a new position only costs the opening fee
+        None => {
+            let opening_fee = perps.query_opening_fee(&deps.querier, denom, order_size)?.fee;
+            Int128::zero().checked_sub(Int128::try_from(opening_fee.amount)?)?
+        }
+    };
+
+    let mut pnl_coin = Coin {
+        denom: base_denom,
+        amount: Uint128::new(pnl.i128().unsigned_abs()),
+    };
+
+    let mut funds = vec![];
+    if pnl < Int128::zero() {
+        decrement_coin_balance(deps.storage, account_id, &pnl_coin)?;
+        funds.push(pnl_coin.clone());
+    } else if pnl > Int128::zero() {
+        pnl_coin.amount = payable_profit(&deps.querier, &perps, &pnl_coin)?;
+        increment_coin_balance(deps.storage, account_id, &pnl_coin)?;
+    }
+
+    let msg = perps.execute_perp_order_msg(account_id, denom, order_size, reduce_only, funds)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "execute_perp_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("denom", denom)
+        .add_attribute("order_size", order_size.to_string())
+        .add_attribute("realized_pnl", pnl.to_string()))
+}
+
+/// Profit the perps contract is able to pay out, capped at its base denom balance
+pub fn payable_profit(
+    querier: &QuerierWrapper,
+    perps: &Perps,
+    profit: &Coin,
+) -> ContractResult<Uint128> {
+    let available = querier.query_balance(perps.address(), &profit.denom)?.amount;
+    Ok(profit.amount.min(available))
+}
+
+/// Credit the profit of a position closed by the perps contract during deleveraging
+pub fn update_balance_after_deleverage(
+    deps: DepsMut,
+    info: MessageInfo,
+    account_id: String,
+    pnl: Int128,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+    if &info.sender != perps.address() {
+        return Err(ContractError::Unauthorized {
+            user: info.sender.to_string(),
+            action: "update balance after deleverage".to_string(),
+        });
+    }
+
+    let base_denom = perps.query_config(&deps.querier)?.base_denom;
+    let amount = must_pay(&info, &base_denom)?;
+    increment_coin_balance(
+        deps.storage,
+        &account_id,
+        &Coin {
+            denom: base_denom,
+            amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_balance_after_deleverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("pnl", pnl.to_string())
+        .add_attribute("amount", amount))
+}
@@ -0,0 +1,83 @@
+use cosmwasm_std::{Coin, DepsMut, Env, Response, Uint128};
+use mars_types::credit_manager::{ActionAmount, ActionCoin, ChangeExpected};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    state::{COIN_BALANCES, PERPS},
+    utils::{decrement_coin_balance, update_balance_msg},
+};
+
+pub fn deposit_to_perp_vault(
+    deps: DepsMut,
+    account_id: &str,
+    coin: &ActionCoin,
+    max_receivable_shares: Option<Uint128>,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+
+    let amount = match coin.amount {
+        ActionAmount::Exact(amount) => amount,
+        ActionAmount::AccountBalance => {
+            COIN_BALANCES.may_load(deps.storage, (account_id, &coin.denom))?.unwrap_or_default()
+        }
+    };
+    if amount.is_zero() {
+        return Err(ContractError::NoAmount);
+    }
+
+    let coin_to_deposit = Coin {
+        denom: coin.denom.clone(),
+        amount,
+    };
+    decrement_coin_balance(deps.storage, account_id, &coin_to_deposit)?;
+
+    let deposit_msg = perps.deposit_msg(account_id, &coin_to_deposit, max_receivable_shares)?;
+
+    Ok(Response::new()
+        .add_message(deposit_msg)
+        .add_attribute("action", "deposit_to_perp_vault")
+        .add_attribute("account_id", account_id)
+        .add_attribute("coin_deposited", coin_to_deposit.to_string()))
+}
+
+pub fn unlock_from_perp_vault(
+    deps: DepsMut,
+    account_id: &str,
+    shares: Uint128,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+    let unlock_msg = perps.unlock_msg(account_id, shares)?;
+
+    Ok(Response::new()
+        .add_message(unlock_msg)
+        .add_attribute("action", "unlock_from_perp_vault")
+        .add_attribute("account_id", account_id)
+        .add_attribute("shares", shares))
+}
+
+pub fn withdraw_from_perp_vault(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    min_receive: Option<Uint128>,
+) -> ContractResult<Response> {
+    let perps = PERPS.load(deps.storage)?;
+    let base_denom = perps.query_config(&deps.querier)?.base_denom;
+
+    let withdraw_msg = perps.withdraw_msg(account_id, min_receive)?;
+
+    // the withdrawn amount is only known after the withdrawal
+    let update_coin_balance_msg = update_balance_msg(
+        &deps.querier,
+        &env.contract.address,
+        account_id,
+        &base_denom,
+        ChangeExpected::Increase,
+    )?;
+
+    Ok(Response::new()
+        .add_message(withdraw_msg)
+        .add_message(update_coin_balance_msg)
+        .add_attribute("action", "withdraw_from_perp_vault")
+        .add_attribute("account_id", account_id))
+}
@@ -0,0 +1,80 @@
+use cosmwasm_std::{Binary, Coins, DepsMut, Env, MessageInfo, Response, Uint128};
+use mars_types::credit_manager::{Action, CallbackMsg};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    state::{PYTH, REENTRANCY_GUARD},
+};
+
+/// Splits the VAAs of the `UpdatePythPrices` actions from the rest of the actions
+pub fn take_pyth_price_updates(actions: Vec<Action>) -> (Vec<Binary>, Vec<Action>) {
+    let mut vaa_data = vec![];
+    let mut other_actions = vec![];
+    for action in actions {
+        match action {
+            Action::UpdatePythPrices {
+                vaa_data: data,
+            } => vaa_data.extend(data),
+            action => other_actions.push(action),
+        }
+    }
+    (vaa_data, other_actions)
+}
+
+/// Pushes the VAAs to the Pyth contract and dispatches the remaining actions of the account in a
+/// callback, so the health of the account is only queried once the prices are updated.
+/// The reentrancy guard is held while the Pyth contract is called and handed over to the
+/// dispatched actions.
+/// Same as the red bank, the exact update fee has to be sent on top of the deposited coins.
+pub fn update_pyth_prices(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    account_id: &str,
+    vaa_data: Vec<Binary>,
+    actions: Vec<Action>,
+    response: Response,
+) -> ContractResult<Response> {
+    let Some(pyth) = PYTH.may_load(deps.storage)? else {
+        return Err(ContractError::InvalidPythPriceUpdate {
+            reason: "pyth contract is not set".to_string(),
+        });
+    };
+
+    REENTRANCY_GUARD.try_lock(deps.storage)?;
+
+    let fee = pyth.query_update_fee(&deps.querier, &vaa_data)?;
+
+    let mut funds = Coins::try_from(info.funds)?;
+    if !fee.amount.is_zero() {
+        let deposited = actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Deposit(coin) if coin.denom == fee.denom => Some(coin.amount),
+                _ => None,
+            })
+            .sum::<Uint128>();
+        let received = funds.amount_of(&fee.denom).saturating_sub(deposited);
+        if received != fee.amount {
+            return Err(ContractError::InvalidPythPriceUpdate {
+                reason: format!("update fee of {fee} required, received: {received}"),
+            });
+        }
+        funds.sub(fee.clone())?;
+    }
+
+    let dispatch_msg = CallbackMsg::DispatchActions {
+        account_id: account_id.to_string(),
+        sender: info.sender,
+        actions,
+        funds: funds.into_vec(),
+    }
+    .into_cosmos_msg(&env.contract.address)?;
+
+    Ok(response
+        .add_message(pyth.update_price_feeds_msg(vaa_data, fee.clone())?)
+        .add_message(dispatch_msg)
+        .add_attribute("action", "update_pyth_prices")
+        .add_attribute("account_id", account_id.to_string())
+        .add_attribute("update_fee", fee.to_string()))
+}
@@ -1,24 +1,33 @@
 use cosmwasm_std::{Coin, Decimal, Deps, Env, Order, StdResult};
-use cw_paginate::{paginate_map, paginate_map_query, PaginationResponse, DEFAULT_LIMIT, MAX_LIMIT};
+use cw_paginate::{
+    paginate_map, paginate_map_query, paginate_prefix_query, PaginationResponse, DEFAULT_LIMIT,
+    MAX_LIMIT,
+};
 use cw_storage_plus::Bound;
 use mars_types::{
     adapters::vault::{Vault, VaultBase, VaultPosition, VaultPositionValue, VaultUnchecked},
     credit_manager::{
-        Account, CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, Positions,
-        SharesResponseItem, VaultBinding, VaultPositionResponseItem, VaultUtilizationResponse,
+        Account, AccountStats, AutoDeleveragePolicy, AutoDeleveragePolicyResponse,
+        CoinBalanceResponseItem, ConfigResponse, DebtAmount, DebtShares, DelegationResponse,
+        Positions, SharesResponseItem, TriggerOrderResponse, VaultBinding,
+        VaultPositionResponseItem, VaultUtilizationResponse,
     },
     health::AccountKind,
     oracle::ActionKind,
+    perps::PerpPosition,
 };
 
 use crate::{
+    delegation::is_delegation_active,
     error::ContractResult,
     state::{
-        ACCOUNT_KINDS, ACCOUNT_NFT, COIN_BALANCES, DEBT_SHARES, HEALTH_CONTRACT, INCENTIVES,
-        MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, PARAMS, RED_BANK, REWARDS_COLLECTOR,
-        SWAPPER, SWAP_FEE, TOTAL_DEBT_SHARES, VAULTS, VAULT_POSITIONS, ZAPPER,
+        ACCOUNT_KINDS, ACCOUNT_NFT, ACCOUNT_STATS, ACCOUNT_STATS_ENABLED, AUTO_DELEVERAGE_POLICIES,
+        COIN_BALANCES, DEBT_SHARES, DELEGATIONS, HEALTH_CONTRACT, INCENTIVES, KEEPER_FEE_CONFIG,
+        LAST_BORROW_HEIGHTS, MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, PARAMS, PERPS,
+        PYTH, RED_BANK, REWARDS_COLLECTOR, SWAPPER, SWAP_FEE, TOTAL_DEBT_SHARES, TRIGGER_ORDERS,
+        VAULTS, VAULT_POSITIONS, ZAPPER,
     },
-    utils::debt_shares_to_amount,
+    utils::{debt_shares_to_amount, query_nft_token_owner},
     vault::vault_utilization_in_deposit_cap_denom,
 };
 
@@ -59,6 +68,10 @@ pub fn query_config(deps: Deps) -> ContractResult<ConfigResponse> {
         zapper: ZAPPER.load(deps.storage)?.address().into(),
         health_contract: HEALTH_CONTRACT.load(deps.storage)?.address().into(),
         rewards_collector: REWARDS_COLLECTOR.may_load(deps.storage)?,
+        perps: PERPS.may_load(deps.storage)?.map(|p| p.address().into()),
+        keeper_fee_config: KEEPER_FEE_CONFIG.may_load(deps.storage)?,
+        account_stats_enabled: ACCOUNT_STATS_ENABLED.may_load(deps.storage)?.unwrap_or_default(),
+        pyth: PYTH.may_load(deps.storage)?.map(|p| p.address().into()),
     })
 }
 
@@ -77,9 +90,17 @@ pub fn query_positions(deps: Deps, account_id: &str) -> ContractResult<Positions
         staked_astro_lps: INCENTIVES
             .load(deps.storage)?
             .query_all_staked_astro_lp_coins(&deps.querier, account_id)?,
+        perps: query_perp_positions(deps, account_id)?,
     })
 }
 
+fn query_perp_positions(deps: Deps, account_id: &str) -> ContractResult<Vec<PerpPosition>> {
+    let Some(perps) = PERPS.may_load(deps.storage)? else {
+        return Ok(vec![]);
+    };
+    Ok(perps.query_positions_by_account(&deps.querier, account_id, ActionKind::Default)?.positions)
+}
+
 pub fn query_all_coin_balances(
     deps: Deps,
     start_after: Option<(String, String)>,
@@ -283,3 +304,151 @@ pub fn query_vault_bindings(
         })
     })
 }
+
+pub fn query_account_trigger_orders(
+    deps: Deps,
+    account_id: &str,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<TriggerOrderResponse>> {
+    let start = start_after.map(|order_id| Bound::ExclusiveRaw(order_id.into_bytes()));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    paginate_prefix_query(
+        &TRIGGER_ORDERS,
+        deps.storage,
+        account_id,
+        start,
+        Some(limit),
+        |_, order| {
+            Ok(TriggerOrderResponse {
+                account_id: account_id.to_string(),
+                order,
+            })
+        },
+    )
+}
+
+pub fn query_all_trigger_orders(
+    deps: Deps,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<TriggerOrderResponse>> {
+    let start = start_after
+        .as_ref()
+        .map(|(account_id, order_id)| Bound::exclusive((account_id.as_str(), order_id.as_str())));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    paginate_map_query(
+        &TRIGGER_ORDERS,
+        deps.storage,
+        start,
+        Some(limit),
+        |(account_id, _), order| {
+            Ok(TriggerOrderResponse {
+                account_id,
+                order,
+            })
+        },
+    )
+}
+
+pub fn query_auto_deleverage_policy(
+    deps: Deps,
+    account_id: &str,
+) -> ContractResult<Option<AutoDeleveragePolicy>> {
+    Ok(AUTO_DELEVERAGE_POLICIES.may_load(deps.storage, account_id)?)
+}
+
+pub fn query_all_auto_deleverage_policies(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<AutoDeleveragePolicyResponse>> {
+    let start = start_after.as_ref().map(|account_id| Bound::exclusive(account_id.as_str()));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    paginate_map_query(
+        &AUTO_DELEVERAGE_POLICIES,
+        deps.storage,
+        start,
+        Some(limit),
+        |account_id, policy| {
+            Ok(AutoDeleveragePolicyResponse {
+                account_id,
+                policy,
+            })
+        },
+    )
+}
+
+pub fn query_delegation(
+    deps: Deps,
+    env: Env,
+    account_id: &str,
+    delegate: &str,
+) -> ContractResult<Option<DelegationResponse>> {
+    let Some((granter, delegation)) = DELEGATIONS.may_load(deps.storage, (account_id, delegate))?
+    else {
+        return Ok(None);
+    };
+
+    let owner = query_nft_token_owner(deps, account_id)?;
+    if !is_delegation_active(&env, &owner, &granter, &delegation) {
+        return Ok(None);
+    }
+
+    Ok(Some(DelegationResponse {
+        account_id: account_id.to_string(),
+        delegate: delegate.to_string(),
+        granter,
+        delegation,
+    }))
+}
+
+/// Inactive delegations (expired or granted by a previous owner) are left out of the page
+pub fn query_account_delegations(
+    deps: Deps,
+    env: Env,
+    account_id: &str,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<DelegationResponse>> {
+    let start = start_after.map(|delegate| Bound::ExclusiveRaw(delegate.into_bytes()));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    let owner = query_nft_token_owner(deps, account_id)?;
+
+    let res = paginate_prefix_query(
+        &DELEGATIONS,
+        deps.storage,
+        account_id,
+        start,
+        Some(limit),
+        |delegate, (granter, delegation)| {
+            Ok(DelegationResponse {
+                account_id: account_id.to_string(),
+                delegate,
+                granter,
+                delegation,
+            })
+        },
+    )?;
+
+    Ok(PaginationResponse {
+        data: res
+            .data
+            .into_iter()
+            .filter(|d| is_delegation_active(&env, &owner, &d.granter, &d.delegation))
+            .collect(),
+        metadata: res.metadata,
+    })
+}
+
+pub fn query_last_borrow_height(deps: Deps, account_id: &str) -> ContractResult<Option<u64>> {
+    Ok(LAST_BORROW_HEIGHTS.may_load(deps.storage, account_id)?)
+}
+
+pub fn query_account_stats(deps: Deps, account_id: &str) -> ContractResult<AccountStats> {
+    Ok(ACCOUNT_STATS.may_load(deps.storage, account_id)?.unwrap_or_default())
+}
@@ -8,6 +8,7 @@ use cw_utils::one_coin;
 use mars_types::credit_manager::{ActionCoin, CallbackMsg::Repay, ExecuteMsg};
 
 use crate::{
+    account_stats::{record_deposit, record_repay},
     error::{ContractError, ContractResult},
     state::{COIN_BALANCES, DEBT_SHARES, RED_BANK, TOTAL_DEBT_SHARES},
     utils::{debt_shares_to_amount, decrement_coin_balance, increment_coin_balance},
@@ -46,6 +47,7 @@ pub fn repay(deps: DepsMut, account_id: &str, coin: &ActionCoin) -> ContractResu
     )?;
 
     decrement_coin_balance(deps.storage, account_id, &coin_to_repay)?;
+    record_repay(deps.storage, account_id, debt_amount, &coin_to_repay)?;
 
     let red_bank = RED_BANK.load(deps.storage)?;
     let red_bank_repay_msg = red_bank.repay_msg(&coin_to_repay)?;
@@ -133,6 +135,7 @@ pub fn repay_from_wallet(
     };
 
     increment_coin_balance(deps.storage, &account_id, &coin_to_repay)?;
+    record_deposit(deps.storage, &account_id, &coin_to_repay)?;
 
     let repay_callback_msg = CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: env.contract.address.to_string(),
@@ -0,0 +1,399 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{Coin, Decimal, Deps, Env, Int128, SignedDecimal, Uint128};
+use mars_rover_health_computer::{apply_actions, coin_amount, ActionsEstimator, PerpOrderEstimate};
+use mars_types::{
+    adapters::vault::{UpdateType, VaultPositionUpdate, VaultUnchecked, VaultUnlockingPosition},
+    credit_manager::{Action, ActionsRejection, Positions, SimulateActionsResponse},
+    health::{AccountKind, HealthState, HealthValuesResponse},
+    oracle::ActionKind,
+    params::TotalDepositResponse,
+    perps::{PerpPosition, PnlAmounts},
+    swapper::SwapperRoute,
+};
+
+use crate::{
+    borrow::query_debt_shares_to_add,
+    error::{ContractError, ContractResult},
+    health::query_health_state,
+    hls::assert_hls_positions,
+    perp::payable_profit,
+    query::query_positions,
+    state::{HEALTH_CONTRACT, ORACLE, PARAMS, PERPS, SWAPPER, SWAP_FEE, ZAPPER},
+    trigger::load_trigger_order,
+    utils::get_account_kind,
+};
+
+/// Estimates the amounts received from swaps and zaps with the swapper and zapper, the debt
+/// shares with the current total debt, and vault and perp actions with the queries of the vaults
+/// and the perps contract
+struct ContractEstimator<'a> {
+    deps: Deps<'a>,
+    env: &'a Env,
+}
+
+impl ActionsEstimator for ContractEstimator<'_> {
+    type Error = ContractError;
+
+    fn debt_shares_to_add(&self, coin: &Coin) -> ContractResult<Uint128> {
+        query_debt_shares_to_add(self.deps, coin)
+    }
+
+    fn swap_fee(&self) -> ContractResult<Decimal> {
+        Ok(SWAP_FEE.load(self.deps.storage)?)
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        coin_in: &Coin,
+        denom_out: &str,
+        route: &Option<SwapperRoute>,
+    ) -> ContractResult<Uint128> {
+        Ok(SWAPPER.load(self.deps.storage)?.estimate_exact_in_swap(
+            &self.deps.querier,
+            coin_in,
+            denom_out,
+            route.clone(),
+        )?)
+    }
+
+    fn estimate_provide_liquidity(
+        &self,
+        coins_in: &[Coin],
+        lp_token_out: &str,
+        _slippage: Decimal,
+    ) -> ContractResult<Uint128> {
+        Ok(ZAPPER.load(self.deps.storage)?.estimate_provide_liquidity(
+            &self.deps.querier,
+            lp_token_out,
+            coins_in,
+        )?)
+    }
+
+    fn estimate_withdraw_liquidity(
+        &self,
+        lp_token: &Coin,
+        _slippage: Decimal,
+    ) -> ContractResult<Vec<Coin>> {
+        Ok(ZAPPER
+            .load(self.deps.storage)?
+            .estimate_withdraw_liquidity(&self.deps.querier, lp_token)?)
+    }
+
+    fn estimate_enter_vault(
+        &self,
+        vault: &VaultUnchecked,
+        coin: &Coin,
+    ) -> ContractResult<VaultPositionUpdate> {
+        let vault = vault.check(self.deps.api)?;
+        let shares = vault.query_preview_deposit(&self.deps.querier, coin.amount)?;
+
+        // Same as `update_vault_coin_balance`
+        Ok(match vault.query_lockup_duration(&self.deps.querier).ok() {
+            None => VaultPositionUpdate::Unlocked(UpdateType::Increment(shares)),
+            Some(_) => VaultPositionUpdate::Locked(UpdateType::Increment(shares)),
+        })
+    }
+
+    fn estimate_exit_vault(&self, vault: &VaultUnchecked, amount: Uint128) -> ContractResult<Coin> {
+        let vault = vault.check(self.deps.api)?;
+        Ok(Coin {
+            denom: vault.query_info(&self.deps.querier)?.base_token,
+            amount: vault.query_preview_redeem(&self.deps.querier, amount)?,
+        })
+    }
+
+    fn estimate_request_vault_unlock(
+        &self,
+        vault: &VaultUnchecked,
+        amount: Uint128,
+    ) -> ContractResult<VaultUnlockingPosition> {
+        Ok(VaultUnlockingPosition {
+            id: 0,
+            coin: self.estimate_exit_vault(vault, amount)?,
+        })
+    }
+
+    fn assert_vault_unlock_released(&self, vault: &VaultUnchecked, id: u64) -> ContractResult<()> {
+        let vault = vault.check(self.deps.api)?;
+        let unlocking_position = vault.query_unlocking_position(&self.deps.querier, id)?;
+        if !unlocking_position.release_at.is_expired(&self.env.block) {
+            return Err(ContractError::UnlockNotReady {});
+        }
+        Ok(())
+    }
+
+    fn estimate_withdraw_from_perp_vault(&self, account_id: &str) -> ContractResult<Coin> {
+        let perps = PERPS.load(self.deps.storage)?;
+        let base_denom = perps.query_config(&self.deps.querier)?.base_denom;
+
+        // Same as the perps vault, all the unlocks whose cooldown has ended are withdrawn
+        let current_time = self.env.block.time.seconds();
+        let amount = perps
+            .query_vault_position(&self.deps.querier, &self.env.contract.address, account_id)?
+            .map(|position| {
+                position
+                    .unlocks
+                    .iter()
+                    .filter(|unlock| unlock.cooldown_end <= current_time)
+                    .map(|unlock| unlock.amount)
+                    .sum::<Uint128>()
+            })
+            .unwrap_or_default();
+
+        Ok(Coin {
+            denom: base_denom,
+            amount,
+        })
+    }
+
+    fn estimate_perp_order(
+        &self,
+        account_id: &str,
+        denom: &str,
+        order_size: Int128,
+        reduce_only: Option<bool>,
+    ) -> ContractResult<PerpOrderEstimate> {
+        let perps = PERPS.load(self.deps.storage)?;
+        let base_denom = perps.query_config(&self.deps.querier)?.base_denom;
+
+        // Same as `execute_perp_order`. The position is re-based at the current price.
+        let position = perps
+            .query_position(&self.deps.querier, account_id, denom, Some(order_size), reduce_only)?
+            .position;
+        let (realized_pnl, position) = match position {
+            Some(mut position) => {
+                let order_pnl = position.unrealized_pnl.clone();
+                position.realized_pnl.add(&order_pnl)?;
+                position.size = position.size.checked_add(order_size)?;
+                position.entry_price = position.current_price;
+                position.unrealized_pnl = PnlAmounts::default();
+                (order_pnl.pnl, Some(position).filter(|p| !p.size.is_zero()))
+            }
+            None => {
+                let opening_fee =
+                    perps.query_opening_fee(&self.deps.querier, denom, order_size)?.fee;
+                let pnl = Int128::zero().checked_sub(Int128::try_from(opening_fee.amount)?)?;
+
+                let oracle = ORACLE.load(self.deps.storage)?;
+                let denom_price =
+                    oracle.query_price(&self.deps.querier, denom, ActionKind::Default)?.price;
+                let base_denom_price =
+                    oracle.query_price(&self.deps.querier, &base_denom, ActionKind::Default)?.price;
+                let current_price = denom_price.checked_div(base_denom_price)?;
+
+                let position = PerpPosition {
+                    denom: denom.to_string(),
+                    base_denom: base_denom.clone(),
+                    size: order_size,
+                    entry_price: current_price,
+                    current_price,
+                    denom_price,
+                    entry_accrued_funding_per_unit_in_base_denom: SignedDecimal::zero(),
+                    unrealized_pnl: PnlAmounts::default(),
+                    realized_pnl: PnlAmounts {
+                        opening_fee: pnl,
+                        pnl,
+                        ..Default::default()
+                    },
+                };
+                (pnl, Some(position))
+            }
+        };
+
+        let realized_pnl = if realized_pnl > Int128::zero() {
+            let profit = Coin {
+                denom: base_denom.clone(),
+                amount: Uint128::new(realized_pnl.i128().unsigned_abs()),
+            };
+            Int128::try_from(payable_profit(&self.deps.querier, &perps, &profit)?)?
+        } else {
+            realized_pnl
+        };
+
+        Ok(PerpOrderEstimate {
+            realized_pnl,
+            base_denom,
+            position,
+        })
+    }
+
+    fn trigger_order_keeper_fee(
+        &self,
+        account_id: &str,
+        trigger_order_id: &str,
+    ) -> ContractResult<Coin> {
+        Ok(load_trigger_order(self.deps, account_id, trigger_order_id)?.keeper_fee)
+    }
+}
+
+/// Applies the actions to a copy of the account's positions the same way `dispatch_actions` and
+/// the callbacks would, then runs the checks made at the end of `UpdateCreditAccount` against
+/// the projected positions.
+pub fn simulate_actions(
+    deps: Deps,
+    env: &Env,
+    account_id: &str,
+    actions: Vec<Action>,
+) -> ContractResult<SimulateActionsResponse> {
+    let initial_positions = query_positions(deps, account_id)?;
+    let mut positions = initial_positions.clone();
+
+    // Same as in `dispatch_actions`, deposit / repay actions don't require health check
+    let no_health_check = actions.iter().all(|action| {
+        matches!(
+            action,
+            Action::Deposit(..)
+                | Action::Repay {
+                    recipient_account_id: None,
+                    ..
+                }
+        )
+    });
+    let prev_health_state = if !no_health_check {
+        Some(query_health_state(deps, account_id, ActionKind::Default)?)
+    } else {
+        None
+    };
+
+    apply_actions(
+        &mut positions,
+        &actions,
+        &ContractEstimator {
+            deps,
+            env,
+        },
+    )?;
+
+    let kind = get_account_kind(deps.storage, account_id)?;
+    let health = HEALTH_CONTRACT.load(deps.storage)?.query_health_values_for_positions(
+        &deps.querier,
+        &positions,
+        kind.clone(),
+        ActionKind::Default,
+    )?;
+
+    // Same order as the callbacks at the end of `dispatch_actions`
+    let mut rejections = vec![];
+
+    if kind == AccountKind::HighLeveredStrategy {
+        match assert_hls_positions(deps, positions.clone()) {
+            Ok(()) => {}
+            Err(ContractError::HLS {
+                reason,
+            }) => rejections.push(ActionsRejection::HlsRules {
+                reason,
+            }),
+            Err(err) => return Err(err),
+        }
+    }
+
+    if let Some(prev_health_state) = prev_health_state {
+        if is_max_ltv_weakened(&prev_health_state, &health) {
+            rejections.push(ActionsRejection::AboveMaxLtv {
+                max_ltv_health_factor: health.max_ltv_health_factor,
+            });
+        }
+    }
+
+    rejections.extend(query_deposit_cap_rejections(
+        deps,
+        &initial_positions,
+        &positions,
+        denoms_for_cap_check(&actions),
+    )?);
+
+    Ok(SimulateActionsResponse {
+        positions,
+        health,
+        rejections,
+    })
+}
+
+/// Same rules as `assert_max_ltv`
+fn is_max_ltv_weakened(prev_health_state: &HealthState, health: &HealthValuesResponse) -> bool {
+    if !health.above_max_ltv {
+        return false;
+    }
+
+    match prev_health_state {
+        HealthState::Healthy => true,
+        HealthState::Unhealthy {
+            max_ltv_health_factor: prev_hf,
+        } => health.max_ltv_health_factor.map_or(false, |new_hf| *prev_hf > new_hf),
+    }
+}
+
+/// Denoms whose deposited amount may go up. `true` if the deposit cap is strictly enforced
+/// (Deposit action), `false` if only an increase of the total deposits is checked.
+fn denoms_for_cap_check(actions: &[Action]) -> BTreeMap<String, bool> {
+    let mut denoms = BTreeMap::new();
+    for action in actions {
+        match action {
+            Action::Deposit(coin) => {
+                denoms.insert(coin.denom.clone(), true);
+            }
+            Action::SwapExactIn {
+                denom_out,
+                ..
+            } => {
+                denoms.entry(denom_out.clone()).or_insert(false);
+            }
+            Action::ProvideLiquidity {
+                lp_token_out,
+                ..
+            } => {
+                denoms.entry(lp_token_out.clone()).or_insert(false);
+            }
+            _ => {}
+        }
+    }
+    denoms
+}
+
+/// Same rules as `assert_deposit_caps`. The change of the coins held by the account (deposited
+/// or lent) is added to the current total deposits.
+fn query_deposit_cap_rejections(
+    deps: Deps,
+    initial_positions: &Positions,
+    positions: &Positions,
+    denoms_for_cap_check: BTreeMap<String, bool>,
+) -> ContractResult<Vec<ActionsRejection>> {
+    let params = PARAMS.load(deps.storage)?;
+
+    let mut rejections = vec![];
+    for (denom, enforced) in denoms_for_cap_check {
+        // Asset is not found (not whitelisted) and it doesn't count towards the cap
+        if params.query_asset_params(&deps.querier, &denom)?.is_none() {
+            continue;
+        }
+
+        let TotalDepositResponse {
+            denom,
+            amount,
+            cap,
+        } = params.query_total_deposit(&deps.querier, &denom)?;
+
+        let held_before = coin_amount(&initial_positions.deposits, &denom)
+            .checked_add(coin_amount(&initial_positions.lends, &denom))?;
+        let held_after = coin_amount(&positions.deposits, &denom)
+            .checked_add(coin_amount(&positions.lends, &denom))?;
+        let new_amount = amount.checked_add(held_after)?.saturating_sub(held_before);
+
+        if !enforced && new_amount <= amount {
+            continue;
+        }
+
+        if new_amount > cap {
+            rejections.push(ActionsRejection::AboveDepositCap {
+                new_value: Coin {
+                    denom,
+                    amount: new_amount,
+                },
+                maximum: cap,
+            });
+        }
+    }
+
+    Ok(rejections)
+}
@@ -4,8 +4,12 @@ use mars_owner::Owner;
 use mars_types::{
     adapters::{
         account_nft::AccountNft, health::HealthContract, incentives::Incentives, oracle::Oracle,
-        params::Params, red_bank::RedBank, rewards_collector::RewardsCollector, swapper::Swapper,
-        vault::VaultPositionAmount, zapper::Zapper,
+        params::Params, perps::Perps, pyth::Pyth, red_bank::RedBank,
+        rewards_collector::RewardsCollector, swapper::Swapper, vault::VaultPositionAmount,
+        zapper::Zapper,
+    },
+    credit_manager::{
+        AccountStats, AutoDeleveragePolicy, Delegation, KeeperFeeConfig, TriggerOrder,
     },
     health::AccountKind,
 };
@@ -22,6 +26,8 @@ pub const ZAPPER: Item<Zapper> = Item::new("zapper");
 pub const HEALTH_CONTRACT: Item<HealthContract> = Item::new("health_contract");
 pub const PARAMS: Item<Params> = Item::new("params");
 pub const INCENTIVES: Item<Incentives> = Item::new("incentives");
+pub const PERPS: Item<Perps> = Item::new("perps");
+pub const PYTH: Item<Pyth> = Item::new("pyth");
 
 // Config
 pub const OWNER: Owner = Owner::new("owner");
@@ -34,6 +40,7 @@ pub const ACCOUNT_KINDS: Map<&str, AccountKind> = Map::new("account_types"); //
 pub const COIN_BALANCES: Map<(&str, &str), Uint128> = Map::new("coin_balance"); // Map<(AccountId, Denom), Amount>
 pub const DEBT_SHARES: Map<(&str, &str), Uint128> = Map::new("debt_shares"); // Map<(AccountId, Denom), Shares>
 pub const TOTAL_DEBT_SHARES: Map<&str, Uint128> = Map::new("total_debt_shares"); // Map<Denom, Shares>
+pub const LAST_BORROW_HEIGHTS: Map<&str, u64> = Map::new("last_borrow_heights"); // Map<AccountId, BlockHeight>
 
 pub const VAULT_POSITIONS: Map<(&str, Addr), VaultPositionAmount> = Map::new("vault_positions"); // Map<(AccountId, VaultAddr), VaultPositionAmount>
 
@@ -49,3 +56,19 @@ pub const VAULTS: Map<&str, Addr> = Map::new("vaults");
 
 // Swap fee
 pub const SWAP_FEE: Item<Decimal> = Item::new("swap_fee");
+
+// Trigger orders
+pub const KEEPER_FEE_CONFIG: Item<KeeperFeeConfig> = Item::new("keeper_fee_config");
+pub const NEXT_TRIGGER_ID: Item<u64> = Item::new("next_trigger_id");
+pub const TRIGGER_ORDERS: Map<(&str, &str), TriggerOrder> = Map::new("trigger_orders"); // Map<(AccountId, OrderId), TriggerOrder>
+
+// Auto-deleveraging
+pub const AUTO_DELEVERAGE_POLICIES: Map<&str, AutoDeleveragePolicy> =
+    Map::new("auto_deleverage_policies"); // Map<AccountId, AutoDeleveragePolicy>
+
+// Delegations
+pub const DELEGATIONS: Map<(&str, &str), (String, Delegation)> = Map::new("delegations"); // Map<(AccountId, Delegate), (Granter, Delegation)>
+
+// Account stats
+pub const ACCOUNT_STATS_ENABLED: Item<bool> = Item::new("account_stats_enabled");
+pub const ACCOUNT_STATS: Map<&str, AccountStats> = Map::new("account_stats"); // Map<AccountId, AccountStats>
@@ -5,6 +5,7 @@ use mars_types::{
 };
 
 use crate::{
+    account_stats::record_swap_fee,
     error::{ContractError, ContractResult},
     state::{COIN_BALANCES, REWARDS_COLLECTOR, SWAPPER, SWAP_FEE},
     utils::{decrement_coin_balance, increment_coin_balance, update_balance_msg},
@@ -47,6 +48,7 @@ pub fn swap_exact_in(
     };
     let rewards_collector_account = REWARDS_COLLECTOR.load(deps.storage)?.account_id;
     increment_coin_balance(deps.storage, &rewards_collector_account, &rc_coin)?;
+    record_swap_fee(deps.storage, account_id, &rc_coin)?;
 
     // Updates coin balances for account after the swap has taken place
     let update_coin_balance_msg = update_balance_msg(
@@ -0,0 +1,63 @@
+use cosmwasm_std::{Deps, DepsMut, Response};
+use mars_types::{credit_manager::ActionCoin, health::AccountKind};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    utils::{
+        decrement_coin_balance, get_account_kind, increment_coin_balance, query_nft_token_owner,
+    },
+    withdraw::get_withdraw_amount,
+};
+
+/// Coins can only be moved between different accounts of the same owner. Fund manager accounts
+/// can't send or receive coins this way, as their funds belong to the vault.
+pub fn assert_transfer_recipient(
+    deps: Deps,
+    account_id: &str,
+    recipient_account_id: &str,
+) -> ContractResult<()> {
+    if account_id == recipient_account_id {
+        return Err(ContractError::InvalidTransfer {
+            reason: "recipient has to be another account".to_string(),
+        });
+    }
+
+    for id in [account_id, recipient_account_id] {
+        if let AccountKind::FundManager {
+            ..
+        } = get_account_kind(deps.storage, id)?
+        {
+            return Err(ContractError::InvalidTransfer {
+                reason: format!("account {id} is a fund manager account"),
+            });
+        }
+    }
+
+    let owner = query_nft_token_owner(deps, account_id)?;
+    let recipient_owner = query_nft_token_owner(deps, recipient_account_id)?;
+    if owner != recipient_owner {
+        return Err(ContractError::InvalidTransfer {
+            reason: format!("account {recipient_account_id} is not owned by {owner}"),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn transfer_to_account(
+    deps: DepsMut,
+    account_id: &str,
+    recipient_account_id: &str,
+    coin: &ActionCoin,
+) -> ContractResult<Response> {
+    let coin_to_transfer = get_withdraw_amount(deps.as_ref(), account_id, coin)?;
+
+    decrement_coin_balance(deps.storage, account_id, &coin_to_transfer)?;
+    increment_coin_balance(deps.storage, recipient_account_id, &coin_to_transfer)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "callback/transfer_to_account")
+        .add_attribute("account_id", account_id)
+        .add_attribute("recipient_account_id", recipient_account_id)
+        .add_attribute("coin_transferred", coin_to_transfer.to_string()))
+}
@@ -0,0 +1,207 @@
+use cosmwasm_std::{BankMsg, Coin, Decimal, Deps, DepsMut, Env, MessageInfo, Response};
+use cw_utils::nonpayable;
+use mars_types::{
+    credit_manager::{Action, Condition, TriggerOrder},
+    oracle::ActionKind,
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    execute::dispatch_account_actions,
+    health::query_health_values,
+    state::{KEEPER_FEE_CONFIG, NEXT_TRIGGER_ID, ORACLE, TRIGGER_ORDERS},
+    utils::{decrement_coin_balance, increment_coin_balance},
+};
+
+pub fn create_trigger_order(
+    deps: DepsMut,
+    env: Env,
+    account_id: &str,
+    actions: Vec<Action>,
+    conditions: Vec<Condition>,
+    keeper_fee: Coin,
+    expires_at: Option<u64>,
+) -> ContractResult<Response> {
+    assert_trigger_actions(&actions)?;
+
+    if conditions.is_empty() {
+        return Err(ContractError::InvalidTriggerOrder {
+            reason: "at least one condition is required".to_string(),
+        });
+    }
+
+    let keeper_fee_config = KEEPER_FEE_CONFIG.may_load(deps.storage)?.ok_or_else(|| {
+        ContractError::InvalidTriggerOrder {
+            reason: "keeper fee config not set".to_string(),
+        }
+    })?;
+    if keeper_fee.denom != keeper_fee_config.min_fee.denom
+        || keeper_fee.amount < keeper_fee_config.min_fee.amount
+    {
+        return Err(ContractError::InvalidTriggerOrder {
+            reason: format!("keeper fee has to be at least {}", keeper_fee_config.min_fee),
+        });
+    }
+
+    if let Some(expires_at) = expires_at {
+        if expires_at <= env.block.time.seconds() {
+            return Err(ContractError::InvalidTriggerOrder {
+                reason: "expiration has to be in the future".to_string(),
+            });
+        }
+    }
+
+    // The fee is held by the contract until the order is executed or deleted
+    decrement_coin_balance(deps.storage, account_id, &keeper_fee)?;
+
+    let next_id = NEXT_TRIGGER_ID.may_load(deps.storage)?.unwrap_or(1);
+    NEXT_TRIGGER_ID.save(deps.storage, &(next_id + 1))?;
+    let order_id = next_id.to_string();
+
+    TRIGGER_ORDERS.save(
+        deps.storage,
+        (account_id, order_id.as_str()),
+        &TriggerOrder {
+            order_id: order_id.clone(),
+            actions,
+            conditions,
+            keeper_fee: keeper_fee.clone(),
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "create_trigger_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("trigger_order_id", order_id)
+        .add_attribute("keeper_fee", keeper_fee.to_string()))
+}
+
+pub fn delete_trigger_order(
+    deps: DepsMut,
+    account_id: &str,
+    trigger_order_id: &str,
+) -> ContractResult<Response> {
+    let order = load_trigger_order(deps.as_ref(), account_id, trigger_order_id)?;
+    TRIGGER_ORDERS.remove(deps.storage, (account_id, trigger_order_id));
+
+    // Refund the keeper fee to the account
+    increment_coin_balance(deps.storage, account_id, &order.keeper_fee)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "delete_trigger_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("trigger_order_id", trigger_order_id))
+}
+
+/// Executes the actions of a trigger order whose conditions are met.
+/// Can be called by anyone; the caller receives the keeper fee.
+pub fn execute_trigger_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: &str,
+    trigger_order_id: &str,
+) -> ContractResult<Response> {
+    nonpayable(&info)?;
+
+    let order = load_trigger_order(deps.as_ref(), account_id, trigger_order_id)?;
+
+    if let Some(expires_at) = order.expires_at {
+        if env.block.time.seconds() >= expires_at {
+            return Err(ContractError::TriggerOrderExpired(trigger_order_id.to_string()));
+        }
+    }
+
+    for condition in order.conditions.iter() {
+        if !is_condition_met(deps.as_ref(), account_id, condition)? {
+            return Err(ContractError::TriggerConditionsNotMet(trigger_order_id.to_string()));
+        }
+    }
+
+    TRIGGER_ORDERS.remove(deps.storage, (account_id, trigger_order_id));
+
+    let keeper = info.sender.clone();
+    let mut response = Response::new()
+        .add_attribute("action", "execute_trigger_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("trigger_order_id", trigger_order_id)
+        .add_attribute("keeper", keeper.to_string());
+
+    if !order.keeper_fee.amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: keeper.into(),
+            amount: vec![order.keeper_fee],
+        });
+    }
+
+    dispatch_account_actions(deps, env, info, account_id, order.actions, response)
+}
+
+pub fn load_trigger_order(
+    deps: Deps,
+    account_id: &str,
+    trigger_order_id: &str,
+) -> ContractResult<TriggerOrder> {
+    TRIGGER_ORDERS.may_load(deps.storage, (account_id, trigger_order_id))?.ok_or_else(|| {
+        ContractError::TriggerOrderNotFound {
+            account_id: account_id.to_string(),
+            order_id: trigger_order_id.to_string(),
+        }
+    })
+}
+
+/// Only actions which keep the funds within the account can be triggered by a keeper
+fn assert_trigger_actions(actions: &[Action]) -> ContractResult<()> {
+    if actions.is_empty() {
+        return Err(ContractError::InvalidTriggerOrder {
+            reason: "at least one action is required".to_string(),
+        });
+    }
+
+    let all_allowed = actions.iter().all(|action| {
+        matches!(
+            action,
+            Action::Borrow(..)
+                | Action::Lend(..)
+                | Action::Reclaim(..)
+                | Action::Repay {
+                    recipient_account_id: None,
+                    ..
+                }
+                | Action::SwapExactIn { .. }
+                | Action::ExecutePerpOrder { .. }
+        )
+    });
+    if !all_allowed {
+        return Err(ContractError::InvalidTriggerOrder {
+            reason: "only borrow, lend, reclaim, repay, swap_exact_in and execute_perp_order actions are allowed".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+fn is_condition_met(deps: Deps, account_id: &str, condition: &Condition) -> ContractResult<bool> {
+    match condition {
+        Condition::OraclePrice {
+            denom,
+            price,
+            comparison,
+        } => {
+            let oracle = ORACLE.load(deps.storage)?;
+            let current_price =
+                oracle.query_price(&deps.querier, denom, ActionKind::Default)?.price;
+            Ok(comparison.is_met(current_price, *price))
+        }
+        Condition::HealthFactor {
+            threshold,
+            comparison,
+        } => {
+            let health = query_health_values(deps, account_id, ActionKind::Default)?;
+            // No debt means an infinite health factor
+            let health_factor = health.liquidation_health_factor.unwrap_or(Decimal::MAX);
+            Ok(comparison.is_met(health_factor, *threshold))
+        }
+    }
+}
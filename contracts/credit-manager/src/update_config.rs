@@ -12,10 +12,11 @@ use crate::{
     error::ContractResult,
     execute::create_credit_account,
     state::{
-        ACCOUNT_NFT, HEALTH_CONTRACT, INCENTIVES, MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE,
-        OWNER, RED_BANK, REWARDS_COLLECTOR, SWAPPER, SWAP_FEE, ZAPPER,
+        ACCOUNT_NFT, ACCOUNT_STATS_ENABLED, HEALTH_CONTRACT, INCENTIVES, KEEPER_FEE_CONFIG,
+        MAX_SLIPPAGE, MAX_UNLOCKING_POSITIONS, ORACLE, OWNER, PERPS, PYTH, RED_BANK,
+        REWARDS_COLLECTOR, SWAPPER, SWAP_FEE, ZAPPER,
     },
-    utils::{assert_max_slippage, assert_swap_fee},
+    utils::{assert_keeper_fee_config, assert_max_slippage, assert_swap_fee},
 };
 
 pub fn update_config(
@@ -103,6 +104,33 @@ pub fn update_config(
             response.add_attribute("key", "incentives").add_attribute("value", unchecked.address());
     }
 
+    if let Some(unchecked) = updates.perps {
+        PERPS.save(deps.storage, &unchecked.check(deps.api)?)?;
+        response =
+            response.add_attribute("key", "perps").add_attribute("value", unchecked.address());
+    }
+
+    if let Some(unchecked) = updates.pyth {
+        PYTH.save(deps.storage, &unchecked.check(deps.api)?)?;
+        response =
+            response.add_attribute("key", "pyth").add_attribute("value", unchecked.address());
+    }
+
+    if let Some(config) = updates.keeper_fee_config {
+        assert_keeper_fee_config(&config)?;
+        KEEPER_FEE_CONFIG.save(deps.storage, &config)?;
+        response = response
+            .add_attribute("key", "keeper_fee_config")
+            .add_attribute("value", config.min_fee.to_string());
+    }
+
+    if let Some(enabled) = updates.account_stats_enabled {
+        ACCOUNT_STATS_ENABLED.save(deps.storage, &enabled)?;
+        response = response
+            .add_attribute("key", "account_stats_enabled")
+            .add_attribute("value", enabled.to_string());
+    }
+
     if let Some(unchecked) = updates.rewards_collector {
         let rewards_collector_addr = deps.api.addr_validate(&unchecked)?;
 
@@ -5,7 +5,7 @@ use cosmwasm_std::{
     Storage, Uint128, WasmMsg,
 };
 use mars_types::{
-    credit_manager::{CallbackMsg, ChangeExpected, ExecuteMsg},
+    credit_manager::{CallbackMsg, ChangeExpected, ExecuteMsg, KeeperFeeConfig},
     health::AccountKind,
 };
 
@@ -58,6 +58,20 @@ pub fn assert_swap_fee(swap_fee: Decimal) -> ContractResult<()> {
     Ok(())
 }
 
+pub fn assert_keeper_fee_config(config: &KeeperFeeConfig) -> ContractResult<()> {
+    if config.min_fee.denom.is_empty() {
+        return Err(ContractError::InvalidConfig {
+            reason: "Keeper fee denom must not be empty".to_string(),
+        });
+    }
+    if config.auto_deleverage_fee_rate >= Decimal::one() {
+        return Err(ContractError::InvalidConfig {
+            reason: "Auto-deleverage fee rate must be less than 1".to_string(),
+        });
+    }
+    Ok(())
+}
+
 pub fn query_nft_token_owner(deps: Deps, account_id: &str) -> ContractResult<String> {
     Ok(ACCOUNT_NFT.load(deps.storage)?.query_nft_token_owner(&deps.querier, account_id)?)
 }
@@ -63,7 +63,7 @@ pub fn liquidate_vault(
 }
 
 fn liquidate_unlocked(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -73,7 +73,7 @@ fn liquidate_unlocked(
 ) -> ContractResult<Response> {
     let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
+    let ((debt, liquidator_request, liquidatee_request), seized) = calculate_vault_liquidation(
         &deps,
         liquidatee_account_id,
         &debt_coin,
@@ -83,7 +83,7 @@ fn liquidate_unlocked(
     )?;
 
     let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+        repay_debt(&mut deps, &env, liquidator_account_id, liquidatee_account_id, &debt, &seized)?;
 
     update_vault_position(
         deps.storage,
@@ -124,6 +124,7 @@ fn liquidate_unlocked(
 
 /// Converts vault coins to their underlying value. This allows for pricing and liquidation
 /// values to be determined. Afterward, the final amount is converted back into vault coins.
+/// Also returns the liquidatee request coin in the vault's base token.
 fn calculate_vault_liquidation(
     deps: &DepsMut,
     liquidatee_account_id: &str,
@@ -131,26 +132,41 @@ fn calculate_vault_liquidation(
     request_vault: &Vault,
     amount: Uint128,
     vault_info: &VaultInfoResponse,
-) -> ContractResult<(Coin, Coin, Coin)> {
+) -> ContractResult<((Coin, Coin, Coin), Coin)> {
     let total_underlying = request_vault.query_preview_redeem(&deps.querier, amount)?;
-    let (debt, mut liquidator_request, mut liquidatee_request) = calculate_liquidation(
+    let (debt, liquidator_request, liquidatee_request) = calculate_liquidation(
         deps,
         liquidatee_account_id,
         debt_coin,
         &vault_info.base_token,
         total_underlying,
     )?;
-    liquidatee_request.denom.clone_from(&vault_info.vault_token);
-    liquidatee_request.amount =
-        amount.checked_multiply_ratio(liquidatee_request.amount, total_underlying)?;
-    liquidator_request.denom.clone_from(&vault_info.vault_token);
-    liquidator_request.amount =
-        amount.checked_multiply_ratio(liquidator_request.amount, total_underlying)?;
-    Ok((debt, liquidator_request, liquidatee_request))
+    Ok((
+        (
+            debt,
+            to_vault_coin(liquidator_request, amount, total_underlying, vault_info)?,
+            to_vault_coin(liquidatee_request.clone(), amount, total_underlying, vault_info)?,
+        ),
+        liquidatee_request,
+    ))
+}
+
+/// Converts a coin of the vault's base token into vault coins, `amount` vault coins being
+/// redeemable for `total_underlying`
+pub fn to_vault_coin(
+    base_coin: Coin,
+    amount: Uint128,
+    total_underlying: Uint128,
+    vault_info: &VaultInfoResponse,
+) -> ContractResult<Coin> {
+    Ok(Coin {
+        denom: vault_info.vault_token.clone(),
+        amount: amount.checked_multiply_ratio(base_coin.amount, total_underlying)?,
+    })
 }
 
 fn liquidate_unlocking(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -168,8 +184,14 @@ fn liquidate_unlocking(
         unlocking_positions.total(),
     )?;
 
-    let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+    let repay_msg = repay_debt(
+        &mut deps,
+        &env,
+        liquidator_account_id,
+        liquidatee_account_id,
+        &debt,
+        &liquidatee_request,
+    )?;
 
     let mut total_to_liquidate = liquidatee_request.amount;
     let mut vault_withdraw_msgs = vec![];
@@ -225,7 +247,7 @@ fn liquidate_unlocking(
 }
 
 fn liquidate_locked(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     liquidator_account_id: &str,
     liquidatee_account_id: &str,
@@ -235,7 +257,7 @@ fn liquidate_locked(
 ) -> ContractResult<Response> {
     let vault_info = request_vault.query_info(&deps.querier)?;
 
-    let (debt, liquidator_request, liquidatee_request) = calculate_vault_liquidation(
+    let ((debt, liquidator_request, liquidatee_request), seized) = calculate_vault_liquidation(
         &deps,
         liquidatee_account_id,
         &debt_coin,
@@ -245,7 +267,7 @@ fn liquidate_locked(
     )?;
 
     let repay_msg =
-        repay_debt(deps.storage, &env, liquidator_account_id, liquidatee_account_id, &debt)?;
+        repay_debt(&mut deps, &env, liquidator_account_id, liquidatee_account_id, &debt, &seized)?;
 
     update_vault_position(
         deps.storage,
@@ -2,6 +2,7 @@ use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Response};
 use mars_types::credit_manager::{ActionAmount, ActionCoin};
 
 use crate::{
+    account_stats::record_withdrawal,
     error::{ContractError, ContractResult},
     state::COIN_BALANCES,
     utils::decrement_coin_balance,
@@ -16,6 +17,7 @@ pub fn withdraw(
     let amount_to_withdraw = get_withdraw_amount(deps.as_ref(), account_id, coin)?;
 
     decrement_coin_balance(deps.storage, account_id, &amount_to_withdraw)?;
+    record_withdrawal(deps.storage, account_id, &amount_to_withdraw)?;
 
     // send coin to recipient
     let transfer_msg = CosmosMsg::Bank(BankMsg::Send {
@@ -32,7 +34,11 @@ pub fn withdraw(
 
 /// Checks if Exact or Account Balance is passed through Action Coin
 /// Also asserts the amount is greater than zero.
-fn get_withdraw_amount(deps: Deps, account_id: &str, coin: &ActionCoin) -> ContractResult<Coin> {
+pub fn get_withdraw_amount(
+    deps: Deps,
+    account_id: &str,
+    coin: &ActionCoin,
+) -> ContractResult<Coin> {
     let amount = match coin.amount {
         ActionAmount::Exact(amount) => amount,
         ActionAmount::AccountBalance => {
@@ -1,10 +1,13 @@
 pub use mars_testing::multitest::helpers;
 
+mod test_account_stats;
+mod test_auto_deleverage;
 mod test_borrow;
 mod test_claim_astro_lp_rewards;
 mod test_claim_rewards;
 mod test_coin_balances;
 mod test_create_credit_account;
+mod test_delegation;
 mod test_deposit;
 mod test_deposit_cap;
 mod test_dispatch;
@@ -13,6 +16,8 @@ mod test_enumerate_coin_balances;
 mod test_enumerate_debt_shares;
 mod test_enumerate_total_debt_shares;
 mod test_enumerate_vault_positions;
+mod test_estimate_liquidation;
+mod test_flash_liquidation;
 mod test_fund_manager_accounts;
 mod test_health;
 mod test_hls_accounts;
@@ -33,13 +38,17 @@ mod test_repay;
 mod test_repay_for_recipient;
 mod test_repay_from_wallet;
 mod test_rewards_collector_whitelist;
+mod test_simulate_actions;
 mod test_stake_astro_lp;
 mod test_swap;
+mod test_trigger_orders;
 mod test_unstake_astro_lp;
 mod test_update_admin;
 mod test_update_config;
 mod test_update_credit_account_with_new_acc;
+mod test_update_credit_accounts;
 mod test_update_nft_config;
+mod test_update_pyth_prices;
 mod test_utilization_query;
 mod test_utilizations_all_query;
 mod test_vault_enter;
@@ -0,0 +1,250 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_types::{
+    credit_manager::{
+        AccountStats,
+        Action::{Borrow, ClaimRewards, Deposit, Liquidate, Repay, SwapExactIn, Withdraw},
+        ConfigUpdates, LiquidateRequest,
+    },
+    oracle::ActionKind,
+    swapper::{OsmoRoute, OsmoSwap, SwapperRoute},
+};
+
+use super::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn stats_are_not_recorded_by_default() {
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(100, "uatom"),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(100))],
+        &coins(100, "uatom"),
+    )
+    .unwrap();
+
+    assert!(!mock.query_config().account_stats_enabled);
+    assert_eq!(mock.query_account_stats(&account_id), AccountStats::default());
+}
+
+#[test]
+fn deposits_withdrawals_fees_and_rewards_are_recorded() {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(10_000, "uatom"),
+        })
+        .swap_fee(Decimal::percent(1))
+        .build()
+        .unwrap();
+    enable_account_stats(&mut mock);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uatom_info.to_coin(10_000)),
+            SwapExactIn {
+                coin_in: uatom_info.to_action_coin(5_000),
+                denom_out: uosmo_info.denom.clone(),
+                min_receive: Uint128::one(),
+                route: Some(SwapperRoute::Osmo(OsmoRoute {
+                    swaps: vec![OsmoSwap {
+                        pool_id: 101,
+                        to: uosmo_info.denom.clone(),
+                    }],
+                })),
+            },
+            Withdraw(uatom_info.to_action_coin(1_000)),
+        ],
+        &coins(10_000, "uatom"),
+    )
+    .unwrap();
+
+    mock.add_incentive_reward(&account_id, coin(123, "uosmo"));
+    mock.update_credit_account(&account_id, &user, vec![ClaimRewards {}], &[]).unwrap();
+
+    let stats = mock.query_account_stats(&account_id);
+    assert_eq!(
+        stats,
+        AccountStats {
+            deposited: vec![coin(10_000, "uatom")],
+            withdrawn: vec![coin(1_000, "uatom")],
+            swap_fees_paid: vec![coin(50, "uatom")],
+            rewards_claimed: vec![coin(123, "uosmo")],
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn interest_is_split_from_principal() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    enable_account_stats(&mut mock);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uosmo_info.to_coin(100))],
+        &coins(300, "uosmo"),
+    )
+    .unwrap();
+
+    let stats = mock.query_account_stats(&account_id);
+    assert_eq!(stats.debt_principal, vec![coin(100, "uosmo")]);
+    assert!(stats.interest_paid.is_empty());
+
+    // Mock red bank charges 1 uosmo of interest on the borrow
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Repay {
+            recipient_account_id: None,
+            coin: uosmo_info.to_action_coin_full_balance(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let stats = mock.query_account_stats(&account_id);
+    assert!(stats.debt_principal.is_empty());
+    assert_eq!(stats.interest_paid, vec![coin(1, "uosmo")]);
+}
+
+#[test]
+fn partial_repay_pays_interest_first() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    enable_account_stats(&mut mock);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(300)), Borrow(uosmo_info.to_coin(100))],
+        &coins(300, "uosmo"),
+    )
+    .unwrap();
+
+    // The debt grew to 101 with the interest of the mock red bank
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Repay {
+            recipient_account_id: None,
+            coin: uosmo_info.to_action_coin(21),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let stats = mock.query_account_stats(&account_id);
+    assert_eq!(stats.debt_principal, vec![coin(80, "uosmo")]);
+    assert_eq!(stats.interest_paid, vec![coin(1, "uosmo")]);
+}
+
+#[test]
+fn liquidation_is_recorded() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .target_health_factor(Decimal::from_atomics(12u128, 1).unwrap())
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(3000, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    enable_account_stats(&mut mock);
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uosmo_info.to_coin(3000)), Borrow(uatom_info.to_coin(1000))],
+        &coins(3000, uosmo_info.denom.clone()),
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(59u128, 1).unwrap(),
+    });
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(100)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(100),
+                request: LiquidateRequest::Deposit(uosmo_info.denom),
+            },
+        ],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    // 2392 uosmo (598) seized for 100 uatom (590) of debt. Out of the debt of 1001, the
+    // interest is repaid first.
+    let stats = mock.query_account_stats(&liquidatee_account_id);
+    assert_eq!(stats.liquidation_losses, Uint128::new(8));
+    assert_eq!(stats.debt_principal, vec![coin(901, "uatom")]);
+    assert_eq!(stats.interest_paid, vec![coin(1, "uatom")]);
+}
+
+fn enable_account_stats(mock: &mut MockEnv) {
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            account_stats_enabled: Some(true),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
@@ -0,0 +1,270 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use mars_credit_manager::error::ContractError;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_types::{
+    credit_manager::{
+        Action::{Borrow, Deposit, SetAutoDeleveragePolicy, Withdraw},
+        AutoDeleveragePolicy, ConfigUpdates, KeeperFeeConfig,
+    },
+    oracle::ActionKind,
+};
+
+use super::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn invalid_policies() {
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&user);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SetAutoDeleveragePolicy {
+            policy: Some(AutoDeleveragePolicy {
+                target_health_factor: Decimal::percent(110),
+                ..default_policy()
+            }),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidAutoDeleveragePolicy {
+            reason: "target health factor has to be greater than the trigger health factor"
+                .to_string(),
+        },
+    );
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SetAutoDeleveragePolicy {
+            policy: Some(AutoDeleveragePolicy {
+                debt_denom: "uatom".to_string(),
+                ..default_policy()
+            }),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidAutoDeleveragePolicy {
+            reason: "collateral and debt denoms have to be different".to_string(),
+        },
+    );
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SetAutoDeleveragePolicy {
+            policy: Some(AutoDeleveragePolicy {
+                slippage: Decimal::percent(100),
+                ..default_policy()
+            }),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::SlippageExceeded {
+            slippage: Decimal::percent(100),
+            max_slippage: Decimal::percent(99),
+        },
+    );
+}
+
+#[test]
+fn set_and_remove_policy() {
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SetAutoDeleveragePolicy {
+            policy: Some(default_policy()),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_auto_deleverage_policy(&account_id), Some(default_policy()));
+    let policies = mock.query_all_auto_deleverage_policies(None, None);
+    assert_eq!(policies.data.len(), 1);
+    assert_eq!(policies.data[0].account_id, account_id);
+    assert_eq!(policies.data[0].policy, default_policy());
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![SetAutoDeleveragePolicy {
+            policy: None,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    assert_eq!(mock.query_auto_deleverage_policy(&account_id), None);
+    assert!(mock.query_all_auto_deleverage_policies(None, None).data.is_empty());
+
+    let keeper = Addr::unchecked("keeper");
+    let res = mock.auto_deleverage(&keeper, &account_id);
+    assert_err(res, ContractError::AutoDeleveragePolicyNotFound(account_id));
+}
+
+#[test]
+fn cannot_deleverage_above_trigger() {
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&user);
+    set_default_policy(&mut mock, &user, &account_id);
+
+    let keeper = Addr::unchecked("keeper");
+    let res = mock.auto_deleverage(&keeper, &account_id);
+    assert_err(
+        res,
+        ContractError::AutoDeleverageNotTriggered {
+            account_id,
+            trigger_health_factor: Decimal::percent(110),
+        },
+    );
+}
+
+#[test]
+fn keeper_deleverages_account() {
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&user);
+    set_default_policy(&mut mock, &user, &account_id);
+
+    // Liquidation health factor drops to 810 / 751 = ~1.078, below the trigger of 1.1
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: "uatom".to_string(),
+        price: Decimal::percent(90),
+    });
+
+    let keeper = Addr::unchecked("keeper");
+    mock.auto_deleverage(&keeper, &account_id).unwrap();
+
+    // Selling 372 uatom (with 1% keeper fee and 1% slippage) brings the health factor back to 1.2:
+    //      (1.2 * 751 - 810) / (1.2 * 0.99 * 0.99 - 0.9) / 0.9
+    assert_eq!(mock.query_balance(&keeper, "uatom"), coin(3, "uatom"));
+
+    // The mock swapper returns 1337 uosmo, all used to repay the debt
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![coin(1000 - 372, "uatom")]);
+    assert_eq!(position.debts.len(), 1);
+    assert_eq!(position.debts[0].amount, Uint128::new(3001 - 1337));
+
+    let health = mock.query_health(&account_id, ActionKind::Default);
+    assert!(health.liquidation_health_factor.unwrap() > Decimal::percent(120));
+
+    // The policy stays in place, but is no longer triggered
+    let res = mock.auto_deleverage(&keeper, &account_id);
+    assert_err(
+        res,
+        ContractError::AutoDeleverageNotTriggered {
+            account_id,
+            trigger_health_factor: Decimal::percent(110),
+        },
+    );
+}
+
+#[test]
+fn only_swapped_amount_is_repaid() {
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&user);
+    set_default_policy(&mut mock, &user, &account_id);
+
+    // Deposit back some of the borrowed uosmo; it has to stay deposited
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(coin(40, "uosmo"))],
+        &[coin(40, "uosmo")],
+    )
+    .unwrap();
+
+    // Liquidation health factor drops to (810 + 7.8) / 751 = ~1.089, below the trigger of 1.1
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: "uatom".to_string(),
+        price: Decimal::percent(90),
+    });
+
+    let keeper = Addr::unchecked("keeper");
+    mock.auto_deleverage(&keeper, &account_id).unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits.len(), 2);
+    assert_eq!(position.deposits[1], coin(40, "uosmo"));
+    assert_eq!(position.debts[0].amount, Uint128::new(3001 - 1337));
+
+    assert!(!mock.query_balance(&keeper, "uatom").amount.is_zero());
+}
+
+/// Sells uatom for uosmo once the liquidation health factor drops below 1.1, up to 1.2
+fn default_policy() -> AutoDeleveragePolicy {
+    AutoDeleveragePolicy {
+        trigger_health_factor: Decimal::percent(110),
+        target_health_factor: Decimal::percent(120),
+        collateral_denom: "uatom".to_string(),
+        debt_denom: "uosmo".to_string(),
+        slippage: Decimal::percent(1),
+        route: None,
+    }
+}
+
+fn set_default_policy(mock: &mut MockEnv, user: &Addr, account_id: &str) {
+    mock.update_credit_account(
+        account_id,
+        user,
+        vec![SetAutoDeleveragePolicy {
+            policy: Some(default_policy()),
+        }],
+        &[],
+    )
+    .unwrap();
+}
+
+/// Creates an account with 1000 uatom deposited and 3000 uosmo borrowed to the wallet (a debt of
+/// 3001 with the interest of the mock red bank). Keepers get 1% of the sold collateral.
+fn setup(user: &Addr) -> (MockEnv, String) {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(1000, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(user).unwrap();
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            keeper_fee_config: Some(KeeperFeeConfig {
+                min_fee: coin(10, "uosmo"),
+                auto_deleverage_fee_rate: Decimal::percent(1),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        user,
+        vec![
+            Deposit(uatom_info.to_coin(1000)),
+            Borrow(uosmo_info.to_coin(3000)),
+            Withdraw(uosmo_info.to_action_coin(3000)),
+        ],
+        &[coin(1000, uatom_info.denom)],
+    )
+    .unwrap();
+
+    (mock, account_id)
+}
@@ -121,6 +121,8 @@ fn success_when_new_debt_asset() {
     let position = mock.query_positions(&account_id);
     assert_eq!(position.deposits.len(), 0);
     assert_eq!(position.debts.len(), 0);
+    assert_eq!(mock.query_last_borrow_height(&account_id), None);
+
     mock.update_credit_account(
         &account_id,
         &user,
@@ -157,6 +159,8 @@ fn success_when_new_debt_asset() {
 
     let res = mock.query_total_debt_shares(&coin_info.denom);
     assert_eq!(res.shares, Uint128::new(42).mul(DEFAULT_DEBT_SHARES_PER_COIN_BORROWED));
+
+    assert_eq!(mock.query_last_borrow_height(&account_id), Some(mock.app.block_info().height));
 }
 
 #[test]
@@ -0,0 +1,562 @@
+use cosmwasm_std::{coin, coins, Addr, Decimal, Uint128};
+use cw_multi_test::Executor;
+use mars_credit_manager::error::ContractError;
+use mars_types::{
+    account_nft::ExecuteMsg as NftExecuteMsg,
+    credit_manager::{
+        AccountPermission,
+        Action::{Borrow, CreateTriggerOrder, Deposit, Repay, TransferToAccount, Withdraw},
+        ActionAmount, ActionCoin, Comparison, Condition, ConfigUpdates, Delegation,
+        KeeperFeeConfig,
+    },
+};
+
+use super::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn invalid_delegations() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    let res = mock.grant_delegation(&delegate, &account_id, &delegate, default_delegation());
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    let res = mock.grant_delegation(&user, &account_id, &user, default_delegation());
+    assert_err(
+        res,
+        ContractError::InvalidDelegation {
+            reason: "delegate can not be the account owner".to_string(),
+        },
+    );
+
+    let res = mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            permissions: vec![],
+            ..default_delegation()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::InvalidDelegation {
+            reason: "at least one permission is required".to_string(),
+        },
+    );
+
+    let res = mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            expires_at: Some(mock.query_block_time()),
+            ..default_delegation()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::InvalidDelegation {
+            reason: "expiration has to be in the future".to_string(),
+        },
+    );
+
+    let res = mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            denom_limits: vec![coin(10, "uatom"), coin(20, "uatom")],
+            ..default_delegation()
+        },
+    );
+    assert_err(
+        res,
+        ContractError::InvalidDelegation {
+            reason: "denom limits have to be unique".to_string(),
+        },
+    );
+}
+
+#[test]
+fn delegate_can_only_dispatch_permitted_actions() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(uatom_info().to_coin(100))],
+        &coins(100, "uatom"),
+    );
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    mock.grant_delegation(&user, &account_id, &delegate, default_delegation()).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(uatom_info().to_coin(100))],
+        &coins(100, "uatom"),
+    )
+    .unwrap();
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![uatom_info().to_coin(1100)]);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Borrow(uosmo_info().to_coin(10))],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("borrow on account {account_id}"),
+        },
+    );
+
+    // A single action without permission rejects the whole transaction
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(uatom_info().to_coin(100)), Withdraw(uatom_info().to_action_coin(1100))],
+        &coins(100, "uatom"),
+    );
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("withdraw on account {account_id}"),
+        },
+    );
+
+    // Actions of trigger orders need permissions as well
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![CreateTriggerOrder {
+            actions: vec![Withdraw(uatom_info().to_action_coin(1000))],
+            conditions: vec![Condition::HealthFactor {
+                threshold: Decimal::percent(500),
+                comparison: Comparison::GreaterThan,
+            }],
+            keeper_fee: coin(10, "uosmo"),
+            expires_at: None,
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("withdraw on account {account_id}"),
+        },
+    );
+}
+
+#[test]
+fn denom_limits_are_used_up() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            permissions: vec![AccountPermission::Withdraw],
+            expires_at: None,
+            denom_limits: vec![coin(100, "uatom")],
+        },
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Withdraw(uatom_info().to_action_coin(60))],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(mock.query_balance(&delegate, "uatom").amount, Uint128::new(1060));
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Withdraw(uatom_info().to_action_coin(50))],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::DelegationLimitExceeded {
+            delegate: delegate.to_string(),
+            account_id: account_id.clone(),
+            denom: "uatom".to_string(),
+            requested: "50".to_string(),
+            remaining: Uint128::new(40),
+        },
+    );
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Withdraw(ActionCoin {
+            denom: "uatom".to_string(),
+            amount: ActionAmount::AccountBalance,
+        })],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::DelegationLimitExceeded {
+            delegate: delegate.to_string(),
+            account_id: account_id.clone(),
+            denom: "uatom".to_string(),
+            requested: "account_balance".to_string(),
+            remaining: Uint128::new(40),
+        },
+    );
+
+    let delegation = mock.query_delegation(&account_id, &delegate).unwrap();
+    assert_eq!(delegation.granter, user.to_string());
+    assert_eq!(delegation.delegation.denom_limits, vec![coin(40, "uatom")]);
+}
+
+#[test]
+fn expired_delegation_is_inactive() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    let expires_at = mock.query_block_time() + 100;
+    mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            expires_at: Some(expires_at),
+            ..default_delegation()
+        },
+    )
+    .unwrap();
+    assert_eq!(mock.query_account_delegations(&account_id, None, None).data.len(), 1);
+
+    mock.set_block_time(expires_at);
+
+    assert_eq!(mock.query_delegation(&account_id, &delegate), None);
+    assert!(mock.query_account_delegations(&account_id, None, None).data.is_empty());
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Repay {
+            recipient_account_id: None,
+            coin: uatom_info().to_action_coin(1),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+}
+
+#[test]
+fn delegation_is_inactive_after_account_transfer() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    mock.grant_delegation(&user, &account_id, &delegate, default_delegation()).unwrap();
+
+    let new_owner = Addr::unchecked("new_owner");
+    let account_nft = mock.query_config().account_nft.unwrap();
+    mock.app
+        .execute_contract(
+            user.clone(),
+            Addr::unchecked(account_nft),
+            &NftExecuteMsg::TransferNft {
+                recipient: new_owner.to_string(),
+                token_id: account_id.clone(),
+            },
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(mock.query_delegation(&account_id, &delegate), None);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Deposit(uatom_info().to_coin(100))],
+        &coins(100, "uatom"),
+    );
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+}
+
+#[test]
+fn owner_and_delegate_can_revoke() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let other_delegate = Addr::unchecked("other_delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    mock.grant_delegation(&user, &account_id, &delegate, default_delegation()).unwrap();
+    mock.grant_delegation(&user, &account_id, &other_delegate, default_delegation()).unwrap();
+
+    let delegations = mock.query_account_delegations(&account_id, None, None);
+    assert_eq!(
+        delegations.data.iter().map(|d| d.delegate.clone()).collect::<Vec<_>>(),
+        vec![delegate.to_string(), other_delegate.to_string()]
+    );
+
+    let res = mock.revoke_delegation(&delegate, &account_id, &other_delegate);
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: delegate.to_string(),
+            account_id: account_id.clone(),
+        },
+    );
+
+    mock.revoke_delegation(&delegate, &account_id, &delegate).unwrap();
+    mock.revoke_delegation(&user, &account_id, &other_delegate).unwrap();
+    assert!(mock.query_account_delegations(&account_id, None, None).data.is_empty());
+
+    let res = mock.revoke_delegation(&user, &account_id, &delegate);
+    assert_err(
+        res,
+        ContractError::InvalidDelegation {
+            reason: format!("{delegate} has no delegation on account {account_id}"),
+        },
+    );
+}
+
+#[test]
+fn repaying_accounts_of_other_owners_requires_withdraw_permission() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    mock.grant_delegation(&user, &account_id, &delegate, default_delegation()).unwrap();
+
+    // Account of the delegate with some uatom debt
+    let delegate_account_id = mock.create_credit_account(&delegate).unwrap();
+    mock.update_credit_account(
+        &delegate_account_id,
+        &delegate,
+        vec![Deposit(uatom_info().to_coin(1000)), Borrow(uatom_info().to_coin(100))],
+        &coins(1000, "uatom"),
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Repay {
+            recipient_account_id: Some(delegate_account_id.clone()),
+            coin: uatom_info().to_action_coin(10),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("withdraw on account {account_id}"),
+        },
+    );
+
+    // Another account of the same owner can be repaid with the Repay permission only
+    let other_account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![TransferToAccount {
+            account_id: other_account_id.clone(),
+            coin: uatom_info().to_action_coin(500),
+        }],
+        &[],
+    )
+    .unwrap();
+    mock.update_credit_account(
+        &other_account_id,
+        &user,
+        vec![Borrow(uatom_info().to_coin(100))],
+        &[],
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Repay {
+            recipient_account_id: Some(other_account_id.clone()),
+            coin: uatom_info().to_action_coin(10),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // With the Withdraw permission, the debt of any account can be repaid
+    mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            permissions: vec![AccountPermission::Repay, AccountPermission::Withdraw],
+            ..default_delegation()
+        },
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &delegate,
+        vec![Repay {
+            recipient_account_id: Some(delegate_account_id),
+            coin: uatom_info().to_action_coin(10),
+        }],
+        &[],
+    )
+    .unwrap();
+}
+
+#[test]
+fn keeper_fees_above_minimum_require_withdraw_permission() {
+    let user = Addr::unchecked("user");
+    let delegate = Addr::unchecked("delegate");
+    let (mut mock, account_id) = setup(&user, &delegate);
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            keeper_fee_config: Some(KeeperFeeConfig {
+                min_fee: coin(10, "uatom"),
+                auto_deleverage_fee_rate: Decimal::zero(),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    mock.grant_delegation(
+        &user,
+        &account_id,
+        &delegate,
+        Delegation {
+            permissions: vec![AccountPermission::TriggerOrder, AccountPermission::Repay],
+            expires_at: None,
+            denom_limits: vec![coin(25, "uatom")],
+        },
+    )
+    .unwrap();
+
+    let trigger_order = |keeper_fee: u128| CreateTriggerOrder {
+        actions: vec![Repay {
+            recipient_account_id: None,
+            coin: uatom_info().to_action_coin(10),
+        }],
+        conditions: vec![Condition::HealthFactor {
+            threshold: Decimal::percent(500),
+            comparison: Comparison::GreaterThan,
+        }],
+        keeper_fee: coin(keeper_fee, "uatom"),
+        expires_at: None,
+    };
+
+    // The delegate could collect the fee by executing the order itself
+    let res = mock.update_credit_account(&account_id, &delegate, vec![trigger_order(1000)], &[]);
+    assert_err(
+        res,
+        ContractError::Unauthorized {
+            user: delegate.to_string(),
+            action: format!("withdraw on account {account_id}"),
+        },
+    );
+
+    // The minimum fee is allowed, but counts towards the denom limits
+    mock.update_credit_account(&account_id, &delegate, vec![trigger_order(10)], &[]).unwrap();
+
+    let delegation = mock.query_delegation(&account_id, &delegate).unwrap();
+    assert_eq!(delegation.delegation.denom_limits, vec![coin(5, "uatom")]);
+
+    let res = mock.update_credit_account(&account_id, &delegate, vec![trigger_order(10)], &[]);
+    assert_err(
+        res,
+        ContractError::DelegationLimitExceeded {
+            delegate: delegate.to_string(),
+            account_id: account_id.clone(),
+            denom: "uatom".to_string(),
+            requested: "10".to_string(),
+            remaining: Uint128::new(5),
+        },
+    );
+}
+
+fn default_delegation() -> Delegation {
+    Delegation {
+        permissions: vec![
+            AccountPermission::Deposit,
+            AccountPermission::Repay,
+            AccountPermission::Swap,
+            AccountPermission::TriggerOrder,
+        ],
+        expires_at: None,
+        denom_limits: vec![],
+    }
+}
+
+fn setup(user: &Addr, delegate: &Addr) -> (MockEnv, String) {
+    let uatom_info = uatom_info();
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), uosmo_info()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(1000, "uatom"),
+        })
+        .fund_account(AccountToFund {
+            addr: delegate.clone(),
+            funds: coins(1000, "uatom"),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        user,
+        vec![Deposit(uatom_info.to_coin(1000))],
+        &coins(1000, "uatom"),
+    )
+    .unwrap();
+
+    (mock, account_id)
+}
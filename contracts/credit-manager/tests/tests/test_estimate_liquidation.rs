@@ -0,0 +1,149 @@
+use cosmwasm_std::{coins, Addr, Coin, Decimal, Uint128};
+use mars_credit_manager::error::ContractError;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_types::{
+    credit_manager::{
+        Action::{Borrow, Deposit, Liquidate, Withdraw},
+        LiquidateRequest,
+    },
+    oracle::ActionKind,
+};
+
+use super::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn estimate_matches_liquidation() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let (mut mock, liquidatee_account_id) = setup();
+
+    let err = mock
+        .estimate_liquidation(
+            &liquidatee_account_id,
+            uatom_info.to_coin(561),
+            LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("is not a liquidatable credit account"));
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(128u128, 2).unwrap(),
+    });
+
+    // Same figures as in `target_health_factor_reached_after_max_debt_repayed`
+    let estimate = mock
+        .estimate_liquidation(
+            &liquidatee_account_id,
+            uatom_info.to_coin(561),
+            LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+        )
+        .unwrap();
+    assert_eq!(estimate.debt_repaid, uatom_info.to_coin(505));
+    assert_eq!(estimate.collateral_liquidated, uosmo_info.to_coin(2632));
+    assert_eq!(estimate.collateral_received, uosmo_info.to_coin(2628));
+    assert_eq!(estimate.protocol_fee, uosmo_info.to_coin(4));
+    assert!(estimate.liquidation_bonus > Decimal::zero());
+
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![
+            Deposit(uatom_info.to_coin(561)),
+            Liquidate {
+                liquidatee_account_id: liquidatee_account_id.clone(),
+                debt_coin: uatom_info.to_coin(561),
+                request: LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            },
+        ],
+        &[uatom_info.to_coin(561)],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&liquidatee_account_id);
+    assert_eq!(position.debts[0].amount, Uint128::new(496));
+
+    let health =
+        mock.query_health(&liquidatee_account_id, position.account_kind, ActionKind::Liquidation);
+    assert_eq!(estimate.health_factor_after, health.liquidation_health_factor);
+}
+
+#[test]
+fn estimate_fails_as_liquidation_would() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let (mut mock, liquidatee_account_id) = setup();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info.denom.clone(),
+        price: Decimal::from_atomics(128u128, 2).unwrap(),
+    });
+
+    let err = mock
+        .estimate_liquidation(
+            &liquidatee_account_id,
+            uatom_info.to_coin(561),
+            LiquidateRequest::Deposit("ujake".to_string()),
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::CoinNotAvailable("ujake".to_string()).to_string()));
+
+    let err = mock
+        .estimate_liquidation(
+            &liquidatee_account_id,
+            uatom_info.to_coin(561),
+            LiquidateRequest::Lend(uosmo_info.denom.clone()),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains(&ContractError::NoneLent.to_string()));
+
+    let err = mock
+        .estimate_liquidation(
+            &liquidatee_account_id,
+            uatom_info.to_coin(561),
+            LiquidateRequest::Perp("uatom".to_string()),
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("perp positions can not be estimated"));
+}
+
+fn setup() -> (MockEnv, String) {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidatee = Addr::unchecked("liquidatee");
+    let liquidator = Addr::unchecked("liquidator");
+    let mut mock = MockEnv::new()
+        .target_health_factor(Decimal::from_atomics(12u128, 1).unwrap())
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(3000, uosmo_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator,
+            funds: coins(3000, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![
+            Deposit(uosmo_info.to_coin(3000)),
+            Borrow(uatom_info.to_coin(1000)),
+            Withdraw(uatom_info.to_action_coin(400)),
+        ],
+        &[Coin::new(3000, uosmo_info.denom)],
+    )
+    .unwrap();
+
+    (mock, liquidatee_account_id)
+}
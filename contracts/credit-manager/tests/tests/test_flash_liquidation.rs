@@ -0,0 +1,199 @@
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use mars_credit_manager::error::ContractError;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+use mars_types::{
+    credit_manager::{
+        Action::{Borrow, Deposit, FlashLiquidate},
+        LiquidateRequest,
+    },
+    oracle::ActionKind,
+    swapper::{OsmoRoute, OsmoSwap, SwapperRoute},
+};
+
+use super::helpers::{
+    assert_err, get_coin, get_debt, uatom_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+#[test]
+fn cannot_flash_liquidate_own_account() {
+    let (mut mock, liquidatee_account_id, _) = setup();
+    let liquidatee = Addr::unchecked("liquidatee");
+
+    let res = mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![FlashLiquidate {
+            liquidatee_account_id: liquidatee_account_id.clone(),
+            debt_coin: uosmo_info().to_coin(10),
+            request: LiquidateRequest::Deposit(uatom_info().denom),
+            route: None,
+            min_profit: Uint128::zero(),
+        }],
+        &[],
+    );
+
+    assert_err(res, ContractError::SelfLiquidation);
+}
+
+#[test]
+fn flash_liquidate_collateral_in_debt_denom() {
+    let uosmo_info = uosmo_info();
+    let (mut mock, liquidatee_account_id, liquidator_account_id) = setup();
+    let liquidator = Addr::unchecked("liquidator");
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![FlashLiquidate {
+            liquidatee_account_id: liquidatee_account_id.clone(),
+            debt_coin: uosmo_info.to_coin(100),
+            request: LiquidateRequest::Deposit(uosmo_info.denom.clone()),
+            route: None,
+            min_profit: Uint128::zero(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // Liquidatee's debt and collateral have been reduced
+    let position = mock.query_positions(&liquidatee_account_id);
+    let osmo_debt = get_debt(&uosmo_info.denom, &position.debts);
+    assert!(osmo_debt.amount < Uint128::new(201));
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert!(osmo_balance.amount < Uint128::new(200));
+
+    // Liquidator kept the bonus, no swap was needed
+    let position = mock.query_positions(&liquidator_account_id);
+    let atom_balance = get_coin("uatom", &position.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(100));
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    let osmo_debt = position.debts.iter().find(|d| d.denom == uosmo_info.denom).map(|d| d.amount);
+    assert!(osmo_balance.amount > osmo_debt.unwrap_or_default());
+}
+
+#[test]
+fn flash_liquidate_swaps_seized_collateral() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let (mut mock, liquidatee_account_id, liquidator_account_id) = setup();
+    let liquidator = Addr::unchecked("liquidator");
+
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![FlashLiquidate {
+            liquidatee_account_id: liquidatee_account_id.clone(),
+            debt_coin: uosmo_info.to_coin(10),
+            request: LiquidateRequest::Deposit(uatom_info.denom.clone()),
+            route: Some(osmo_route()),
+            min_profit: Uint128::new(1000),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // Liquidatee's atom was seized
+    let position = mock.query_positions(&liquidatee_account_id);
+    let atom_balance = get_coin(&uatom_info.denom, &position.deposits);
+    assert!(atom_balance.amount < Uint128::new(300));
+
+    // Only the seized atom was swapped, the liquidator's own deposit is untouched
+    let position = mock.query_positions(&liquidator_account_id);
+    let atom_balance = get_coin(&uatom_info.denom, &position.deposits);
+    assert_eq!(atom_balance.amount, Uint128::new(100));
+
+    // Swap result minus the repaid loan
+    let osmo_balance = get_coin(&uosmo_info.denom, &position.deposits);
+    assert_eq!(osmo_balance.amount, MOCK_SWAP_RESULT - Uint128::new(10));
+}
+
+#[test]
+fn flash_liquidation_reverts_if_profit_too_low() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let (mut mock, liquidatee_account_id, liquidator_account_id) = setup();
+    let liquidator = Addr::unchecked("liquidator");
+
+    let res = mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![FlashLiquidate {
+            liquidatee_account_id: liquidatee_account_id.clone(),
+            debt_coin: uosmo_info.to_coin(10),
+            request: LiquidateRequest::Deposit(uatom_info.denom.clone()),
+            route: Some(osmo_route()),
+            min_profit: MOCK_SWAP_RESULT,
+        }],
+        &[],
+    );
+
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    assert!(matches!(
+        err,
+        ContractError::FlashLiquidationProfitTooLow { min_profit, profit }
+            if min_profit == MOCK_SWAP_RESULT && profit < MOCK_SWAP_RESULT
+    ));
+
+    // Nothing changed for the liquidator
+    let position = mock.query_positions(&liquidator_account_id);
+    assert_eq!(position.deposits, vec![uatom_info.to_coin(100)]);
+    assert!(position.debts.is_empty());
+}
+
+/// Liquidatee with 300 uatom deposited and 200 uosmo borrowed, made liquidatable by a uosmo price
+/// increase. Liquidator holding 100 uatom only.
+fn setup() -> (MockEnv, String, String) {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+    let liquidator = Addr::unchecked("liquidator");
+    let liquidatee = Addr::unchecked("liquidatee");
+    let mut mock = MockEnv::new()
+        .target_health_factor(Decimal::from_atomics(12u128, 1).unwrap())
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: liquidatee.clone(),
+            funds: coins(300, uatom_info.denom.clone()),
+        })
+        .fund_account(AccountToFund {
+            addr: liquidator.clone(),
+            funds: coins(100, uatom_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+
+    let liquidatee_account_id = mock.create_credit_account(&liquidatee).unwrap();
+    mock.update_credit_account(
+        &liquidatee_account_id,
+        &liquidatee,
+        vec![Deposit(uatom_info.to_coin(300)), Borrow(uosmo_info.to_coin(200))],
+        &[uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uosmo_info.denom.clone(),
+        price: Decimal::from_atomics(10u128, 0).unwrap(),
+    });
+
+    let liquidator_account_id = mock.create_credit_account(&liquidator).unwrap();
+    mock.update_credit_account(
+        &liquidator_account_id,
+        &liquidator,
+        vec![Deposit(uatom_info.to_coin(100))],
+        &[uatom_info.to_coin(100)],
+    )
+    .unwrap();
+
+    (mock, liquidatee_account_id, liquidator_account_id)
+}
+
+fn osmo_route() -> SwapperRoute {
+    SwapperRoute::Osmo(OsmoRoute {
+        swaps: vec![OsmoSwap {
+            pool_id: 101,
+            to: uosmo_info().denom,
+        }],
+    })
+}
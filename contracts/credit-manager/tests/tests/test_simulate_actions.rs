@@ -0,0 +1,272 @@
+use cosmwasm_std::{Addr, Uint128};
+use mars_credit_manager::borrow::DEFAULT_DEBT_SHARES_PER_COIN_BORROWED;
+use mars_mock_vault::contract::STARTING_VAULT_SHARES;
+use mars_swapper_mock::contract::MOCK_SWAP_RESULT;
+use mars_types::{
+    credit_manager::{
+        Action::{
+            Borrow, Deposit, EnterVault, Lend, Liquidate, Repay, RequestVaultUnlock, SwapExactIn,
+            Withdraw,
+        },
+        ActionAmount, ActionCoin, ActionsRejection, DebtAmount, LiquidateRequest,
+    },
+    health::HealthError,
+    params::{AssetParams, AssetParamsUpdate},
+};
+
+use super::helpers::{
+    locked_vault_info, lp_token_info, uatom_info, ujake_info, uosmo_info, AccountToFund, MockEnv,
+};
+
+#[test]
+fn simulation_does_not_change_positions() {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[uatom_info.clone(), uosmo_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![
+                Deposit(uatom_info.to_coin(300)),
+                Borrow(uosmo_info.to_coin(100)),
+                Lend(uosmo_info.to_action_coin(40)),
+                Withdraw(uatom_info.to_action_coin(50)),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(res.positions.deposits, vec![uatom_info.to_coin(250), uosmo_info.to_coin(60)]);
+    assert_eq!(res.positions.lends, vec![uosmo_info.to_coin(40)]);
+    assert_eq!(
+        res.positions.debts,
+        vec![DebtAmount {
+            denom: uosmo_info.denom.clone(),
+            shares: Uint128::new(100) * DEFAULT_DEBT_SHARES_PER_COIN_BORROWED,
+            amount: Uint128::new(100),
+        }]
+    );
+    assert!(!res.health.total_debt_value.is_zero());
+    assert!(!res.health.above_max_ltv);
+    assert!(res.rejections.is_empty());
+
+    let position = mock.query_positions(&account_id);
+    assert!(position.deposits.is_empty());
+    assert!(position.debts.is_empty());
+    assert!(position.lends.is_empty());
+}
+
+#[test]
+fn simulate_repay_and_swap() {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![uatom_info.to_coin(300)],
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uatom_info.to_coin(300)), Borrow(uosmo_info.to_coin(100))],
+        &[uatom_info.to_coin(300)],
+    )
+    .unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![
+                Repay {
+                    recipient_account_id: None,
+                    coin: uosmo_info.to_action_coin(50),
+                },
+                SwapExactIn {
+                    coin_in: ActionCoin {
+                        denom: uatom_info.denom.clone(),
+                        amount: ActionAmount::AccountBalance,
+                    },
+                    denom_out: uosmo_info.denom.clone(),
+                    min_receive: Uint128::one(),
+                    route: None,
+                },
+            ],
+        )
+        .unwrap();
+
+    // Debt of 101 (mock red bank adds one unit of interest), shares reduced proportionally
+    assert_eq!(
+        res.positions.debts,
+        vec![DebtAmount {
+            denom: uosmo_info.denom.clone(),
+            shares: Uint128::new(50_495_050),
+            amount: Uint128::new(51),
+        }]
+    );
+    // All uatom swapped, the output is estimated by the swapper
+    assert_eq!(res.positions.deposits, vec![uosmo_info.to_coin(50 + MOCK_SWAP_RESULT.u128())]);
+    assert!(res.rejections.is_empty());
+}
+
+#[test]
+fn simulation_reports_max_ltv_rejection() {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[uatom_info.clone(), uosmo_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![Deposit(uatom_info.to_coin(100)), Borrow(uosmo_info.to_coin(10_000))],
+        )
+        .unwrap();
+
+    assert!(res.health.above_max_ltv);
+    assert_eq!(
+        res.rejections,
+        vec![ActionsRejection::AboveMaxLtv {
+            max_ltv_health_factor: res.health.max_ltv_health_factor,
+        }]
+    );
+}
+
+#[test]
+fn simulation_reports_deposit_cap_rejection() {
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[uatom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let mut params: AssetParams = mock.query_asset_params(&uatom_info.denom);
+    params.deposit_cap = Uint128::new(100);
+    mock.update_asset_params(AssetParamsUpdate::AddOrUpdate {
+        params: params.into(),
+    });
+
+    let res = mock.simulate_actions(&account_id, vec![Deposit(uatom_info.to_coin(101))]).unwrap();
+
+    assert_eq!(
+        res.rejections,
+        vec![ActionsRejection::AboveDepositCap {
+            new_value: uatom_info.to_coin(101),
+            maximum: Uint128::new(100),
+        }]
+    );
+}
+
+#[test]
+fn simulation_reports_hls_rejection() {
+    let uatom_info = uatom_info();
+    let ujake_info = ujake_info();
+    let user = Addr::unchecked("user");
+    let mut mock =
+        MockEnv::new().set_params(&[uatom_info.clone(), ujake_info.clone()]).build().unwrap();
+    let account_id = mock.create_hls_account(&user);
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![
+                Deposit(uatom_info.to_coin(1_000)),
+                Borrow(uatom_info.to_coin(10)),
+                Borrow(ujake_info.to_coin(10)),
+            ],
+        )
+        .unwrap();
+
+    assert_eq!(
+        res.rejections,
+        vec![ActionsRejection::HlsRules {
+            reason: "Account has more than one debt denom".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn cannot_simulate_unsupported_actions() {
+    let uatom_info = uatom_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().set_params(&[uatom_info.clone()]).build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let err = mock
+        .simulate_actions(
+            &account_id,
+            vec![Liquidate {
+                liquidatee_account_id: "2".to_string(),
+                debt_coin: uatom_info.to_coin(10),
+                request: LiquidateRequest::Deposit(uatom_info.denom.clone()),
+            }],
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&HealthError::UnsupportedAction("liquidate".to_string()).to_string()));
+
+    let err = mock
+        .simulate_actions(&account_id, vec![Withdraw(uatom_info.to_action_coin(10))])
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&HealthError::DenomNotPresent(uatom_info.denom.clone()).to_string()));
+}
+
+#[test]
+fn vault_actions_are_simulated() {
+    let lp_token = lp_token_info();
+    let leverage_vault = locked_vault_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[lp_token.clone()])
+        .vault_configs(&[leverage_vault.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![lp_token.to_coin(200)],
+        })
+        .build()
+        .unwrap();
+    let vault = mock.get_vault(&leverage_vault);
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let actions = vec![
+        Deposit(lp_token.to_coin(200)),
+        EnterVault {
+            vault: vault.clone(),
+            coin: lp_token.to_action_coin(23),
+        },
+    ];
+    let res = mock.simulate_actions(&account_id, actions.clone()).unwrap();
+
+    assert_eq!(res.positions.deposits, vec![lp_token.to_coin(177)]);
+    assert_eq!(res.positions.vaults.len(), 1);
+    assert_eq!(res.positions.vaults[0].amount.locked(), STARTING_VAULT_SHARES);
+
+    mock.update_credit_account(&account_id, &user, actions, &[lp_token.to_coin(200)]).unwrap();
+    assert_eq!(mock.query_positions(&account_id).vaults, res.positions.vaults);
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![RequestVaultUnlock {
+                vault,
+                amount: STARTING_VAULT_SHARES,
+            }],
+        )
+        .unwrap();
+
+    let unlocking = res.positions.vaults[0].amount.unlocking().positions();
+    assert_eq!(res.positions.vaults[0].amount.locked(), Uint128::zero());
+    assert_eq!(unlocking.len(), 1);
+    assert_eq!(unlocking[0].coin, lp_token.to_coin(23));
+}
@@ -0,0 +1,402 @@
+use cosmwasm_std::{coin, coins, Addr, Coin, Decimal, Uint128};
+use mars_credit_manager::error::ContractError;
+use mars_mock_oracle::msg::CoinPrice;
+use mars_types::{
+    credit_manager::{
+        Action::{self, Borrow, CreateTriggerOrder, DeleteTriggerOrder, Deposit, Withdraw},
+        ActionAmount, ActionCoin, Comparison, Condition, ConfigUpdates, KeeperFeeConfig,
+    },
+    oracle::ActionKind,
+};
+
+use super::helpers::{assert_err, uosmo_info, AccountToFund, CoinInfo, MockEnv};
+
+#[test]
+fn keeper_fee_config_required() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(coin_info.to_coin(1000)),
+            price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uosmo"), None),
+        ],
+        &[coin(1000, coin_info.denom)],
+    );
+
+    assert_err(
+        res,
+        ContractError::InvalidTriggerOrder {
+            reason: "keeper fee config not set".to_string(),
+        },
+    );
+}
+
+#[test]
+fn keeper_fee_below_minimum() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(&coin_info, Decimal::percent(20), coin(9, "uosmo"), None)],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidTriggerOrder {
+            reason: "keeper fee has to be at least 10uosmo".to_string(),
+        },
+    );
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uatom"), None)],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidTriggerOrder {
+            reason: "keeper fee has to be at least 10uosmo".to_string(),
+        },
+    );
+}
+
+#[test]
+fn actions_moving_funds_out_of_account_not_allowed() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CreateTriggerOrder {
+            actions: vec![Withdraw(ActionCoin {
+                denom: coin_info.denom.clone(),
+                amount: ActionAmount::AccountBalance,
+            })],
+            conditions: vec![price_condition(&coin_info, Decimal::percent(20))],
+            keeper_fee: coin(10, "uosmo"),
+            expires_at: None,
+        }],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::InvalidTriggerOrder {
+            reason: "only borrow, lend, reclaim, repay, swap_exact_in and execute_perp_order actions are allowed".to_string(),
+        },
+    );
+}
+
+#[test]
+fn expiration_has_to_be_in_the_future() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    let now = mock.query_block_time();
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uosmo"), Some(now))],
+        &[],
+    );
+
+    assert_err(
+        res,
+        ContractError::InvalidTriggerOrder {
+            reason: "expiration has to be in the future".to_string(),
+        },
+    );
+}
+
+#[test]
+fn create_trigger_order_holds_keeper_fee() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uosmo"), None),
+            price_trigger_order(&coin_info, Decimal::percent(10), coin(15, "uosmo"), None),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![coin(975, "uosmo")]);
+
+    let orders = mock.query_account_trigger_orders(&account_id, None, None);
+    assert_eq!(orders.data.len(), 2);
+    assert_eq!(orders.data[0].order.order_id, "1");
+    assert_eq!(orders.data[0].order.keeper_fee, coin(10, "uosmo"));
+    assert_eq!(orders.data[1].order.order_id, "2");
+    assert_eq!(orders.data[1].order.keeper_fee, coin(15, "uosmo"));
+
+    let orders = mock.query_account_trigger_orders(&account_id, Some("1".to_string()), None);
+    assert_eq!(orders.data.len(), 1);
+    assert_eq!(orders.data[0].order.order_id, "2");
+
+    let orders = mock.query_all_trigger_orders(None, Some(1));
+    assert_eq!(orders.data.len(), 1);
+    assert!(orders.metadata.has_more);
+    assert_eq!(orders.data[0].account_id, account_id);
+}
+
+#[test]
+fn delete_trigger_order_refunds_keeper_fee() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uosmo"), None)],
+        &[],
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![DeleteTriggerOrder {
+            trigger_order_id: "2".to_string(),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::TriggerOrderNotFound {
+            account_id: account_id.clone(),
+            order_id: "2".to_string(),
+        },
+    );
+
+    let res = mock
+        .simulate_actions(
+            &account_id,
+            vec![DeleteTriggerOrder {
+                trigger_order_id: "1".to_string(),
+            }],
+        )
+        .unwrap();
+    assert_eq!(res.positions.deposits, vec![coin(1000, "uosmo")]);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![DeleteTriggerOrder {
+            trigger_order_id: "1".to_string(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![coin(1000, "uosmo")]);
+
+    let orders = mock.query_account_trigger_orders(&account_id, None, None);
+    assert!(orders.data.is_empty());
+}
+
+#[test]
+fn trigger_order_executed_once_conditions_met() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(&coin_info, Decimal::percent(20), coin(10, "uosmo"), None)],
+        &[],
+    )
+    .unwrap();
+
+    let res = mock.execute_trigger_order(&keeper, &account_id, "1");
+    assert_err(res, ContractError::TriggerConditionsNotMet("1".to_string()));
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: coin_info.denom.clone(),
+        price: Decimal::percent(15),
+    });
+
+    mock.execute_trigger_order(&keeper, &account_id, "1").unwrap();
+
+    let keeper_balance = mock.query_balance(&keeper, &coin_info.denom);
+    assert_eq!(keeper_balance.amount, Uint128::new(10));
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![coin(1090, "uosmo")]);
+    assert_eq!(position.debts.len(), 1);
+    assert_eq!(position.debts[0].amount, Uint128::new(101));
+
+    // An executed order can't be executed again
+    let res = mock.execute_trigger_order(&keeper, &account_id, "1");
+    assert_err(
+        res,
+        ContractError::TriggerOrderNotFound {
+            account_id,
+            order_id: "1".to_string(),
+        },
+    );
+}
+
+#[test]
+fn health_factor_condition() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![CreateTriggerOrder {
+            actions: vec![Borrow(coin_info.to_coin(100))],
+            conditions: vec![Condition::HealthFactor {
+                threshold: Decimal::percent(500),
+                comparison: Comparison::GreaterThan,
+            }],
+            keeper_fee: coin(10, "uosmo"),
+            expires_at: None,
+        }],
+        &[],
+    )
+    .unwrap();
+
+    // Without debt the health factor is infinite
+    mock.execute_trigger_order(&keeper, &account_id, "1").unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.debts.len(), 1);
+}
+
+#[test]
+fn expired_trigger_order_cannot_be_executed() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let keeper = Addr::unchecked("keeper");
+    let (mut mock, account_id) = setup(&coin_info, &user);
+
+    let expires_at = mock.query_block_time() + 100;
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![price_trigger_order(
+            &coin_info,
+            Decimal::percent(20),
+            coin(10, "uosmo"),
+            Some(expires_at),
+        )],
+        &[],
+    )
+    .unwrap();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Default,
+        denom: coin_info.denom.clone(),
+        price: Decimal::percent(15),
+    });
+    mock.increment_by_time(100);
+
+    let res = mock.execute_trigger_order(&keeper, &account_id, "1");
+    assert_err(res, ContractError::TriggerOrderExpired("1".to_string()));
+
+    // The owner can still delete the order to get the keeper fee back
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![DeleteTriggerOrder {
+            trigger_order_id: "1".to_string(),
+        }],
+        &[],
+    )
+    .unwrap();
+
+    let position = mock.query_positions(&account_id);
+    assert_eq!(position.deposits, vec![coin(1000, "uosmo")]);
+}
+
+/// Creates an account with 1000 uosmo deposited and sets the min keeper fee to 10 uosmo
+fn setup(coin_info: &CoinInfo, user: &Addr) -> (MockEnv, String) {
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(1000, coin_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(user).unwrap();
+
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            keeper_fee_config: Some(KeeperFeeConfig {
+                min_fee: coin(10, "uosmo"),
+                auto_deleverage_fee_rate: Decimal::zero(),
+            }),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        user,
+        vec![Deposit(coin_info.to_coin(1000))],
+        &[coin(1000, coin_info.denom.clone())],
+    )
+    .unwrap();
+
+    (mock, account_id)
+}
+
+fn price_condition(coin_info: &CoinInfo, price: Decimal) -> Condition {
+    Condition::OraclePrice {
+        denom: coin_info.denom.clone(),
+        price,
+        comparison: Comparison::LessThan,
+    }
+}
+
+/// Borrows 100 of the coin once its price drops below `price`
+fn price_trigger_order(
+    coin_info: &CoinInfo,
+    price: Decimal,
+    keeper_fee: Coin,
+    expires_at: Option<u64>,
+) -> Action {
+    CreateTriggerOrder {
+        actions: vec![Borrow(coin_info.to_coin(100))],
+        conditions: vec![price_condition(coin_info, price)],
+        keeper_fee,
+        expires_at,
+    }
+}
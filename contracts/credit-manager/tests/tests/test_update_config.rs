@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Decimal, Empty, Uint128};
+use cosmwasm_std::{coin, Addr, Decimal, Empty, Uint128};
 use cw_multi_test::Executor;
 use mars_credit_manager::error::ContractError;
 use mars_mock_oracle::msg::{CoinPrice, InstantiateMsg as OracleInstantiateMsg};
@@ -6,10 +6,10 @@ use mars_testing::multitest::modules::token_factory::CustomApp;
 use mars_types::{
     adapters::{
         health::HealthContractUnchecked, incentives::IncentivesUnchecked, oracle::OracleUnchecked,
-        red_bank::RedBankUnchecked, rewards_collector::RewardsCollector, swapper::SwapperBase,
-        zapper::ZapperBase,
+        perps::PerpsUnchecked, pyth::PythUnchecked, red_bank::RedBankUnchecked,
+        rewards_collector::RewardsCollector, swapper::SwapperBase, zapper::ZapperBase,
     },
-    credit_manager::ConfigUpdates,
+    credit_manager::{ConfigUpdates, KeeperFeeConfig},
     health::AccountKind,
     oracle::ActionKind,
 };
@@ -35,6 +35,10 @@ fn only_owner_can_update_config() {
             health_contract: None,
             rewards_collector: None,
             swap_fee: None,
+            perps: None,
+            keeper_fee_config: None,
+            account_stats_enabled: None,
+            pyth: None,
         },
     );
 
@@ -93,6 +97,12 @@ fn update_config_works_with_full_config() {
     let new_health_contract = HealthContractUnchecked::new("new_health_contract".to_string());
     let new_rewards_collector = "rewards_collector_contract_new".to_string();
     let new_swap_fee = Decimal::percent(1);
+    let new_perps = PerpsUnchecked::new("new_perps".to_string());
+    let new_pyth = PythUnchecked::new("new_pyth".to_string());
+    let new_keeper_fee_config = KeeperFeeConfig {
+        min_fee: coin(1000, "uusdc"),
+        auto_deleverage_fee_rate: Decimal::percent(1),
+    };
 
     mock.update_config(
         &Addr::unchecked(original_config.ownership.owner.clone().unwrap()),
@@ -108,6 +118,10 @@ fn update_config_works_with_full_config() {
             health_contract: Some(new_health_contract.clone()),
             rewards_collector: Some(new_rewards_collector.clone()),
             swap_fee: Some(new_swap_fee),
+            perps: Some(new_perps.clone()),
+            keeper_fee_config: Some(new_keeper_fee_config.clone()),
+            account_stats_enabled: Some(true),
+            pyth: Some(new_pyth.clone()),
         },
     )
     .unwrap();
@@ -157,6 +171,18 @@ fn update_config_works_with_full_config() {
 
     assert_eq!(&new_config.incentives, new_incentives.address());
     assert_ne!(new_config.incentives, original_config.incentives);
+
+    assert_eq!(new_config.perps.as_ref(), Some(new_perps.address()));
+    assert_ne!(new_config.perps, original_config.perps);
+
+    assert_eq!(new_config.keeper_fee_config, Some(new_keeper_fee_config));
+    assert_ne!(new_config.keeper_fee_config, original_config.keeper_fee_config);
+
+    assert!(new_config.account_stats_enabled);
+    assert!(!original_config.account_stats_enabled);
+
+    assert_eq!(new_config.pyth.as_ref(), Some(new_pyth.address()));
+    assert_ne!(new_config.pyth, original_config.pyth);
 }
 
 #[test]
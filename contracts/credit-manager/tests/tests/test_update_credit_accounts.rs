@@ -0,0 +1,293 @@
+use cosmwasm_std::{coin, coins, Addr, Coins, Uint128};
+use mars_credit_manager::error::ContractError;
+use mars_types::credit_manager::{
+    Action::{Borrow, Deposit, TransferToAccount},
+    ActionAmount, ActionCoin,
+};
+
+use super::helpers::{assert_err, uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn updates_have_to_be_unique_and_not_empty() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_accounts(&user, vec![], &[]);
+    assert_err(
+        res,
+        ContractError::InvalidAccountUpdates {
+            reason: "at least one account update is required".to_string(),
+        },
+    );
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![(account_id.clone(), vec![]), (account_id.clone(), vec![])],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidAccountUpdates {
+            reason: format!("account {account_id} is updated more than once"),
+        },
+    );
+}
+
+#[test]
+fn only_owner_can_update_accounts() {
+    let user = Addr::unchecked("user");
+    let other_user = Addr::unchecked("other_user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let other_account_id = mock.create_credit_account(&other_user).unwrap();
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![(account_id, vec![]), (other_account_id.clone(), vec![])],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::NotTokenOwner {
+            user: user.to_string(),
+            account_id: other_account_id,
+        },
+    );
+}
+
+#[test]
+fn actions_of_multiple_accounts_are_dispatched() {
+    let uatom_info = uatom_info();
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uatom_info.clone(), uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin(300, "uatom"), coin(500, "uosmo")],
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    let res = mock
+        .update_credit_accounts(
+            &user,
+            vec![
+                (
+                    account_id_a.clone(),
+                    vec![Deposit(uatom_info.to_coin(300)), Borrow(uosmo_info.to_coin(50))],
+                ),
+                (account_id_b.clone(), vec![Deposit(uosmo_info.to_coin(500))]),
+            ],
+            &[coin(300, "uatom"), coin(500, "uosmo")],
+        )
+        .unwrap();
+    assert!(res.events.iter().any(|event| event
+        .attributes
+        .iter()
+        .any(|attr| attr.value == "rover/execute/update_credit_accounts")));
+
+    let position_a = mock.query_positions(&account_id_a);
+    assert_eq!(position_a.deposits, vec![uatom_info.to_coin(300), uosmo_info.to_coin(50)]);
+    assert_eq!(position_a.debts.len(), 1);
+
+    let position_b = mock.query_positions(&account_id_b);
+    assert_eq!(position_b.deposits, vec![uosmo_info.to_coin(500)]);
+    assert!(position_b.debts.is_empty());
+}
+
+#[test]
+fn funds_sent_have_to_be_used() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(500, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![
+            (account_id_a, vec![Deposit(uosmo_info.to_coin(200))]),
+            (account_id_b, vec![Deposit(uosmo_info.to_coin(200))]),
+        ],
+        &coins(500, "uosmo"),
+    );
+    assert_err(
+        res,
+        ContractError::ExtraFundsReceived(Coins::try_from(coins(100, "uosmo")).unwrap()),
+    );
+}
+
+#[test]
+fn transfer_requires_accounts_of_the_same_owner() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let other_user = Addr::unchecked("other_user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(500, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+    let other_account_id = mock.create_credit_account(&other_user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(500))],
+        &coins(500, "uosmo"),
+    )
+    .unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![TransferToAccount {
+            account_id: account_id.clone(),
+            coin: uosmo_info.to_action_coin(100),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidTransfer {
+            reason: "recipient has to be another account".to_string(),
+        },
+    );
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![TransferToAccount {
+            account_id: other_account_id.clone(),
+            coin: uosmo_info.to_action_coin(100),
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidTransfer {
+            reason: format!("account {other_account_id} is not owned by {user}"),
+        },
+    );
+}
+
+#[test]
+fn transfer_moves_coin_balances() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(500, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id_a,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(500)),
+            TransferToAccount {
+                account_id: account_id_b.clone(),
+                coin: uosmo_info.to_action_coin(200),
+            },
+        ],
+        &coins(500, "uosmo"),
+    )
+    .unwrap();
+    assert_eq!(mock.query_positions(&account_id_a).deposits, vec![uosmo_info.to_coin(300)]);
+    assert_eq!(mock.query_positions(&account_id_b).deposits, vec![uosmo_info.to_coin(200)]);
+
+    mock.update_credit_account(
+        &account_id_b,
+        &user,
+        vec![TransferToAccount {
+            account_id: account_id_a.clone(),
+            coin: ActionCoin {
+                denom: uosmo_info.denom.clone(),
+                amount: ActionAmount::AccountBalance,
+            },
+        }],
+        &[],
+    )
+    .unwrap();
+    assert_eq!(mock.query_positions(&account_id_a).deposits, vec![uosmo_info.to_coin(500)]);
+    assert!(mock.query_positions(&account_id_b).deposits.is_empty());
+
+    // Total balance of the credit manager is unchanged
+    let rover = mock.rover.clone();
+    assert_eq!(mock.query_balance(&rover, "uosmo").amount, Uint128::new(500));
+}
+
+#[test]
+fn health_is_checked_after_all_accounts_are_updated() {
+    let uosmo_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(3000, "uosmo"),
+        })
+        .build()
+        .unwrap();
+    let account_id_a = mock.create_credit_account(&user).unwrap();
+    let account_id_b = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id_a,
+        &user,
+        vec![Deposit(uosmo_info.to_coin(3000))],
+        &coins(3000, "uosmo"),
+    )
+    .unwrap();
+
+    // Borrowing without collateral leaves the account unhealthy
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![(account_id_b.clone(), vec![Borrow(uosmo_info.to_coin(1000))])],
+        &[],
+    );
+    let err: ContractError = res.unwrap_err().downcast().unwrap();
+    assert!(matches!(err, ContractError::AboveMaxLTV { .. }));
+
+    // The collateral transferred by a later update is taken into account
+    mock.update_credit_accounts(
+        &user,
+        vec![
+            (account_id_b.clone(), vec![Borrow(uosmo_info.to_coin(1000))]),
+            (
+                account_id_a.clone(),
+                vec![TransferToAccount {
+                    account_id: account_id_b.clone(),
+                    coin: uosmo_info.to_action_coin(3000),
+                }],
+            ),
+        ],
+        &[],
+    )
+    .unwrap();
+
+    assert!(mock.query_positions(&account_id_a).deposits.is_empty());
+    let position_b = mock.query_positions(&account_id_b);
+    assert_eq!(position_b.deposits, vec![uosmo_info.to_coin(4000)]);
+    assert_eq!(position_b.debts.len(), 1);
+}
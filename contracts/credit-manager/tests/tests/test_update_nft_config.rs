@@ -35,6 +35,7 @@ fn only_owner_can_update_nft_config() {
                     max_value_for_burn: None,
                     health_contract_addr: None,
                     credit_manager_contract_addr: None,
+                    transfer_policy: None,
                 },
             },
             &[],
@@ -89,6 +90,7 @@ fn update_config_works_with_full_config() {
             max_value_for_burn: new_max_value,
             health_contract_addr: new_health_contract.clone(),
             credit_manager_contract_addr: new_cm_contract.clone(),
+            transfer_policy: None,
         }),
         Some(cw721_base::Action::TransferOwnership {
             new_owner: new_proposed.clone().unwrap().into(),
@@ -0,0 +1,216 @@
+use cosmwasm_std::{coin, to_json_binary, Addr, Binary, Coin};
+use cw_multi_test::Executor;
+use mars_credit_manager::error::ContractError;
+use mars_mock_pyth::msg::{InstantiateMsg, QueryMsg};
+use mars_types::{
+    adapters::pyth::PythUnchecked,
+    credit_manager::{
+        Action::{Deposit, UpdatePythPrices},
+        ConfigUpdates,
+    },
+};
+use pyth_sdk_cw::{Price, PriceFeed, PriceFeedResponse, PriceIdentifier};
+
+use super::helpers::{assert_err, mock_pyth_contract, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn pyth_contract_has_to_be_set() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![UpdatePythPrices {
+            vaa_data: vec![price_feed_vaa()],
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidPythPriceUpdate {
+            reason: "pyth contract is not set".to_string(),
+        },
+    );
+}
+
+#[test]
+fn update_fee_has_to_be_sent() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    deploy_pyth(&mut mock, coin(2, "uosmo"));
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![UpdatePythPrices {
+            vaa_data: vec![price_feed_vaa()],
+        }],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidPythPriceUpdate {
+            reason: "update fee of 2uosmo required, received: 0".to_string(),
+        },
+    );
+}
+
+#[test]
+fn extra_update_fee_is_rejected() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin(300, coin_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    deploy_pyth(&mut mock, coin(2, coin_info.denom.clone()));
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            UpdatePythPrices {
+                vaa_data: vec![price_feed_vaa()],
+            },
+            Deposit(coin_info.to_coin(100)),
+        ],
+        &[coin_info.to_coin(103)],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidPythPriceUpdate {
+            reason: "update fee of 2uosmo required, received: 3".to_string(),
+        },
+    );
+}
+
+#[test]
+fn prices_can_not_be_updated_for_multiple_accounts() {
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new().build().unwrap();
+    deploy_pyth(&mut mock, coin(2, "uosmo"));
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    let res = mock.update_credit_accounts(
+        &user,
+        vec![(
+            account_id,
+            vec![UpdatePythPrices {
+                vaa_data: vec![price_feed_vaa()],
+            }],
+        )],
+        &[],
+    );
+    assert_err(
+        res,
+        ContractError::InvalidPythPriceUpdate {
+            reason: "prices can only be updated through UpdateCreditAccount".to_string(),
+        },
+    );
+}
+
+#[test]
+fn prices_are_updated_before_other_actions() {
+    let coin_info = uosmo_info();
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[coin_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: vec![coin(300, coin_info.denom.clone())],
+        })
+        .build()
+        .unwrap();
+    let pyth = deploy_pyth(&mut mock, coin(2, coin_info.denom.clone()));
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    // the fee is sent on top of the deposited coins
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            UpdatePythPrices {
+                vaa_data: vec![price_feed_vaa()],
+            },
+            Deposit(coin_info.to_coin(100)),
+        ],
+        &[coin_info.to_coin(102)],
+    )
+    .unwrap();
+
+    let res = mock.query_positions(&account_id);
+    assert_eq!(res.deposits, vec![coin_info.to_coin(100)]);
+    assert_eq!(mock.query_balance(&pyth, &coin_info.denom), coin_info.to_coin(2));
+    assert_eq!(mock.query_balance(&user, &coin_info.denom), coin_info.to_coin(198));
+
+    let res: PriceFeedResponse = mock
+        .app
+        .wrap()
+        .query_wasm_smart(
+            pyth,
+            &QueryMsg::PriceFeed {
+                id: price_feed().id,
+            },
+        )
+        .unwrap();
+    assert_eq!(res.price_feed, price_feed());
+}
+
+fn deploy_pyth(mock: &mut MockEnv, update_fee: Coin) -> Addr {
+    let code_id = mock.app.store_code(mock_pyth_contract());
+    let owner = Addr::unchecked(mock.query_config().ownership.owner.unwrap());
+    let pyth = mock
+        .app
+        .instantiate_contract(
+            code_id,
+            owner.clone(),
+            &InstantiateMsg {
+                update_fee: Some(update_fee),
+            },
+            &[],
+            "mock-pyth",
+            None,
+        )
+        .unwrap();
+
+    mock.update_config(
+        &owner,
+        ConfigUpdates {
+            pyth: Some(PythUnchecked::new(pyth.to_string())),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    pyth
+}
+
+fn price_feed() -> PriceFeed {
+    let price = Price {
+        price: 100000,
+        conf: 50,
+        expo: -5,
+        publish_time: 1571797419,
+    };
+    PriceFeed::new(
+        PriceIdentifier::from_hex(
+            "61226d39beea19d334f17c2febce27e12646d84675924ebb02b9cdaea68727e3",
+        )
+        .unwrap(),
+        price,
+        price,
+    )
+}
+
+// The mock Pyth contract accepts JSON encoded price feeds in place of VAAs
+fn price_feed_vaa() -> Binary {
+    to_json_binary(&price_feed()).unwrap()
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use cosmwasm_std::{Deps, StdResult};
-use mars_rover_health_computer::{DenomsData, HealthComputer, VaultsData};
+use mars_rover_health_computer::{DenomsData, HealthComputer, PerpsData, VaultsData};
 use mars_types::{
     credit_manager::Positions,
     health::{AccountKind, HealthResult, HealthState, HealthValuesResponse},
@@ -34,6 +34,7 @@ pub fn compute_health(
         .collect::<StdResult<HashMap<_, _>>>()?;
     let vault_base_token_denoms = vault_infos.values().map(|v| &v.base_token).collect::<Vec<_>>();
     let staked_lp_denoms = positions.staked_astro_lps.iter().map(|d| &d.denom).collect::<Vec<_>>();
+    let perp_base_denoms = positions.perps.iter().map(|p| &p.base_denom).collect::<Vec<_>>();
 
     // Collect prices + asset
     let mut denoms_data: DenomsData = Default::default();
@@ -43,6 +44,7 @@ pub fn compute_health(
         .chain(lend_denoms)
         .chain(vault_base_token_denoms)
         .chain(staked_lp_denoms)
+        .chain(perp_base_denoms)
         .try_for_each(|denom| -> StdResult<()> {
             let params_opt = q.params.query_asset_params(&deps.querier, denom)?;
             // If the asset is not supported, we skip it (both params and price)
@@ -65,11 +67,23 @@ pub fn compute_health(
         Ok(())
     })?;
 
+    // Collect all perp data. Perp denoms don't need asset params, only a price.
+    let mut perps_data: PerpsData = Default::default();
+    positions.perps.iter().try_for_each(|p| -> StdResult<()> {
+        let params = q.params.query_perp_params(&deps.querier, &p.denom)?;
+        perps_data.params.insert(p.denom.clone(), params);
+
+        let price = q.oracle.query_price(&deps.querier, &p.denom, action.clone())?.price;
+        denoms_data.prices.insert(p.denom.clone(), price);
+        Ok(())
+    })?;
+
     let computer = HealthComputer {
         kind,
         positions,
         denoms_data,
         vaults_data,
+        perps_data,
     };
 
     Ok(computer.compute_health()?.into())
@@ -86,6 +100,17 @@ pub fn health_values(
     compute_health(deps, kind, q, positions, action)
 }
 
+/// Health of positions that are not (yet) stored in the credit manager, e.g. simulated ones
+pub fn health_values_for_positions(
+    deps: Deps,
+    positions: Positions,
+    kind: AccountKind,
+    action: ActionKind,
+) -> HealthResult<HealthValuesResponse> {
+    let q = HealthQuerier::new(&deps)?;
+    compute_health(deps, kind, q, positions, action)
+}
+
 pub fn health_state(
     deps: Deps,
     account_id: &str,
@@ -97,7 +122,7 @@ pub fn health_state(
 
     // Helpful to not have to do computations & query the oracle for cases
     // like liquidations where oracle circuit breakers may hinder it.
-    if positions.debts.is_empty() {
+    if positions.debts.is_empty() && positions.perps.is_empty() {
         return Ok(HealthState::Healthy);
     }
 
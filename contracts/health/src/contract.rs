@@ -6,7 +6,7 @@ use mars_owner::OwnerInit::SetInitialOwner;
 use mars_types::health::{ConfigResponse, ExecuteMsg, HealthResult, InstantiateMsg, QueryMsg};
 
 use crate::{
-    compute::{health_state, health_values},
+    compute::{health_state, health_values, health_values_for_positions},
     migrations,
     state::{CREDIT_MANAGER, OWNER},
     update_config::update_config,
@@ -68,6 +68,11 @@ pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> HealthResult<Binary> {
             kind,
             action,
         } => to_json_binary(&health_state(deps, &account_id, kind, action)?),
+        QueryMsg::HealthValuesForPositions {
+            positions,
+            kind,
+            action,
+        } => to_json_binary(&health_values_for_positions(deps, positions, kind, action)?),
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
     };
     res.map_err(Into::into)
@@ -19,6 +19,7 @@ pub fn default_asset_params(denom: &str) -> AssetParamsUnchecked {
         red_bank: RedBankSettings {
             deposit_enabled: false,
             borrow_enabled: false,
+            isolation: None,
         },
         max_loan_to_value: Decimal::from_str("0.4523").unwrap(),
         liquidation_threshold: Decimal::from_str("0.5").unwrap(),
@@ -30,5 +31,6 @@ pub fn default_asset_params(denom: &str) -> AssetParamsUnchecked {
         },
         protocol_liquidation_fee: Decimal::percent(2u64),
         deposit_cap: Default::default(),
+        borrow_cap: Default::default(),
     }
 }
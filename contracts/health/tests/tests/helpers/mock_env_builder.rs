@@ -137,6 +137,10 @@ impl MockEnvBuilder {
                         zapper: "n/a".to_string(),
                         health_contract: "n/a".to_string(),
                         rewards_collector: None,
+                        perps: None,
+                        keeper_fee_config: None,
+                        account_stats_enabled: None,
+                        pyth: None,
                     },
                 },
                 &[],
@@ -190,8 +194,10 @@ impl MockEnvBuilder {
                 owner.clone(),
                 &ParamsInstantiateMsg {
                     owner: owner.to_string(),
+                    risk_manager: None,
                     address_provider: "n/a".to_string(),
                     target_health_factor: Decimal::from_str("1.2").unwrap(),
+                    max_perp_params: 40,
                 },
                 &[],
                 "mock-params-contract",
@@ -26,6 +26,7 @@ fn zero_debts_results_in_healthy_state() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -63,6 +64,7 @@ fn computing_health_when_healthy() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -99,6 +101,7 @@ fn computing_health_when_unhealthy() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
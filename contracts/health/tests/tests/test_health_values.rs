@@ -59,6 +59,7 @@ fn computes_correct_position_with_zero_assets() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -106,6 +107,7 @@ fn adds_vault_base_denoms_to_oracle_and_red_bank() {
                 }),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -126,6 +128,7 @@ fn adds_vault_base_denoms_to_oracle_and_red_bank() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value,
             liquidation_threshold,
@@ -137,6 +140,7 @@ fn adds_vault_base_denoms_to_oracle_and_red_bank() {
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     };
 
@@ -192,12 +196,14 @@ fn whitelisted_coins_work() {
         red_bank: RedBankSettings {
             deposit_enabled: false,
             borrow_enabled: false,
+            isolation: None,
         },
         max_loan_to_value,
         liquidation_threshold,
         liquidation_bonus,
         protocol_liquidation_fee: Decimal::percent(2u64),
         deposit_cap: Default::default(),
+        borrow_cap: Default::default(),
     };
 
     let update = AddOrUpdate {
@@ -222,6 +228,7 @@ fn whitelisted_coins_work() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -280,6 +287,7 @@ fn vault_whitelist_affects_max_ltv() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(vault_token_amount)),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
@@ -297,6 +305,7 @@ fn vault_whitelist_affects_max_ltv() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_str("0.4523").unwrap(),
             liquidation_threshold: Decimal::from_str("0.5").unwrap(),
@@ -308,6 +317,7 @@ fn vault_whitelist_affects_max_ltv() {
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     };
 
@@ -382,6 +392,7 @@ fn not_whitelisted_coins_work() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
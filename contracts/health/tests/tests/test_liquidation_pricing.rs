@@ -34,6 +34,7 @@ fn uses_liquidation_pricing() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(4523u128, 4).unwrap(),
             liquidation_threshold: Decimal::from_atomics(5u128, 1).unwrap(),
@@ -45,6 +46,7 @@ fn uses_liquidation_pricing() {
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     };
 
@@ -68,6 +70,7 @@ fn uses_liquidation_pricing() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
     );
 
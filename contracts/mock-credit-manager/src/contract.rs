@@ -4,9 +4,9 @@ use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Resp
 use mars_types::credit_manager::QueryMsg;
 
 use crate::{
-    execute::{set_account_kind_response, set_position_response},
+    execute::{set_account_kind_response, set_last_borrow_height, set_position_response},
     msg::{ExecuteMsg, InstantiateMsg},
-    query::{query_account_kind, query_config, query_positions},
+    query::{query_account_kind, query_config, query_last_borrow_height, query_positions},
     state::CONFIG,
 };
 
@@ -37,6 +37,10 @@ pub fn execute(
             account_id,
             kind,
         } => set_account_kind_response(deps, account_id, kind),
+        ExecuteMsg::SetLastBorrowHeight {
+            account_id,
+            height,
+        } => set_last_borrow_height(deps, account_id, height),
     }
 }
 
@@ -50,6 +54,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AccountKind {
             account_id,
         } => to_json_binary(&query_account_kind(deps, account_id)?),
+        QueryMsg::LastBorrowHeight {
+            account_id,
+        } => to_json_binary(&query_last_borrow_height(deps, account_id)?),
         _ => unimplemented!("query msg not supported"),
     }
 }
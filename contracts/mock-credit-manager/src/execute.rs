@@ -1,7 +1,7 @@
 use cosmwasm_std::{DepsMut, Response, StdResult};
 use mars_types::{credit_manager::Positions, health::AccountKind};
 
-use crate::state::{ACCOUNT_KINDS, POSITION_RESPONSES};
+use crate::state::{ACCOUNT_KINDS, LAST_BORROW_HEIGHTS, POSITION_RESPONSES};
 
 pub fn set_position_response(
     deps: DepsMut,
@@ -20,3 +20,12 @@ pub fn set_account_kind_response(
     ACCOUNT_KINDS.save(deps.storage, &account_id, &kind)?;
     Ok(Response::new())
 }
+
+pub fn set_last_borrow_height(
+    deps: DepsMut,
+    account_id: String,
+    height: u64,
+) -> StdResult<Response> {
+    LAST_BORROW_HEIGHTS.save(deps.storage, &account_id, &height)?;
+    Ok(Response::new())
+}
@@ -19,4 +19,8 @@ pub enum ExecuteMsg {
         account_id: String,
         kind: AccountKind,
     },
+    SetLastBorrowHeight {
+        account_id: String,
+        height: u64,
+    },
 }
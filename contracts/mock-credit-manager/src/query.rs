@@ -4,7 +4,7 @@ use mars_types::{
     health::AccountKind,
 };
 
-use crate::state::{ACCOUNT_KINDS, CONFIG, POSITION_RESPONSES};
+use crate::state::{ACCOUNT_KINDS, CONFIG, LAST_BORROW_HEIGHTS, POSITION_RESPONSES};
 
 pub fn query_positions(deps: Deps, account_id: String) -> StdResult<Positions> {
     POSITION_RESPONSES.load(deps.storage, &account_id)
@@ -17,3 +17,7 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 pub fn query_account_kind(deps: Deps, account_id: String) -> StdResult<AccountKind> {
     Ok(ACCOUNT_KINDS.may_load(deps.storage, &account_id)?.unwrap_or(AccountKind::Default))
 }
+
+pub fn query_last_borrow_height(deps: Deps, account_id: String) -> StdResult<Option<u64>> {
+    LAST_BORROW_HEIGHTS.may_load(deps.storage, &account_id)
+}
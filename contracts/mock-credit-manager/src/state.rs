@@ -9,3 +9,5 @@ pub const CONFIG: Item<ConfigResponse> = Item::new("config");
 pub const POSITION_RESPONSES: Map<&str, Positions> = Map::new("position_responses"); // Map<account_id, Positions>
 
 pub const ACCOUNT_KINDS: Map<&str, AccountKind> = Map::new("account_types");
+
+pub const LAST_BORROW_HEIGHTS: Map<&str, u64> = Map::new("last_borrow_heights");
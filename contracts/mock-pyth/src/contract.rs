@@ -1,21 +1,54 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
-    StdResult,
+    coin, entry_point, from_json, to_json_binary, Binary, Coin, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128,
+};
+use pyth_sdk_cw::{Price, PriceFeed, PriceFeedResponse, PriceIdentifier};
+
+use crate::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{PRICE_FEEDS, UPDATE_FEE},
 };
-use pyth_sdk_cw::{Price, PriceFeed, PriceFeedResponse, PriceIdentifier, QueryMsg};
 
 #[entry_point]
 pub fn instantiate(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
     _info: MessageInfo,
-    _msg: Empty,
+    msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    UPDATE_FEE.save(deps.storage, &msg.update_fee.unwrap_or_else(|| coin(0, "uosmo")))?;
     Ok(Response::default())
 }
 
 #[entry_point]
-pub fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdatePriceFeeds {
+            data,
+        } => update_price_feeds(deps, info, data),
+    }
+}
+
+fn update_price_feeds(deps: DepsMut, info: MessageInfo, data: Vec<Binary>) -> StdResult<Response> {
+    let fee = update_fee(deps.as_ref(), data.len())?;
+    let received =
+        info.funds.iter().find(|c| c.denom == fee.denom).map(|c| c.amount).unwrap_or_default();
+    if received < fee.amount {
+        return Err(StdError::generic_err(format!(
+            "Insufficient fee: {received}, required: {fee}"
+        )));
+    }
+
+    for vaa in data {
+        let price_feed: PriceFeed = from_json(&vaa)?;
+        PRICE_FEEDS.save(deps.storage, &price_feed.id.to_hex(), &price_feed)?;
+    }
+
     Ok(Response::default())
 }
 
@@ -24,12 +57,27 @@ pub fn query(deps: Deps, _: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::PriceFeed {
             id,
-        } => to_json_binary(&mocked_price_feed(deps, id)?),
-        _ => panic!("Unsupported query!"),
+        } => to_json_binary(&query_price_feed(deps, id)?),
+        QueryMsg::GetUpdateFee {
+            vaas,
+        } => to_json_binary(&update_fee(deps, vaas.len())?),
     }
 }
 
-fn mocked_price_feed(_deps: Deps, id: PriceIdentifier) -> StdResult<PriceFeedResponse> {
+fn update_fee(deps: Deps, vaas_count: usize) -> StdResult<Coin> {
+    let fee = UPDATE_FEE.load(deps.storage)?;
+    let amount = fee.amount.checked_mul(Uint128::from(vaas_count as u128))?;
+    Ok(coin(amount.u128(), fee.denom))
+}
+
+/// Pushed price feed, mocked price feed if none was pushed for the id
+fn query_price_feed(deps: Deps, id: PriceIdentifier) -> StdResult<PriceFeedResponse> {
+    if let Some(price_feed) = PRICE_FEEDS.may_load(deps.storage, &id.to_hex())? {
+        return Ok(PriceFeedResponse {
+            price_feed,
+        });
+    }
+
     let price_feed_response = PriceFeedResponse {
         price_feed: PriceFeed::new(
             id,
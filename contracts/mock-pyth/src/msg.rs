@@ -0,0 +1,23 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Binary, Coin};
+use pyth_sdk_cw::PriceIdentifier;
+
+#[cw_serde]
+#[derive(Default)]
+pub struct InstantiateMsg {
+    /// Fee charged per VAA, no fee if not set
+    pub update_fee: Option<Coin>,
+}
+
+/// Price updates are accepted as JSON encoded `PriceFeed`s instead of signed VAAs
+pub use mars_types::pyth::ExecuteMsg;
+
+#[cw_serde]
+pub enum QueryMsg {
+    PriceFeed {
+        id: PriceIdentifier,
+    },
+    GetUpdateFee {
+        vaas: Vec<Binary>,
+    },
+}
@@ -0,0 +1,6 @@
+use cosmwasm_std::Coin;
+use cw_storage_plus::{Item, Map};
+use pyth_sdk_cw::PriceFeed;
+
+pub const UPDATE_FEE: Item<Coin> = Item::new("update_fee");
+pub const PRICE_FEEDS: Map<&str, PriceFeed> = Map::new("price_feeds"); // Map<FeedIdHex, PriceFeed>
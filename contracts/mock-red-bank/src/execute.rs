@@ -30,6 +30,7 @@ pub fn init_asset(
         collateral_total_scaled: Uint128::zero(),
         debt_total_scaled: Uint128::zero(),
         interest_rate_model: params.interest_rate_model.unwrap(),
+        flash_loan_fee_rate: params.flash_loan_fee_rate.unwrap_or_default(),
     };
 
     MARKETS.save(deps.storage, &denom, &market)?;
@@ -1,11 +1,14 @@
-use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Coin, Deps, Order, StdError, StdResult, Storage, Uint128};
 use cw_utils::Duration;
 use cw_vault_standard::{extensions::lockup::UnlockingPosition, msg::VaultInfoResponse};
+use mars_types::oracle::ActionKind;
 
 use crate::{
+    contract::STARTING_VAULT_SHARES,
     error::{ContractError::NotLockingVault, ContractResult},
     state::{
-        COIN_BALANCE, LOCKUP_TIME, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS, VAULT_TOKEN_DENOM,
+        COIN_BALANCE, LOCKUP_TIME, ORACLE, TOTAL_VAULT_SHARES, UNLOCKING_POSITIONS,
+        VAULT_TOKEN_DENOM,
     },
 };
 
@@ -23,6 +26,27 @@ pub fn shares_to_base_denom_amount(
     }
 }
 
+/// Vault shares minted when depositing `amount` of the base token, same as in `deposit`
+pub fn query_preview_deposit(deps: Deps, amount: Uint128) -> ContractResult<Uint128> {
+    let total_shares = query_vault_token_supply(deps.storage)?;
+    if total_shares.is_zero() {
+        return Ok(STARTING_VAULT_SHARES);
+    }
+
+    let oracle = ORACLE.load(deps.storage)?;
+    let balance = COIN_BALANCE.load(deps.storage)?;
+    let total_vault_value = oracle.query_value(&deps.querier, &balance, ActionKind::Default)?;
+    let assets_value = oracle.query_value(
+        &deps.querier,
+        &Coin {
+            denom: balance.denom,
+            amount,
+        },
+        ActionKind::Default,
+    )?;
+    Ok(total_shares.checked_multiply_ratio(assets_value, total_vault_value)?)
+}
+
 pub fn query_vault_info(deps: Deps) -> ContractResult<VaultInfoResponse> {
     let base_token = COIN_BALANCE.load(deps.storage)?.denom;
     let vault_token = VAULT_TOKEN_DENOM.load(deps.storage)?;
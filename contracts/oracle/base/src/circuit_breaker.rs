@@ -0,0 +1,35 @@
+use cosmwasm_std::Decimal;
+use mars_types::oracle::{CircuitBreaker, LastPrice};
+
+use crate::{ContractError::InvalidCircuitBreaker, ContractResult};
+
+pub fn assert_circuit_breaker(circuit_breaker: &CircuitBreaker) -> ContractResult<()> {
+    let max_change = circuit_breaker.max_change_per_second;
+    if max_change.is_zero() || max_change > Decimal::one() {
+        return Err(InvalidCircuitBreaker {
+            reason: "max_change_per_second must be in the range of (0;1>".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether the price moved outside of the band around the last accepted price. The band widens by
+/// `max_change_per_second` with every second elapsed since the last price was accepted.
+pub fn is_tripped(
+    circuit_breaker: &CircuitBreaker,
+    last_price: &LastPrice,
+    price: Decimal,
+    current_time: u64,
+) -> ContractResult<bool> {
+    // Relative changes can't be measured against a zero price
+    if last_price.price.is_zero() {
+        return Ok(false);
+    }
+
+    let elapsed = current_time.saturating_sub(last_price.timestamp);
+    let max_change =
+        circuit_breaker.max_change_per_second.checked_mul(Decimal::from_ratio(elapsed, 1u128))?;
+    let change = price.abs_diff(last_price.price).checked_div(last_price.price)?;
+
+    Ok(change > max_change)
+}
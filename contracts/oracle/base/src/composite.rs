@@ -0,0 +1,153 @@
+use cosmwasm_std::{CustomQuery, Decimal, Deps, Env};
+use cw_storage_plus::Map;
+use mars_types::oracle::{ActionKind, Config};
+
+use crate::{
+    ContractError::{
+        self, InvalidPrice, InvalidPriceSource, NoSnapshotWithinTolerance, NoSnapshots,
+        NotEnoughSnapshots, Std,
+    },
+    ContractResult, PriceSourceChecked,
+};
+
+/// Minimum number of sources a median price source has to be composed of
+pub const MIN_MEDIAN_SOURCES: usize = 2;
+
+/// Maximum number of sources a median price source can be composed of. Every source is queried
+/// on each price query, so we bound the gas spent on it.
+pub const MAX_MEDIAN_SOURCES: usize = 10;
+
+pub fn assert_median(sources_count: usize) -> ContractResult<()> {
+    if !(MIN_MEDIAN_SOURCES..=MAX_MEDIAN_SOURCES).contains(&sources_count) {
+        return Err(InvalidPriceSource {
+            reason: format!(
+                "median requires between {MIN_MEDIAN_SOURCES} and {MAX_MEDIAN_SOURCES} sources"
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Composite price sources query all of their sources on every price query. To bound the gas
+/// spent on it, their sources can't be composite price sources themselves.
+pub fn assert_not_nested(is_composite: bool) -> ContractResult<()> {
+    if is_composite {
+        return Err(InvalidPriceSource {
+            reason: "composite price sources can't be nested".to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn assert_guarded(max_deviation: Decimal) -> ContractResult<()> {
+    if max_deviation.is_zero() || max_deviation > Decimal::one() {
+        return Err(InvalidPriceSource {
+            reason: "max_deviation must be in the range of (0;1>".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Median of the prices returned by the healthy sources. Sources failing to return a price are
+/// skipped. For an even number of healthy sources the two middle prices are averaged.
+#[allow(clippy::too_many_arguments)]
+pub fn query_median_price<C: CustomQuery, P: PriceSourceChecked<C>>(
+    deps: &Deps<C>,
+    env: &Env,
+    denom: &str,
+    config: &Config,
+    price_sources: &Map<&str, P>,
+    kind: ActionKind,
+    sources: &[P],
+) -> ContractResult<Decimal> {
+    let mut prices = sources
+        .iter()
+        .filter_map(|source| {
+            source.query_price(deps, env, denom, config, price_sources, kind.clone()).ok()
+        })
+        .collect::<Vec<_>>();
+
+    if prices.is_empty() {
+        return Err(InvalidPrice {
+            reason: format!("none of the median sources returned a price for {denom}"),
+        });
+    }
+
+    prices.sort();
+
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        return Ok(prices[mid]);
+    }
+    Ok(prices[mid - 1].checked_add(prices[mid])?.checked_div(Decimal::percent(200))?)
+}
+
+/// Price of the primary source. The secondary source is only queried if the primary one has no
+/// price available or a stale one; any other error of the primary source is returned.
+#[allow(clippy::too_many_arguments)]
+pub fn query_fallback_price<C: CustomQuery, P: PriceSourceChecked<C>>(
+    deps: &Deps<C>,
+    env: &Env,
+    denom: &str,
+    config: &Config,
+    price_sources: &Map<&str, P>,
+    kind: ActionKind,
+    primary: &P,
+    secondary: &P,
+) -> ContractResult<Decimal> {
+    match primary.query_price(deps, env, denom, config, price_sources, kind.clone()) {
+        Err(err) if is_price_unavailable(&err) => {
+            secondary.query_price(deps, env, denom, config, price_sources, kind)
+        }
+        res => res,
+    }
+}
+
+/// Errors of a price source without a usable price: failed queries of the price provider,
+/// missing TWAP snapshots and prices rejected as stale or unreliable (e.g. a Pyth price older
+/// than `max_staleness`, or a TWAP during chain downtime).
+fn is_price_unavailable(err: &ContractError) -> bool {
+    matches!(
+        err,
+        Std(_)
+            | InvalidPrice { .. }
+            | NoSnapshots {}
+            | NoSnapshotWithinTolerance {}
+            | NotEnoughSnapshots {}
+    )
+}
+
+/// Price of the source, rejected if it deviates from the reference price by more than
+/// `max_deviation` (relative to the reference price).
+#[allow(clippy::too_many_arguments)]
+pub fn query_guarded_price<C: CustomQuery, P: PriceSourceChecked<C>>(
+    deps: &Deps<C>,
+    env: &Env,
+    denom: &str,
+    config: &Config,
+    price_sources: &Map<&str, P>,
+    kind: ActionKind,
+    source: &P,
+    reference: &P,
+    max_deviation: Decimal,
+) -> ContractResult<Decimal> {
+    let price = source.query_price(deps, env, denom, config, price_sources, kind.clone())?;
+    let reference_price = reference.query_price(deps, env, denom, config, price_sources, kind)?;
+
+    if reference_price.is_zero() {
+        return Err(InvalidPrice {
+            reason: "reference price can't be zero".to_string(),
+        });
+    }
+
+    let deviation = price.abs_diff(reference_price).checked_div(reference_price)?;
+    if deviation > max_deviation {
+        return Err(InvalidPrice {
+            reason: format!(
+                "deviation {deviation} from reference price exceeds max allowed {max_deviation}"
+            ),
+        });
+    }
+
+    Ok(price)
+}
@@ -1,18 +1,22 @@
 use std::marker::PhantomData;
 
 use cosmwasm_std::{
-    to_json_binary, Addr, Binary, CustomQuery, Deps, DepsMut, Env, MessageInfo, Order, Response,
-    StdError, StdResult,
+    attr, to_json_binary, Addr, Attribute, Binary, CustomQuery, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Order, Response, StdError, StdResult,
 };
 use cw_storage_plus::{Bound, Item, Map};
 use mars_owner::{Owner, OwnerInit::SetInitialOwner, OwnerUpdate};
 use mars_types::oracle::{
-    ActionKind, Config, ConfigResponse, ExecuteMsg, InstantiateMsg, PriceResponse,
-    PriceSourceResponse, QueryMsg,
+    ActionKind, CircuitBreaker, CircuitBreakerResponse, Config, ConfigResponse, ExecuteMsg,
+    InstantiateMsg, LastPrice, LiquidationFallback, PriceResponse, PriceSourceResponse, QueryMsg,
 };
 use mars_utils::helpers::validate_native_denom;
 
-use crate::{error::ContractResult, ContractError, PriceSourceChecked, PriceSourceUnchecked};
+use crate::{
+    circuit_breaker::{assert_circuit_breaker, is_tripped},
+    error::ContractResult,
+    ContractError, PriceSourceChecked, PriceSourceUnchecked,
+};
 
 const DEFAULT_LIMIT: u32 = 10;
 const MAX_LIMIT: u32 = 30;
@@ -29,6 +33,10 @@ where
     pub config: Item<'a, Config>,
     /// The price source of each coin denom
     pub price_sources: Map<'a, &'a str, P>,
+    /// The circuit breaker of each coin denom
+    pub circuit_breakers: Map<'a, &'a str, CircuitBreaker>,
+    /// The last price of each coin denom accepted by its circuit breaker
+    pub last_prices: Map<'a, &'a str, LastPrice>,
     /// Phantom data holds the unchecked price source type
     pub unchecked_price_source: PhantomData<PU>,
     /// Phantom data holds the custom query type
@@ -50,6 +58,8 @@ where
             owner: Owner::new("owner"),
             config: Item::new("config"),
             price_sources: Map::new("price_sources"),
+            circuit_breakers: Map::new("circuit_breakers"),
+            last_prices: Map::new("last_prices"),
             unchecked_price_source: PhantomData,
             custom_query: PhantomData,
             instantiate_msg: PhantomData,
@@ -92,6 +102,7 @@ where
     pub fn execute(
         &self,
         deps: DepsMut<C>,
+        env: Env,
         info: MessageInfo,
         msg: ExecuteMsg<PU, E>,
     ) -> ContractResult<Response> {
@@ -107,6 +118,13 @@ where
             ExecuteMsg::UpdateConfig {
                 base_denom,
             } => self.update_config(deps, info.sender, base_denom),
+            ExecuteMsg::SetCircuitBreaker {
+                denom,
+                circuit_breaker,
+            } => self.set_circuit_breaker(deps, info.sender, denom, circuit_breaker),
+            ExecuteMsg::UpdatePrices {
+                denoms,
+            } => self.update_prices(deps, env, denoms),
             // Custom messages should be handled by the implementing contract
             ExecuteMsg::Custom(_) => Err(ContractError::MissingCustomExecuteParams {}),
         }
@@ -142,6 +160,13 @@ where
                 limit,
                 kind.unwrap_or(ActionKind::Default),
             )?),
+            QueryMsg::CircuitBreaker {
+                denom,
+            } => to_json_binary(&self.query_circuit_breaker(deps, env, denom)?),
+            // Custom queries should be handled by the implementing contract
+            QueryMsg::TwapSnapshotCoverage {
+                ..
+            } => return Err(ContractError::UnsupportedQuery {}),
         };
         res.map_err(Into::into)
     }
@@ -186,6 +211,8 @@ where
         self.owner.assert_owner(deps.storage, &sender_addr)?;
 
         self.price_sources.remove(deps.storage, &denom);
+        self.circuit_breakers.remove(deps.storage, &denom);
+        self.last_prices.remove(deps.storage, &denom);
 
         Ok(Response::new()
             .add_attribute("action", "remove_price_source")
@@ -217,6 +244,100 @@ where
         Ok(response)
     }
 
+    fn set_circuit_breaker(
+        &self,
+        deps: DepsMut<C>,
+        sender_addr: Addr,
+        denom: String,
+        circuit_breaker: Option<CircuitBreaker>,
+    ) -> ContractResult<Response> {
+        self.owner.assert_owner(deps.storage, &sender_addr)?;
+
+        let mut response = Response::new()
+            .add_attribute("action", "set_circuit_breaker")
+            .add_attribute("denom", &denom);
+
+        match circuit_breaker {
+            Some(circuit_breaker) => {
+                assert_circuit_breaker(&circuit_breaker)?;
+                if !self.price_sources.has(deps.storage, &denom) {
+                    return Err(ContractError::InvalidCircuitBreaker {
+                        reason: format!("no price source found for denom: {denom}"),
+                    });
+                }
+                self.circuit_breakers.save(deps.storage, &denom, &circuit_breaker)?;
+                response = response.add_attribute(
+                    "max_change_per_second",
+                    circuit_breaker.max_change_per_second.to_string(),
+                );
+            }
+            None => {
+                self.circuit_breakers.remove(deps.storage, &denom);
+                self.last_prices.remove(deps.storage, &denom);
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn update_prices(
+        &self,
+        deps: DepsMut<C>,
+        env: Env,
+        denoms: Vec<String>,
+    ) -> ContractResult<Response> {
+        let cfg = self.config.load(deps.storage)?;
+        let current_time = env.block.time.seconds();
+        let mut attrs: Vec<Attribute> = vec![];
+
+        for denom in denoms {
+            let circuit_breaker = self
+                .circuit_breakers
+                .may_load(deps.storage, &denom)?
+                .ok_or_else(|| ContractError::InvalidCircuitBreaker {
+                    reason: format!("no circuit breaker found for denom: {denom}"),
+                })?;
+
+            let price_source = self.query_price_source(deps.as_ref(), denom.clone())?.price_source;
+            let price = price_source.query_price(
+                &deps.as_ref(),
+                &env,
+                &denom,
+                &cfg,
+                &self.price_sources,
+                ActionKind::Default,
+            )?;
+
+            let accepted = match self.last_prices.may_load(deps.storage, &denom)? {
+                Some(last_price) => {
+                    !is_tripped(&circuit_breaker, &last_price, price, current_time)?
+                }
+                None => true,
+            };
+            if accepted {
+                self.last_prices.save(
+                    deps.storage,
+                    &denom,
+                    &LastPrice {
+                        price,
+                        timestamp: current_time,
+                    },
+                )?;
+            }
+
+            attrs.extend(vec![
+                attr("denom", denom),
+                attr("price", price.to_string()),
+                attr("accepted", accepted.to_string()),
+            ]);
+        }
+
+        Ok(Response::new()
+            .add_attribute("action", "update_prices")
+            .add_attribute("timestamp", current_time.to_string())
+            .add_attributes(attrs))
+    }
+
     fn query_config(&self, deps: Deps<C>) -> StdResult<ConfigResponse> {
         let owner_state = self.owner.query(deps.storage)?;
         let cfg = self.config.load(deps.storage)?;
@@ -274,12 +395,12 @@ where
         let price_source = self.query_price_source(deps, denom.clone())?.price_source;
 
         Ok(PriceResponse {
-            price: price_source.query_price(
+            price: self.query_price_with_circuit_breaker(
                 &deps,
                 &env,
                 &denom,
                 &cfg,
-                &self.price_sources,
+                &price_source,
                 kind,
             )?,
             denom,
@@ -305,12 +426,12 @@ where
             .map(|item| {
                 let (k, v) = item?;
                 Ok(PriceResponse {
-                    price: v.query_price(
+                    price: self.query_price_with_circuit_breaker(
                         &deps,
                         &env,
                         &k,
                         &cfg,
-                        &self.price_sources,
+                        &v,
                         kind.clone(),
                     )?,
                     denom: k,
@@ -318,4 +439,70 @@ where
             })
             .collect()
     }
+
+    /// Price of the price source, checked against the circuit breaker of the denom (if any).
+    /// While the circuit breaker is tripped, prices for `ActionKind::Default` are rejected and
+    /// liquidations use the configured fallback.
+    fn query_price_with_circuit_breaker(
+        &self,
+        deps: &Deps<C>,
+        env: &Env,
+        denom: &str,
+        cfg: &Config,
+        price_source: &P,
+        kind: ActionKind,
+    ) -> ContractResult<Decimal> {
+        let price =
+            price_source.query_price(deps, env, denom, cfg, &self.price_sources, kind.clone())?;
+
+        let Some(circuit_breaker) = self.circuit_breakers.may_load(deps.storage, denom)? else {
+            return Ok(price);
+        };
+        let Some(last_price) = self.last_prices.may_load(deps.storage, denom)? else {
+            return Ok(price);
+        };
+        if !is_tripped(&circuit_breaker, &last_price, price, env.block.time.seconds())? {
+            return Ok(price);
+        }
+
+        match (kind, circuit_breaker.liquidation_fallback) {
+            (ActionKind::Liquidation, LiquidationFallback::CurrentPrice) => Ok(price),
+            (ActionKind::Liquidation, LiquidationFallback::LastPrice) => Ok(last_price.price),
+            (ActionKind::Default, _) => Err(ContractError::CircuitBreakerTripped {
+                denom: denom.to_string(),
+                price,
+                last_price: last_price.price,
+            }),
+        }
+    }
+
+    fn query_circuit_breaker(
+        &self,
+        deps: Deps<C>,
+        env: Env,
+        denom: String,
+    ) -> ContractResult<CircuitBreakerResponse> {
+        let cfg = self.config.load(deps.storage)?;
+        let circuit_breaker = self.circuit_breakers.may_load(deps.storage, &denom)?;
+        let last_price = self.last_prices.may_load(deps.storage, &denom)?;
+
+        let current_price = self.price_sources.may_load(deps.storage, &denom)?.and_then(|ps| {
+            ps.query_price(&deps, &env, &denom, &cfg, &self.price_sources, ActionKind::Default).ok()
+        });
+
+        let tripped = match (&circuit_breaker, &last_price, current_price) {
+            (Some(cb), Some(lp), Some(price)) => {
+                is_tripped(cb, lp, price, env.block.time.seconds())?
+            }
+            _ => false,
+        };
+
+        Ok(CircuitBreakerResponse {
+            denom,
+            circuit_breaker,
+            last_price,
+            current_price,
+            tripped,
+        })
+    }
 }
@@ -1,6 +1,7 @@
 use cosmwasm_std::{
     CheckedFromRatioError, CheckedMultiplyFractionError, CheckedMultiplyRatioError,
-    ConversionOverflowError, DecimalRangeExceeded, DivideByZeroError, OverflowError, StdError,
+    ConversionOverflowError, Decimal, DecimalRangeExceeded, DivideByZeroError, OverflowError,
+    StdError,
 };
 use mars_owner::OwnerError;
 use mars_utils::error::ValidationError;
@@ -57,6 +58,9 @@ pub enum ContractError {
     #[error("Missing custom execute params")]
     MissingCustomExecuteParams {},
 
+    #[error("Query not supported by this oracle")]
+    UnsupportedQuery {},
+
     #[error("Price source is not TWAP")]
     PriceSourceNotTwap {},
 
@@ -77,6 +81,18 @@ pub enum ContractError {
 
     #[error("Missing astroport pool params")]
     MissingAstroportPoolParams {},
+
+    #[error("Invalid circuit breaker: {reason}")]
+    InvalidCircuitBreaker {
+        reason: String,
+    },
+
+    #[error("Circuit breaker tripped for {denom}: price {price}, last accepted {last_price}")]
+    CircuitBreakerTripped {
+        denom: String,
+        price: Decimal,
+        last_price: Decimal,
+    },
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
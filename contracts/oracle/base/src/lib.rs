@@ -2,6 +2,8 @@ mod contract;
 mod error;
 mod traits;
 
+pub mod circuit_breaker;
+pub mod composite;
 pub mod lp_pricing;
 pub mod pyth;
 pub mod redemption_rate;
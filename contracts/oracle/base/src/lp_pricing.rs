@@ -45,3 +45,74 @@ pub fn query_xyk_lp_price<P: PriceSourceChecked<Empty>>(
 
     Ok(Decimal::from_ratio(pool_value_u128, total_shares))
 }
+
+/// The value of one unit of liquidity of a concentrated liquidity position with the price range
+/// of `[sqrt_price_lower^2, sqrt_price_upper^2]`.
+///
+/// The amounts backing the liquidity are computed at the pool price implied by the oracle prices of
+/// the assets instead of the current pool price. The current pool price can be moved by a swap
+/// within a single transaction, while the fair price can't.
+///
+/// NOTE: Price sources must exist for both assets in the pool.
+#[allow(clippy::too_many_arguments)]
+pub fn query_concentrated_liquidity_price<P: PriceSourceChecked<Empty>>(
+    deps: &Deps,
+    env: &Env,
+    config: &Config,
+    price_sources: &Map<&str, P>,
+    kind: ActionKind,
+    denom0: &str,
+    denom1: &str,
+    sqrt_price_lower: Decimal256,
+    sqrt_price_upper: Decimal256,
+) -> ContractResult<Decimal> {
+    let coin0_price = price_sources.load(deps.storage, denom0)?.query_price(
+        deps,
+        env,
+        denom0,
+        config,
+        price_sources,
+        kind.clone(),
+    )?;
+    let coin1_price = price_sources.load(deps.storage, denom1)?.query_price(
+        deps,
+        env,
+        denom1,
+        config,
+        price_sources,
+        kind,
+    )?;
+
+    compute_concentrated_liquidity_price(
+        coin0_price,
+        coin1_price,
+        sqrt_price_lower,
+        sqrt_price_upper,
+    )
+}
+
+pub fn compute_concentrated_liquidity_price(
+    coin0_price: Decimal,
+    coin1_price: Decimal,
+    sqrt_price_lower: Decimal256,
+    sqrt_price_upper: Decimal256,
+) -> ContractResult<Decimal> {
+    let coin0_price = Decimal256::from(coin0_price);
+    let coin1_price = Decimal256::from(coin1_price);
+
+    // Price of coin0 denominated in coin1 at which the pool would be balanced against the oracle,
+    // outside of the position's range the liquidity consists of only one of the assets
+    let sqrt_price =
+        coin0_price.checked_div(coin1_price)?.sqrt().clamp(sqrt_price_lower, sqrt_price_upper);
+
+    // amount0 = L * (1 / sqrt_price - 1 / sqrt_price_upper)
+    // amount1 = L * (sqrt_price - sqrt_price_lower)
+    let amount0 = sqrt_price_upper
+        .checked_sub(sqrt_price)?
+        .checked_div(sqrt_price.checked_mul(sqrt_price_upper)?)?;
+    let amount1 = sqrt_price.checked_sub(sqrt_price_lower)?;
+
+    let value = amount0.checked_mul(coin0_price)?.checked_add(amount1.checked_mul(coin1_price)?)?;
+
+    Ok(Decimal::try_from(value)?)
+}
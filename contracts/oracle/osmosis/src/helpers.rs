@@ -1,10 +1,10 @@
 use mars_oracle_base::{ContractError, ContractResult};
 use mars_osmosis::{
-    helpers::{CommonPoolData, Pool},
+    helpers::{CommonPoolData, Pool, MAX_TICK, MIN_INITIALIZED_TICK},
     BalancerPool,
 };
 
-use crate::DowntimeDetector;
+use crate::{DowntimeDetector, TickRange};
 
 /// 48 hours in seconds
 const TWO_DAYS_IN_SECONDS: u64 = 172800u64;
@@ -56,6 +56,49 @@ pub fn assert_osmosis_xyk_lp_pool(pool: &Pool) -> ContractResult<()> {
     Ok(())
 }
 
+/// Assert the Osmosis pool is a ConcentratedLiquidity pool and the tick range of the position is
+/// valid for it
+pub fn assert_osmosis_cl_position(pool: &Pool, range: &Option<TickRange>) -> ContractResult<()> {
+    let Pool::ConcentratedLiquidity(cl_pool) = pool else {
+        return Err(ContractError::InvalidPriceSource {
+            reason: format!(
+                "expecting pool {} to be a ConcentratedLiquidity pool",
+                pool.get_pool_id()
+            ),
+        });
+    };
+
+    let Some(range) = range else {
+        return Ok(());
+    };
+
+    if range.lower_tick >= range.upper_tick {
+        return Err(ContractError::InvalidPriceSource {
+            reason: format!(
+                "lower tick {} must be less than upper tick {}",
+                range.lower_tick, range.upper_tick
+            ),
+        });
+    }
+
+    if range.lower_tick < MIN_INITIALIZED_TICK || range.upper_tick > MAX_TICK {
+        return Err(ContractError::InvalidPriceSource {
+            reason: format!("tick range {range} out of bounds {MIN_INITIALIZED_TICK}:{MAX_TICK}"),
+        });
+    }
+
+    let tick_spacing = cl_pool.tick_spacing as i64;
+    if range.lower_tick % tick_spacing != 0 || range.upper_tick % tick_spacing != 0 {
+        return Err(ContractError::InvalidPriceSource {
+            reason: format!(
+                "ticks of range {range} must be multiples of the tick spacing {tick_spacing}"
+            ),
+        });
+    }
+
+    Ok(())
+}
+
 /// Assert the Osmosis pool has exactly two assets
 fn assert_pool_has_two_assets(pool: &Pool) -> ContractResult<()> {
     let pool_id = pool.get_pool_id();
@@ -5,5 +5,6 @@ pub mod msg;
 mod price_source;
 
 pub use price_source::{
-    DowntimeDetector, OsmosisPriceSourceChecked, OsmosisPriceSourceUnchecked, Twap, TwapKind,
+    DowntimeDetector, OsmosisPriceSourceChecked, OsmosisPriceSourceUnchecked, TickRange, Twap,
+    TwapKind,
 };
@@ -10,8 +10,9 @@ use mars_oracle_base::{
     ContractResult, PriceSourceChecked, PriceSourceUnchecked,
 };
 use mars_osmosis::helpers::{
-    query_arithmetic_twap_price, query_geometric_twap_price, query_pool, query_spot_price,
-    recovered_since_downtime_of_length, Pool,
+    query_arithmetic_twap_price, query_concentrated_liquidity_pool, query_geometric_twap_price,
+    query_pool, query_spot_price, recovered_since_downtime_of_length, tick_to_price, Pool,
+    MAX_TICK, MIN_INITIALIZED_TICK,
 };
 use mars_types::oracle::{ActionKind, Config};
 use mars_utils::helpers::validate_native_denom;
@@ -89,6 +90,17 @@ pub enum OsmosisPriceSource<T> {
     XykLiquidityToken {
         pool_id: u64,
     },
+    /// Osmosis concentrated liquidity position price quoted in OSMO. The price is the value of one
+    /// unit of liquidity provided in the tick range of the position.
+    ///
+    /// The amounts of the pool assets backing the liquidity are computed at the pool price implied
+    /// by the oracle prices of the assets, so manipulating the pool price doesn't change the value.
+    ConcentratedLiquidityPosition {
+        pool_id: u64,
+
+        /// Tick range of the position, full range if not set
+        range: Option<TickRange>,
+    },
     /// Osmosis geometric twap price quoted in OSMO for staked asset.
     ///
     /// Equation to calculate the price:
@@ -181,6 +193,52 @@ pub enum OsmosisPriceSource<T> {
         /// Params to query redemption rate
         redemption_rate: RedemptionRate<T>,
     },
+    /// Median of the prices returned by the given sources.
+    ///
+    /// Sources failing to return a price (e.g. a stale Pyth price) are skipped. The price query
+    /// only fails if none of the sources returns a price.
+    Median {
+        sources: Vec<OsmosisPriceSource<T>>,
+    },
+    /// Price of the primary source. The secondary source is only used if the primary one fails.
+    Fallback {
+        primary: Box<OsmosisPriceSource<T>>,
+        secondary: Box<OsmosisPriceSource<T>>,
+    },
+    /// Price of the source, rejected if it deviates too much from the price of the reference
+    /// source.
+    Guarded {
+        source: Box<OsmosisPriceSource<T>>,
+        reference: Box<OsmosisPriceSource<T>>,
+
+        /// The maximum deviation (percentage) of the price from the reference price
+        max_deviation: Decimal,
+    },
+}
+
+#[cw_serde]
+pub struct TickRange {
+    /// Lower tick of the position, has to be a multiple of the pool's tick spacing
+    pub lower_tick: i64,
+
+    /// Upper tick of the position, has to be a multiple of the pool's tick spacing
+    pub upper_tick: i64,
+}
+
+impl TickRange {
+    /// Range of a full range position, it behaves the same as liquidity in an XYK pool
+    pub fn full_range() -> Self {
+        Self {
+            lower_tick: MIN_INITIALIZED_TICK,
+            upper_tick: MAX_TICK,
+        }
+    }
+}
+
+impl fmt::Display for TickRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.lower_tick, self.upper_tick)
+    }
 }
 
 #[cw_serde]
@@ -245,6 +303,18 @@ impl Twap {
 pub type OsmosisPriceSourceUnchecked = OsmosisPriceSource<String>;
 pub type OsmosisPriceSourceChecked = OsmosisPriceSource<Addr>;
 
+impl<T> OsmosisPriceSource<T> {
+    /// Median, Fallback and Guarded price sources, which are composed of other price sources
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self,
+            OsmosisPriceSource::Median { .. }
+                | OsmosisPriceSource::Fallback { .. }
+                | OsmosisPriceSource::Guarded { .. }
+        )
+    }
+}
+
 impl fmt::Display for OsmosisPriceSourceChecked {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let label = match self {
@@ -273,6 +343,13 @@ impl fmt::Display for OsmosisPriceSourceChecked {
             OsmosisPriceSource::XykLiquidityToken {
                 pool_id,
             } => format!("xyk_liquidity_token:{pool_id}"),
+            OsmosisPriceSource::ConcentratedLiquidityPosition {
+                pool_id,
+                range,
+            } => {
+                let range = range.as_ref().map_or("full_range".to_string(), |r| r.to_string());
+                format!("concentrated_liquidity_position:{pool_id}:{range}")
+            }
             OsmosisPriceSource::StakedGeometricTwap {
                 transitive_denom,
                 pool_id,
@@ -310,6 +387,21 @@ impl fmt::Display for OsmosisPriceSourceChecked {
                 } = redemption_rate;
                 format!("lsd:{transitive_denom}:{pool_id}:{window_size}:{dd_fmt}:{kind}:{contract_addr}:{max_staleness}")
             }
+            OsmosisPriceSource::Median {
+                sources,
+            } => {
+                let sources = sources.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                format!("median:[{}]", sources.join(","))
+            }
+            OsmosisPriceSource::Fallback {
+                primary,
+                secondary,
+            } => format!("fallback:[{primary},{secondary}]"),
+            OsmosisPriceSource::Guarded {
+                source,
+                reference,
+                max_deviation,
+            } => format!("guarded:[{source},{reference}]:{max_deviation}"),
         };
         write!(f, "{label}")
     }
@@ -375,6 +467,17 @@ impl PriceSourceUnchecked<OsmosisPriceSourceChecked, Empty> for OsmosisPriceSour
                     pool_id: *pool_id,
                 })
             }
+            OsmosisPriceSourceUnchecked::ConcentratedLiquidityPosition {
+                pool_id,
+                range,
+            } => {
+                let pool = query_pool(&deps.querier, *pool_id)?;
+                helpers::assert_osmosis_cl_position(&pool, range)?;
+                Ok(OsmosisPriceSourceChecked::ConcentratedLiquidityPosition {
+                    pool_id: *pool_id,
+                    range: range.clone(),
+                })
+            }
             OsmosisPriceSourceUnchecked::StakedGeometricTwap {
                 transitive_denom,
                 pool_id,
@@ -437,6 +540,67 @@ impl PriceSourceUnchecked<OsmosisPriceSourceChecked, Empty> for OsmosisPriceSour
                     },
                 })
             }
+            OsmosisPriceSourceUnchecked::Median {
+                sources,
+            } => {
+                mars_oracle_base::composite::assert_median(sources.len())?;
+                for source in sources {
+                    mars_oracle_base::composite::assert_not_nested(source.is_composite())?;
+                }
+                Ok(OsmosisPriceSourceChecked::Median {
+                    sources: sources
+                        .iter()
+                        .map(|source| {
+                            source.clone().validate(deps, denom, base_denom, price_sources)
+                        })
+                        .collect::<ContractResult<_>>()?,
+                })
+            }
+            OsmosisPriceSourceUnchecked::Fallback {
+                primary,
+                secondary,
+            } => {
+                mars_oracle_base::composite::assert_not_nested(primary.is_composite())?;
+                mars_oracle_base::composite::assert_not_nested(secondary.is_composite())?;
+                Ok(OsmosisPriceSourceChecked::Fallback {
+                    primary: Box::new(primary.clone().validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                    secondary: Box::new(secondary.clone().validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                })
+            }
+            OsmosisPriceSourceUnchecked::Guarded {
+                source,
+                reference,
+                max_deviation,
+            } => {
+                mars_oracle_base::composite::assert_guarded(*max_deviation)?;
+                mars_oracle_base::composite::assert_not_nested(source.is_composite())?;
+                mars_oracle_base::composite::assert_not_nested(reference.is_composite())?;
+                Ok(OsmosisPriceSourceChecked::Guarded {
+                    source: Box::new(source.clone().validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                    reference: Box::new(reference.clone().validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                    max_deviation: *max_deviation,
+                })
+            }
         }
     }
 }
@@ -503,6 +667,18 @@ impl PriceSourceChecked<Empty> for OsmosisPriceSourceChecked {
                 price_sources,
                 kind,
             ),
+            OsmosisPriceSourceChecked::ConcentratedLiquidityPosition {
+                pool_id,
+                range,
+            } => Self::query_concentrated_liquidity_position_price(
+                deps,
+                env,
+                *pool_id,
+                range,
+                config,
+                price_sources,
+                kind,
+            ),
             OsmosisPriceSourceChecked::StakedGeometricTwap {
                 transitive_denom,
                 pool_id,
@@ -562,6 +738,45 @@ impl PriceSourceChecked<Empty> for OsmosisPriceSourceChecked {
                     kind,
                 )
             }
+            OsmosisPriceSourceChecked::Median {
+                sources,
+            } => mars_oracle_base::composite::query_median_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                sources,
+            ),
+            OsmosisPriceSourceChecked::Fallback {
+                primary,
+                secondary,
+            } => mars_oracle_base::composite::query_fallback_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                primary.as_ref(),
+                secondary.as_ref(),
+            ),
+            OsmosisPriceSourceChecked::Guarded {
+                source,
+                reference,
+                max_deviation,
+            } => mars_oracle_base::composite::query_guarded_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                source.as_ref(),
+                reference.as_ref(),
+                *max_deviation,
+            ),
         }
     }
 }
@@ -632,6 +847,35 @@ impl OsmosisPriceSourceChecked {
         )
     }
 
+    fn query_concentrated_liquidity_position_price(
+        deps: &Deps,
+        env: &Env,
+        pool_id: u64,
+        range: &Option<TickRange>,
+        config: &Config,
+        price_sources: &Map<&str, Self>,
+        kind: ActionKind,
+    ) -> ContractResult<Decimal> {
+        // ConcentratedLiquidity pool and tick range asserted during price source creation
+        let pool = query_concentrated_liquidity_pool(&deps.querier, pool_id)?;
+        let range = range.clone().unwrap_or_else(TickRange::full_range);
+
+        let sqrt_price_lower = tick_to_price(range.lower_tick)?.sqrt();
+        let sqrt_price_upper = tick_to_price(range.upper_tick)?.sqrt();
+
+        lp_pricing::query_concentrated_liquidity_price(
+            deps,
+            env,
+            config,
+            price_sources,
+            kind,
+            &pool.token0,
+            &pool.token1,
+            sqrt_price_lower,
+            sqrt_price_upper,
+        )
+    }
+
     /// Staked asset price quoted in OSMO.
     ///
     /// stAsset/OSMO = stAsset/Asset * Asset/OSMO
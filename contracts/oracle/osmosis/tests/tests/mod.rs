@@ -1,10 +1,13 @@
 mod helpers;
 
 mod test_admin;
+mod test_circuit_breaker;
 mod test_custom_execute;
 mod test_migration_v2;
 mod test_price_source_fmt;
 mod test_query_price;
+mod test_query_price_for_cl_position;
+mod test_query_price_for_composite;
 mod test_query_price_for_pyth;
 mod test_remove_price_source;
 mod test_set_price_source;
@@ -0,0 +1,246 @@
+use cosmwasm_std::{
+    from_json,
+    testing::{MockApi, MockStorage},
+    Decimal, Deps, DepsMut, OwnedDeps, Response,
+};
+use mars_oracle_base::ContractError;
+use mars_oracle_osmosis::{
+    contract::entry::{execute, query},
+    msg::ExecuteMsg,
+    OsmosisPriceSourceUnchecked,
+};
+use mars_owner::OwnerError::NotOwner;
+use mars_testing::{mock_env_at_block_time, mock_info, MarsMockQuerier};
+use mars_types::oracle::{
+    ActionKind, CircuitBreaker, CircuitBreakerResponse, LastPrice, LiquidationFallback,
+    PriceResponse, QueryMsg,
+};
+use osmosis_std::types::osmosis::poolmanager::v1beta1::SpotPriceResponse;
+
+use super::helpers;
+
+const START_TIME: u64 = 1_700_000_000;
+
+#[test]
+fn setting_circuit_breaker_with_invalid_params() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    let err = set_circuit_breaker(deps.as_mut(), "jake", "umars", Some(circuit_breaker_with(1000)))
+        .unwrap_err();
+    assert_eq!(err, ContractError::Owner(NotOwner {}));
+
+    let err = set_circuit_breaker(deps.as_mut(), "owner", "umars", Some(circuit_breaker_with(0)))
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidCircuitBreaker {
+            reason: "max_change_per_second must be in the range of (0;1>".to_string()
+        }
+    );
+
+    let err =
+        set_circuit_breaker(deps.as_mut(), "owner", "umars", Some(circuit_breaker_with(1000)))
+            .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidCircuitBreaker {
+            reason: "no price source found for denom: umars".to_string()
+        }
+    );
+
+    let err = update_prices(deps.as_mut(), "umars", START_TIME).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidCircuitBreaker {
+            reason: "no circuit breaker found for denom: umars".to_string()
+        }
+    );
+}
+
+#[test]
+fn circuit_breaker_rejects_prices_outside_of_band() {
+    let mut deps = setup_spot_with_circuit_breaker(LiquidationFallback::LastPrice);
+
+    // first price is accepted without a reference
+    set_spot_price(&mut deps.querier, Decimal::one());
+    let res = update_prices(deps.as_mut(), "umars", START_TIME).unwrap();
+    assert_eq!(attribute(&res, "accepted"), "true");
+
+    // 0.1% per second allows a move of 1% after 10 seconds
+    set_spot_price(&mut deps.querier, Decimal::percent(101));
+    let time = START_TIME + 10;
+    assert_eq!(query_price(deps.as_ref(), time, ActionKind::Default), Decimal::percent(101));
+
+    set_spot_price(&mut deps.querier, Decimal::percent(150));
+    let err = query_price_err(deps.as_ref(), time, ActionKind::Default);
+    assert_eq!(
+        err,
+        ContractError::CircuitBreakerTripped {
+            denom: "umars".to_string(),
+            price: Decimal::percent(150),
+            last_price: Decimal::one(),
+        }
+    );
+    // liquidations fall back to the last accepted price
+    assert_eq!(query_price(deps.as_ref(), time, ActionKind::Liquidation), Decimal::one());
+
+    let res = query_circuit_breaker(deps.as_ref(), time);
+    assert_eq!(
+        res,
+        CircuitBreakerResponse {
+            denom: "umars".to_string(),
+            circuit_breaker: Some(circuit_breaker_with(1)),
+            last_price: Some(LastPrice {
+                price: Decimal::one(),
+                timestamp: START_TIME,
+            }),
+            current_price: Some(Decimal::percent(150)),
+            tripped: true,
+        }
+    );
+
+    // the tripped price is not stored
+    let res = update_prices(deps.as_mut(), "umars", time).unwrap();
+    assert_eq!(attribute(&res, "accepted"), "false");
+    assert_eq!(
+        query_circuit_breaker(deps.as_ref(), time).last_price.unwrap().timestamp,
+        START_TIME
+    );
+
+    // the band widens with time, the new price is accepted eventually
+    let time = START_TIME + 500;
+    assert_eq!(query_price(deps.as_ref(), time, ActionKind::Default), Decimal::percent(150));
+    let res = update_prices(deps.as_mut(), "umars", time).unwrap();
+    assert_eq!(attribute(&res, "accepted"), "true");
+    assert_eq!(
+        query_circuit_breaker(deps.as_ref(), time).last_price,
+        Some(LastPrice {
+            price: Decimal::percent(150),
+            timestamp: time,
+        })
+    );
+}
+
+#[test]
+fn circuit_breaker_keeps_liquidations_open() {
+    let mut deps = setup_spot_with_circuit_breaker(LiquidationFallback::CurrentPrice);
+
+    set_spot_price(&mut deps.querier, Decimal::one());
+    update_prices(deps.as_mut(), "umars", START_TIME).unwrap();
+
+    set_spot_price(&mut deps.querier, Decimal::percent(50));
+    let time = START_TIME + 10;
+    query_price_err(deps.as_ref(), time, ActionKind::Default);
+    assert_eq!(query_price(deps.as_ref(), time, ActionKind::Liquidation), Decimal::percent(50));
+
+    // removing the circuit breaker removes the last accepted price as well
+    set_circuit_breaker(deps.as_mut(), "owner", "umars", None).unwrap();
+    assert_eq!(query_price(deps.as_ref(), time, ActionKind::Default), Decimal::percent(50));
+    let res = query_circuit_breaker(deps.as_ref(), time);
+    assert_eq!(res.circuit_breaker, None);
+    assert_eq!(res.last_price, None);
+    assert!(!res.tripped);
+}
+
+fn setup_spot_with_circuit_breaker(
+    liquidation_fallback: LiquidationFallback,
+) -> OwnedDeps<MockStorage, MockApi, MarsMockQuerier> {
+    let mut deps = helpers::setup_test_with_pools();
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Spot {
+            pool_id: 89,
+        },
+    );
+    set_circuit_breaker(
+        deps.as_mut(),
+        "owner",
+        "umars",
+        Some(CircuitBreaker {
+            liquidation_fallback,
+            ..circuit_breaker_with(1)
+        }),
+    )
+    .unwrap();
+    deps
+}
+
+/// Circuit breaker allowing a change of `permille` per mille per second
+fn circuit_breaker_with(permille: u64) -> CircuitBreaker {
+    CircuitBreaker {
+        max_change_per_second: Decimal::permille(permille),
+        liquidation_fallback: LiquidationFallback::LastPrice,
+    }
+}
+
+fn set_circuit_breaker(
+    deps: DepsMut,
+    sender: &str,
+    denom: &str,
+    circuit_breaker: Option<CircuitBreaker>,
+) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env_at_block_time(START_TIME),
+        mock_info(sender),
+        ExecuteMsg::SetCircuitBreaker {
+            denom: denom.to_string(),
+            circuit_breaker,
+        },
+    )
+}
+
+fn update_prices(deps: DepsMut, denom: &str, time: u64) -> Result<Response, ContractError> {
+    execute(
+        deps,
+        mock_env_at_block_time(time),
+        mock_info("keeper"),
+        ExecuteMsg::UpdatePrices {
+            denoms: vec![denom.to_string()],
+        },
+    )
+}
+
+fn set_spot_price(querier: &mut MarsMockQuerier, price: Decimal) {
+    querier.set_spot_price(
+        89,
+        "umars",
+        "uosmo",
+        SpotPriceResponse {
+            spot_price: price.to_string(),
+        },
+    );
+}
+
+fn price_query(kind: ActionKind) -> QueryMsg {
+    QueryMsg::Price {
+        denom: "umars".to_string(),
+        kind: Some(kind),
+    }
+}
+
+fn query_price(deps: Deps, time: u64, kind: ActionKind) -> Decimal {
+    let res = query(deps, mock_env_at_block_time(time), price_query(kind)).unwrap();
+    from_json::<PriceResponse>(res).unwrap().price
+}
+
+fn query_price_err(deps: Deps, time: u64, kind: ActionKind) -> ContractError {
+    query(deps, mock_env_at_block_time(time), price_query(kind)).unwrap_err()
+}
+
+fn query_circuit_breaker(deps: Deps, time: u64) -> CircuitBreakerResponse {
+    let res = query(
+        deps,
+        mock_env_at_block_time(time),
+        QueryMsg::CircuitBreaker {
+            denom: "umars".to_string(),
+        },
+    )
+    .unwrap();
+    from_json(res).unwrap()
+}
+
+fn attribute(res: &Response, key: &str) -> String {
+    res.attributes.iter().find(|a| a.key == key).unwrap().value.clone()
+}
@@ -2,7 +2,7 @@ use cosmwasm_std::{testing::mock_env, Empty};
 use mars_oracle_base::ContractError;
 use mars_oracle_osmosis::contract::entry;
 use mars_testing::mock_info;
-use mars_types::oracle::ExecuteMsg;
+use mars_types::oracle::{ExecuteMsg, QueryMsg};
 
 use super::helpers;
 
@@ -15,3 +15,15 @@ fn custom_execute() {
     let res_err = entry::execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
     assert_eq!(res_err, ContractError::MissingCustomExecuteParams {});
 }
+
+#[test]
+fn twap_snapshot_coverage_not_supported() {
+    let deps = helpers::setup_test();
+
+    let msg = QueryMsg::TwapSnapshotCoverage {
+        start_after: None,
+        limit: None,
+    };
+    let res_err = entry::query(deps.as_ref(), mock_env(), msg).unwrap_err();
+    assert_eq!(res_err, ContractError::UnsupportedQuery {});
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::{Addr, Decimal};
 use mars_oracle_base::redemption_rate::RedemptionRate;
-use mars_oracle_osmosis::{DowntimeDetector, OsmosisPriceSourceChecked, Twap, TwapKind};
+use mars_oracle_osmosis::{DowntimeDetector, OsmosisPriceSourceChecked, TickRange, Twap, TwapKind};
 use osmosis_std::types::osmosis::downtimedetector::v1beta1::Downtime;
 use pyth_sdk_cw::PriceIdentifier;
 
@@ -99,6 +99,24 @@ fn display_xyk_lp_price_source() {
     assert_eq!(ps.to_string(), "xyk_liquidity_token:224")
 }
 
+#[test]
+fn display_concentrated_liquidity_position_price_source() {
+    let ps = OsmosisPriceSourceChecked::ConcentratedLiquidityPosition {
+        pool_id: 1066,
+        range: None,
+    };
+    assert_eq!(ps.to_string(), "concentrated_liquidity_position:1066:full_range");
+
+    let ps = OsmosisPriceSourceChecked::ConcentratedLiquidityPosition {
+        pool_id: 1066,
+        range: Some(TickRange {
+            lower_tick: -100,
+            upper_tick: 8_000_000,
+        }),
+    };
+    assert_eq!(ps.to_string(), "concentrated_liquidity_position:1066:-100:8000000");
+}
+
 #[test]
 fn display_pyth_price_source() {
     let ps = OsmosisPriceSourceChecked::Pyth {
@@ -174,3 +192,33 @@ fn display_lsd_price_source() {
     };
     assert_eq!(ps.to_string(), "lsd:transitive:456:380:Some(Duration30m:552):geometric_twap:osmo1zw4fxj4pt0pu0jdd7cs6gecdj3pvfxhhtgkm4w2y44jp60hywzvssud6uc:1234");
 }
+
+#[test]
+fn display_composite_price_sources() {
+    let fixed = |price: u64| OsmosisPriceSourceChecked::Fixed {
+        price: Decimal::percent(price),
+    };
+
+    let ps = OsmosisPriceSourceChecked::Median {
+        sources: vec![
+            fixed(100),
+            OsmosisPriceSourceChecked::Spot {
+                pool_id: 123,
+            },
+        ],
+    };
+    assert_eq!(ps.to_string(), "median:[fixed:1,spot:123]");
+
+    let ps = OsmosisPriceSourceChecked::Fallback {
+        primary: Box::new(ps),
+        secondary: Box::new(fixed(50)),
+    };
+    assert_eq!(ps.to_string(), "fallback:[median:[fixed:1,spot:123],fixed:0.5]");
+
+    let ps = OsmosisPriceSourceChecked::Guarded {
+        source: Box::new(fixed(100)),
+        reference: Box::new(fixed(110)),
+        max_deviation: Decimal::percent(5),
+    };
+    assert_eq!(ps.to_string(), "guarded:[fixed:1,fixed:1.1]:0.05");
+}
@@ -0,0 +1,103 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{
+    testing::{MockApi, MockStorage},
+    Decimal, Deps, OwnedDeps,
+};
+use mars_oracle_osmosis::{OsmosisPriceSourceUnchecked, TickRange};
+use mars_testing::MarsMockQuerier;
+use mars_types::oracle::{PriceResponse, QueryMsg};
+
+use super::helpers;
+
+// Price of 1 at tick 0 and price of 9 at tick 8_000_000
+fn band() -> Option<TickRange> {
+    Some(TickRange {
+        lower_tick: 0,
+        upper_tick: 8_000_000,
+    })
+}
+
+fn setup_cl_position(
+    ujuno_price: Decimal,
+    range: Option<TickRange>,
+) -> OwnedDeps<MockStorage, MockApi, MarsMockQuerier> {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "uosmo",
+        OsmosisPriceSourceUnchecked::Fixed {
+            price: Decimal::one(),
+        },
+    );
+    helpers::set_price_source(
+        deps.as_mut(),
+        "ujuno",
+        OsmosisPriceSourceUnchecked::Fixed {
+            price: ujuno_price,
+        },
+    );
+    helpers::set_price_source(
+        deps.as_mut(),
+        "ujuno_uosmo_cl",
+        OsmosisPriceSourceUnchecked::ConcentratedLiquidityPosition {
+            pool_id: 7777,
+            range,
+        },
+    );
+
+    deps
+}
+
+fn query_cl_price(deps: Deps) -> Decimal {
+    let res: PriceResponse = helpers::query(
+        deps,
+        QueryMsg::Price {
+            denom: "ujuno_uosmo_cl".to_string(),
+            kind: None,
+        },
+    );
+    res.price
+}
+
+#[test]
+fn querying_full_range_position_price() {
+    let deps = setup_cl_position(Decimal::percent(400), None);
+
+    // Juno price: 4
+    // Osmo price: 1
+    // sqrt_price = sqrt(4 / 1) = 2, sqrt_price_lower = 10^-6, sqrt_price_upper = 10^19
+    // amount0 = 1 / 2 - 1 / 10^19 = 0.499999999999999999 (rounded down)
+    // amount1 = 2 - 10^-6 = 1.999999
+    // value = 0.499999999999999999 * 4 + 1.999999 * 1 = 3.999998999999999996
+    //
+    // Same as the value of liquidity in an XYK pool 2 * sqrt(4 * 1) minus the dust at the bounds.
+    assert_eq!(query_cl_price(deps.as_ref()), Decimal::from_str("3.999998999999999996").unwrap());
+}
+
+#[test]
+fn querying_banded_position_price() {
+    let deps = setup_cl_position(Decimal::percent(400), band());
+
+    // sqrt_price = 2, sqrt_price_lower = 1, sqrt_price_upper = 3
+    // amount0 = 1 / 2 - 1 / 3 = 0.166666666666666666 (rounded down)
+    // amount1 = 2 - 1 = 1
+    // value = 0.166666666666666666 * 4 + 1 * 1 = 1.666666666666666664
+    assert_eq!(query_cl_price(deps.as_ref()), Decimal::from_str("1.666666666666666664").unwrap());
+}
+
+#[test]
+fn querying_out_of_range_position_price() {
+    // Below the range the position consists of juno only
+    // amount0 = 1 / 1 - 1 / 3 = 0.666666666666666666 (rounded down)
+    // value = 0.666666666666666666 * 0.5 = 0.333333333333333333
+    let deps = setup_cl_position(Decimal::percent(50), band());
+    assert_eq!(query_cl_price(deps.as_ref()), Decimal::from_str("0.333333333333333333").unwrap());
+
+    // Above the range the position consists of osmo only
+    // amount1 = 3 - 1 = 2
+    // value = 2 * 1 = 2
+    let deps = setup_cl_position(Decimal::percent(1600), band());
+    assert_eq!(query_cl_price(deps.as_ref()), Decimal::from_ratio(2u128, 1u128));
+}
@@ -0,0 +1,142 @@
+use cosmwasm_std::{Decimal, Deps};
+use mars_oracle_base::ContractError;
+use mars_oracle_osmosis::OsmosisPriceSourceUnchecked;
+use mars_testing::MarsMockQuerier;
+use mars_types::oracle::{PriceResponse, QueryMsg};
+use osmosis_std::types::osmosis::poolmanager::v1beta1::SpotPriceResponse;
+
+use super::helpers;
+
+fn fixed(price: u64) -> OsmosisPriceSourceUnchecked {
+    OsmosisPriceSourceUnchecked::Fixed {
+        price: Decimal::percent(price),
+    }
+}
+
+// umars/uosmo spot price, fails to return a price until the spot price is set in the querier
+fn spot() -> OsmosisPriceSourceUnchecked {
+    OsmosisPriceSourceUnchecked::Spot {
+        pool_id: 89,
+    }
+}
+
+fn set_spot_price(querier: &mut MarsMockQuerier, price: Decimal) {
+    querier.set_spot_price(
+        89,
+        "umars",
+        "uosmo",
+        SpotPriceResponse {
+            spot_price: price.to_string(),
+        },
+    );
+}
+
+fn query_umars_price(deps: Deps) -> Decimal {
+    let res: PriceResponse = helpers::query(
+        deps,
+        QueryMsg::Price {
+            denom: "umars".to_string(),
+            kind: None,
+        },
+    );
+    res.price
+}
+
+#[test]
+fn querying_median_price() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Median {
+            sources: vec![fixed(100), spot(), fixed(300)],
+        },
+    );
+
+    // the spot price is not available, median of the two healthy sources
+    assert_eq!(query_umars_price(deps.as_ref()), Decimal::percent(200));
+
+    set_spot_price(&mut deps.querier, Decimal::percent(250));
+    assert_eq!(query_umars_price(deps.as_ref()), Decimal::percent(250));
+}
+
+#[test]
+fn querying_median_price_without_healthy_sources() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Median {
+            sources: vec![spot(), spot()],
+        },
+    );
+
+    let err = helpers::query_err(
+        deps.as_ref(),
+        QueryMsg::Price {
+            denom: "umars".to_string(),
+            kind: None,
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InvalidPrice {
+            reason: "none of the median sources returned a price for umars".to_string()
+        }
+    );
+}
+
+#[test]
+fn querying_fallback_price() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Fallback {
+            primary: Box::new(spot()),
+            secondary: Box::new(fixed(120)),
+        },
+    );
+
+    // primary fails, secondary is used
+    assert_eq!(query_umars_price(deps.as_ref()), Decimal::percent(120));
+
+    set_spot_price(&mut deps.querier, Decimal::percent(150));
+    assert_eq!(query_umars_price(deps.as_ref()), Decimal::percent(150));
+}
+
+#[test]
+fn querying_guarded_price() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Guarded {
+            source: Box::new(spot()),
+            reference: Box::new(fixed(100)),
+            max_deviation: Decimal::percent(10),
+        },
+    );
+
+    set_spot_price(&mut deps.querier, Decimal::percent(109));
+    assert_eq!(query_umars_price(deps.as_ref()), Decimal::percent(109));
+
+    set_spot_price(&mut deps.querier, Decimal::percent(85));
+    let err = helpers::query_err(
+        deps.as_ref(),
+        QueryMsg::Price {
+            denom: "umars".to_string(),
+            kind: None,
+        },
+    );
+    assert_eq!(
+        err,
+        ContractError::InvalidPrice {
+            reason: "deviation 0.15 from reference price exceeds max allowed 0.1".to_string()
+        }
+    );
+}
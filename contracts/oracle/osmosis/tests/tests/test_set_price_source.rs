@@ -5,7 +5,8 @@ use mars_oracle_base::{redemption_rate::RedemptionRate, ContractError};
 use mars_oracle_osmosis::{
     contract::entry::execute,
     msg::{ExecuteMsg, PriceSourceResponse},
-    DowntimeDetector, OsmosisPriceSourceChecked, OsmosisPriceSourceUnchecked, Twap, TwapKind,
+    DowntimeDetector, OsmosisPriceSourceChecked, OsmosisPriceSourceUnchecked, TickRange, Twap,
+    TwapKind,
 };
 use mars_owner::OwnerError::NotOwner;
 use mars_testing::mock_info;
@@ -1064,6 +1065,84 @@ fn setting_price_source_xyk_lp() {
     );
 }
 
+#[test]
+fn setting_price_source_cl_position() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    let mut set_price_source_cl_position = |pool_id: u64, range: Option<TickRange>| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner"),
+            ExecuteMsg::SetPriceSource {
+                denom: "ujuno_uosmo_cl".to_string(),
+                price_source: OsmosisPriceSourceUnchecked::ConcentratedLiquidityPosition {
+                    pool_id,
+                    range,
+                },
+            },
+        )
+    };
+    let range = |lower_tick: i64, upper_tick: i64| {
+        Some(TickRange {
+            lower_tick,
+            upper_tick,
+        })
+    };
+
+    // attempting to use XYK pool
+    let err = set_price_source_cl_position(89, None).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "expecting pool 89 to be a ConcentratedLiquidity pool".to_string()
+        }
+    );
+
+    let err = set_price_source_cl_position(7777, range(1000, 1000)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "lower tick 1000 must be less than upper tick 1000".to_string()
+        }
+    );
+
+    let err = set_price_source_cl_position(7777, range(-108_000_100, 1000)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "tick range -108000100:1000 out of bounds -108000000:342000000".to_string()
+        }
+    );
+
+    let err = set_price_source_cl_position(7777, range(-1000, 1050)).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "ticks of range -1000:1050 must be multiples of the tick spacing 100"
+                .to_string()
+        }
+    );
+
+    // properly set cl position price sources
+    set_price_source_cl_position(7777, range(-1000, 1000)).unwrap();
+    set_price_source_cl_position(7777, None).unwrap();
+
+    let res: PriceSourceResponse = helpers::query(
+        deps.as_ref(),
+        QueryMsg::PriceSource {
+            denom: "ujuno_uosmo_cl".to_string(),
+        },
+    );
+    assert_eq!(
+        res.price_source,
+        OsmosisPriceSourceChecked::ConcentratedLiquidityPosition {
+            pool_id: 7777,
+            range: None,
+        }
+    );
+}
+
 #[test]
 fn setting_price_source_pyth_with_invalid_params() {
     let mut deps = helpers::setup_test();
@@ -1208,6 +1287,118 @@ fn setting_price_source_pyth_successfully() {
     );
 }
 
+#[test]
+fn setting_price_source_composite_with_invalid_params() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    let mut set_price_source = |price_source: OsmosisPriceSourceUnchecked| {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner"),
+            ExecuteMsg::SetPriceSource {
+                denom: "umars".to_string(),
+                price_source,
+            },
+        )
+    };
+    let fixed = |price: u64| OsmosisPriceSourceUnchecked::Fixed {
+        price: Decimal::percent(price),
+    };
+
+    // a median of a single source is not a median; should fail
+    let err = set_price_source(OsmosisPriceSourceUnchecked::Median {
+        sources: vec![fixed(100)],
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "median requires between 2 and 10 sources".to_string()
+        }
+    );
+
+    // max deviation has to be positive; should fail
+    let err = set_price_source(OsmosisPriceSourceUnchecked::Guarded {
+        source: Box::new(fixed(100)),
+        reference: Box::new(fixed(101)),
+        max_deviation: Decimal::zero(),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "max_deviation must be in the range of (0;1>".to_string()
+        }
+    );
+
+    // composite price sources can't be nested; should fail
+    let err = set_price_source(OsmosisPriceSourceUnchecked::Fallback {
+        primary: Box::new(OsmosisPriceSourceUnchecked::Median {
+            sources: vec![fixed(100), fixed(101)],
+        }),
+        secondary: Box::new(fixed(100)),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "composite price sources can't be nested".to_string()
+        }
+    );
+
+    // nested sources are validated as well; should fail
+    let err = set_price_source(OsmosisPriceSourceUnchecked::Fallback {
+        primary: Box::new(OsmosisPriceSourceUnchecked::Spot {
+            pool_id: 1,
+        }),
+        secondary: Box::new(fixed(100)),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "pool 1 does not contain umars".to_string()
+        }
+    );
+}
+
+#[test]
+fn setting_price_source_composite_successfully() {
+    let mut deps = helpers::setup_test_with_pools();
+
+    helpers::set_price_source(
+        deps.as_mut(),
+        "umars",
+        OsmosisPriceSourceUnchecked::Fallback {
+            primary: Box::new(OsmosisPriceSourceUnchecked::Spot {
+                pool_id: 89,
+            }),
+            secondary: Box::new(OsmosisPriceSourceUnchecked::Fixed {
+                price: Decimal::percent(90),
+            }),
+        },
+    );
+
+    let res: PriceSourceResponse = helpers::query(
+        deps.as_ref(),
+        QueryMsg::PriceSource {
+            denom: "umars".to_string(),
+        },
+    );
+    assert_eq!(
+        res.price_source,
+        OsmosisPriceSourceChecked::Fallback {
+            primary: Box::new(OsmosisPriceSourceChecked::Spot {
+                pool_id: 89,
+            }),
+            secondary: Box::new(OsmosisPriceSourceChecked::Fixed {
+                price: Decimal::percent(90),
+            }),
+        }
+    );
+}
+
 #[test]
 fn querying_price_source() {
     let mut deps = helpers::setup_test_with_pools();
@@ -1,12 +1,33 @@
-use cosmwasm_std::{attr, Attribute, DepsMut, Env, Response};
+use astroport_v5::{factory::PairType, pair_concentrated::ConcentratedPoolParams};
+use cosmwasm_std::{
+    attr, from_json, Addr, Attribute, Decimal, Decimal256, Deps, DepsMut, Env, MessageInfo, Order,
+    QuerierWrapper, Response, Storage, Uint128, Uint256,
+};
+use cw_storage_plus::Bound;
 use mars_oracle_base::{ContractError, ContractResult};
-use mars_types::oracle::AstroportTwapSnapshot;
+use mars_types::oracle::{
+    AstroportPoolSnapshot, AstroportTwapSnapshot, TwapSnapshotCoverageResponse, TwapSnapshotGap,
+    TwapSnapshotKind,
+};
+use mars_utils::error::ValidationError;
 
 use crate::{
-    contract::WasmOracle, helpers::query_astroport_cumulative_price,
-    state::ASTROPORT_TWAP_SNAPSHOTS, WasmPriceSourceChecked,
+    contract::WasmOracle,
+    helpers::{
+        query_astroport_config, query_astroport_cumulative_price, query_astroport_pair_info,
+        query_astroport_pcl_curve_invariant, query_astroport_pool,
+        query_astroport_ss_curve_invariant,
+    },
+    state::{
+        ASTROPORT_POOL_SNAPSHOTS, ASTROPORT_TWAP_SNAPSHOTS, DEFAULT_MAX_TWAP_SNAPSHOTS,
+        MAX_TWAP_SNAPSHOTS,
+    },
+    LpTwap,
 };
 
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 pub trait ExecuteTwapSnapshots {
     fn execute_record_astroport_twap_snapshots(
         &self,
@@ -14,6 +35,24 @@ pub trait ExecuteTwapSnapshots {
         env: Env,
         denoms: Vec<String>,
     ) -> ContractResult<Response>;
+
+    fn execute_set_max_twap_snapshots(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        pair_address: String,
+        max_snapshots: u32,
+    ) -> ContractResult<Response>;
+}
+
+pub trait QueryTwapSnapshots {
+    fn query_twap_snapshot_coverage(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> ContractResult<Vec<TwapSnapshotCoverageResponse>>;
 }
 
 impl ExecuteTwapSnapshots for WasmOracle<'_> {
@@ -30,53 +69,88 @@ impl ExecuteTwapSnapshots for WasmOracle<'_> {
             let price_source = self.price_sources.load(deps.storage, &denom)?;
 
             // Asset must be configured to use TWAP price source
-            let (pair_address, window_size, tolerance) = match price_source {
-                WasmPriceSourceChecked::AstroportTwap {
-                    pair_address,
-                    window_size,
-                    tolerance,
-                } => (pair_address, window_size, tolerance),
-                WasmPriceSourceChecked::Lsd {
-                    transitive_denom: _,
-                    twap,
-                    redemption_rate: _,
-                } => (twap.pair_address, twap.window_size, twap.tolerance),
-                _ => {
-                    return Err(ContractError::PriceSourceNotTwap {});
-                }
-            };
+            let price_twap = price_source.astroport_twap();
+            let pool_twap = price_source.astroport_pool_twap();
+            if price_twap.is_none() && pool_twap.is_none() {
+                return Err(ContractError::PriceSourceNotTwap {});
+            }
+
+            if let Some((pair_address, window_size, tolerance)) = price_twap {
+                // Load existing snapshots. If there's none, we initialize an empty vector
+                let mut snapshots =
+                    ASTROPORT_TWAP_SNAPSHOTS.load(deps.storage, &denom).unwrap_or_else(|_| vec![]);
+
+                // A potential attack is to repeatly call `RecordTwapSnapshots` so that
+                // `snapshots` becomes a very big vector, so that calculating the average price
+                // becomes extremely gas expensive. To deter this, we reject a new snapshot if the
+                // most recent snapshot is less than `tolerance` seconds ago, or more if needed to
+                // stay under the maximum number of snapshots of the pair.
+                let interval =
+                    twap_snapshot_interval(deps.storage, pair_address, window_size, tolerance)?;
+                let too_recent =
+                    snapshots.last().is_some_and(|latest| timestamp - latest.timestamp < interval);
+
+                if !too_recent {
+                    // Query new price data
+                    let price_cumulative =
+                        query_astroport_cumulative_price(&deps.querier, pair_address, &denom)?;
+
+                    // Purge snapshots that are too old, i.e. more than (window_size + tolerance)
+                    // away from the current timestamp. These snapshots will never be used in the
+                    // future for calculating average prices
+                    snapshots.retain(|snapshot| {
+                        timestamp - snapshot.timestamp <= window_size + tolerance
+                    });
+
+                    snapshots.push(AstroportTwapSnapshot {
+                        timestamp,
+                        price_cumulative,
+                    });
+
+                    ASTROPORT_TWAP_SNAPSHOTS.save(deps.storage, &denom, &snapshots)?;
 
-            // Load existing snapshots. If there's none, we initialize an empty vector
-            let mut snapshots =
-                ASTROPORT_TWAP_SNAPSHOTS.load(deps.storage, &denom).unwrap_or_else(|_| vec![]);
-
-            // A potential attack is to repeatly call `RecordTwapSnapshots` so that `snapshots` becomes a
-            // very big vector, so that calculating the average price becomes extremely gas expensive.
-            // To deter this, we reject a new snapshot if the most recent snapshot is less than `tolerance`
-            // seconds ago.
-            if let Some(latest_snapshot) = snapshots.last() {
-                if timestamp - latest_snapshot.timestamp < tolerance {
-                    continue;
+                    attrs.extend(vec![
+                        attr("denom", &denom),
+                        attr("price_cumulative", price_cumulative),
+                    ]);
                 }
             }
 
-            // Query new price data
-            let price_cumulative =
-                query_astroport_cumulative_price(&deps.querier, &pair_address, &denom)?;
+            if let Some((pair_address, twap)) = pool_twap {
+                let mut snapshots =
+                    ASTROPORT_POOL_SNAPSHOTS.may_load(deps.storage, &denom)?.unwrap_or_default();
 
-            // Purge snapshots that are too old, i.e. more than (window_size + tolerance) away from the
-            // current timestamp. These snapshots will never be used in the future for calculating
-            // average prices
-            snapshots.retain(|snapshot| timestamp - snapshot.timestamp <= window_size + tolerance);
+                // Same as for the price snapshots, recording is throttled by the tolerance and the
+                // maximum number of snapshots
+                let interval = twap_snapshot_interval(
+                    deps.storage,
+                    pair_address,
+                    twap.window_size,
+                    twap.tolerance,
+                )?;
+                let too_recent =
+                    snapshots.last().is_some_and(|latest| timestamp - latest.timestamp < interval);
+
+                if !too_recent {
+                    let snapshot =
+                        query_astroport_pool_state(&deps.querier, pair_address, timestamp)?;
+
+                    // Purge snapshots that will never be used for calculating average pool states
+                    snapshots.retain(|snapshot| {
+                        timestamp - snapshot.timestamp <= twap.window_size + twap.tolerance
+                    });
 
-            snapshots.push(AstroportTwapSnapshot {
-                timestamp,
-                price_cumulative,
-            });
+                    attrs.extend(vec![
+                        attr("denom", &denom),
+                        attr("total_share", snapshot.total_share),
+                        attr("curve_invariant", snapshot.curve_invariant.to_string()),
+                    ]);
 
-            ASTROPORT_TWAP_SNAPSHOTS.save(deps.storage, &denom, &snapshots)?;
+                    snapshots.push(snapshot);
 
-            attrs.extend(vec![attr("denom", denom), attr("price_cumulative", price_cumulative)]);
+                    ASTROPORT_POOL_SNAPSHOTS.save(deps.storage, &denom, &snapshots)?;
+                }
+            }
         }
 
         Ok(Response::new()
@@ -84,4 +158,273 @@ impl ExecuteTwapSnapshots for WasmOracle<'_> {
             .add_attribute("timestamp", timestamp.to_string())
             .add_attributes(attrs))
     }
+
+    fn execute_set_max_twap_snapshots(
+        &self,
+        deps: DepsMut,
+        info: MessageInfo,
+        pair_address: String,
+        max_snapshots: u32,
+    ) -> ContractResult<Response> {
+        self.owner.assert_owner(deps.storage, &info.sender)?;
+
+        let pair_address = deps.api.addr_validate(&pair_address)?;
+
+        // The snapshots kept have to cover the TWAP window of every price source using the pair
+        let mut min_snapshots = MIN_TWAP_SNAPSHOTS;
+        for item in self.price_sources.range(deps.storage, None, None, Order::Ascending) {
+            let (_, price_source) = item?;
+            if let Some((address, window_size, tolerance)) = price_source.astroport_twap() {
+                if *address == pair_address {
+                    min_snapshots = min_snapshots.max(min_twap_snapshots(window_size, tolerance));
+                }
+            }
+            if let Some((address, twap)) = price_source.astroport_pool_twap() {
+                if *address == pair_address {
+                    min_snapshots =
+                        min_snapshots.max(min_twap_snapshots(twap.window_size, twap.tolerance));
+                }
+            }
+        }
+
+        if max_snapshots < min_snapshots {
+            return Err(ValidationError::InvalidParam {
+                param_name: "max_snapshots".to_string(),
+                invalid_value: max_snapshots.to_string(),
+                predicate: format!(">= {min_snapshots}"),
+            }
+            .into());
+        }
+
+        MAX_TWAP_SNAPSHOTS.save(deps.storage, &pair_address, &max_snapshots)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set_max_twap_snapshots")
+            .add_attribute("pair_address", pair_address.to_string())
+            .add_attribute("max_snapshots", max_snapshots.to_string()))
+    }
+}
+
+impl QueryTwapSnapshots for WasmOracle<'_> {
+    fn query_twap_snapshot_coverage(
+        &self,
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> ContractResult<Vec<TwapSnapshotCoverageResponse>> {
+        let current_time = env.block.time.seconds();
+        let start = start_after.map(|denom| Bound::ExclusiveRaw(denom.into_bytes()));
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let mut coverages = vec![];
+        for item in self.price_sources.range(deps.storage, start, None, Order::Ascending) {
+            if coverages.len() >= limit {
+                break;
+            }
+
+            let (denom, price_source) = item?;
+
+            if let Some((pair_address, window_size, tolerance)) = price_source.astroport_twap() {
+                let timestamps = ASTROPORT_TWAP_SNAPSHOTS
+                    .may_load(deps.storage, &denom)?
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|snapshot| snapshot.timestamp)
+                    .collect::<Vec<_>>();
+                coverages.push(snapshot_coverage(
+                    &denom,
+                    TwapSnapshotKind::Price,
+                    pair_address,
+                    window_size,
+                    tolerance,
+                    &timestamps,
+                    current_time,
+                ));
+            }
+
+            if let Some((pair_address, twap)) = price_source.astroport_pool_twap() {
+                let timestamps = ASTROPORT_POOL_SNAPSHOTS
+                    .may_load(deps.storage, &denom)?
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|snapshot| snapshot.timestamp)
+                    .collect::<Vec<_>>();
+                coverages.push(snapshot_coverage(
+                    &denom,
+                    TwapSnapshotKind::Pool,
+                    pair_address,
+                    twap.window_size,
+                    twap.tolerance,
+                    &timestamps,
+                    current_time,
+                ));
+            }
+        }
+
+        Ok(coverages)
+    }
+}
+
+/// Time-weighted average of the pool state recorded in the snapshots of `denom`.
+///
+/// The state of every snapshot is held until the next snapshot, the state of the most recent one
+/// until the current block time. The window starts at the oldest snapshot whose period from the
+/// current block time is within tolerance of the window size, same as for the TWAP price.
+pub fn query_average_pool_state(
+    storage: &dyn Storage,
+    current_time: u64,
+    denom: &str,
+    twap: &LpTwap,
+) -> ContractResult<AstroportPoolSnapshot> {
+    let snapshots =
+        ASTROPORT_POOL_SNAPSHOTS.may_load(storage, denom)?.ok_or(ContractError::NoSnapshots {})?;
+
+    if snapshots.len() < 2 {
+        return Err(ContractError::NotEnoughSnapshots {});
+    }
+
+    let start = snapshots
+        .iter()
+        .position(|snapshot| {
+            current_time.abs_diff(snapshot.timestamp).abs_diff(twap.window_size) <= twap.tolerance
+        })
+        .ok_or(ContractError::NoSnapshotWithinTolerance {})?;
+    let snapshots = &snapshots[start..];
+
+    let mut total_share = Uint256::zero();
+    let mut curve_invariant = Decimal256::zero();
+    let mut price_scale = Decimal256::zero();
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let end = snapshots.get(i + 1).map_or(current_time, |next| next.timestamp);
+        let duration = end - snapshot.timestamp;
+
+        total_share = total_share
+            .checked_add(Uint256::from(snapshot.total_share).checked_mul(duration.into())?)?;
+        curve_invariant = curve_invariant.checked_add(
+            snapshot.curve_invariant.checked_mul(Decimal256::from_ratio(duration, 1u128))?,
+        )?;
+        if let Some(snapshot_price_scale) = snapshot.price_scale {
+            price_scale = price_scale.checked_add(
+                Decimal256::from(snapshot_price_scale)
+                    .checked_mul(Decimal256::from_ratio(duration, 1u128))?,
+            )?;
+        }
+    }
+
+    // The period is greater than zero, because the tolerance is less than the window size
+    let period = current_time - snapshots[0].timestamp;
+    let period_dec = Decimal256::from_ratio(period, 1u128);
+
+    Ok(AstroportPoolSnapshot {
+        timestamp: current_time,
+        total_share: Uint128::try_from(total_share.checked_div(period.into())?)?,
+        curve_invariant: curve_invariant.checked_div(period_dec)?,
+        price_scale: match snapshots[0].price_scale {
+            Some(_) => Some(Decimal::try_from(price_scale.checked_div(period_dec)?)?),
+            None => None,
+        },
+    })
+}
+
+/// Queries the state of a PCL or StableSwap pool used for pricing its liquidity token
+fn query_astroport_pool_state(
+    querier: &QuerierWrapper,
+    pair_address: &Addr,
+    timestamp: u64,
+) -> ContractResult<AstroportPoolSnapshot> {
+    let pair_info = query_astroport_pair_info(querier, pair_address)?;
+    let (curve_invariant, price_scale) = match pair_info.pair_type {
+        PairType::Stable {} => {
+            let curve_invariant = query_astroport_ss_curve_invariant(querier, pair_address)?;
+            (Decimal256::from_ratio(curve_invariant, 1u128), None)
+        }
+        PairType::Custom(ref custom) if custom == "concentrated" => {
+            let pool_config = query_astroport_config(querier, pair_address)?;
+            let pool_params = match pool_config.params {
+                Some(params) => from_json::<ConcentratedPoolParams>(params)?,
+                None => return Err(ContractError::MissingAstroportPoolParams {}),
+            };
+            let curve_invariant = query_astroport_pcl_curve_invariant(querier, pair_address)?;
+            (curve_invariant, Some(pool_params.price_scale))
+        }
+        _ => return Err(ContractError::InvalidPairType {}),
+    };
+
+    let pool = query_astroport_pool(querier, pair_address)?;
+
+    Ok(AstroportPoolSnapshot {
+        timestamp,
+        total_share: pool.total_share,
+        curve_invariant,
+        price_scale,
+    })
+}
+
+/// Minimum number of snapshots needed to compute a TWAP
+const MIN_TWAP_SNAPSHOTS: u32 = 2;
+
+/// Number of snapshots kept for a TWAP window when they are recorded `2 * tolerance` seconds apart.
+/// Any wider and there may be no snapshot within tolerance of the window size to anchor the TWAP.
+fn min_twap_snapshots(window_size: u64, tolerance: u64) -> u32 {
+    let retained_period = window_size.saturating_add(tolerance);
+    let snapshots = retained_period.div_ceil(tolerance.max(1).saturating_mul(2)).saturating_add(1);
+    u32::try_from(snapshots).unwrap_or(u32::MAX).max(MIN_TWAP_SNAPSHOTS)
+}
+
+/// Minimum number of seconds between two snapshots of a denom priced with the pair.
+///
+/// Snapshots older than `window_size + tolerance` are purged, so recording them this far apart
+/// keeps at most the maximum number of snapshots configured for the pair. The interval is never
+/// less than the tolerance, and never more than twice the tolerance since the maximum can not be
+/// set below `min_twap_snapshots`.
+fn twap_snapshot_interval(
+    storage: &dyn Storage,
+    pair_address: &Addr,
+    window_size: u64,
+    tolerance: u64,
+) -> ContractResult<u64> {
+    let max_snapshots = MAX_TWAP_SNAPSHOTS
+        .may_load(storage, pair_address)?
+        .unwrap_or(DEFAULT_MAX_TWAP_SNAPSHOTS)
+        .max(min_twap_snapshots(window_size, tolerance));
+    let interval = window_size.saturating_add(tolerance).div_ceil(u64::from(max_snapshots) - 1);
+    Ok(interval.max(tolerance))
+}
+
+fn snapshot_coverage(
+    denom: &str,
+    kind: TwapSnapshotKind,
+    pair_address: &Addr,
+    window_size: u64,
+    tolerance: u64,
+    timestamps: &[u64],
+    current_time: u64,
+) -> TwapSnapshotCoverageResponse {
+    let gaps = timestamps
+        .windows(2)
+        .filter(|pair| pair[1] - pair[0] > 2 * tolerance)
+        .map(|pair| TwapSnapshotGap {
+            start: pair[0],
+            end: pair[1],
+        })
+        .collect();
+
+    let sufficient = timestamps.len() >= 2
+        && timestamps
+            .iter()
+            .any(|ts| current_time.abs_diff(*ts).abs_diff(window_size) <= tolerance);
+
+    TwapSnapshotCoverageResponse {
+        denom: denom.to_string(),
+        kind,
+        pair_address: pair_address.to_string(),
+        window_size,
+        tolerance,
+        snapshots: timestamps.len() as u32,
+        oldest_timestamp: timestamps.first().copied(),
+        latest_timestamp: timestamps.last().copied(),
+        gaps,
+        sufficient,
+    }
 }
@@ -3,7 +3,8 @@ use mars_oracle_base::OracleBase;
 use mars_types::oracle::{WasmOracleCustomExecuteMsg, WasmOracleCustomInitParams};
 
 use crate::{
-    astroport_twap::ExecuteTwapSnapshots, WasmPriceSourceChecked, WasmPriceSourceUnchecked,
+    astroport_twap::{ExecuteTwapSnapshots, QueryTwapSnapshots},
+    WasmPriceSourceChecked, WasmPriceSourceUnchecked,
 };
 
 /// The Wasm oracle contract inherits logics from the base oracle contract, with the Wasm query
@@ -22,7 +23,9 @@ pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 #[cfg(not(feature = "library"))]
 pub mod entry {
-    use cosmwasm_std::{entry_point, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response};
+    use cosmwasm_std::{
+        entry_point, to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    };
     use cw2::set_contract_version;
     use mars_oracle_base::{ContractError, ContractResult};
     use mars_types::oracle::{ExecuteMsg, InstantiateMsg, QueryMsg};
@@ -70,14 +73,33 @@ pub mod entry {
                 WasmOracleCustomExecuteMsg::RecordTwapSnapshots {
                     denoms,
                 } => contract.execute_record_astroport_twap_snapshots(deps, env, denoms),
+                WasmOracleCustomExecuteMsg::SetMaxTwapSnapshots {
+                    pair_address,
+                    max_snapshots,
+                } => {
+                    contract.execute_set_max_twap_snapshots(deps, info, pair_address, max_snapshots)
+                }
             },
-            _ => contract.execute(deps, info, msg),
+            _ => contract.execute(deps, env, info, msg),
         }
     }
 
     #[entry_point]
     pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
-        WasmOracle::default().query(deps, env, msg)
+        let contract = WasmOracle::default();
+        match msg {
+            QueryMsg::TwapSnapshotCoverage {
+                start_after,
+                limit,
+            } => to_json_binary(&contract.query_twap_snapshot_coverage(
+                deps,
+                env,
+                start_after,
+                limit,
+            )?)
+            .map_err(Into::into),
+            _ => contract.query(deps, env, msg),
+        }
     }
 
     #[entry_point]
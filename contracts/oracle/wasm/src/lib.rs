@@ -7,5 +7,5 @@ mod price_source;
 mod state;
 
 pub use price_source::{
-    AstroportTwap, WasmPriceSource, WasmPriceSourceChecked, WasmPriceSourceUnchecked,
+    AstroportTwap, LpTwap, WasmPriceSource, WasmPriceSourceChecked, WasmPriceSourceUnchecked,
 };
@@ -19,6 +19,7 @@ use mars_types::oracle::{ActionKind, AstroportTwapSnapshot, Config};
 use pyth_sdk_cw::PriceIdentifier;
 
 use crate::{
+    astroport_twap::query_average_pool_state,
     helpers::{
         adjust_precision, astro_native_asset, get_astroport_pair_denoms,
         get_other_astroport_pair_denom, normalize_price, period_diff, query_astroport_config,
@@ -130,10 +131,37 @@ pub enum WasmPriceSource<A> {
     PclLiquidityToken {
         /// Address of the Astroport pair
         pair_address: A,
+        /// Average the pool state over a TWAP window instead of using the current pool state.
+        /// Requires the pool state snapshots to be recorded with `RecordTwapSnapshots`.
+        twap: Option<LpTwap>,
     },
     SsLiquidityToken {
         /// Address of the Astroport pair
         pair_address: A,
+        /// Average the pool state over a TWAP window instead of using the current pool state.
+        /// Requires the pool state snapshots to be recorded with `RecordTwapSnapshots`.
+        twap: Option<LpTwap>,
+    },
+    /// Median of the prices returned by the given sources.
+    ///
+    /// Sources failing to return a price (e.g. a stale Pyth price) are skipped. The price query
+    /// only fails if none of the sources returns a price.
+    Median {
+        sources: Vec<WasmPriceSource<A>>,
+    },
+    /// Price of the primary source. The secondary source is only used if the primary one fails.
+    Fallback {
+        primary: Box<WasmPriceSource<A>>,
+        secondary: Box<WasmPriceSource<A>>,
+    },
+    /// Price of the source, rejected if it deviates too much from the price of the reference
+    /// source.
+    Guarded {
+        source: Box<WasmPriceSource<A>>,
+        reference: Box<WasmPriceSource<A>>,
+
+        /// The maximum deviation (percentage) of the price from the reference price
+        max_deviation: Decimal,
     },
 }
 
@@ -147,9 +175,53 @@ pub struct AstroportTwap<A> {
     pub tolerance: u64,
 }
 
+/// TWAP window of a liquidity token price source. The snapshot within tolerance of the window size
+/// is searched the same way as for the Astroport TWAP price source.
+#[cw_serde]
+pub struct LpTwap {
+    /// The size of the sliding TWAP window in seconds.
+    pub window_size: u64,
+    /// The tolerance in seconds for the sliding TWAP window.
+    pub tolerance: u64,
+}
+
+impl fmt::Display for LpTwap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.window_size, self.tolerance)
+    }
+}
+
+impl LpTwap {
+    fn validate(&self) -> ContractResult<()> {
+        if self.tolerance >= self.window_size {
+            return Err(ContractError::InvalidPriceSource {
+                reason: "tolerance must be less than window size".to_string(),
+            });
+        }
+        if self.window_size <= 1 {
+            return Err(ContractError::InvalidPriceSource {
+                reason: "window_size must be greater than 1".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
 pub type WasmPriceSourceUnchecked = WasmPriceSource<String>;
 pub type WasmPriceSourceChecked = WasmPriceSource<Addr>;
 
+impl<A> WasmPriceSource<A> {
+    /// Median, Fallback and Guarded price sources, which are composed of other price sources
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self,
+            WasmPriceSource::Median { .. }
+                | WasmPriceSource::Fallback { .. }
+                | WasmPriceSource::Guarded { .. }
+        )
+    }
+}
+
 impl fmt::Display for WasmPriceSourceChecked {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let label = match self {
@@ -191,8 +263,16 @@ impl fmt::Display for WasmPriceSourceChecked {
                 format!("lsd:{transitive_denom}:{pair_address}:{window_size}:{tolerance}:{contract_addr}:{max_staleness}")
             },
             WasmPriceSource::XykLiquidityToken { pair_address } => format!("xyk_liquidity_token:{pair_address}"),
-            WasmPriceSource::PclLiquidityToken { pair_address } => format!("pcl_liquidity_token:{pair_address}"),
-            WasmPriceSource::SsLiquidityToken { pair_address } => format!("stable_swap_liquidity_token:{pair_address}"),
+            WasmPriceSource::PclLiquidityToken { pair_address, twap: None } => format!("pcl_liquidity_token:{pair_address}"),
+            WasmPriceSource::PclLiquidityToken { pair_address, twap: Some(twap) } => format!("pcl_liquidity_token:{pair_address}:twap:{twap}"),
+            WasmPriceSource::SsLiquidityToken { pair_address, twap: None } => format!("stable_swap_liquidity_token:{pair_address}"),
+            WasmPriceSource::SsLiquidityToken { pair_address, twap: Some(twap) } => format!("stable_swap_liquidity_token:{pair_address}:twap:{twap}"),
+            WasmPriceSource::Median { sources } => {
+                let sources = sources.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+                format!("median:[{}]", sources.join(","))
+            }
+            WasmPriceSource::Fallback { primary, secondary } => format!("fallback:[{primary},{secondary}]"),
+            WasmPriceSource::Guarded { source, reference, max_deviation } => format!("guarded:[{source},{reference}]:{max_deviation}"),
         };
         write!(f, "{label}")
     }
@@ -351,7 +431,12 @@ impl PriceSourceUnchecked<WasmPriceSourceChecked, Empty> for WasmPriceSourceUnch
             }
             WasmPriceSource::PclLiquidityToken {
                 pair_address,
+                twap,
             } => {
+                if let Some(twap) = &twap {
+                    twap.validate()?;
+                }
+
                 let pair_address = deps.api.addr_validate(&pair_address)?;
                 validate_astroport_lp_pool_for_type(
                     deps,
@@ -362,11 +447,17 @@ impl PriceSourceUnchecked<WasmPriceSourceChecked, Empty> for WasmPriceSourceUnch
 
                 Ok(WasmPriceSourceChecked::PclLiquidityToken {
                     pair_address,
+                    twap,
                 })
             }
             WasmPriceSource::SsLiquidityToken {
                 pair_address,
+                twap,
             } => {
+                if let Some(twap) = &twap {
+                    twap.validate()?;
+                }
+
                 let pair_address = deps.api.addr_validate(&pair_address)?;
                 validate_astroport_lp_pool_for_type(
                     deps,
@@ -377,6 +468,56 @@ impl PriceSourceUnchecked<WasmPriceSourceChecked, Empty> for WasmPriceSourceUnch
 
                 Ok(WasmPriceSourceChecked::SsLiquidityToken {
                     pair_address,
+                    twap,
+                })
+            }
+            WasmPriceSource::Median {
+                sources,
+            } => {
+                mars_oracle_base::composite::assert_median(sources.len())?;
+                for source in &sources {
+                    mars_oracle_base::composite::assert_not_nested(source.is_composite())?;
+                }
+                Ok(WasmPriceSourceChecked::Median {
+                    sources: sources
+                        .into_iter()
+                        .map(|source| source.validate(deps, denom, base_denom, price_sources))
+                        .collect::<ContractResult<_>>()?,
+                })
+            }
+            WasmPriceSource::Fallback {
+                primary,
+                secondary,
+            } => {
+                mars_oracle_base::composite::assert_not_nested(primary.is_composite())?;
+                mars_oracle_base::composite::assert_not_nested(secondary.is_composite())?;
+                Ok(WasmPriceSourceChecked::Fallback {
+                    primary: Box::new(primary.validate(deps, denom, base_denom, price_sources)?),
+                    secondary: Box::new(secondary.validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                })
+            }
+            WasmPriceSource::Guarded {
+                source,
+                reference,
+                max_deviation,
+            } => {
+                mars_oracle_base::composite::assert_guarded(max_deviation)?;
+                mars_oracle_base::composite::assert_not_nested(source.is_composite())?;
+                mars_oracle_base::composite::assert_not_nested(reference.is_composite())?;
+                Ok(WasmPriceSourceChecked::Guarded {
+                    source: Box::new(source.validate(deps, denom, base_denom, price_sources)?),
+                    reference: Box::new(reference.validate(
+                        deps,
+                        denom,
+                        base_denom,
+                        price_sources,
+                    )?),
+                    max_deviation,
                 })
             }
         }
@@ -472,19 +613,129 @@ impl PriceSourceChecked<Empty> for WasmPriceSourceChecked {
             ),
             WasmPriceSource::PclLiquidityToken {
                 pair_address,
+                twap,
             } => query_pcl_liquidity_token_price(
                 deps,
                 env,
+                denom,
                 config,
                 price_sources,
                 pair_address,
+                twap,
                 kind,
             ),
             WasmPriceSource::SsLiquidityToken {
                 pair_address,
-            } => {
-                query_ss_liquidity_token_price(deps, env, config, price_sources, pair_address, kind)
+                twap,
+            } => query_ss_liquidity_token_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                pair_address,
+                twap,
+                kind,
+            ),
+            WasmPriceSource::Median {
+                sources,
+            } => mars_oracle_base::composite::query_median_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                sources,
+            ),
+            WasmPriceSource::Fallback {
+                primary,
+                secondary,
+            } => mars_oracle_base::composite::query_fallback_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                primary.as_ref(),
+                secondary.as_ref(),
+            ),
+            WasmPriceSource::Guarded {
+                source,
+                reference,
+                max_deviation,
+            } => mars_oracle_base::composite::query_guarded_price(
+                deps,
+                env,
+                denom,
+                config,
+                price_sources,
+                kind,
+                source.as_ref(),
+                reference.as_ref(),
+                *max_deviation,
+            ),
+        }
+    }
+}
+
+impl WasmPriceSourceChecked {
+    /// Params of the Astroport TWAP used by the price source, for which snapshots have to be
+    /// recorded. Composite price sources use the first TWAP found among their sources.
+    pub fn astroport_twap(&self) -> Option<(&Addr, u64, u64)> {
+        match self {
+            WasmPriceSource::AstroportTwap {
+                pair_address,
+                window_size,
+                tolerance,
+            } => Some((pair_address, *window_size, *tolerance)),
+            WasmPriceSource::Lsd {
+                twap,
+                ..
+            } => Some((&twap.pair_address, twap.window_size, twap.tolerance)),
+            WasmPriceSource::Median {
+                sources,
+            } => sources.iter().find_map(|source| source.astroport_twap()),
+            WasmPriceSource::Fallback {
+                primary,
+                secondary,
+            } => primary.astroport_twap().or_else(|| secondary.astroport_twap()),
+            WasmPriceSource::Guarded {
+                source,
+                reference,
+                ..
+            } => source.astroport_twap().or_else(|| reference.astroport_twap()),
+            _ => None,
+        }
+    }
+
+    /// Params of the TWAP of the pool state used by a liquidity token price source, for which pool
+    /// snapshots have to be recorded. Composite price sources use the first one found among their
+    /// sources.
+    pub fn astroport_pool_twap(&self) -> Option<(&Addr, &LpTwap)> {
+        match self {
+            WasmPriceSource::PclLiquidityToken {
+                pair_address,
+                twap: Some(twap),
             }
+            | WasmPriceSource::SsLiquidityToken {
+                pair_address,
+                twap: Some(twap),
+            } => Some((pair_address, twap)),
+            WasmPriceSource::Median {
+                sources,
+            } => sources.iter().find_map(|source| source.astroport_pool_twap()),
+            WasmPriceSource::Fallback {
+                primary,
+                secondary,
+            } => primary.astroport_pool_twap().or_else(|| secondary.astroport_pool_twap()),
+            WasmPriceSource::Guarded {
+                source,
+                reference,
+                ..
+            } => source.astroport_pool_twap().or_else(|| reference.astroport_pool_twap()),
+            _ => None,
         }
     }
 }
@@ -762,12 +1013,15 @@ fn query_xyk_liquidity_token_price(
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_pcl_liquidity_token_price(
     deps: &Deps,
     env: &Env,
+    denom: &str,
     config: &Config,
     price_sources: &Map<&str, WasmPriceSourceChecked>,
     pair_address: &Addr,
+    twap: &Option<LpTwap>,
     kind: ActionKind,
 ) -> ContractResult<Decimal> {
     // PCL pool asserted during price source creation
@@ -775,13 +1029,26 @@ fn query_pcl_liquidity_token_price(
     let coin0 = pool.assets[0].as_coin()?;
     let coin1 = pool.assets[1].as_coin()?;
 
-    let pool_config = query_astroport_config(&deps.querier, pair_address)?;
-    let pool_params = match pool_config.params {
-        Some(params) => from_json::<ConcentratedPoolParams>(params)?,
-        None => return Err(ContractError::MissingAstroportPoolParams {}),
-    };
+    let (total_share, price_scale, curve_invariant) = match twap {
+        Some(twap) => {
+            let state =
+                query_average_pool_state(deps.storage, env.block.time.seconds(), denom, twap)?;
+            let price_scale =
+                state.price_scale.ok_or(ContractError::MissingAstroportPoolParams {})?;
+            (state.total_share, price_scale, state.curve_invariant)
+        }
+        None => {
+            let pool_config = query_astroport_config(&deps.querier, pair_address)?;
+            let pool_params = match pool_config.params {
+                Some(params) => from_json::<ConcentratedPoolParams>(params)?,
+                None => return Err(ContractError::MissingAstroportPoolParams {}),
+            };
 
-    let curve_invariant = query_astroport_pcl_curve_invariant(&deps.querier, pair_address)?;
+            let curve_invariant = query_astroport_pcl_curve_invariant(&deps.querier, pair_address)?;
+
+            (pool.total_share, pool_params.price_scale, curve_invariant)
+        }
+    };
 
     query_pcl_lp_price(
         deps,
@@ -791,18 +1058,21 @@ fn query_pcl_liquidity_token_price(
         kind,
         coin0,
         coin1,
-        pool.total_share,
-        pool_params.price_scale,
+        total_share,
+        price_scale,
         curve_invariant,
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 fn query_ss_liquidity_token_price(
     deps: &Deps,
     env: &Env,
+    denom: &str,
     config: &Config,
     price_sources: &Map<&str, WasmPriceSourceChecked>,
     pair_address: &Addr,
+    twap: &Option<LpTwap>,
     kind: ActionKind,
 ) -> ContractResult<Decimal> {
     // StableSwap pool asserted during price source creation
@@ -810,7 +1080,16 @@ fn query_ss_liquidity_token_price(
     let coin0 = pool.assets[0].as_coin()?;
     let coin1 = pool.assets[1].as_coin()?;
 
-    let curve_invariant = query_astroport_ss_curve_invariant(&deps.querier, pair_address)?;
+    let (total_share, curve_invariant) = match twap {
+        Some(twap) => {
+            let state =
+                query_average_pool_state(deps.storage, env.block.time.seconds(), denom, twap)?;
+            (state.total_share, Uint128::try_from(state.curve_invariant.to_uint_floor())?)
+        }
+        None => {
+            (pool.total_share, query_astroport_ss_curve_invariant(&deps.querier, pair_address)?)
+        }
+    };
 
     query_stable_swap_lp_price(
         deps,
@@ -820,7 +1099,7 @@ fn query_ss_liquidity_token_price(
         kind,
         coin0,
         coin1,
-        pool.total_share,
+        total_share,
         curve_invariant,
     )
 }
@@ -1,9 +1,20 @@
 use cosmwasm_std::Addr;
 use cw_storage_plus::{Item, Map};
-use mars_types::oracle::AstroportTwapSnapshot;
+use mars_types::oracle::{AstroportPoolSnapshot, AstroportTwapSnapshot};
+
+/// Maximum number of TWAP snapshots kept per denom of a pair if not configured by the owner
+pub const DEFAULT_MAX_TWAP_SNAPSHOTS: u32 = 100;
 
 /// The Astroport Factory contract address
 pub const ASTROPORT_FACTORY: Item<Addr> = Item::new("astroport_factory");
 
 /// TWAP snapshots indexed by denom
 pub const ASTROPORT_TWAP_SNAPSHOTS: Map<&str, Vec<AstroportTwapSnapshot>> = Map::new("snapshots");
+
+/// Pool state snapshots of liquidity token price sources with TWAP, indexed by denom
+pub const ASTROPORT_POOL_SNAPSHOTS: Map<&str, Vec<AstroportPoolSnapshot>> =
+    Map::new("pool_snapshots");
+
+/// Maximum number of snapshots kept per denom, indexed by the pair the denom is priced with.
+/// The oldest ones are pruned.
+pub const MAX_TWAP_SNAPSHOTS: Map<&Addr, u32> = Map::new("max_twap_snapshots");
@@ -3,4 +3,5 @@ mod helpers;
 mod prop_tests;
 mod test_migration_v2;
 mod test_price_source;
+mod test_twap_snapshots;
 mod test_update_admin;
@@ -19,7 +19,7 @@ use cw_storage_plus::Map;
 use mars_oracle_base::{redemption_rate::RedemptionRate, ContractError, PriceSourceUnchecked};
 use mars_oracle_wasm::{
     contract::entry::{self, execute},
-    AstroportTwap, WasmPriceSource, WasmPriceSourceChecked, WasmPriceSourceUnchecked,
+    AstroportTwap, LpTwap, WasmPriceSource, WasmPriceSourceChecked, WasmPriceSourceUnchecked,
 };
 use mars_types::oracle::{ExecuteMsg, PriceResponse, QueryMsg};
 use pyth_sdk_cw::PriceIdentifier;
@@ -132,6 +132,24 @@ fn display_lp_token_price_source() {
     )
 }
 
+#[test]
+fn display_lp_token_price_source_with_twap() {
+    let ps = WasmPriceSourceChecked::PclLiquidityToken {
+        pair_address: Addr::unchecked("astro_addr"),
+        twap: Some(LpTwap {
+            window_size: 101,
+            tolerance: 16,
+        }),
+    };
+    assert_eq!(ps.to_string(), "pcl_liquidity_token:astro_addr:twap:101:16");
+
+    let ps = WasmPriceSourceChecked::SsLiquidityToken {
+        pair_address: Addr::unchecked("astro_addr"),
+        twap: None,
+    };
+    assert_eq!(ps.to_string(), "stable_swap_liquidity_token:astro_addr");
+}
+
 #[test]
 fn validate_fixed_price_source() {
     let ps = WasmPriceSource::Fixed {
@@ -145,6 +163,88 @@ fn validate_fixed_price_source() {
     assert!(res.is_ok());
 }
 
+#[test]
+fn display_composite_price_sources() {
+    let ps = WasmPriceSourceChecked::Fallback {
+        primary: Box::new(WasmPriceSource::Median {
+            sources: vec![
+                WasmPriceSource::Fixed {
+                    price: Decimal::one(),
+                },
+                WasmPriceSource::AstroportSpot {
+                    pair_address: Addr::unchecked("fake_addr"),
+                },
+            ],
+        }),
+        secondary: Box::new(WasmPriceSource::Guarded {
+            source: Box::new(WasmPriceSource::Fixed {
+                price: Decimal::percent(50),
+            }),
+            reference: Box::new(WasmPriceSource::Fixed {
+                price: Decimal::percent(52),
+            }),
+            max_deviation: Decimal::percent(5),
+        }),
+    };
+    assert_eq!(
+        ps.to_string(),
+        "fallback:[median:[fixed:1,astroport_spot:fake_addr.],guarded:[fixed:0.5,fixed:0.52]:0.05]"
+    )
+}
+
+#[test]
+fn validate_composite_price_source() {
+    let deps = mock_dependencies();
+    let price_sources = Map::new("price_sources");
+    let fixed = || WasmPriceSource::Fixed {
+        price: Decimal::one(),
+    };
+
+    let ps = WasmPriceSourceUnchecked::Median {
+        sources: vec![fixed()],
+    };
+    let err = ps.validate(&deps.as_ref(), "uosmo", "uusd", &price_sources).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "median requires between 2 and 10 sources".to_string()
+        }
+    );
+
+    let ps = WasmPriceSourceUnchecked::Guarded {
+        source: Box::new(fixed()),
+        reference: Box::new(fixed()),
+        max_deviation: Decimal::percent(101),
+    };
+    let err = ps.validate(&deps.as_ref(), "uosmo", "uusd", &price_sources).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "max_deviation must be in the range of (0;1>".to_string()
+        }
+    );
+
+    let ps = WasmPriceSourceUnchecked::Guarded {
+        source: Box::new(WasmPriceSource::Median {
+            sources: vec![fixed(), fixed()],
+        }),
+        reference: Box::new(fixed()),
+        max_deviation: Decimal::percent(5),
+    };
+    let err = ps.validate(&deps.as_ref(), "uosmo", "uusd", &price_sources).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidPriceSource {
+            reason: "composite price sources can't be nested".to_string()
+        }
+    );
+
+    let ps = WasmPriceSourceUnchecked::Median {
+        sources: vec![fixed(), fixed()],
+    };
+    assert!(ps.validate(&deps.as_ref(), "uosmo", "uusd", &price_sources).is_ok());
+}
+
 #[test]
 fn test_set_price_source_fixed() {
     let owned_runner = get_test_runner();
@@ -467,6 +567,112 @@ fn querying_pyth_price_if_publish_price_too_old() {
     );
 }
 
+#[test]
+fn querying_composite_price_if_pyth_price_too_old() {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let robot = WasmOracleTestRobot::new(
+        &runner,
+        get_contracts(&runner),
+        &get_test_runner().init_default_account().unwrap(),
+        None,
+    );
+
+    let mut deps = helpers::setup_test(&robot.astroport_contracts.factory.address);
+
+    // price source used to convert USD to base_denom
+    helpers::set_price_source(
+        deps.as_mut(),
+        "usd",
+        WasmPriceSourceUnchecked::Fixed {
+            price: Decimal::from_str("1000000").unwrap(),
+        },
+    );
+
+    let price_id = PriceIdentifier::from_hex(
+        "61226d39beea19d334f17c2febce27e12646d84675924ebb02b9cdaea68727e3",
+    )
+    .unwrap();
+
+    let max_staleness = 30u64;
+    let pyth = WasmPriceSourceUnchecked::Pyth {
+        contract_addr: "pyth_contract_addr".to_string(),
+        price_feed_id: price_id,
+        max_staleness,
+        max_confidence: Decimal::percent(12),
+        max_deviation: Decimal::percent(14),
+        denom_decimals: 6,
+    };
+    let fixed = |price: &str| WasmPriceSourceUnchecked::Fixed {
+        price: Decimal::from_str(price).unwrap(),
+    };
+
+    let price_publish_time = 1677157333u64;
+    deps.querier.set_pyth_price(
+        price_id,
+        PriceFeedResponse {
+            price_feed: PriceFeed::new(
+                price_id,
+                Price {
+                    price: 1371155677,
+                    conf: 646723,
+                    expo: -8,
+                    publish_time: price_publish_time as i64,
+                },
+                Price {
+                    price: 1365133270,
+                    conf: 574566,
+                    expo: -8,
+                    publish_time: price_publish_time as i64,
+                },
+            ),
+        },
+    );
+
+    let query_price = |deps: cosmwasm_std::Deps, block_time: u64| {
+        let res = entry::query(
+            deps,
+            mock_env_at_block_time(block_time),
+            QueryMsg::Price {
+                denom: "uatom".to_string(),
+                kind: None,
+            },
+        )
+        .unwrap();
+        from_json::<PriceResponse>(res).unwrap().price
+    };
+    let stale_time = price_publish_time + max_staleness + 1u64;
+
+    // Pyth price is stale, the secondary source is used
+    helpers::set_price_source(
+        deps.as_mut(),
+        "uatom",
+        WasmPriceSourceUnchecked::Fallback {
+            primary: Box::new(pyth.clone()),
+            secondary: Box::new(fixed("13.5")),
+        },
+    );
+    assert_eq!(
+        query_price(deps.as_ref(), price_publish_time),
+        Decimal::from_str("13.71155677").unwrap()
+    );
+    assert_eq!(query_price(deps.as_ref(), stale_time), Decimal::from_str("13.5").unwrap());
+
+    // Pyth price is stale, median of the remaining sources
+    helpers::set_price_source(
+        deps.as_mut(),
+        "uatom",
+        WasmPriceSourceUnchecked::Median {
+            sources: vec![pyth, fixed("13.5"), fixed("13.9")],
+        },
+    );
+    assert_eq!(
+        query_price(deps.as_ref(), price_publish_time),
+        Decimal::from_str("13.71155677").unwrap()
+    );
+    assert_eq!(query_price(deps.as_ref(), stale_time), Decimal::from_str("13.7").unwrap());
+}
+
 #[test]
 fn querying_pyth_price_if_signed() {
     let owned_runner = get_test_runner();
@@ -1051,6 +1257,7 @@ pub fn test_validate_and_query_astroport_pcl_lp_price_source(
 
     let price_source = WasmPriceSourceUnchecked::PclLiquidityToken {
         pair_address: pair_address.clone(),
+        twap: None,
     };
 
     // Validate the price sources
@@ -1142,6 +1349,7 @@ pub fn test_validate_and_query_astroport_ss_lp_price_source(
 
     let price_source = WasmPriceSourceUnchecked::SsLiquidityToken {
         pair_address: pair_address.clone(),
+        twap: None,
     };
 
     // Validate the price sources
@@ -0,0 +1,261 @@
+use astroport::factory::PairType;
+use cosmwasm_std::{coin, Decimal, Empty};
+use cw_it::{
+    astroport::{
+        robot::AstroportTestRobot,
+        utils::{native_asset, native_info},
+    },
+    robot::TestRobot,
+    test_tube::{Module, Wasm},
+    traits::{CwItRunner, DEFAULT_COIN_AMOUNT},
+};
+use mars_oracle_wasm::{LpTwap, WasmPriceSourceUnchecked};
+use mars_testing::{
+    test_runner::get_test_runner,
+    wasm_oracle::{astro_init_params, fixed_source, get_contracts, WasmOracleTestRobot},
+};
+use mars_types::oracle::{
+    ExecuteMsg, TwapSnapshotGap, TwapSnapshotKind, WasmOracleCustomExecuteMsg,
+};
+use test_case::test_case;
+
+fn lp_price_source(
+    pair_type: &PairType,
+    pair_address: &str,
+    twap: Option<LpTwap>,
+) -> WasmPriceSourceUnchecked {
+    match pair_type {
+        PairType::Stable {} => WasmPriceSourceUnchecked::SsLiquidityToken {
+            pair_address: pair_address.to_string(),
+            twap,
+        },
+        _ => WasmPriceSourceUnchecked::PclLiquidityToken {
+            pair_address: pair_address.to_string(),
+            twap,
+        },
+    }
+}
+
+#[test_case(PairType::Custom("concentrated".to_string()); "PCL")]
+#[test_case(PairType::Stable {}; "StableSwap")]
+fn lp_token_price_with_twap_uses_recorded_pool_state(pair_type: PairType) {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let admin = &runner
+        .init_account(&[coin(DEFAULT_COIN_AMOUNT, "uatom"), coin(DEFAULT_COIN_AMOUNT, "untrn")])
+        .unwrap();
+    let robot = WasmOracleTestRobot::new(&runner, get_contracts(&runner), admin, Some("uusd"));
+
+    let (pair_address, _) = robot.create_astroport_pair(
+        pair_type.clone(),
+        &[native_info("uatom"), native_info("untrn")],
+        astro_init_params(&pair_type),
+        admin,
+        Some(&[1171210862745u128, 1171210862745u128]),
+        Some(&[6, 6]),
+    );
+
+    let twap = LpTwap {
+        window_size: 100,
+        tolerance: 10,
+    };
+    robot
+        .set_price_sources(
+            vec![("uatom", fixed_source(Decimal::one())), ("untrn", fixed_source(Decimal::one()))],
+            admin,
+        )
+        .set_price_source("lp_twap", lp_price_source(&pair_type, &pair_address, Some(twap)), admin)
+        .set_price_source("lp_spot", lp_price_source(&pair_type, &pair_address, None), admin)
+        .record_twap_snapshots(&["lp_twap"], admin)
+        .increase_time(100)
+        .record_twap_snapshots(&["lp_twap"], admin);
+
+    // The pool didn't change, so the average state is the current state
+    let spot_price = robot.query_price("lp_spot").price;
+    robot.assert_price("lp_twap", spot_price);
+
+    // Changes of the pool state are only picked up once they are recorded
+    robot.swap_on_astroport_pair(
+        &pair_address,
+        native_asset("uatom", 500000000000u128),
+        None,
+        None,
+        Some(Decimal::percent(50)),
+        admin,
+    );
+    assert_ne!(robot.query_price("lp_spot").price, spot_price);
+    robot.assert_price("lp_twap", spot_price);
+}
+
+#[test]
+fn lp_token_price_with_twap_requires_snapshots() {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let admin = &runner
+        .init_account(&[coin(DEFAULT_COIN_AMOUNT, "uatom"), coin(DEFAULT_COIN_AMOUNT, "untrn")])
+        .unwrap();
+    let robot = WasmOracleTestRobot::new(&runner, get_contracts(&runner), admin, Some("uusd"));
+
+    let pair_type = PairType::Stable {};
+    let (pair_address, _) = robot.create_astroport_pair(
+        pair_type.clone(),
+        &[native_info("uatom"), native_info("untrn")],
+        astro_init_params(&pair_type),
+        admin,
+        Some(&[1171210862745u128, 1171210862745u128]),
+        Some(&[6, 6]),
+    );
+
+    let twap = LpTwap {
+        window_size: 100,
+        tolerance: 10,
+    };
+    robot
+        .set_price_sources(
+            vec![("uatom", fixed_source(Decimal::one())), ("untrn", fixed_source(Decimal::one()))],
+            admin,
+        )
+        .set_price_source("lp_twap", lp_price_source(&pair_type, &pair_address, Some(twap)), admin)
+        .record_twap_snapshots(&["lp_twap"], admin);
+
+    let coverage = robot.query_twap_snapshot_coverage();
+    assert_eq!(coverage.len(), 1);
+    assert_eq!(coverage[0].denom, "lp_twap");
+    assert_eq!(coverage[0].kind, TwapSnapshotKind::Pool);
+    assert_eq!(coverage[0].snapshots, 1);
+    assert!(!coverage[0].sufficient);
+
+    let err = robot
+        .wasm()
+        .query::<_, mars_types::oracle::PriceResponse>(
+            &robot.mars_oracle_contract_addr,
+            &mars_types::oracle::QueryMsg::Price {
+                denom: "lp_twap".to_string(),
+                kind: None,
+            },
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("There needs to be at least two TWAP snapshots"));
+}
+
+#[test]
+fn max_twap_snapshots_widens_the_spacing_and_coverage_reports_gaps() {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let admin = &runner.init_default_account().unwrap();
+    let robot = WasmOracleTestRobot::new(&runner, get_contracts(&runner), admin, Some("uosmo"));
+
+    let (pair_address, _) = robot.create_default_astro_pair(admin);
+    let price_source = WasmPriceSourceUnchecked::AstroportTwap {
+        pair_address: pair_address.clone(),
+        tolerance: 20,
+        window_size: 40,
+    };
+
+    // 3 snapshots cover the 60 seconds kept (window + tolerance) if recorded 30 seconds apart
+    robot
+        .set_price_source("uatom", price_source, admin)
+        .set_max_twap_snapshots(&pair_address, 3, admin)
+        .record_twap_snapshots(&["uatom"], admin)
+        .increase_time(20)
+        .record_twap_snapshots(&["uatom"], admin);
+    assert_eq!(robot.query_twap_snapshot_coverage()[0].snapshots, 1);
+
+    robot
+        .increase_time(10)
+        .record_twap_snapshots(&["uatom"], admin)
+        .increase_time(30)
+        .record_twap_snapshots(&["uatom"], admin)
+        .increase_time(30)
+        .record_twap_snapshots(&["uatom"], admin);
+
+    let coverage = robot.query_twap_snapshot_coverage();
+    assert_eq!(coverage.len(), 1);
+    let coverage = &coverage[0];
+    assert_eq!(coverage.kind, TwapSnapshotKind::Price);
+    assert_eq!(coverage.pair_address, pair_address);
+    assert_eq!(coverage.snapshots, 3);
+    let latest = coverage.latest_timestamp.unwrap();
+    assert_eq!(latest - coverage.oldest_timestamp.unwrap(), 60);
+    assert!(coverage.gaps.is_empty());
+    assert!(coverage.sufficient);
+
+    robot.assert_price("uatom", Decimal::from_ratio(1u128, 10u128));
+
+    // Snapshots more than 2 * tolerance apart leave a gap
+    robot.increase_time(50).record_twap_snapshots(&["uatom"], admin);
+    let coverage = &robot.query_twap_snapshot_coverage()[0];
+    assert_eq!(coverage.snapshots, 2);
+    assert_eq!(
+        coverage.gaps,
+        vec![TwapSnapshotGap {
+            start: latest,
+            end: latest + 50,
+        }]
+    );
+}
+
+#[test]
+fn snapshots_are_spaced_to_stay_under_the_default_maximum() {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let admin = &runner.init_default_account().unwrap();
+    let robot = WasmOracleTestRobot::new(&runner, get_contracts(&runner), admin, Some("uosmo"));
+
+    // Recorded every 5 seconds, the 605 seconds kept would need 122 snapshots, more than the
+    // default maximum of 100. Snapshots are recorded 7 seconds apart instead.
+    let (pair_address, _) = robot.create_default_astro_pair(admin);
+    let price_source = WasmPriceSourceUnchecked::AstroportTwap {
+        pair_address,
+        tolerance: 5,
+        window_size: 600,
+    };
+    robot.set_price_source("uatom", price_source, admin).record_twap_snapshots(&["uatom"], admin);
+    for _ in 0..120 {
+        robot.increase_time(5).record_twap_snapshots(&["uatom"], admin);
+    }
+
+    // Attempts every 5 seconds end up recorded every 10 seconds
+    let coverage = &robot.query_twap_snapshot_coverage()[0];
+    assert_eq!(coverage.snapshots, 61);
+    assert_eq!(coverage.latest_timestamp.unwrap() - coverage.oldest_timestamp.unwrap(), 600);
+    assert!(coverage.gaps.is_empty());
+    assert!(coverage.sufficient);
+
+    robot.assert_price("uatom", Decimal::from_ratio(1u128, 10u128));
+}
+
+#[test]
+fn max_twap_snapshots_has_to_allow_a_twap() {
+    let owned_runner = get_test_runner();
+    let runner = owned_runner.as_ref();
+    let admin = &runner.init_default_account().unwrap();
+    let robot = WasmOracleTestRobot::new(&runner, get_contracts(&runner), admin, Some("uosmo"));
+
+    let (pair_address, _) = robot.create_default_astro_pair(admin);
+    robot.set_price_source(
+        "uatom",
+        WasmPriceSourceUnchecked::AstroportTwap {
+            pair_address: pair_address.clone(),
+            tolerance: 20,
+            window_size: 100,
+        },
+        admin,
+    );
+
+    let set_max_twap_snapshots = |max_snapshots: u32| {
+        let msg = ExecuteMsg::<Empty, WasmOracleCustomExecuteMsg>::Custom(
+            WasmOracleCustomExecuteMsg::SetMaxTwapSnapshots {
+                pair_address: pair_address.clone(),
+                max_snapshots,
+            },
+        );
+        Wasm::new(&runner).execute(&robot.mars_oracle_contract_addr, &msg, &[], admin)
+    };
+
+    // Recorded 2 * 20 seconds apart, ceil((100 + 20) / 40) + 1 snapshots cover the window
+    let err = set_max_twap_snapshots(3).unwrap_err();
+    assert!(err.to_string().contains("max_snapshots is 3, but it should be >= 4"));
+
+    set_max_twap_snapshots(4).unwrap();
+}
@@ -1,26 +1,35 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response};
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use mars_owner::OwnerInit::SetInitialOwner;
 use mars_types::params::{
-    CmEmergencyUpdate, EmergencyUpdate, ExecuteMsg, InstantiateMsg, QueryMsg,
+    CmEmergencyUpdate, EmergencyUpdate, ExecuteMsg, InstantiateMsg, PerpsEmergencyUpdate, QueryMsg,
     RedBankEmergencyUpdate,
 };
 
 use crate::{
-    emergency_powers::{disable_borrowing, disallow_coin, set_zero_deposit_cap, set_zero_max_ltv},
+    emergency_powers::{
+        disable_borrowing, disable_perp_trading, disallow_coin, set_zero_deposit_cap,
+        set_zero_max_ltv,
+    },
     error::{ContractError, ContractResult},
     execute::{
-        assert_thf, update_asset_params, update_config, update_target_health_factor,
-        update_vault_config,
+        assert_thf, update_asset_params, update_config, update_perp_params,
+        update_target_health_factor, update_vault_config,
     },
     migrations,
     query::{
-        query_all_asset_params, query_all_total_deposits_v2, query_all_vault_configs,
-        query_all_vault_configs_v2, query_config, query_total_deposit, query_vault_config,
+        query_all_asset_params, query_all_perp_params, query_all_total_borrows,
+        query_all_total_deposits_v2, query_all_vault_configs, query_all_vault_configs_v2,
+        query_asset_params, query_config, query_pending_updates, query_total_borrow,
+        query_total_deposit, query_vault_config,
+    },
+    state::{
+        ADDRESS_PROVIDER, LTV_RAMPS, MAX_PERP_PARAMS, OWNER, PERP_PARAMS, RISK_MANAGER,
+        TARGET_HEALTH_FACTOR,
     },
-    state::{ADDRESS_PROVIDER, ASSET_PARAMS, OWNER, TARGET_HEALTH_FACTOR},
+    timelock::{cancel_pending_update, execute_pending_update},
 };
 
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -43,19 +52,25 @@ pub fn instantiate(
         },
     )?;
 
+    if let Some(risk_manager) = msg.risk_manager {
+        RISK_MANAGER.save(deps.storage, &deps.api.addr_validate(&risk_manager)?)?;
+    }
+
     let address_provider_addr = deps.api.addr_validate(&msg.address_provider)?;
     ADDRESS_PROVIDER.save(deps.storage, &address_provider_addr)?;
 
     assert_thf(msg.target_health_factor)?;
     TARGET_HEALTH_FACTOR.save(deps.storage, &msg.target_health_factor)?;
 
+    MAX_PERP_PARAMS.save(deps.storage, &msg.max_perp_params)?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> ContractResult<Response> {
@@ -63,10 +78,12 @@ pub fn execute(
         ExecuteMsg::UpdateOwner(update) => Ok(OWNER.update(deps, info, update)?),
         ExecuteMsg::UpdateConfig {
             address_provider,
-        } => update_config(deps, info, address_provider),
-        ExecuteMsg::UpdateAssetParams(update) => update_asset_params(deps, info, update),
+            update_delay,
+        } => update_config(deps, env, info, address_provider, update_delay),
+        ExecuteMsg::UpdateAssetParams(update) => update_asset_params(deps, env, info, update),
         ExecuteMsg::UpdateTargetHealthFactor(mcf) => update_target_health_factor(deps, info, mcf),
-        ExecuteMsg::UpdateVaultConfig(update) => update_vault_config(deps, info, update),
+        ExecuteMsg::UpdateVaultConfig(update) => update_vault_config(deps, env, info, update),
+        ExecuteMsg::UpdatePerpParams(update) => update_perp_params(deps, info, update),
         ExecuteMsg::EmergencyUpdate(update) => match update {
             EmergencyUpdate::RedBank(rb_u) => match rb_u {
                 RedBankEmergencyUpdate::DisableBorrowing(denom) => {
@@ -80,7 +97,18 @@ pub fn execute(
                     set_zero_deposit_cap(deps, info, &v)
                 }
             },
+            EmergencyUpdate::Perps(p_u) => match p_u {
+                PerpsEmergencyUpdate::DisableTrading(denom) => {
+                    disable_perp_trading(deps, info, &denom)
+                }
+            },
         },
+        ExecuteMsg::ExecutePendingUpdate {
+            id,
+        } => execute_pending_update(deps, env, id),
+        ExecuteMsg::CancelPendingUpdate {
+            id,
+        } => cancel_pending_update(deps, info, id),
     }
 }
 
@@ -91,11 +119,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::AssetParams {
             denom,
-        } => to_json_binary(&ASSET_PARAMS.may_load(deps.storage, &denom)?),
+        } => to_json_binary(&query_asset_params(deps, &env, &denom)?),
         QueryMsg::AllAssetParams {
             start_after,
             limit,
-        } => to_json_binary(&query_all_asset_params(deps, start_after, limit)?),
+        } => to_json_binary(&query_all_asset_params(deps, &env, start_after, limit)?),
+        QueryMsg::LtvRamp {
+            denom,
+        } => to_json_binary(&LTV_RAMPS.may_load(deps.storage, &denom)?),
         QueryMsg::VaultConfig {
             address,
         } => to_json_binary(&query_vault_config(deps, &address)?),
@@ -107,6 +138,13 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             start_after,
             limit,
         } => to_json_binary(&query_all_vault_configs_v2(deps, start_after, limit)?),
+        QueryMsg::PerpParams {
+            denom,
+        } => to_json_binary(&PERP_PARAMS.load(deps.storage, &denom)?),
+        QueryMsg::AllPerpParams {
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_perp_params(deps, start_after, limit)?),
         QueryMsg::TargetHealthFactor {} => {
             to_json_binary(&TARGET_HEALTH_FACTOR.load(deps.storage)?)
         }
@@ -117,11 +155,33 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             start_after,
             limit,
         } => to_json_binary(&query_all_total_deposits_v2(deps, start_after, limit)?),
+        QueryMsg::TotalBorrow {
+            denom,
+        } => to_json_binary(&query_total_borrow(deps, &env, denom)?),
+        QueryMsg::AllTotalBorrows {
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_total_borrows(deps, start_after, limit)?),
+        QueryMsg::PendingUpdates {
+            start_after,
+            limit,
+        } => to_json_binary(&query_pending_updates(deps, start_after, limit)?),
     };
     res.map_err(Into::into)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    migrations::v2_1_0::migrate(deps)
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    let from_version = get_contract_version(deps.storage)?.version;
+
+    // Contracts on v2.1.0 only need the latest step, older ones go through v2.1.0 first
+    if from_version != "2.1.0" {
+        migrations::v2_1_0::migrate(deps.branch())?;
+    }
+    migrations::v2_2_0::migrate(deps)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
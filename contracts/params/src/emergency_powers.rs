@@ -2,7 +2,8 @@ use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response, Uint128};
 
 use crate::{
     error::ContractError,
-    state::{ASSET_PARAMS, OWNER, VAULT_CONFIGS},
+    execute::update_market_msg,
+    state::{ASSET_PARAMS, OWNER, PERP_PARAMS, VAULT_CONFIGS},
 };
 
 pub fn disable_borrowing(
@@ -80,3 +81,22 @@ pub fn set_zero_deposit_cap(
 
     Ok(response)
 }
+
+pub fn disable_perp_trading(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: &str,
+) -> Result<Response, ContractError> {
+    OWNER.assert_emergency_owner(deps.storage, &info.sender)?;
+
+    let mut params = PERP_PARAMS.load(deps.storage, denom)?;
+    params.enabled = false;
+    PERP_PARAMS.save(deps.storage, denom, &params)?;
+
+    let response = Response::new()
+        .add_message(update_market_msg(deps.as_ref(), params)?)
+        .add_attribute("action", "emergency_disable_perp_trading")
+        .add_attribute("denom", denom.to_string());
+
+    Ok(response)
+}
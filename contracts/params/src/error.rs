@@ -26,4 +26,20 @@ pub enum ContractError {
 
     #[error("{0}")]
     Version(#[from] VersionError),
+
+    #[error("Max perp params reached: {max}")]
+    MaxPerpParamsReached {
+        max: u8,
+    },
+
+    #[error("Pending update {id} not found")]
+    PendingUpdateNotFound {
+        id: u64,
+    },
+
+    #[error("Pending update {id} can't be executed before {eta}")]
+    PendingUpdateNotReady {
+        id: u64,
+        eta: u64,
+    },
 }
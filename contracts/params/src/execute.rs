@@ -1,19 +1,40 @@
-use cosmwasm_std::{Decimal, DepsMut, MessageInfo, Response};
-use mars_types::params::{AssetParamsUpdate, VaultConfigUpdate};
+use cosmwasm_std::{
+    to_json_binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Order, Response,
+    StdResult, WasmMsg,
+};
+use mars_types::{
+    address_provider::{self, MarsAddressType},
+    params::{
+        AssetParamsUpdate, PendingUpdateKind, PerpParams, PerpParamsUpdate, VaultConfigUpdate,
+    },
+    perps,
+};
 use mars_utils::{error::ValidationError, helpers::option_string_to_addr};
 
 use crate::{
     error::{ContractError, ContractResult},
-    state::{ADDRESS_PROVIDER, ASSET_PARAMS, OWNER, TARGET_HEALTH_FACTOR, VAULT_CONFIGS},
+    ltv_ramp::{ramp_down_ltv, remove_replaced_ltv_ramp},
+    state::{
+        ADDRESS_PROVIDER, ASSET_PARAMS, MAX_PERP_PARAMS, OWNER, PERP_PARAMS, RISK_MANAGER,
+        TARGET_HEALTH_FACTOR, UPDATE_DELAY, VAULT_CONFIGS,
+    },
+    timelock::{
+        apply_timelocked_asset_params, apply_timelocked_vault_config, has_timelocked_asset_changes,
+        has_timelocked_vault_changes, schedule_update,
+    },
 };
 
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// Increasing the update delay takes effect immediately, while lowering it is scheduled behind the
+/// current delay so that it can't be used to skip the time lock.
 pub fn update_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     address_provider: Option<String>,
+    update_delay: Option<u64>,
 ) -> Result<Response, ContractError> {
     OWNER.assert_owner(deps.storage, &info.sender)?;
 
@@ -21,9 +42,34 @@ pub fn update_config(
     let updated_addr = option_string_to_addr(deps.api, address_provider, current_addr)?;
     ADDRESS_PROVIDER.save(deps.storage, &updated_addr)?;
 
-    Ok(Response::new()
+    let mut response = Response::new()
         .add_attribute("action", "update_config")
-        .add_attribute("address_provider", updated_addr.to_string()))
+        .add_attribute("address_provider", updated_addr.to_string());
+
+    let current_delay = UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default();
+    match update_delay {
+        Some(delay) if delay < current_delay => {
+            let pending = schedule_update(
+                deps.storage,
+                &env,
+                current_delay,
+                PendingUpdateKind::UpdateDelay(delay),
+            )?;
+            response = response
+                .add_attribute("update_delay", current_delay.to_string())
+                .add_attribute("pending_update_id", pending.id.to_string())
+                .add_attribute("eta", pending.eta.to_string());
+        }
+        Some(delay) => {
+            UPDATE_DELAY.save(deps.storage, &delay)?;
+            response = response.add_attribute("update_delay", delay.to_string());
+        }
+        None => {
+            response = response.add_attribute("update_delay", current_delay.to_string());
+        }
+    }
+
+    Ok(response)
 }
 
 pub fn update_target_health_factor(
@@ -43,8 +89,13 @@ pub fn update_target_health_factor(
     Ok(response)
 }
 
+/// New assets are listed immediately. Changes to the max LTV, liquidation threshold, liquidation
+/// bonus or caps of a listed asset are scheduled if an update delay is set, while the other fields
+/// are updated immediately. Lowering the max LTV and liquidation threshold gradually is done with
+/// a ramp-down instead.
 pub fn update_asset_params(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     update: AssetParamsUpdate,
 ) -> ContractResult<Response> {
@@ -57,19 +108,63 @@ pub fn update_asset_params(
             params: unchecked,
         } => {
             let params = unchecked.check(deps.api)?;
+            let update_delay = UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default();
 
-            ASSET_PARAMS.save(deps.storage, &params.denom, &params)?;
-            response = response
-                .add_attribute("action_type", "add_or_update")
-                .add_attribute("denom", params.denom);
+            match ASSET_PARAMS.may_load(deps.storage, &params.denom)? {
+                Some(current)
+                    if update_delay > 0 && has_timelocked_asset_changes(&current, &params) =>
+                {
+                    let immediate = apply_timelocked_asset_params(params.clone(), &current);
+                    ASSET_PARAMS.save(deps.storage, &params.denom, &immediate)?;
+
+                    let pending = schedule_update(
+                        deps.storage,
+                        &env,
+                        update_delay,
+                        PendingUpdateKind::AssetParams(params.clone()),
+                    )?;
+                    response = response
+                        .add_attribute("action_type", "schedule_update")
+                        .add_attribute("denom", params.denom)
+                        .add_attribute("pending_update_id", pending.id.to_string())
+                        .add_attribute("eta", pending.eta.to_string());
+                }
+                current_opt => {
+                    if let Some(current) = current_opt {
+                        remove_replaced_ltv_ramp(deps.storage, &current, &params);
+                    }
+                    ASSET_PARAMS.save(deps.storage, &params.denom, &params)?;
+                    response = response
+                        .add_attribute("action_type", "add_or_update")
+                        .add_attribute("denom", params.denom);
+                }
+            }
+        }
+        AssetParamsUpdate::RampDown {
+            denom,
+            max_loan_to_value,
+            liquidation_threshold,
+            end_time,
+        } => {
+            return ramp_down_ltv(
+                deps,
+                env,
+                denom,
+                max_loan_to_value,
+                liquidation_threshold,
+                end_time,
+            );
         }
     }
 
     Ok(response)
 }
 
+/// Same as for asset params, changes to the max LTV, liquidation threshold or deposit cap of a
+/// listed vault are scheduled if an update delay is set.
 pub fn update_vault_config(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     update: VaultConfigUpdate,
 ) -> ContractResult<Response> {
@@ -82,16 +177,99 @@ pub fn update_vault_config(
             config,
         } => {
             let checked = config.check(deps.api)?;
-            VAULT_CONFIGS.save(deps.storage, &checked.addr, &checked)?;
+            let update_delay = UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default();
+
+            match VAULT_CONFIGS.may_load(deps.storage, &checked.addr)? {
+                Some(current)
+                    if update_delay > 0 && has_timelocked_vault_changes(&current, &checked) =>
+                {
+                    let immediate = apply_timelocked_vault_config(checked.clone(), &current);
+                    VAULT_CONFIGS.save(deps.storage, &checked.addr, &immediate)?;
+
+                    let pending = schedule_update(
+                        deps.storage,
+                        &env,
+                        update_delay,
+                        PendingUpdateKind::VaultConfig(checked.clone()),
+                    )?;
+                    response = response
+                        .add_attribute("action_type", "schedule_update")
+                        .add_attribute("addr", checked.addr)
+                        .add_attribute("pending_update_id", pending.id.to_string())
+                        .add_attribute("eta", pending.eta.to_string());
+                }
+                _ => {
+                    VAULT_CONFIGS.save(deps.storage, &checked.addr, &checked)?;
+                    response = response
+                        .add_attribute("action_type", "add_or_update")
+                        .add_attribute("addr", checked.addr);
+                }
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+/// Perp params can be updated by the owner or the risk manager
+pub fn update_perp_params(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: PerpParamsUpdate,
+) -> ContractResult<Response> {
+    let is_risk_manager = RISK_MANAGER.may_load(deps.storage)?.is_some_and(|rm| rm == info.sender);
+    if !is_risk_manager {
+        OWNER.assert_owner(deps.storage, &info.sender)?;
+    }
+
+    let mut response = Response::new().add_attribute("action", "update_perp_param");
+
+    match update {
+        PerpParamsUpdate::AddOrUpdate {
+            params,
+        } => {
+            params.validate()?;
+
+            if !PERP_PARAMS.has(deps.storage, &params.denom) {
+                let max = MAX_PERP_PARAMS.load(deps.storage)?;
+                let count = PERP_PARAMS.keys(deps.storage, None, None, Order::Ascending).count();
+                if count >= max as usize {
+                    return Err(ContractError::MaxPerpParamsReached {
+                        max,
+                    });
+                }
+            }
+
+            PERP_PARAMS.save(deps.storage, &params.denom, &params)?;
+
             response = response
+                .add_message(update_market_msg(deps.as_ref(), params.clone())?)
                 .add_attribute("action_type", "add_or_update")
-                .add_attribute("addr", checked.addr);
+                .add_attribute("denom", params.denom);
         }
     }
 
     Ok(response)
 }
 
+/// Forward the perp params to the perps contract so it can create or update the market
+pub fn update_market_msg(deps: Deps, params: PerpParams) -> StdResult<CosmosMsg> {
+    let address_provider_addr = ADDRESS_PROVIDER.load(deps.storage)?;
+    let perps_addr = address_provider::helpers::query_contract_addr(
+        deps,
+        &address_provider_addr,
+        MarsAddressType::Perps,
+    )?;
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: perps_addr.to_string(),
+        msg: to_json_binary(&perps::ExecuteMsg::UpdateMarket {
+            params,
+        })?,
+        funds: vec![],
+    }))
+}
+
 pub fn assert_thf(thf: Decimal) -> Result<(), ContractError> {
     if thf < Decimal::one() || thf > Decimal::from_atomics(2u128, 0u32)? {
         return Err(ValidationError::InvalidParam {
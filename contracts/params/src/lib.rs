@@ -2,6 +2,8 @@ pub mod contract;
 pub mod emergency_powers;
 pub mod error;
 pub mod execute;
+pub mod ltv_ramp;
 pub mod migrations;
 pub mod query;
 pub mod state;
+pub mod timelock;
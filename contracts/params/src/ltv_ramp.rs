@@ -0,0 +1,82 @@
+use cosmwasm_std::{Decimal, DepsMut, Env, Response, Storage};
+use mars_types::params::{AssetParams, LtvRamp};
+use mars_utils::error::ValidationError;
+
+use crate::{
+    error::ContractResult,
+    state::{ASSET_PARAMS, LTV_RAMPS, UPDATE_DELAY},
+};
+
+/// Start lowering the max LTV and liquidation threshold of a listed asset.
+///
+/// The stored params hold the targets straight away, the ramp only overrides them with the
+/// interpolated values in queries until `end_time`. A ramp can't be shorter than the update delay,
+/// otherwise it could be used to bypass the time lock.
+pub fn ramp_down_ltv(
+    deps: DepsMut,
+    env: Env,
+    denom: String,
+    max_loan_to_value: Decimal,
+    liquidation_threshold: Decimal,
+    end_time: u64,
+) -> ContractResult<Response> {
+    let current_time = env.block.time.seconds();
+
+    let mut params = ASSET_PARAMS.load(deps.storage, &denom)?;
+    apply_ltv_ramp(deps.storage, &mut params, current_time)?;
+
+    let update_delay = UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default();
+    let min_end_time = current_time + update_delay;
+    if end_time < min_end_time {
+        return Err(ValidationError::InvalidParam {
+            param_name: "end_time".to_string(),
+            invalid_value: end_time.to_string(),
+            predicate: format!(">= {min_end_time} (current time + update delay)"),
+        }
+        .into());
+    }
+
+    let ramp = LtvRamp {
+        start_time: current_time,
+        end_time,
+        start_max_loan_to_value: params.max_loan_to_value,
+        start_liquidation_threshold: params.liquidation_threshold,
+        target_max_loan_to_value: max_loan_to_value,
+        target_liquidation_threshold: liquidation_threshold,
+    };
+    ramp.validate()?;
+
+    params.max_loan_to_value = max_loan_to_value;
+    params.liquidation_threshold = liquidation_threshold;
+    ASSET_PARAMS.save(deps.storage, &denom, &params)?;
+    LTV_RAMPS.save(deps.storage, &denom, &ramp)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_asset_param")
+        .add_attribute("action_type", "ramp_down")
+        .add_attribute("denom", denom)
+        .add_attribute("max_loan_to_value", max_loan_to_value.to_string())
+        .add_attribute("liquidation_threshold", liquidation_threshold.to_string())
+        .add_attribute("end_time", end_time.to_string()))
+}
+
+/// Replace the max LTV and liquidation threshold with the current values of the asset's ramp, if any
+pub fn apply_ltv_ramp(
+    store: &dyn Storage,
+    params: &mut AssetParams,
+    current_time: u64,
+) -> ContractResult<()> {
+    if let Some(ramp) = LTV_RAMPS.may_load(store, &params.denom)? {
+        ramp.apply(params, current_time);
+    }
+    Ok(())
+}
+
+/// An explicit change of the max LTV or liquidation threshold ends an ongoing ramp
+pub fn remove_replaced_ltv_ramp(store: &mut dyn Storage, current: &AssetParams, new: &AssetParams) {
+    if current.max_loan_to_value != new.max_loan_to_value
+        || current.liquidation_threshold != new.liquidation_threshold
+    {
+        LTV_RAMPS.remove(store, &new.denom);
+    }
+}
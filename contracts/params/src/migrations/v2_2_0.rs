@@ -0,0 +1,26 @@
+use cosmwasm_std::DepsMut;
+use cw2::{assert_contract_version, set_contract_version};
+
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    error::ContractError,
+    state::MAX_PERP_PARAMS,
+};
+
+const FROM_VERSION: &str = "2.1.0";
+
+/// Max number of perps that can be created, set for contracts instantiated before perps existed
+const DEFAULT_MAX_PERP_PARAMS: u8 = 40;
+
+pub fn migrate(deps: DepsMut) -> Result<(), ContractError> {
+    // make sure we're migrating the correct contract and from the correct version
+    assert_contract_version(deps.storage, &format!("crates.io:{CONTRACT_NAME}"), FROM_VERSION)?;
+
+    if MAX_PERP_PARAMS.may_load(deps.storage)?.is_none() {
+        MAX_PERP_PARAMS.save(deps.storage, &DEFAULT_MAX_PERP_PARAMS)?;
+    }
+
+    set_contract_version(deps.storage, format!("crates.io:{CONTRACT_NAME}"), CONTRACT_VERSION)?;
+
+    Ok(())
+}
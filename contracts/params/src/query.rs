@@ -1,16 +1,23 @@
 use cosmwasm_std::{Addr, Deps, Env, Order, StdResult, Uint128};
 use cw_paginate::{paginate_map_query, PaginationResponse};
 use cw_storage_plus::Bound;
-use mars_interest_rate::get_underlying_liquidity_amount;
+use mars_interest_rate::{get_underlying_debt_amount, get_underlying_liquidity_amount};
 use mars_types::{
     address_provider::{self, helpers::query_contract_addrs, MarsAddressType},
-    params::{AssetParams, ConfigResponse, TotalDepositResponse, VaultConfig},
+    params::{
+        AssetParams, ConfigResponse, PendingUpdate, PerpParams, TotalBorrowResponse,
+        TotalDepositResponse, VaultConfig,
+    },
     red_bank::{self, Market, MarketV2Response},
 };
 
 use crate::{
     error::{ContractError, ContractResult},
-    state::{ADDRESS_PROVIDER, ASSET_PARAMS, VAULT_CONFIGS},
+    ltv_ramp::apply_ltv_ramp,
+    state::{
+        ADDRESS_PROVIDER, ASSET_PARAMS, PENDING_UPDATES, PERP_PARAMS, RISK_MANAGER, UPDATE_DELAY,
+        VAULT_CONFIGS,
+    },
 };
 
 pub const DEFAULT_LIMIT: u32 = 10;
@@ -19,17 +26,51 @@ pub const MAX_LIMIT: u32 = 30;
 pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse {
         address_provider: ADDRESS_PROVIDER.load(deps.storage)?.to_string(),
+        risk_manager: RISK_MANAGER.may_load(deps.storage)?.map(|addr| addr.to_string()),
+        update_delay: UPDATE_DELAY.may_load(deps.storage)?.unwrap_or_default(),
     })
 }
 
+/// Max LTV and liquidation threshold are the current values of an ongoing ramp-down, if any
+pub fn query_asset_params(
+    deps: Deps,
+    env: &Env,
+    denom: &str,
+) -> ContractResult<Option<AssetParams>> {
+    let Some(mut params) = ASSET_PARAMS.may_load(deps.storage, denom)? else {
+        return Ok(None);
+    };
+    apply_ltv_ramp(deps.storage, &mut params, env.block.time.seconds())?;
+    Ok(Some(params))
+}
+
 pub fn query_all_asset_params(
     deps: Deps,
+    env: &Env,
     start_after: Option<String>,
     limit: Option<u32>,
-) -> StdResult<Vec<AssetParams>> {
+) -> ContractResult<Vec<AssetParams>> {
     let start = start_after.as_ref().map(|denom| Bound::exclusive(denom.as_str()));
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     ASSET_PARAMS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| {
+            let (_, mut params) = res?;
+            apply_ltv_ramp(deps.storage, &mut params, env.block.time.seconds())?;
+            Ok(params)
+        })
+        .collect()
+}
+
+pub fn query_all_perp_params(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PerpParams>> {
+    let start = start_after.as_ref().map(|denom| Bound::exclusive(denom.as_str()));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    PERP_PARAMS
         .range(deps.storage, start, None, Order::Ascending)
         .take(limit)
         .map(|res| Ok(res?.1))
@@ -85,6 +126,19 @@ pub fn query_all_vault_configs_v2(
     })
 }
 
+pub fn query_pending_updates(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<PendingUpdate>> {
+    let start = start_after.map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    paginate_map_query(&PENDING_UPDATES, deps.storage, start, Some(limit), |_id, pending| {
+        Ok::<PendingUpdate, ContractError>(pending)
+    })
+}
+
 /// Query and compute the total deposited amount of the given asset across Red
 /// Bank (RB) and Credit Manager (CM).
 ///
@@ -238,6 +292,82 @@ pub fn query_all_total_deposits_v2(
     })
 }
 
+/// Credit Manager borrows from Red Bank, so the Red Bank market debt covers the amount borrowed
+/// through both products.
+pub fn query_total_borrow(deps: Deps, env: &Env, denom: String) -> StdResult<TotalBorrowResponse> {
+    let current_timestamp = env.block.time.seconds();
+
+    let address_provider_addr = ADDRESS_PROVIDER.load(deps.storage)?;
+    let red_bank_addr = address_provider::helpers::query_contract_addr(
+        deps,
+        &address_provider_addr,
+        MarsAddressType::RedBank,
+    )?;
+
+    // if the market doesn't exist on RB, we default to zero
+    let amount = deps
+        .querier
+        .query_wasm_smart::<Option<Market>>(
+            red_bank_addr,
+            &red_bank::QueryMsg::Market {
+                denom: denom.clone(),
+            },
+        )?
+        .map(|market| {
+            get_underlying_debt_amount(market.debt_total_scaled, &market, current_timestamp)
+        })
+        .transpose()?
+        .unwrap_or_else(Uint128::zero);
+
+    let asset_params = ASSET_PARAMS.load(deps.storage, &denom)?;
+
+    Ok(TotalBorrowResponse {
+        denom,
+        amount,
+        cap: asset_params.borrow_cap,
+    })
+}
+
+pub fn query_all_total_borrows(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<TotalBorrowResponse>> {
+    let address_provider_addr = ADDRESS_PROVIDER.load(deps.storage)?;
+    let red_bank_addr = address_provider::helpers::query_contract_addr(
+        deps,
+        &address_provider_addr,
+        MarsAddressType::RedBank,
+    )?;
+
+    let rb_markets = deps.querier.query_wasm_smart::<PaginationResponse<MarketV2Response>>(
+        red_bank_addr,
+        &red_bank::QueryMsg::MarketsV2 {
+            start_after,
+            limit,
+        },
+    )?;
+
+    let total_borrows = rb_markets
+        .data
+        .into_iter()
+        .map(|market| {
+            let denom = market.market.denom;
+            let asset_params = ASSET_PARAMS.load(deps.storage, &denom)?;
+            Ok(TotalBorrowResponse {
+                denom,
+                amount: market.debt_total_amount,
+                cap: asset_params.borrow_cap,
+            })
+        })
+        .collect::<StdResult<Vec<TotalBorrowResponse>>>()?;
+
+    Ok(PaginationResponse {
+        data: total_borrows,
+        metadata: rb_markets.metadata,
+    })
+}
+
 fn query_astro_incentives_deposit(
     deps: Deps,
     denom: &str,
@@ -1,10 +1,17 @@
 use cosmwasm_std::{Addr, Decimal};
 use cw_storage_plus::{Item, Map};
 use mars_owner::Owner;
-use mars_types::params::{AssetParams, VaultConfig};
+use mars_types::params::{AssetParams, LtvRamp, PendingUpdate, PerpParams, VaultConfig};
 
 pub const OWNER: Owner = Owner::new("owner");
+pub const RISK_MANAGER: Item<Addr> = Item::new("risk_manager");
 pub const ADDRESS_PROVIDER: Item<Addr> = Item::new("address_provider");
 pub const ASSET_PARAMS: Map<&str, AssetParams> = Map::new("asset_params");
+pub const LTV_RAMPS: Map<&str, LtvRamp> = Map::new("ltv_ramps");
 pub const VAULT_CONFIGS: Map<&Addr, VaultConfig> = Map::new("vault_configs");
 pub const TARGET_HEALTH_FACTOR: Item<Decimal> = Item::new("target_health_factor");
+pub const PERP_PARAMS: Map<&str, PerpParams> = Map::new("perp_params");
+pub const MAX_PERP_PARAMS: Item<u8> = Item::new("max_perp_params");
+pub const UPDATE_DELAY: Item<u64> = Item::new("update_delay");
+pub const PENDING_UPDATES: Map<u64, PendingUpdate> = Map::new("pending_updates");
+pub const NEXT_PENDING_UPDATE_ID: Item<u64> = Item::new("next_pending_update_id");
@@ -0,0 +1,136 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Storage};
+use mars_types::params::{
+    AssetParams, AssetParamsUnchecked, PendingUpdate, PendingUpdateKind, VaultConfig,
+    VaultConfigUnchecked,
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    ltv_ramp::remove_replaced_ltv_ramp,
+    state::{
+        ASSET_PARAMS, NEXT_PENDING_UPDATE_ID, OWNER, PENDING_UPDATES, UPDATE_DELAY, VAULT_CONFIGS,
+    },
+};
+
+/// Whether the update changes any of the time-locked asset params
+pub fn has_timelocked_asset_changes(current: &AssetParams, new: &AssetParams) -> bool {
+    current.max_loan_to_value != new.max_loan_to_value
+        || current.liquidation_threshold != new.liquidation_threshold
+        || current.liquidation_bonus != new.liquidation_bonus
+        || current.deposit_cap != new.deposit_cap
+        || current.borrow_cap != new.borrow_cap
+}
+
+/// Whether the update changes any of the time-locked vault config fields
+pub fn has_timelocked_vault_changes(current: &VaultConfig, new: &VaultConfig) -> bool {
+    current.max_loan_to_value != new.max_loan_to_value
+        || current.liquidation_threshold != new.liquidation_threshold
+        || current.deposit_cap != new.deposit_cap
+}
+
+/// Copy the time-locked asset params from `from` into `into`
+pub fn apply_timelocked_asset_params(into: AssetParams, from: &AssetParams) -> AssetParams {
+    AssetParams {
+        max_loan_to_value: from.max_loan_to_value,
+        liquidation_threshold: from.liquidation_threshold,
+        liquidation_bonus: from.liquidation_bonus.clone(),
+        deposit_cap: from.deposit_cap,
+        borrow_cap: from.borrow_cap,
+        ..into
+    }
+}
+
+/// Copy the time-locked vault config fields from `from` into `into`
+pub fn apply_timelocked_vault_config(into: VaultConfig, from: &VaultConfig) -> VaultConfig {
+    VaultConfig {
+        max_loan_to_value: from.max_loan_to_value,
+        liquidation_threshold: from.liquidation_threshold,
+        deposit_cap: from.deposit_cap.clone(),
+        ..into
+    }
+}
+
+pub fn schedule_update(
+    store: &mut dyn Storage,
+    env: &Env,
+    update_delay: u64,
+    update: PendingUpdateKind,
+) -> ContractResult<PendingUpdate> {
+    let id = NEXT_PENDING_UPDATE_ID.may_load(store)?.unwrap_or(1);
+    NEXT_PENDING_UPDATE_ID.save(store, &(id + 1))?;
+
+    let pending = PendingUpdate {
+        id,
+        eta: env.block.time.seconds() + update_delay,
+        update,
+    };
+    PENDING_UPDATES.save(store, id, &pending)?;
+
+    Ok(pending)
+}
+
+/// Apply the time-locked fields of a pending update on top of the current params. The other fields
+/// could have been changed in the meantime (e.g. by an emergency update) and are kept as they are.
+pub fn execute_pending_update(deps: DepsMut, env: Env, id: u64) -> ContractResult<Response> {
+    let pending = PENDING_UPDATES.may_load(deps.storage, id)?.ok_or(
+        ContractError::PendingUpdateNotFound {
+            id,
+        },
+    )?;
+
+    if env.block.time.seconds() < pending.eta {
+        return Err(ContractError::PendingUpdateNotReady {
+            id,
+            eta: pending.eta,
+        });
+    }
+
+    PENDING_UPDATES.remove(deps.storage, id);
+
+    let mut response = Response::new()
+        .add_attribute("action", "execute_pending_update")
+        .add_attribute("id", id.to_string());
+
+    match pending.update {
+        PendingUpdateKind::AssetParams(params) => {
+            let current = ASSET_PARAMS.load(deps.storage, &params.denom)?;
+            let updated = apply_timelocked_asset_params(current.clone(), &params);
+            let updated = AssetParamsUnchecked::from(updated).check(deps.api)?;
+            remove_replaced_ltv_ramp(deps.storage, &current, &updated);
+            ASSET_PARAMS.save(deps.storage, &updated.denom, &updated)?;
+            response = response.add_attribute("denom", updated.denom);
+        }
+        PendingUpdateKind::VaultConfig(config) => {
+            let current = VAULT_CONFIGS.load(deps.storage, &config.addr)?;
+            let updated = apply_timelocked_vault_config(current, &config);
+            let updated = VaultConfigUnchecked::from(updated).check(deps.api)?;
+            VAULT_CONFIGS.save(deps.storage, &updated.addr, &updated)?;
+            response = response.add_attribute("addr", updated.addr);
+        }
+        PendingUpdateKind::UpdateDelay(delay) => {
+            UPDATE_DELAY.save(deps.storage, &delay)?;
+            response = response.add_attribute("update_delay", delay.to_string());
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn cancel_pending_update(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> ContractResult<Response> {
+    OWNER.assert_owner(deps.storage, &info.sender)?;
+
+    if !PENDING_UPDATES.has(deps.storage, id) {
+        return Err(ContractError::PendingUpdateNotFound {
+            id,
+        });
+    }
+    PENDING_UPDATES.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_pending_update")
+        .add_attribute("id", id.to_string()))
+}
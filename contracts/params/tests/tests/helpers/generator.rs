@@ -15,6 +15,7 @@ pub fn default_asset_params(denom: &str) -> AssetParamsUnchecked {
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: false,
+            isolation: None,
         },
         max_loan_to_value: Decimal::from_str("0.6").unwrap(),
         liquidation_threshold: Decimal::from_str("0.7").unwrap(),
@@ -26,6 +27,7 @@ pub fn default_asset_params(denom: &str) -> AssetParamsUnchecked {
         },
         protocol_liquidation_fee: Decimal::percent(2),
         deposit_cap: Uint128::new(1_000_000_000),
+        borrow_cap: Uint128::new(1_000_000_000),
     }
 }
 
@@ -7,7 +7,7 @@ use cw_paginate::PaginationResponse;
 use mars_owner::{OwnerResponse, OwnerUpdate};
 use mars_types::params::{
     AssetParams, AssetParamsUpdate, ConfigResponse, EmergencyUpdate, ExecuteMsg, InstantiateMsg,
-    QueryMsg, VaultConfig, VaultConfigUpdate,
+    LtvRamp, PendingUpdate, QueryMsg, VaultConfig, VaultConfigUpdate,
 };
 
 use super::contracts::mock_params_contract;
@@ -21,6 +21,7 @@ pub struct MockEnvBuilder {
     pub app: BasicApp,
     pub target_health_factor: Option<Decimal>,
     pub emergency_owner: Option<String>,
+    pub risk_manager: Option<String>,
 }
 
 #[allow(clippy::new_ret_no_self)]
@@ -30,6 +31,7 @@ impl MockEnv {
             app: App::default(),
             target_health_factor: None,
             emergency_owner: None,
+            risk_manager: None,
         }
     }
 
@@ -95,11 +97,53 @@ impl MockEnv {
             self.params_contract.clone(),
             &ExecuteMsg::UpdateConfig {
                 address_provider,
+                update_delay: None,
             },
             &[],
         )
     }
 
+    pub fn update_delay(&mut self, sender: &Addr, update_delay: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.params_contract.clone(),
+            &ExecuteMsg::UpdateConfig {
+                address_provider: None,
+                update_delay: Some(update_delay),
+            },
+            &[],
+        )
+    }
+
+    pub fn execute_pending_update(&mut self, sender: &Addr, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.params_contract.clone(),
+            &ExecuteMsg::ExecutePendingUpdate {
+                id,
+            },
+            &[],
+        )
+    }
+
+    pub fn cancel_pending_update(&mut self, sender: &Addr, id: u64) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.params_contract.clone(),
+            &ExecuteMsg::CancelPendingUpdate {
+                id,
+            },
+            &[],
+        )
+    }
+
+    pub fn increment_by_time(&mut self, seconds: u64) {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(seconds);
+            block.height += 1;
+        });
+    }
+
     pub fn emergency_update(
         &mut self,
         sender: &Addr,
@@ -155,6 +199,18 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_ltv_ramp(&self, denom: &str) -> Option<LtvRamp> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.params_contract.clone(),
+                &QueryMsg::LtvRamp {
+                    denom: denom.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_vault_config(&self, addr: &str) -> VaultConfig {
         self.app
             .wrap()
@@ -208,6 +264,23 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_pending_updates(
+        &self,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> PaginationResponse<PendingUpdate> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.params_contract.clone(),
+                &QueryMsg::PendingUpdates {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_config(&self) -> ConfigResponse {
         self.app
             .wrap()
@@ -225,8 +298,10 @@ impl MockEnvBuilder {
             Addr::unchecked("owner"),
             &InstantiateMsg {
                 owner: "owner".to_string(),
+                risk_manager: self.risk_manager.clone(),
                 address_provider: "address_provider".to_string(),
                 target_health_factor: self.get_target_health_factor(),
+                max_perp_params: 40,
             },
             &[],
             "mock-params-contract",
@@ -276,4 +351,9 @@ impl MockEnvBuilder {
         self.emergency_owner = Some(eo.to_string());
         self
     }
+
+    pub fn risk_manager(&mut self, rm: &str) -> &mut Self {
+        self.risk_manager = Some(rm.to_string());
+        self
+    }
 }
@@ -2,10 +2,14 @@ mod helpers;
 
 mod test_all_total_deposits_v2;
 mod test_asset_validation;
+mod test_borrow_cap;
 mod test_deposit_cap;
 mod test_emergency_powers;
+mod test_ltv_ramp;
 mod test_migration_v2;
+mod test_migration_v2_2_0;
 mod test_owner;
+mod test_pending_updates;
 mod test_query_all_vault_configs_v2;
 mod test_target_health_factor;
 mod test_update_asset_params;
@@ -1,10 +1,10 @@
 use std::str::FromStr;
 
-use cosmwasm_std::Decimal;
+use cosmwasm_std::{Decimal, Uint128};
 use mars_params::error::ContractError;
 use mars_types::{
     error::MarsError::Validation,
-    params::{AssetParamsUpdate, HlsAssetType, HlsParamsUnchecked},
+    params::{AssetParamsUpdate, HlsAssetType, HlsParamsUnchecked, IsolationParams},
 };
 use mars_utils::error::ValidationError::{InvalidDenom, InvalidParam};
 
@@ -200,6 +200,54 @@ fn correlations_must_be_valid_denoms() {
     );
 }
 
+#[test]
+fn isolation_borrowable_denoms_not_empty() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let mut params = default_asset_params("denom_xyz");
+    params.red_bank.isolation = Some(IsolationParams {
+        borrowable_denoms: vec![],
+        debt_ceiling: Uint128::new(1_000_000),
+    });
+
+    let res = mock.update_asset_params(
+        &mock.query_owner(),
+        AssetParamsUpdate::AddOrUpdate {
+            params,
+        },
+    );
+    assert_err(
+        res,
+        ContractError::Mars(Validation(InvalidParam {
+            param_name: "borrowable_denoms".to_string(),
+            invalid_value: "[]".to_string(),
+            predicate: "not empty".to_string(),
+        })),
+    );
+}
+
+#[test]
+fn isolation_borrowable_denoms_must_be_valid_denoms() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let mut params = default_asset_params("denom_xyz");
+    params.red_bank.isolation = Some(IsolationParams {
+        borrowable_denoms: vec!["uusdc".to_string(), "AA".to_string()],
+        debt_ceiling: Uint128::new(1_000_000),
+    });
+
+    let res = mock.update_asset_params(
+        &mock.query_owner(),
+        AssetParamsUpdate::AddOrUpdate {
+            params,
+        },
+    );
+    assert_err(
+        res,
+        ContractError::Mars(Validation(InvalidDenom {
+            reason: "Invalid denom length".to_string(),
+        })),
+    );
+}
+
 #[test]
 fn protocol_liquidation_fee_less_than_one() {
     let mut mock = MockEnv::new().build().unwrap();
@@ -0,0 +1,66 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_interest_rate::get_underlying_debt_amount;
+use mars_params::{
+    query::query_total_borrow,
+    state::{ADDRESS_PROVIDER, ASSET_PARAMS},
+};
+use mars_testing::{mock_dependencies, mock_env_at_block_time};
+use mars_types::{params::TotalBorrowResponse, red_bank::Market};
+use test_case::test_case;
+
+use super::helpers::default_asset_params;
+
+const MOCK_DENOM: &str = "utoken";
+const TIMESTAMP: u64 = 1690573960;
+
+#[test_case(
+    Market {
+        denom: MOCK_DENOM.into(),
+        debt_total_scaled: Uint128::zero(),
+        borrow_index: Decimal::one(),
+        indexes_last_updated: TIMESTAMP,
+        ..Default::default()
+    };
+    "zero debt"
+)]
+#[test_case(
+    Market {
+        denom: MOCK_DENOM.into(),
+        debt_total_scaled: Uint128::new(442125932248737808),
+        borrow_index: Decimal::from_str("1.038573178004442716").unwrap(),
+        borrow_rate: Decimal::from_str("0.05").unwrap(),
+        indexes_last_updated: 1690573862,
+        ..Default::default()
+    };
+    "debt with accrued interest"
+)]
+fn querying_total_borrow(rb_market: Market) {
+    let mut deps = mock_dependencies(&[]);
+    let env = mock_env_at_block_time(TIMESTAMP);
+
+    let denom = rb_market.denom.clone();
+
+    let params_unchecked = default_asset_params(&denom);
+    let params = params_unchecked.check(deps.as_ref().api).unwrap();
+
+    // setup
+    deps.querier.set_redbank_market(rb_market.clone());
+    ADDRESS_PROVIDER.save(deps.as_mut().storage, &Addr::unchecked("address_provider")).unwrap();
+    ASSET_PARAMS.save(deps.as_mut().storage, &denom, &params).unwrap();
+
+    // Red Bank debt includes Credit Manager borrows
+    let exp_total_borrow =
+        get_underlying_debt_amount(rb_market.debt_total_scaled, &rb_market, TIMESTAMP).unwrap();
+
+    let res = query_total_borrow(deps.as_ref(), &env, denom.clone()).unwrap();
+    assert_eq!(
+        res,
+        TotalBorrowResponse {
+            denom,
+            amount: exp_total_borrow,
+            cap: params.borrow_cap,
+        }
+    );
+}
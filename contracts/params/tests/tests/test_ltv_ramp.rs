@@ -0,0 +1,170 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Decimal};
+use mars_owner::OwnerError;
+use mars_params::error::ContractError;
+use mars_types::params::AssetParamsUpdate;
+use mars_utils::error::ValidationError;
+
+use super::helpers::{assert_err, default_asset_params, MockEnv};
+
+const DURATION: u64 = 1000;
+
+fn setup() -> MockEnv {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: default_asset_params("uatom"),
+        },
+    )
+    .unwrap();
+    mock
+}
+
+fn ramp_down(ltv: &str, lqt: &str, end_time: u64) -> AssetParamsUpdate {
+    AssetParamsUpdate::RampDown {
+        denom: "uatom".to_string(),
+        max_loan_to_value: Decimal::from_str(ltv).unwrap(),
+        liquidation_threshold: Decimal::from_str(lqt).unwrap(),
+        end_time,
+    }
+}
+
+#[test]
+fn only_owner_can_ramp_down() {
+    let mut mock = setup();
+    let end_time = mock.app.block_info().time.seconds() + DURATION;
+
+    let bad_guy = Addr::unchecked("doctor_otto_983");
+    let res = mock.update_asset_params(&bad_guy, ramp_down("0.4", "0.5", end_time));
+    assert_err(res, ContractError::Owner(OwnerError::NotOwner {}));
+}
+
+#[test]
+fn ramp_can_only_lower_values() {
+    let mut mock = setup();
+    let owner = mock.query_owner();
+    let end_time = mock.app.block_info().time.seconds() + DURATION;
+
+    let res = mock.update_asset_params(&owner, ramp_down("0.65", "0.7", end_time));
+    assert_err(
+        res,
+        ContractError::Validation(ValidationError::InvalidParam {
+            param_name: "max_loan_to_value".to_string(),
+            invalid_value: "0.65".to_string(),
+            predicate: "<= 0.6 (current max LTV)".to_string(),
+        }),
+    );
+
+    let res = mock.update_asset_params(&owner, ramp_down("0.5", "0.5", end_time));
+    assert_err(
+        res,
+        ContractError::Validation(ValidationError::InvalidParam {
+            param_name: "liquidation_threshold".to_string(),
+            invalid_value: "0.5".to_string(),
+            predicate: "> 0.5 (max LTV)".to_string(),
+        }),
+    );
+}
+
+#[test]
+fn ramp_cannot_be_shorter_than_update_delay() {
+    let mut mock = setup();
+    let owner = mock.query_owner();
+    mock.update_delay(&owner, DURATION).unwrap();
+    let current_time = mock.app.block_info().time.seconds();
+
+    let res = mock.update_asset_params(&owner, ramp_down("0.4", "0.5", current_time + 10));
+    assert_err(
+        res,
+        ContractError::Validation(ValidationError::InvalidParam {
+            param_name: "end_time".to_string(),
+            invalid_value: (current_time + 10).to_string(),
+            predicate: format!(">= {} (current time + update delay)", current_time + DURATION),
+        }),
+    );
+
+    mock.update_asset_params(&owner, ramp_down("0.4", "0.5", current_time + DURATION)).unwrap();
+}
+
+#[test]
+fn values_are_interpolated_until_end_time() {
+    let mut mock = setup();
+    let owner = mock.query_owner();
+    let end_time = mock.app.block_info().time.seconds() + DURATION;
+
+    mock.update_asset_params(&owner, ramp_down("0.4", "0.5", end_time)).unwrap();
+
+    let params = mock.query_asset_params("uatom");
+    assert_eq!(params.max_loan_to_value, Decimal::from_str("0.6").unwrap());
+    assert_eq!(params.liquidation_threshold, Decimal::from_str("0.7").unwrap());
+
+    mock.increment_by_time(DURATION / 4);
+    let params = mock.query_asset_params("uatom");
+    assert_eq!(params.max_loan_to_value, Decimal::from_str("0.55").unwrap());
+    assert_eq!(params.liquidation_threshold, Decimal::from_str("0.65").unwrap());
+
+    // All asset params query returns the same values
+    let all_params = mock.query_all_asset_params(None, None);
+    assert_eq!(all_params, vec![params]);
+
+    mock.increment_by_time(DURATION);
+    let params = mock.query_asset_params("uatom");
+    assert_eq!(params.max_loan_to_value, Decimal::from_str("0.4").unwrap());
+    assert_eq!(params.liquidation_threshold, Decimal::from_str("0.5").unwrap());
+}
+
+#[test]
+fn new_ramp_starts_from_current_values() {
+    let mut mock = setup();
+    let owner = mock.query_owner();
+    let start_time = mock.app.block_info().time.seconds();
+
+    mock.update_asset_params(&owner, ramp_down("0.4", "0.5", start_time + DURATION)).unwrap();
+    mock.increment_by_time(DURATION / 2);
+
+    mock.update_asset_params(&owner, ramp_down("0.3", "0.4", start_time + 2 * DURATION)).unwrap();
+
+    let ramp = mock.query_ltv_ramp("uatom").unwrap();
+    assert_eq!(ramp.start_time, start_time + DURATION / 2);
+    assert_eq!(ramp.start_max_loan_to_value, Decimal::from_str("0.5").unwrap());
+    assert_eq!(ramp.start_liquidation_threshold, Decimal::from_str("0.6").unwrap());
+}
+
+#[test]
+fn explicit_update_ends_ramp() {
+    let mut mock = setup();
+    let owner = mock.query_owner();
+    let end_time = mock.app.block_info().time.seconds() + DURATION;
+
+    mock.update_asset_params(&owner, ramp_down("0.4", "0.5", end_time)).unwrap();
+
+    // Updating other fields keeps the ramp going
+    let mut params = default_asset_params("uatom");
+    params.max_loan_to_value = Decimal::from_str("0.4").unwrap();
+    params.liquidation_threshold = Decimal::from_str("0.5").unwrap();
+    params.red_bank.borrow_enabled = true;
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: params.clone(),
+        },
+    )
+    .unwrap();
+    assert!(mock.query_ltv_ramp("uatom").is_some());
+
+    // Setting the max LTV directly replaces the ramp
+    params.max_loan_to_value = Decimal::from_str("0.45").unwrap();
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params,
+        },
+    )
+    .unwrap();
+    assert!(mock.query_ltv_ramp("uatom").is_none());
+    let params = mock.query_asset_params("uatom");
+    assert_eq!(params.max_loan_to_value, Decimal::from_str("0.45").unwrap());
+}
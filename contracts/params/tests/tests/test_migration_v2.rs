@@ -1,6 +1,6 @@
 use cosmwasm_std::{attr, testing::mock_env, Empty, Event};
 use cw2::{ContractVersion, VersionError};
-use mars_params::{contract::migrate, error::ContractError};
+use mars_params::{contract::migrate, error::ContractError, state::MAX_PERP_PARAMS};
 use mars_testing::mock_dependencies;
 
 #[test]
@@ -47,12 +47,15 @@ fn successful_migration() {
     assert!(res.data.is_none());
     assert_eq!(
         res.attributes,
-        vec![attr("action", "migrate"), attr("from_version", "2.0.1"), attr("to_version", "2.1.0")]
+        vec![attr("action", "migrate"), attr("from_version", "2.0.1"), attr("to_version", "2.2.0")]
     );
 
+    // Both steps are run in a single migration
     let new_contract_version = ContractVersion {
         contract: "crates.io:mars-params".to_string(),
-        version: "2.1.0".to_string(),
+        version: "2.2.0".to_string(),
     };
     assert_eq!(cw2::get_contract_version(deps.as_ref().storage).unwrap(), new_contract_version);
+
+    assert_eq!(MAX_PERP_PARAMS.load(deps.as_ref().storage).unwrap(), 40);
 }
@@ -0,0 +1,39 @@
+use cosmwasm_std::{attr, testing::mock_env, Empty, Event};
+use cw2::ContractVersion;
+use mars_params::{contract::migrate, state::MAX_PERP_PARAMS};
+use mars_testing::mock_dependencies;
+
+#[test]
+fn successful_migration() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-params", "2.1.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(res.events, vec![] as Vec<Event>);
+    assert!(res.data.is_none());
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "migrate"), attr("from_version", "2.1.0"), attr("to_version", "2.2.0")]
+    );
+
+    let new_contract_version = ContractVersion {
+        contract: "crates.io:mars-params".to_string(),
+        version: "2.2.0".to_string(),
+    };
+    assert_eq!(cw2::get_contract_version(deps.as_ref().storage).unwrap(), new_contract_version);
+
+    assert_eq!(MAX_PERP_PARAMS.load(deps.as_ref().storage).unwrap(), 40);
+}
+
+#[test]
+fn max_perp_params_is_kept_if_already_set() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-params", "2.1.0").unwrap();
+    MAX_PERP_PARAMS.save(deps.as_mut().storage, &5).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    assert_eq!(MAX_PERP_PARAMS.load(deps.as_ref().storage).unwrap(), 5);
+}
@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_owner::OwnerError;
+use mars_params::error::ContractError;
+use mars_types::params::{AssetParamsUpdate, PendingUpdateKind, VaultConfigUpdate};
+
+use super::helpers::{assert_err, default_asset_params, default_vault_config, MockEnv};
+
+const DELAY: u64 = 86400;
+
+#[test]
+fn only_owner_can_set_update_delay() {
+    let mut mock = MockEnv::new().build().unwrap();
+    assert_eq!(mock.query_config().update_delay, 0);
+
+    let bad_guy = Addr::unchecked("doctor_otto_983");
+    let res = mock.update_delay(&bad_guy, DELAY);
+    assert_err(res, ContractError::Owner(OwnerError::NotOwner {}));
+
+    mock.update_delay(&mock.query_owner(), DELAY).unwrap();
+    assert_eq!(mock.query_config().update_delay, DELAY);
+}
+
+#[test]
+fn lowering_update_delay_is_scheduled() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+    mock.update_delay(&owner, DELAY).unwrap();
+
+    // Increasing the delay applies immediately
+    mock.update_delay(&owner, DELAY * 2).unwrap();
+    assert_eq!(mock.query_config().update_delay, DELAY * 2);
+    assert!(mock.query_pending_updates(None, None).data.is_empty());
+
+    // Lowering it has to wait for the current delay
+    mock.update_delay(&owner, 0).unwrap();
+    assert_eq!(mock.query_config().update_delay, DELAY * 2);
+
+    let pending = mock.query_pending_updates(None, None).data;
+    assert_eq!(pending.len(), 1);
+    let pending = pending.first().unwrap();
+    assert_eq!(pending.update, PendingUpdateKind::UpdateDelay(0));
+    assert_eq!(pending.eta, mock.app.block_info().time.seconds() + DELAY * 2);
+
+    mock.increment_by_time(DELAY);
+    let res = mock.execute_pending_update(&owner, pending.id);
+    assert_err(
+        res,
+        ContractError::PendingUpdateNotReady {
+            id: pending.id,
+            eta: pending.eta,
+        },
+    );
+
+    mock.increment_by_time(DELAY);
+    mock.execute_pending_update(&owner, pending.id).unwrap();
+    assert_eq!(mock.query_config().update_delay, 0);
+}
+
+#[test]
+fn new_asset_is_listed_immediately() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+    mock.update_delay(&owner, DELAY).unwrap();
+
+    let params = default_asset_params("uatom");
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: params.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(params, mock.query_asset_params("uatom").into());
+    assert!(mock.query_pending_updates(None, None).data.is_empty());
+}
+
+#[test]
+fn risk_param_changes_are_scheduled() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+
+    let initial = default_asset_params("uatom");
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: initial.clone(),
+        },
+    )
+    .unwrap();
+    mock.update_delay(&owner, DELAY).unwrap();
+
+    let mut new_params = initial.clone();
+    new_params.max_loan_to_value = Decimal::from_str("0.4").unwrap();
+    new_params.borrow_cap = Uint128::new(1_000);
+    new_params.red_bank.borrow_enabled = true;
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: new_params.clone(),
+        },
+    )
+    .unwrap();
+
+    // Non time-locked fields are updated immediately, risk params stay unchanged
+    let current = mock.query_asset_params("uatom");
+    assert!(current.red_bank.borrow_enabled);
+    assert_eq!(current.max_loan_to_value, initial.max_loan_to_value);
+    assert_eq!(current.borrow_cap, initial.borrow_cap);
+
+    let pending = mock.query_pending_updates(None, None).data;
+    assert_eq!(pending.len(), 1);
+    let pending = pending.first().unwrap();
+    assert_eq!(pending.id, 1);
+    assert_eq!(pending.eta, mock.app.block_info().time.seconds() + DELAY);
+    match &pending.update {
+        PendingUpdateKind::AssetParams(params) => {
+            assert_eq!(params.max_loan_to_value, new_params.max_loan_to_value)
+        }
+        _ => panic!("expected asset params update"),
+    }
+
+    let anyone = Addr::unchecked("anyone");
+    let res = mock.execute_pending_update(&anyone, 1);
+    assert_err(
+        res,
+        ContractError::PendingUpdateNotReady {
+            id: 1,
+            eta: pending.eta,
+        },
+    );
+
+    mock.increment_by_time(DELAY);
+    mock.execute_pending_update(&anyone, 1).unwrap();
+
+    assert_eq!(new_params, mock.query_asset_params("uatom").into());
+    assert!(mock.query_pending_updates(None, None).data.is_empty());
+
+    let res = mock.execute_pending_update(&anyone, 1);
+    assert_err(
+        res,
+        ContractError::PendingUpdateNotFound {
+            id: 1,
+        },
+    );
+}
+
+#[test]
+fn execution_keeps_fields_updated_in_the_meantime() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+
+    let initial = default_asset_params("uatom");
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: initial.clone(),
+        },
+    )
+    .unwrap();
+    mock.update_delay(&owner, DELAY).unwrap();
+
+    let mut new_params = initial.clone();
+    new_params.deposit_cap = Uint128::new(1_000);
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: new_params,
+        },
+    )
+    .unwrap();
+
+    // Deposits disabled after the update was scheduled
+    let mut disabled = initial;
+    disabled.red_bank.deposit_enabled = false;
+    mock.update_asset_params(
+        &owner,
+        AssetParamsUpdate::AddOrUpdate {
+            params: disabled,
+        },
+    )
+    .unwrap();
+
+    mock.increment_by_time(DELAY);
+    mock.execute_pending_update(&owner, 1).unwrap();
+
+    let current = mock.query_asset_params("uatom");
+    assert_eq!(current.deposit_cap, Uint128::new(1_000));
+    assert!(!current.red_bank.deposit_enabled);
+}
+
+#[test]
+fn owner_can_cancel_pending_update() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.query_owner();
+
+    let vault = "vault_addr";
+    let initial = default_vault_config(vault);
+    mock.update_vault_config(
+        &owner,
+        VaultConfigUpdate::AddOrUpdate {
+            config: initial.clone(),
+        },
+    )
+    .unwrap();
+    mock.update_delay(&owner, DELAY).unwrap();
+
+    let mut new_config = initial.clone();
+    new_config.deposit_cap = coin(1_000, "uusdc");
+    mock.update_vault_config(
+        &owner,
+        VaultConfigUpdate::AddOrUpdate {
+            config: new_config,
+        },
+    )
+    .unwrap();
+    assert_eq!(mock.query_vault_config(vault).deposit_cap, initial.deposit_cap);
+    assert_eq!(mock.query_pending_updates(None, None).data.len(), 1);
+
+    let bad_guy = Addr::unchecked("doctor_otto_983");
+    let res = mock.cancel_pending_update(&bad_guy, 1);
+    assert_err(res, ContractError::Owner(OwnerError::NotOwner {}));
+
+    mock.cancel_pending_update(&owner, 1).unwrap();
+    assert!(mock.query_pending_updates(None, None).data.is_empty());
+
+    mock.increment_by_time(DELAY);
+    let res = mock.execute_pending_update(&owner, 1);
+    assert_err(
+        res,
+        ContractError::PendingUpdateNotFound {
+            id: 1,
+        },
+    );
+    assert_eq!(mock.query_vault_config(vault).deposit_cap, initial.deposit_cap);
+}
@@ -11,6 +11,15 @@ fn address_provider_set_on_init() {
     assert_eq!(config.address_provider, "address_provider".to_string());
 }
 
+#[test]
+fn risk_manager_set_on_init() {
+    let mock = MockEnv::new().build().unwrap();
+    assert_eq!(mock.query_config().risk_manager, None);
+
+    let mock = MockEnv::new().risk_manager("risk_manager").build().unwrap();
+    assert_eq!(mock.query_config().risk_manager, Some("risk_manager".to_string()));
+}
+
 #[test]
 fn only_owner_can_update_address_provider() {
     let mut mock = MockEnv::new().build().unwrap();
@@ -0,0 +1,181 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use cw2::set_contract_version;
+use mars_owner::OwnerInit::SetInitialOwner;
+use mars_types::{
+    oracle::ActionKind,
+    perps::{ExecuteMsg, InstantiateMsg, QueryMsg, VaultState},
+};
+use mars_utils::helpers::{decimal_param_le_one, integer_param_gt_zero, validate_native_denom};
+
+use crate::{
+    deleverage::deleverage,
+    error::ContractResult,
+    execute::{update_config, update_market},
+    position_management::{close_all_positions, execute_order},
+    query,
+    state::{CONFIG, OWNER, VAULT_STATE},
+    vault::{self, compute_vault, query_oracle, query_vault_position},
+};
+
+pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> ContractResult<Response> {
+    set_contract_version(deps.storage, format!("crates.io:{CONTRACT_NAME}"), CONTRACT_VERSION)?;
+
+    OWNER.initialize(
+        deps.storage,
+        deps.api,
+        SetInitialOwner {
+            owner: info.sender.into(),
+        },
+    )?;
+
+    validate_native_denom(&msg.base_denom)?;
+    decimal_param_le_one(msg.protocol_fee_rate, "protocol_fee_rate")?;
+    integer_param_gt_zero(msg.max_positions as u64, "max_positions")?;
+    integer_param_gt_zero(msg.max_unlocks as u64, "max_unlocks")?;
+
+    CONFIG.save(deps.storage, &msg.check(deps.api)?)?;
+    VAULT_STATE.save(deps.storage, &VaultState::default())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> ContractResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateOwner(update) => Ok(OWNER.update(deps, info, update)?),
+        ExecuteMsg::UpdateConfig {
+            updates,
+        } => update_config(deps, info, updates),
+        ExecuteMsg::Deposit {
+            account_id,
+            max_shares_receivable,
+        } => vault::deposit(deps, env, info, account_id, max_shares_receivable),
+        ExecuteMsg::Unlock {
+            account_id,
+            shares,
+        } => vault::unlock(deps, env, info, account_id, shares),
+        ExecuteMsg::Withdraw {
+            account_id,
+            min_receive,
+        } => vault::withdraw(deps, env, info, account_id, min_receive),
+        ExecuteMsg::ExecuteOrder {
+            account_id,
+            denom,
+            size,
+            reduce_only,
+        } => execute_order(deps, env, info, account_id, denom, size, reduce_only),
+        ExecuteMsg::CloseAllPositions {
+            account_id,
+            action,
+        } => {
+            close_all_positions(deps, env, info, account_id, action.unwrap_or(ActionKind::Default))
+        }
+        ExecuteMsg::Deleverage {
+            account_id,
+            denom,
+        } => deleverage(deps, env, account_id, denom),
+        ExecuteMsg::UpdateMarket {
+            params,
+        } => update_market(deps, env, info, params),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+    let res = match msg {
+        QueryMsg::Owner {} => to_json_binary(&OWNER.query(deps.storage)?),
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::Vault {
+            action,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            let oracle = query_oracle(deps, &config)?;
+            to_json_binary(&compute_vault(
+                deps,
+                &env,
+                &config,
+                &oracle,
+                action.unwrap_or(ActionKind::Default),
+            )?)
+        }
+        QueryMsg::MarketState {
+            denom,
+        } => to_json_binary(&query::query_market_state(deps, denom)?),
+        QueryMsg::Market {
+            denom,
+        } => to_json_binary(&query::query_market(deps, env, denom)?),
+        QueryMsg::Markets {
+            start_after,
+            limit,
+        } => to_json_binary(&query::query_markets(deps, env, start_after, limit)?),
+        QueryMsg::VaultPosition {
+            user_address,
+            account_id,
+        } => to_json_binary(&query_vault_position(deps, env, user_address, account_id)?),
+        QueryMsg::Position {
+            account_id,
+            denom,
+            order_size,
+            reduce_only,
+        } => to_json_binary(&query::query_position(
+            deps,
+            env,
+            account_id,
+            denom,
+            order_size,
+            reduce_only,
+        )?),
+        QueryMsg::Positions {
+            start_after,
+            limit,
+        } => to_json_binary(&query::query_positions(deps, env, start_after, limit)?),
+        QueryMsg::PositionsByAccount {
+            account_id,
+            action,
+        } => to_json_binary(&query::query_positions_by_account(
+            deps,
+            env,
+            account_id,
+            action.unwrap_or(ActionKind::Default),
+        )?),
+        QueryMsg::MarketAccounting {
+            denom,
+        } => to_json_binary(&query::query_market_accounting(deps, env, denom)?),
+        QueryMsg::TotalAccounting {} => to_json_binary(&query::query_total_accounting(deps, env)?),
+        QueryMsg::RealizedPnlByAccountAndMarket {
+            account_id,
+            denom,
+        } => to_json_binary(&query::query_realized_pnl_by_account_and_market(
+            deps, account_id, denom,
+        )?),
+        QueryMsg::UnpaidProfit {
+            account_id,
+        } => to_json_binary(&query::query_unpaid_profit(deps, account_id)?),
+        QueryMsg::OpeningFee {
+            denom,
+            size,
+        } => to_json_binary(&query::query_opening_fee(deps, denom, size)?),
+        QueryMsg::PositionFees {
+            account_id,
+            denom,
+            new_size,
+        } => to_json_binary(&query::query_position_fees(deps, account_id, denom, new_size)?),
+    };
+    res.map_err(Into::into)
+}
@@ -0,0 +1,108 @@
+use cosmwasm_std::{
+    coins, to_json_binary, CosmosMsg, Decimal, DepsMut, Env, Int128, Response, WasmMsg,
+};
+use mars_types::{
+    adapters::{oracle::Oracle, params::Params},
+    address_provider::MarsAddressType,
+    credit_manager,
+    oracle::ActionKind,
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    position_management::{record_unpaid_profit, update_position},
+    state::{CONFIG, POSITIONS},
+    utils::{abs, query_contract_addrs, query_prices},
+    vault::compute_vault,
+};
+
+/// Close a profitable position while the vault is undercollateralized, so that the vault can't
+/// be drained by traders' profits. No closing fee is charged and the profit is sent to the Credit
+/// Manager on behalf of the account.
+pub fn deleverage(
+    deps: DepsMut,
+    env: Env,
+    account_id: String,
+    denom: String,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !config.deleverage_enabled {
+        return Err(ContractError::DeleverageDisabled);
+    }
+
+    let addrs = query_contract_addrs(
+        deps.as_ref(),
+        &config,
+        vec![MarsAddressType::Oracle, MarsAddressType::Params, MarsAddressType::CreditManager],
+    )?;
+    let oracle = Oracle::new(addrs[&MarsAddressType::Oracle].clone());
+    let params = Params::new(addrs[&MarsAddressType::Params].clone());
+    let credit_manager = &addrs[&MarsAddressType::CreditManager];
+
+    let vault = compute_vault(deps.as_ref(), &env, &config, &oracle, ActionKind::Default)?;
+    let undercollateralized = vault
+        .collateralization_ratio
+        .map(|cr| cr < config.target_vault_collateralization_ratio)
+        .unwrap_or(false);
+    if !undercollateralized {
+        return Err(ContractError::DeleverageNotAllowed {
+            target: config.target_vault_collateralization_ratio,
+        });
+    }
+
+    if !POSITIONS.has(deps.storage, (account_id.as_str(), denom.as_str())) {
+        return Err(ContractError::PositionNotFound {
+            account_id,
+            denom,
+        });
+    }
+
+    let perp_params = params.query_perp_params(&deps.querier, &denom)?;
+    let prices =
+        query_prices(&deps.querier, &oracle, &denom, &config.base_denom, ActionKind::Default)?;
+
+    let update = update_position(
+        deps.storage,
+        env.block.time.seconds(),
+        &config,
+        &account_id,
+        &denom,
+        Int128::zero(),
+        &prices,
+        perp_params.opening_fee_rate,
+        Decimal::zero(),
+    )?;
+
+    let pnl = update.realized_pnl.pnl;
+    if pnl <= Int128::zero() {
+        return Err(ContractError::PositionNotProfitable {
+            pnl,
+        });
+    }
+
+    // Same as for regular closes, the payout is capped at the balance of the contract
+    let available = deps.querier.query_balance(&env.contract.address, &config.base_denom)?.amount;
+    let payout = abs(pnl).min(available);
+    let unpaid_profit = abs(pnl) - payout;
+    record_unpaid_profit(deps.storage, &account_id, &[(denom.clone(), pnl)], unpaid_profit)?;
+
+    let mut response = Response::new();
+    if !payout.is_zero() {
+        response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: credit_manager.to_string(),
+            msg: to_json_binary(&credit_manager::ExecuteMsg::UpdateBalanceAfterDeleverage {
+                account_id: account_id.clone(),
+                pnl,
+            })?,
+            funds: coins(payout.u128(), &config.base_denom),
+        }));
+    }
+
+    Ok(response
+        .add_attribute("action", "deleverage")
+        .add_attribute("account_id", account_id)
+        .add_attribute("denom", denom)
+        .add_attribute("realized_pnl", pnl.to_string())
+        .add_attribute("unpaid_profit", unpaid_profit))
+}
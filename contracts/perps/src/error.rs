@@ -0,0 +1,184 @@
+use cosmwasm_std::{
+    CheckedFromRatioError, CheckedMultiplyFractionError, CheckedMultiplyRatioError,
+    ConversionOverflowError, Decimal, DecimalRangeExceeded, DivideByZeroError, Int128,
+    OverflowError, SignedDecimalRangeExceeded, StdError, Uint128,
+};
+use cw2::VersionError;
+use cw_utils::PaymentError;
+use mars_owner::OwnerError;
+use mars_utils::error::ValidationError;
+use thiserror::Error;
+
+pub type ContractResult<T> = Result<T, ContractError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error(transparent)]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Overflow(#[from] OverflowError),
+
+    #[error(transparent)]
+    CheckedFromRatio(#[from] CheckedFromRatioError),
+
+    #[error(transparent)]
+    CheckedMultiplyRatio(#[from] CheckedMultiplyRatioError),
+
+    #[error(transparent)]
+    CheckedMultiplyFraction(#[from] CheckedMultiplyFractionError),
+
+    #[error(transparent)]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
+    #[error(transparent)]
+    DecimalRangeExceeded(#[from] DecimalRangeExceeded),
+
+    #[error(transparent)]
+    SignedDecimalRangeExceeded(#[from] SignedDecimalRangeExceeded),
+
+    #[error(transparent)]
+    DivideByZero(#[from] DivideByZeroError),
+
+    #[error(transparent)]
+    Owner(#[from] OwnerError),
+
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
+    #[error(transparent)]
+    Validation(#[from] ValidationError),
+
+    #[error(transparent)]
+    Version(#[from] VersionError),
+
+    #[error("Sender is not the {expected} contract: {actual}")]
+    SenderIsNotExpectedContract {
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Market not found: {denom}")]
+    MarketNotFound {
+        denom: String,
+    },
+
+    #[error("Market is disabled: {denom}")]
+    MarketDisabled {
+        denom: String,
+    },
+
+    #[error("Order size can't be zero")]
+    ZeroOrderSize,
+
+    #[error("Position not found for account {account_id} in market {denom}")]
+    PositionNotFound {
+        account_id: String,
+        denom: String,
+    },
+
+    #[error("Reduce only order can only reduce the current position size. Current: {current}, order: {order}")]
+    IllegalReduceOnlyOrder {
+        current: Int128,
+        order: Int128,
+    },
+
+    #[error("Max number of open positions reached: {max_positions}")]
+    MaxPositionsReached {
+        max_positions: u8,
+    },
+
+    #[error("Position value {found} is below the minimum of {min}")]
+    PositionTooSmall {
+        min: Uint128,
+        found: Uint128,
+    },
+
+    #[error("Position value {found} is above the maximum of {max}")]
+    PositionTooBig {
+        max: Uint128,
+        found: Uint128,
+    },
+
+    #[error("Long open interest value {found} would exceed the maximum of {max}")]
+    LongOpenInterestReached {
+        max: Uint128,
+        found: Uint128,
+    },
+
+    #[error("Short open interest value {found} would exceed the maximum of {max}")]
+    ShortOpenInterestReached {
+        max: Uint128,
+        found: Uint128,
+    },
+
+    #[error("Net open interest value {found} would exceed the maximum of {max}")]
+    NetOpenInterestReached {
+        max: Uint128,
+        found: Uint128,
+    },
+
+    #[error("Invalid payment, expected {expected} {denom}, got {received}")]
+    InvalidPayment {
+        denom: String,
+        expected: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Minted shares {shares} exceed the maximum of {max_shares_receivable}")]
+    MaximumSharesExceeded {
+        shares: Uint128,
+        max_shares_receivable: Uint128,
+    },
+
+    #[error("Vault is insolvent, deposits are not accepted")]
+    VaultInsolvent,
+
+    #[error("Only the credit manager can provide an account id")]
+    AccountIdNotAllowed,
+
+    #[error("Insufficient shares to unlock. Available: {available}, requested: {requested}")]
+    InsufficientShares {
+        available: Uint128,
+        requested: Uint128,
+    },
+
+    #[error("Can't unlock zero shares")]
+    ZeroShares,
+
+    #[error("Max number of unlocks reached: {max_unlocks}")]
+    MaxUnlocksReached {
+        max_unlocks: u8,
+    },
+
+    #[error("No unlocked shares to withdraw")]
+    UnlockedPositionsNotFound,
+
+    #[error("Vault withdrawals are disabled")]
+    VaultWithdrawDisabled,
+
+    #[error("Received amount {received} is below the minimum of {min_receive}")]
+    MinimumReceiveExceeded {
+        min_receive: Uint128,
+        received: Uint128,
+    },
+
+    #[error("Vault collateralization ratio {current} would fall below the target of {target}")]
+    VaultUndercollateralized {
+        current: Decimal,
+        target: Decimal,
+    },
+
+    #[error("Deleverage is disabled")]
+    DeleverageDisabled,
+
+    #[error("Deleverage is only allowed when the vault collateralization ratio is below the target of {target}")]
+    DeleverageNotAllowed {
+        target: Decimal,
+    },
+
+    #[error("Only profitable positions can be deleveraged, unrealized pnl: {pnl}")]
+    PositionNotProfitable {
+        pnl: Int128,
+    },
+}
@@ -0,0 +1,119 @@
+use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Response};
+use mars_types::{
+    address_provider::MarsAddressType, oracle::ActionKind, params::PerpParams, perps::ConfigUpdates,
+};
+use mars_utils::helpers::{decimal_param_le_one, integer_param_gt_zero};
+
+use crate::{
+    error::ContractResult,
+    market::{new_market_state, MarketStateExt},
+    state::{CONFIG, MARKET_STATES, OWNER},
+    utils::{assert_sender_is_contract, query_prices},
+    vault::query_oracle,
+};
+
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    updates: ConfigUpdates,
+) -> ContractResult<Response> {
+    OWNER.assert_owner(deps.storage, &info.sender)?;
+
+    let mut config = CONFIG.load(deps.storage)?;
+    let mut response = Response::new().add_attribute("action", "update_config");
+
+    if let Some(address_provider) = updates.address_provider {
+        config.address_provider = deps.api.addr_validate(&address_provider)?;
+        response = response.add_attribute("address_provider", address_provider);
+    }
+
+    if let Some(cooldown_period) = updates.cooldown_period {
+        config.cooldown_period = cooldown_period;
+        response = response.add_attribute("cooldown_period", cooldown_period.to_string());
+    }
+
+    if let Some(max_positions) = updates.max_positions {
+        integer_param_gt_zero(max_positions as u64, "max_positions")?;
+        config.max_positions = max_positions;
+        response = response.add_attribute("max_positions", max_positions.to_string());
+    }
+
+    if let Some(protocol_fee_rate) = updates.protocol_fee_rate {
+        decimal_param_le_one(protocol_fee_rate, "protocol_fee_rate")?;
+        config.protocol_fee_rate = protocol_fee_rate;
+        response = response.add_attribute("protocol_fee_rate", protocol_fee_rate.to_string());
+    }
+
+    if let Some(target_cr) = updates.target_vault_collateralization_ratio {
+        config.target_vault_collateralization_ratio = target_cr;
+        response =
+            response.add_attribute("target_vault_collateralization_ratio", target_cr.to_string());
+    }
+
+    if let Some(deleverage_enabled) = updates.deleverage_enabled {
+        config.deleverage_enabled = deleverage_enabled;
+        response = response.add_attribute("deleverage_enabled", deleverage_enabled.to_string());
+    }
+
+    if let Some(vault_withdraw_enabled) = updates.vault_withdraw_enabled {
+        config.vault_withdraw_enabled = vault_withdraw_enabled;
+        response =
+            response.add_attribute("vault_withdraw_enabled", vault_withdraw_enabled.to_string());
+    }
+
+    if let Some(max_unlocks) = updates.max_unlocks {
+        integer_param_gt_zero(max_unlocks as u64, "max_unlocks")?;
+        config.max_unlocks = max_unlocks;
+        response = response.add_attribute("max_unlocks", max_unlocks.to_string());
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(response)
+}
+
+/// Create a market or update the funding parameters and the status of an existing one.
+/// Funding is accrued with the old parameters first, so that they are not applied retroactively.
+pub fn update_market(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    params: PerpParams,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_sender_is_contract(deps.as_ref(), &config, &info.sender, MarsAddressType::Params)?;
+
+    let current_time = env.block.time.seconds();
+    let ms = match MARKET_STATES.may_load(deps.storage, &params.denom)? {
+        Some(mut ms) => {
+            // without open interest no funding accrues, so the price is irrelevant
+            let price = if ms.long_oi.is_zero() && ms.short_oi.is_zero() {
+                Decimal::zero()
+            } else {
+                let oracle = query_oracle(deps.as_ref(), &config)?;
+                query_prices(
+                    &deps.querier,
+                    &oracle,
+                    &params.denom,
+                    &config.base_denom,
+                    ActionKind::Default,
+                )?
+                .price
+            };
+            ms.accrue_funding(current_time, price)?;
+
+            ms.enabled = params.enabled;
+            ms.funding.max_funding_velocity = params.max_funding_velocity;
+            ms.funding.skew_scale = params.skew_scale;
+            ms
+        }
+        None => new_market_state(&params, current_time),
+    };
+
+    MARKET_STATES.save(deps.storage, &params.denom, &ms)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "update_market")
+        .add_attribute("denom", params.denom)
+        .add_attribute("enabled", params.enabled.to_string()))
+}
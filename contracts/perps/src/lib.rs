@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod deleverage;
+pub mod error;
+pub mod execute;
+pub mod market;
+pub mod position;
+pub mod position_management;
+pub mod query;
+pub mod state;
+pub mod utils;
+pub mod vault;
@@ -0,0 +1,228 @@
+use cosmwasm_std::{Decimal, Int128, SignedDecimal, Uint128};
+use mars_types::{
+    params::PerpParams,
+    perps::{CashFlow, Funding, MarketState, PnlAmounts},
+};
+
+use crate::{
+    error::ContractResult,
+    utils::{abs, decimal_to_signed_decimal, int_to_signed_decimal, uint_to_int},
+};
+
+pub const SECONDS_IN_DAY: u64 = 86400;
+
+pub fn new_market_state(params: &PerpParams, current_time: u64) -> MarketState {
+    MarketState {
+        enabled: params.enabled,
+        long_oi: Uint128::zero(),
+        short_oi: Uint128::zero(),
+        funding: Funding {
+            max_funding_velocity: params.max_funding_velocity,
+            skew_scale: params.skew_scale,
+            last_funding_rate: SignedDecimal::zero(),
+            last_funding_accrued_per_unit_in_base_denom: SignedDecimal::zero(),
+        },
+        last_updated: current_time,
+        total_entry_cost: SignedDecimal::zero(),
+        total_entry_funding: SignedDecimal::zero(),
+        cash_flow: CashFlow::default(),
+    }
+}
+
+pub trait MarketStateExt {
+    /// Net open interest, positive if longs outweigh shorts
+    fn skew(&self) -> ContractResult<Int128>;
+
+    /// Funding rate (per day) at the given time.
+    ///
+    /// The rate drifts with a velocity proportional to the skew:
+    ///   velocity = clamp(skew / skew_scale, -1, 1) * max_funding_velocity
+    ///   rate = last_rate + velocity * elapsed_days
+    /// A positive rate means longs pay shorts.
+    fn current_funding_rate(&self, current_time: u64) -> ContractResult<SignedDecimal>;
+
+    /// Accumulate the funding accrued since the last update. Has to be called before the open
+    /// interest changes, otherwise the new skew would be applied retroactively.
+    fn accrue_funding(&mut self, current_time: u64, price: Decimal) -> ContractResult<()>;
+
+    /// Add a position to the market totals
+    fn increase_open_interest(
+        &mut self,
+        size: Int128,
+        entry_price: Decimal,
+        entry_accrued_funding_per_unit: SignedDecimal,
+    ) -> ContractResult<()>;
+
+    /// Remove a position from the market totals
+    fn decrease_open_interest(
+        &mut self,
+        size: Int128,
+        entry_price: Decimal,
+        entry_accrued_funding_per_unit: SignedDecimal,
+    ) -> ContractResult<()>;
+
+    /// Aggregated unrealized PnL of all open positions, from the traders' perspective.
+    /// Funding has to be accrued up to the current time beforehand.
+    fn unrealized_pnl(&self, price: Decimal) -> ContractResult<PnlAmounts>;
+
+    /// Record PnL realized by a trader in the market cash flow.
+    /// Returns the part of the fees that goes to the protocol.
+    fn apply_realized_pnl(
+        &mut self,
+        realized_pnl: &PnlAmounts,
+        protocol_fee_rate: Decimal,
+    ) -> ContractResult<Uint128>;
+}
+
+impl MarketStateExt for MarketState {
+    fn skew(&self) -> ContractResult<Int128> {
+        Ok(uint_to_int(self.long_oi)?.checked_sub(uint_to_int(self.short_oi)?)?)
+    }
+
+    fn current_funding_rate(&self, current_time: u64) -> ContractResult<SignedDecimal> {
+        let skew = int_to_signed_decimal(self.skew()?)?;
+        let skew_scale = int_to_signed_decimal(uint_to_int(self.funding.skew_scale)?)?;
+        let proportional_skew = skew
+            .checked_div(skew_scale)?
+            .clamp(SignedDecimal::negative_one(), SignedDecimal::one());
+        let velocity = proportional_skew
+            .checked_mul(decimal_to_signed_decimal(self.funding.max_funding_velocity)?)?;
+
+        let elapsed_days = elapsed_days(self.last_updated, current_time)?;
+        Ok(self.funding.last_funding_rate.checked_add(velocity.checked_mul(elapsed_days)?)?)
+    }
+
+    fn accrue_funding(&mut self, current_time: u64, price: Decimal) -> ContractResult<()> {
+        if current_time <= self.last_updated {
+            return Ok(());
+        }
+
+        let current_funding_rate = self.current_funding_rate(current_time)?;
+
+        // the rate changes linearly, so the average rate over the period is the midpoint
+        let avg_funding_rate = self
+            .funding
+            .last_funding_rate
+            .checked_add(current_funding_rate)?
+            .checked_mul(SignedDecimal::percent(50))?;
+        let elapsed_days = elapsed_days(self.last_updated, current_time)?;
+        let unrecorded_funding_per_unit = avg_funding_rate
+            .checked_mul(elapsed_days)?
+            .checked_mul(decimal_to_signed_decimal(price)?)?;
+
+        self.funding.last_funding_accrued_per_unit_in_base_denom = self
+            .funding
+            .last_funding_accrued_per_unit_in_base_denom
+            .checked_add(unrecorded_funding_per_unit)?;
+        self.funding.last_funding_rate = current_funding_rate;
+        self.last_updated = current_time;
+
+        Ok(())
+    }
+
+    fn increase_open_interest(
+        &mut self,
+        size: Int128,
+        entry_price: Decimal,
+        entry_accrued_funding_per_unit: SignedDecimal,
+    ) -> ContractResult<()> {
+        if size > Int128::zero() {
+            self.long_oi = self.long_oi.checked_add(abs(size))?;
+        } else {
+            self.short_oi = self.short_oi.checked_add(abs(size))?;
+        }
+
+        let size = int_to_signed_decimal(size)?;
+        self.total_entry_cost = self
+            .total_entry_cost
+            .checked_add(size.checked_mul(decimal_to_signed_decimal(entry_price)?)?)?;
+        self.total_entry_funding = self
+            .total_entry_funding
+            .checked_add(size.checked_mul(entry_accrued_funding_per_unit)?)?;
+
+        Ok(())
+    }
+
+    fn decrease_open_interest(
+        &mut self,
+        size: Int128,
+        entry_price: Decimal,
+        entry_accrued_funding_per_unit: SignedDecimal,
+    ) -> ContractResult<()> {
+        if size > Int128::zero() {
+            self.long_oi = self.long_oi.checked_sub(abs(size))?;
+        } else {
+            self.short_oi = self.short_oi.checked_sub(abs(size))?;
+        }
+
+        let size = int_to_signed_decimal(size)?;
+        self.total_entry_cost = self
+            .total_entry_cost
+            .checked_sub(size.checked_mul(decimal_to_signed_decimal(entry_price)?)?)?;
+        self.total_entry_funding = self
+            .total_entry_funding
+            .checked_sub(size.checked_mul(entry_accrued_funding_per_unit)?)?;
+
+        Ok(())
+    }
+
+    fn unrealized_pnl(&self, price: Decimal) -> ContractResult<PnlAmounts> {
+        let skew = int_to_signed_decimal(self.skew()?)?;
+
+        // sum(size * (price - entry_price)) = skew * price - sum(size * entry_price)
+        let price_pnl = skew
+            .checked_mul(decimal_to_signed_decimal(price)?)?
+            .checked_sub(self.total_entry_cost)?
+            .to_int_floor();
+
+        // sum(-size * (accrued - entry_accrued)) = sum(size * entry_accrued) - skew * accrued
+        let accrued_funding = self
+            .total_entry_funding
+            .checked_sub(
+                skew.checked_mul(self.funding.last_funding_accrued_per_unit_in_base_denom)?,
+            )?
+            .to_int_floor();
+
+        Ok(PnlAmounts {
+            price_pnl,
+            accrued_funding,
+            opening_fee: Int128::zero(),
+            closing_fee: Int128::zero(),
+            pnl: price_pnl.checked_add(accrued_funding)?,
+        })
+    }
+
+    fn apply_realized_pnl(
+        &mut self,
+        realized_pnl: &PnlAmounts,
+        protocol_fee_rate: Decimal,
+    ) -> ContractResult<Uint128> {
+        let opening_fee = abs(realized_pnl.opening_fee);
+        let closing_fee = abs(realized_pnl.closing_fee);
+        let opening_protocol_fee = opening_fee.checked_mul_floor(protocol_fee_rate)?;
+        let closing_protocol_fee = closing_fee.checked_mul_floor(protocol_fee_rate)?;
+
+        let cf = &mut self.cash_flow;
+        cf.price_pnl = cf.price_pnl.checked_sub(realized_pnl.price_pnl)?;
+        cf.accrued_funding = cf.accrued_funding.checked_sub(realized_pnl.accrued_funding)?;
+        cf.opening_fee = cf
+            .opening_fee
+            .checked_add(uint_to_int(opening_fee.checked_sub(opening_protocol_fee)?)?)?;
+        cf.closing_fee = cf
+            .closing_fee
+            .checked_add(uint_to_int(closing_fee.checked_sub(closing_protocol_fee)?)?)?;
+
+        let protocol_fee = opening_protocol_fee.checked_add(closing_protocol_fee)?;
+        cf.protocol_fee = cf.protocol_fee.checked_add(protocol_fee)?;
+
+        Ok(protocol_fee)
+    }
+}
+
+fn elapsed_days(last_updated: u64, current_time: u64) -> ContractResult<SignedDecimal> {
+    let elapsed = current_time.saturating_sub(last_updated);
+    Ok(SignedDecimal::checked_from_ratio(
+        Int128::new(elapsed as i128),
+        Int128::new(SECONDS_IN_DAY as i128),
+    )?)
+}
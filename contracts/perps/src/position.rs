@@ -0,0 +1,81 @@
+use cosmwasm_std::{Decimal, Int128, SignedDecimal, Uint128};
+use mars_types::perps::{PnlAmounts, Position};
+
+use crate::{
+    error::ContractResult,
+    utils::{abs, decimal_to_signed_decimal, int_to_signed_decimal, negate},
+};
+
+/// Split a position change into the opened and the closed size (both absolute).
+/// Opening fees are charged on the former, closing fees on the latter.
+pub fn opened_and_closed_sizes(old_size: Int128, new_size: Int128) -> (Uint128, Uint128) {
+    let old_abs = abs(old_size);
+    let new_abs = abs(new_size);
+
+    let flipped = !old_size.is_zero()
+        && !new_size.is_zero()
+        && (old_size > Int128::zero()) != (new_size > Int128::zero());
+    if flipped {
+        return (new_abs, old_abs);
+    }
+
+    if new_abs >= old_abs {
+        (new_abs - old_abs, Uint128::zero())
+    } else {
+        (Uint128::zero(), old_abs - new_abs)
+    }
+}
+
+/// Fee for trading the given size at the given price, rounded up
+pub fn trading_fee(size: Uint128, price: Decimal, rate: Decimal) -> ContractResult<Uint128> {
+    Ok(size.checked_mul_ceil(price.checked_mul(rate)?)?)
+}
+
+/// PnL realized when moving a position (None if there is no position yet) to `new_size`.
+///
+/// The whole price and funding PnL of the current position is realized, and fees are charged on
+/// the opened and closed parts of the position. The remaining position (if any) starts over at
+/// the current price.
+pub fn compute_pnl(
+    position: Option<&Position>,
+    new_size: Int128,
+    current_price: Decimal,
+    current_accrued_funding_per_unit: SignedDecimal,
+    opening_fee_rate: Decimal,
+    closing_fee_rate: Decimal,
+) -> ContractResult<PnlAmounts> {
+    let (old_size, price_pnl, accrued_funding) = match position {
+        Some(position) => {
+            let size = int_to_signed_decimal(position.size)?;
+
+            let price_diff = decimal_to_signed_decimal(current_price)?
+                .checked_sub(decimal_to_signed_decimal(position.entry_price)?)?;
+            let price_pnl = size.checked_mul(price_diff)?.to_int_floor();
+
+            // longs pay (and shorts receive) funding when the rate is positive
+            let funding_diff = current_accrued_funding_per_unit
+                .checked_sub(position.entry_accrued_funding_per_unit_in_base_denom)?;
+            let accrued_funding = (-size.checked_mul(funding_diff)?).to_int_floor();
+
+            (position.size, price_pnl, accrued_funding)
+        }
+        None => (Int128::zero(), Int128::zero(), Int128::zero()),
+    };
+
+    let (opened, closed) = opened_and_closed_sizes(old_size, new_size);
+    let opening_fee = negate(trading_fee(opened, current_price, opening_fee_rate)?)?;
+    let closing_fee = negate(trading_fee(closed, current_price, closing_fee_rate)?)?;
+
+    let pnl = price_pnl
+        .checked_add(accrued_funding)?
+        .checked_add(opening_fee)?
+        .checked_add(closing_fee)?;
+
+    Ok(PnlAmounts {
+        price_pnl,
+        accrued_funding,
+        opening_fee,
+        closing_fee,
+        pnl,
+    })
+}
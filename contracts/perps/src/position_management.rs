@@ -0,0 +1,515 @@
+use cosmwasm_std::{
+    coins, Addr, BankMsg, CosmosMsg, Decimal, DepsMut, Env, Int128, MessageInfo, Order, Response,
+    Storage, Uint128,
+};
+use mars_types::{
+    adapters::{oracle::Oracle, params::Params},
+    address_provider::MarsAddressType,
+    oracle::ActionKind,
+    params::PerpParams,
+    perps::{Config, MarketState, PnlAmounts, Position},
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    market::MarketStateExt,
+    position::{compute_pnl, opened_and_closed_sizes},
+    state::{CONFIG, MARKET_STATES, POSITIONS, REALIZED_PNL, UNPAID_PROFITS, VAULT_STATE},
+    utils::{
+        abs, assert_sender_is_contract, position_value, query_contract_addrs, query_prices,
+        MarketPrices,
+    },
+};
+
+/// Result of moving a position to a new size
+pub struct PositionUpdate {
+    /// PnL realized by the trader
+    pub realized_pnl: PnlAmounts,
+    /// Part of the fees that goes to the protocol
+    pub protocol_fee: Uint128,
+    /// Market state after the update
+    pub market_state: MarketState,
+}
+
+/// Move the position of the account to `new_size` (zero closes it), realizing its PnL and
+/// updating the market accordingly.
+#[allow(clippy::too_many_arguments)]
+pub fn update_position(
+    storage: &mut dyn Storage,
+    current_time: u64,
+    config: &Config<Addr>,
+    account_id: &str,
+    denom: &str,
+    new_size: Int128,
+    prices: &MarketPrices,
+    opening_fee_rate: Decimal,
+    closing_fee_rate: Decimal,
+) -> ContractResult<PositionUpdate> {
+    let mut ms =
+        MARKET_STATES.may_load(storage, denom)?.ok_or_else(|| ContractError::MarketNotFound {
+            denom: denom.to_string(),
+        })?;
+
+    // funding has to be accrued with the skew from before the update
+    ms.accrue_funding(current_time, prices.price)?;
+    let current_accrued_funding_per_unit = ms.funding.last_funding_accrued_per_unit_in_base_denom;
+
+    let position = POSITIONS.may_load(storage, (account_id, denom))?;
+
+    let realized_pnl = compute_pnl(
+        position.as_ref(),
+        new_size,
+        prices.price,
+        current_accrued_funding_per_unit,
+        opening_fee_rate,
+        closing_fee_rate,
+    )?;
+
+    if let Some(position) = &position {
+        ms.decrease_open_interest(
+            position.size,
+            position.entry_price,
+            position.entry_accrued_funding_per_unit_in_base_denom,
+        )?;
+    }
+
+    let protocol_fee = ms.apply_realized_pnl(&realized_pnl, config.protocol_fee_rate)?;
+
+    if new_size.is_zero() {
+        POSITIONS.remove(storage, (account_id, denom));
+    } else {
+        ms.increase_open_interest(new_size, prices.price, current_accrued_funding_per_unit)?;
+
+        let mut position_realized_pnl =
+            position.map(|position| position.realized_pnl).unwrap_or_default();
+        position_realized_pnl.add(&realized_pnl)?;
+
+        POSITIONS.save(
+            storage,
+            (account_id, denom),
+            &Position {
+                size: new_size,
+                entry_price: prices.price,
+                entry_accrued_funding_per_unit_in_base_denom: current_accrued_funding_per_unit,
+                realized_pnl: position_realized_pnl,
+            },
+        )?;
+    }
+
+    REALIZED_PNL.update(storage, (account_id, denom), |pnl| -> ContractResult<_> {
+        let mut pnl = pnl.unwrap_or_default();
+        pnl.add(&realized_pnl)?;
+        Ok(pnl)
+    })?;
+
+    MARKET_STATES.save(storage, denom, &ms)?;
+
+    Ok(PositionUpdate {
+        realized_pnl,
+        protocol_fee,
+        market_state: ms,
+    })
+}
+
+/// A reduce-only order is valid if it reduces the size of an existing position without flipping it
+pub fn assert_reduce_only(
+    reduce_only: Option<bool>,
+    old_size: Int128,
+    order_size: Int128,
+) -> ContractResult<()> {
+    if !reduce_only.unwrap_or(false) {
+        return Ok(());
+    }
+
+    let new_size = old_size.checked_add(order_size)?;
+    let (opened, _) = opened_and_closed_sizes(old_size, new_size);
+    if old_size.is_zero() || !opened.is_zero() {
+        return Err(ContractError::IllegalReduceOnlyOrder {
+            current: old_size,
+            order: order_size,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn execute_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: String,
+    denom: String,
+    size: Int128,
+    reduce_only: Option<bool>,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_sender_is_contract(
+        deps.as_ref(),
+        &config,
+        &info.sender,
+        MarsAddressType::CreditManager,
+    )?;
+
+    if size.is_zero() {
+        return Err(ContractError::ZeroOrderSize);
+    }
+
+    let ms = MARKET_STATES.may_load(deps.storage, &denom)?.ok_or_else(|| {
+        ContractError::MarketNotFound {
+            denom: denom.clone(),
+        }
+    })?;
+
+    let old_size = POSITIONS
+        .may_load(deps.storage, (account_id.as_str(), denom.as_str()))?
+        .map(|position| position.size)
+        .unwrap_or_default();
+    let new_size = old_size.checked_add(size)?;
+
+    assert_reduce_only(reduce_only, old_size, size)?;
+
+    let (opened, _) = opened_and_closed_sizes(old_size, new_size);
+    let increases_exposure = !opened.is_zero();
+
+    if increases_exposure && !ms.enabled {
+        return Err(ContractError::MarketDisabled {
+            denom,
+        });
+    }
+
+    if old_size.is_zero() {
+        let open_positions =
+            POSITIONS.prefix(&account_id).keys(deps.storage, None, None, Order::Ascending).count();
+        if open_positions >= config.max_positions as usize {
+            return Err(ContractError::MaxPositionsReached {
+                max_positions: config.max_positions,
+            });
+        }
+    }
+
+    let addrs = query_contract_addrs(
+        deps.as_ref(),
+        &config,
+        vec![MarsAddressType::Oracle, MarsAddressType::Params, MarsAddressType::RewardsCollector],
+    )?;
+    let oracle = Oracle::new(addrs[&MarsAddressType::Oracle].clone());
+    let params = Params::new(addrs[&MarsAddressType::Params].clone());
+
+    let perp_params = params.query_perp_params(&deps.querier, &denom)?;
+    let prices =
+        query_prices(&deps.querier, &oracle, &denom, &config.base_denom, ActionKind::Default)?;
+
+    if !new_size.is_zero() {
+        assert_position_value(&perp_params, new_size, &prices, increases_exposure)?;
+    }
+
+    let update = update_position(
+        deps.storage,
+        env.block.time.seconds(),
+        &config,
+        &account_id,
+        &denom,
+        new_size,
+        &prices,
+        perp_params.opening_fee_rate,
+        perp_params.closing_fee_rate,
+    )?;
+
+    if increases_exposure {
+        assert_open_interest(&perp_params, &ms, &update.market_state, &prices)?;
+    }
+
+    let (msgs, unpaid_profit) = settle(
+        deps,
+        &env,
+        &info,
+        &config,
+        &account_id,
+        &[(denom.clone(), update.realized_pnl.pnl)],
+        update.protocol_fee,
+        &addrs[&MarsAddressType::RewardsCollector],
+    )?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "execute_order")
+        .add_attribute("account_id", account_id)
+        .add_attribute("denom", denom)
+        .add_attribute("order_size", size.to_string())
+        .add_attribute("new_size", new_size.to_string())
+        .add_attribute("entry_price", prices.price.to_string())
+        .add_attribute("realized_pnl", update.realized_pnl.pnl.to_string())
+        .add_attribute("unpaid_profit", unpaid_profit))
+}
+
+pub fn close_all_positions(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: String,
+    action: ActionKind,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    assert_sender_is_contract(
+        deps.as_ref(),
+        &config,
+        &info.sender,
+        MarsAddressType::CreditManager,
+    )?;
+
+    let addrs = query_contract_addrs(
+        deps.as_ref(),
+        &config,
+        vec![MarsAddressType::Oracle, MarsAddressType::Params, MarsAddressType::RewardsCollector],
+    )?;
+    let oracle = Oracle::new(addrs[&MarsAddressType::Oracle].clone());
+    let params = Params::new(addrs[&MarsAddressType::Params].clone());
+
+    let denoms = POSITIONS
+        .prefix(&account_id)
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut pnls = vec![];
+    let mut total_pnl = Int128::zero();
+    let mut total_protocol_fee = Uint128::zero();
+    for denom in &denoms {
+        let perp_params = params.query_perp_params(&deps.querier, denom)?;
+        let prices =
+            query_prices(&deps.querier, &oracle, denom, &config.base_denom, action.clone())?;
+
+        let update = update_position(
+            deps.storage,
+            env.block.time.seconds(),
+            &config,
+            &account_id,
+            denom,
+            Int128::zero(),
+            &prices,
+            perp_params.opening_fee_rate,
+            perp_params.closing_fee_rate,
+        )?;
+
+        total_pnl = total_pnl.checked_add(update.realized_pnl.pnl)?;
+        total_protocol_fee = total_protocol_fee.checked_add(update.protocol_fee)?;
+        pnls.push((denom.clone(), update.realized_pnl.pnl));
+    }
+
+    let (msgs, unpaid_profit) = settle(
+        deps,
+        &env,
+        &info,
+        &config,
+        &account_id,
+        &pnls,
+        total_protocol_fee,
+        &addrs[&MarsAddressType::RewardsCollector],
+    )?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "close_all_positions")
+        .add_attribute("account_id", account_id)
+        .add_attribute("closed_positions", denoms.len().to_string())
+        .add_attribute("realized_pnl", total_pnl.to_string())
+        .add_attribute("unpaid_profit", unpaid_profit))
+}
+
+/// Settle the realized PnL with the Credit Manager (the sender): losses have to be sent along with
+/// the message, profits are sent back. The protocol part of the fees goes to the rewards collector.
+///
+/// Payouts are capped at the base denom balance of the contract, so that closing a position
+/// doesn't fail while the vault is short on liquidity. Profits are paid before the protocol fee.
+/// The part of the profit that can't be paid out is recorded for the account (see
+/// `record_unpaid_profit`), an unpaid protocol fee stays in the vault.
+///
+/// Returns the messages to send and the unpaid profit.
+#[allow(clippy::too_many_arguments)]
+fn settle(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config<Addr>,
+    account_id: &str,
+    pnls: &[(String, Int128)],
+    protocol_fee: Uint128,
+    rewards_collector: &Addr,
+) -> ContractResult<(Vec<CosmosMsg>, Uint128)> {
+    let mut pnl = Int128::zero();
+    for (_, denom_pnl) in pnls {
+        pnl = pnl.checked_add(*denom_pnl)?;
+    }
+
+    let received = cw_utils::may_pay(info, &config.base_denom)?;
+    let expected = if pnl < Int128::zero() {
+        abs(pnl)
+    } else {
+        Uint128::zero()
+    };
+    if received != expected {
+        return Err(ContractError::InvalidPayment {
+            denom: config.base_denom.clone(),
+            expected,
+            received,
+        });
+    }
+
+    let mut available =
+        deps.querier.query_balance(&env.contract.address, &config.base_denom)?.amount;
+    let mut unpaid_profit = Uint128::zero();
+
+    let mut msgs = vec![];
+    if pnl > Int128::zero() {
+        let profit = abs(pnl);
+        let payout = profit.min(available);
+        available -= payout;
+        unpaid_profit = profit - payout;
+        if !payout.is_zero() {
+            msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: info.sender.to_string(),
+                amount: coins(payout.u128(), &config.base_denom),
+            }));
+        }
+    }
+    if !protocol_fee.is_zero() {
+        let fee = protocol_fee.min(available);
+        let unpaid_fee = protocol_fee - fee;
+        if !fee.is_zero() {
+            msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                to_address: rewards_collector.to_string(),
+                amount: coins(fee.u128(), &config.base_denom),
+            }));
+        }
+        if !unpaid_fee.is_zero() {
+            VAULT_STATE.update(deps.storage, |mut vs| -> ContractResult<_> {
+                vs.total_balance = vs.total_balance.checked_add(Int128::try_from(unpaid_fee)?)?;
+                Ok(vs)
+            })?;
+        }
+    }
+
+    record_unpaid_profit(deps.storage, account_id, pnls, unpaid_profit)?;
+
+    Ok((msgs, unpaid_profit))
+}
+
+/// Record the profit which couldn't be paid out as owed to the account, instead of letting the
+/// vault keep it. The realized PnL only reflects what was actually paid: the unpaid amount is
+/// taken off the price PnL of the profitable markets, in the order given.
+pub fn record_unpaid_profit(
+    storage: &mut dyn Storage,
+    account_id: &str,
+    pnls: &[(String, Int128)],
+    unpaid_profit: Uint128,
+) -> ContractResult<()> {
+    if unpaid_profit.is_zero() {
+        return Ok(());
+    }
+
+    UNPAID_PROFITS.update(storage, account_id, |owed| -> ContractResult<_> {
+        Ok(owed.unwrap_or_default().checked_add(unpaid_profit)?)
+    })?;
+
+    let mut remaining = unpaid_profit;
+    for (denom, pnl) in pnls {
+        if remaining.is_zero() {
+            break;
+        }
+        if *pnl <= Int128::zero() {
+            continue;
+        }
+
+        let unpaid = abs(*pnl).min(remaining);
+        remaining -= unpaid;
+
+        let unpaid = Int128::zero().checked_sub(Int128::try_from(unpaid)?)?;
+        let deduction = PnlAmounts {
+            price_pnl: unpaid,
+            pnl: unpaid,
+            ..Default::default()
+        };
+
+        REALIZED_PNL.update(storage, (account_id, denom), |realized| -> ContractResult<_> {
+            let mut realized = realized.unwrap_or_default();
+            realized.add(&deduction)?;
+            Ok(realized)
+        })?;
+
+        // A position that was only reduced keeps its own record of the realized PnL
+        if let Some(mut position) = POSITIONS.may_load(storage, (account_id, denom))? {
+            position.realized_pnl.add(&deduction)?;
+            POSITIONS.save(storage, (account_id, denom), &position)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn assert_position_value(
+    perp_params: &PerpParams,
+    new_size: Int128,
+    prices: &MarketPrices,
+    increases_exposure: bool,
+) -> ContractResult<()> {
+    let value = position_value(new_size, prices.denom_price)?;
+
+    if value < perp_params.min_position_value {
+        return Err(ContractError::PositionTooSmall {
+            min: perp_params.min_position_value,
+            found: value,
+        });
+    }
+
+    if let Some(max) = perp_params.max_position_value {
+        if increases_exposure && value > max {
+            return Err(ContractError::PositionTooBig {
+                max,
+                found: value,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Open interest caps are only enforced on the sides that grow with the order
+fn assert_open_interest(
+    perp_params: &PerpParams,
+    before: &MarketState,
+    after: &MarketState,
+    prices: &MarketPrices,
+) -> ContractResult<()> {
+    if after.long_oi > before.long_oi {
+        let long_oi_value = after.long_oi.checked_mul_floor(prices.denom_price)?;
+        if long_oi_value > perp_params.max_long_oi_value {
+            return Err(ContractError::LongOpenInterestReached {
+                max: perp_params.max_long_oi_value,
+                found: long_oi_value,
+            });
+        }
+    }
+
+    if after.short_oi > before.short_oi {
+        let short_oi_value = after.short_oi.checked_mul_floor(prices.denom_price)?;
+        if short_oi_value > perp_params.max_short_oi_value {
+            return Err(ContractError::ShortOpenInterestReached {
+                max: perp_params.max_short_oi_value,
+                found: short_oi_value,
+            });
+        }
+    }
+
+    let net_oi_before = abs(before.skew()?);
+    let net_oi_after = abs(after.skew()?);
+    if net_oi_after > net_oi_before {
+        let net_oi_value = net_oi_after.checked_mul_floor(prices.denom_price)?;
+        if net_oi_value > perp_params.max_net_oi_value {
+            return Err(ContractError::NetOpenInterestReached {
+                max: perp_params.max_net_oi_value,
+                found: net_oi_value,
+            });
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,384 @@
+use cosmwasm_std::{Addr, Coin, Deps, Env, Int128, Order, Uint128};
+use cw_paginate::{paginate_map_query, PaginationResponse};
+use cw_storage_plus::Bound;
+use mars_types::{
+    adapters::{oracle::Oracle, params::Params},
+    address_provider::MarsAddressType,
+    oracle::ActionKind,
+    perps::{
+        AccountingResponse, CashFlow, Config, MarketResponse, MarketState, MarketStateResponse,
+        PerpPosition, PnlAmounts, Position, PositionFeesResponse, PositionResponse,
+        PositionsByAccountResponse, TradingFee,
+    },
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    market::MarketStateExt,
+    position::{compute_pnl, opened_and_closed_sizes, trading_fee},
+    position_management::assert_reduce_only,
+    state::{CONFIG, MARKET_STATES, POSITIONS, REALIZED_PNL, UNPAID_PROFITS},
+    utils::{query_contract_addrs, query_prices, MarketPrices},
+    vault::query_oracle,
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+pub fn query_market_state(deps: Deps, denom: String) -> ContractResult<MarketStateResponse> {
+    let ms = load_market_state(deps, &denom)?;
+    Ok(MarketStateResponse {
+        denom,
+        enabled: ms.enabled,
+        long_oi: ms.long_oi,
+        short_oi: ms.short_oi,
+        funding: ms.funding,
+        last_updated: ms.last_updated,
+        total_entry_cost: ms.total_entry_cost,
+        total_entry_funding: ms.total_entry_funding,
+        cash_flow: ms.cash_flow,
+    })
+}
+
+pub fn query_market(deps: Deps, env: Env, denom: String) -> ContractResult<MarketResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = query_oracle(deps, &config)?;
+    let ms = load_market_state(deps, &denom)?;
+    market_response(deps, &env, &oracle, denom, ms)
+}
+
+pub fn query_markets(
+    deps: Deps,
+    env: Env,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PaginationResponse<MarketResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = query_oracle(deps, &config)?;
+
+    let start = start_after.as_deref().map(Bound::exclusive);
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    paginate_map_query(&MARKET_STATES, deps.storage, start, Some(limit), |denom, ms| {
+        market_response(deps, &env, &oracle, denom, ms)
+    })
+}
+
+pub fn query_position(
+    deps: Deps,
+    env: Env,
+    account_id: String,
+    denom: String,
+    order_size: Option<Int128>,
+    reduce_only: Option<bool>,
+) -> ContractResult<PositionResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let Some(position) = POSITIONS.may_load(deps.storage, (account_id.as_str(), denom.as_str()))?
+    else {
+        return Ok(PositionResponse {
+            account_id,
+            position: None,
+        });
+    };
+
+    if let Some(order_size) = order_size {
+        assert_reduce_only(reduce_only, position.size, order_size)?;
+    }
+
+    let (oracle, params) = query_oracle_and_params(deps, &config)?;
+    let ms = load_market_state(deps, &denom)?;
+    let prices =
+        query_prices(&deps.querier, &oracle, &denom, &config.base_denom, ActionKind::Default)?;
+
+    // without an order the position is valued as if it was fully closed
+    let new_size = position.size.checked_add(order_size.unwrap_or(-position.size))?;
+    let perp_position =
+        perp_position(deps, &env, &config, &params, denom, position, ms, &prices, new_size)?;
+
+    Ok(PositionResponse {
+        account_id,
+        position: Some(perp_position),
+    })
+}
+
+pub fn query_positions(
+    deps: Deps,
+    env: Env,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> ContractResult<Vec<PositionResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    let (oracle, params) = query_oracle_and_params(deps, &config)?;
+
+    let start = start_after
+        .as_ref()
+        .map(|(account_id, denom)| Bound::exclusive((account_id.as_str(), denom.as_str())));
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    POSITIONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let ((account_id, denom), position) = item?;
+            let ms = load_market_state(deps, &denom)?;
+            let prices = query_prices(
+                &deps.querier,
+                &oracle,
+                &denom,
+                &config.base_denom,
+                ActionKind::Default,
+            )?;
+            Ok(PositionResponse {
+                account_id,
+                position: Some(perp_position(
+                    deps,
+                    &env,
+                    &config,
+                    &params,
+                    denom,
+                    position,
+                    ms,
+                    &prices,
+                    Int128::zero(),
+                )?),
+            })
+        })
+        .collect()
+}
+
+pub fn query_positions_by_account(
+    deps: Deps,
+    env: Env,
+    account_id: String,
+    action: ActionKind,
+) -> ContractResult<PositionsByAccountResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (oracle, params) = query_oracle_and_params(deps, &config)?;
+
+    let positions = POSITIONS
+        .prefix(&account_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, position) = item?;
+            let ms = load_market_state(deps, &denom)?;
+            let prices =
+                query_prices(&deps.querier, &oracle, &denom, &config.base_denom, action.clone())?;
+            perp_position(
+                deps,
+                &env,
+                &config,
+                &params,
+                denom,
+                position,
+                ms,
+                &prices,
+                Int128::zero(),
+            )
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+
+    Ok(PositionsByAccountResponse {
+        account_id,
+        positions,
+    })
+}
+
+pub fn query_market_accounting(
+    deps: Deps,
+    env: Env,
+    denom: String,
+) -> ContractResult<AccountingResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = query_oracle(deps, &config)?;
+    let ms = load_market_state(deps, &denom)?;
+    market_accounting(deps, &env, &config, &oracle, &denom, ms)
+}
+
+pub fn query_total_accounting(deps: Deps, env: Env) -> ContractResult<AccountingResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let oracle = query_oracle(deps, &config)?;
+
+    let mut cash_flow = CashFlow::default();
+    let mut unrealized_pnl = PnlAmounts::default();
+    for item in MARKET_STATES.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, ms) = item?;
+        let accounting = market_accounting(deps, &env, &config, &oracle, &denom, ms)?;
+
+        cash_flow.price_pnl = cash_flow.price_pnl.checked_add(accounting.cash_flow.price_pnl)?;
+        cash_flow.opening_fee =
+            cash_flow.opening_fee.checked_add(accounting.cash_flow.opening_fee)?;
+        cash_flow.closing_fee =
+            cash_flow.closing_fee.checked_add(accounting.cash_flow.closing_fee)?;
+        cash_flow.accrued_funding =
+            cash_flow.accrued_funding.checked_add(accounting.cash_flow.accrued_funding)?;
+        cash_flow.protocol_fee =
+            cash_flow.protocol_fee.checked_add(accounting.cash_flow.protocol_fee)?;
+
+        unrealized_pnl.add(&accounting.unrealized_pnl)?;
+    }
+
+    Ok(AccountingResponse {
+        cash_flow,
+        unrealized_pnl,
+    })
+}
+
+pub fn query_realized_pnl_by_account_and_market(
+    deps: Deps,
+    account_id: String,
+    denom: String,
+) -> ContractResult<PnlAmounts> {
+    Ok(REALIZED_PNL
+        .may_load(deps.storage, (account_id.as_str(), denom.as_str()))?
+        .unwrap_or_default())
+}
+
+pub fn query_unpaid_profit(deps: Deps, account_id: String) -> ContractResult<Uint128> {
+    Ok(UNPAID_PROFITS.may_load(deps.storage, &account_id)?.unwrap_or_default())
+}
+
+pub fn query_opening_fee(deps: Deps, denom: String, size: Int128) -> ContractResult<TradingFee> {
+    let config = CONFIG.load(deps.storage)?;
+    let (oracle, params) = query_oracle_and_params(deps, &config)?;
+
+    let perp_params = params.query_perp_params(&deps.querier, &denom)?;
+    let prices =
+        query_prices(&deps.querier, &oracle, &denom, &config.base_denom, ActionKind::Default)?;
+
+    let (opened, _) = opened_and_closed_sizes(Int128::zero(), size);
+    let fee = trading_fee(opened, prices.price, perp_params.opening_fee_rate)?;
+
+    Ok(TradingFee {
+        rate: perp_params.opening_fee_rate,
+        fee: Coin {
+            denom: config.base_denom,
+            amount: fee,
+        },
+    })
+}
+
+pub fn query_position_fees(
+    deps: Deps,
+    account_id: String,
+    denom: String,
+    new_size: Int128,
+) -> ContractResult<PositionFeesResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let (oracle, params) = query_oracle_and_params(deps, &config)?;
+
+    let perp_params = params.query_perp_params(&deps.querier, &denom)?;
+    let prices =
+        query_prices(&deps.querier, &oracle, &denom, &config.base_denom, ActionKind::Default)?;
+
+    let old_size = POSITIONS
+        .may_load(deps.storage, (account_id.as_str(), denom.as_str()))?
+        .map(|position| position.size)
+        .unwrap_or_default();
+    let (opened, closed) = opened_and_closed_sizes(old_size, new_size);
+
+    Ok(PositionFeesResponse {
+        base_denom: config.base_denom,
+        opening_fee: trading_fee(opened, prices.price, perp_params.opening_fee_rate)?,
+        closing_fee: trading_fee(closed, prices.price, perp_params.closing_fee_rate)?,
+    })
+}
+
+fn load_market_state(deps: Deps, denom: &str) -> ContractResult<MarketState> {
+    MARKET_STATES.may_load(deps.storage, denom)?.ok_or_else(|| ContractError::MarketNotFound {
+        denom: denom.to_string(),
+    })
+}
+
+fn query_oracle_and_params(deps: Deps, config: &Config<Addr>) -> ContractResult<(Oracle, Params)> {
+    let addrs =
+        query_contract_addrs(deps, config, vec![MarsAddressType::Oracle, MarsAddressType::Params])?;
+    Ok((
+        Oracle::new(addrs[&MarsAddressType::Oracle].clone()),
+        Params::new(addrs[&MarsAddressType::Params].clone()),
+    ))
+}
+
+fn market_response(
+    deps: Deps,
+    env: &Env,
+    oracle: &Oracle,
+    denom: String,
+    ms: MarketState,
+) -> ContractResult<MarketResponse> {
+    let denom_price = oracle.query_price(&deps.querier, &denom, ActionKind::Default)?.price;
+
+    Ok(MarketResponse {
+        denom,
+        enabled: ms.enabled,
+        long_oi: ms.long_oi,
+        long_oi_value: ms.long_oi.checked_mul_floor(denom_price)?,
+        short_oi: ms.short_oi,
+        short_oi_value: ms.short_oi.checked_mul_floor(denom_price)?,
+        current_funding_rate: ms.current_funding_rate(env.block.time.seconds())?,
+    })
+}
+
+fn market_accounting(
+    deps: Deps,
+    env: &Env,
+    config: &Config<Addr>,
+    oracle: &Oracle,
+    denom: &str,
+    mut ms: MarketState,
+) -> ContractResult<AccountingResponse> {
+    let unrealized_pnl = if ms.long_oi.is_zero() && ms.short_oi.is_zero() {
+        PnlAmounts::default()
+    } else {
+        let prices =
+            query_prices(&deps.querier, oracle, denom, &config.base_denom, ActionKind::Default)?;
+        ms.accrue_funding(env.block.time.seconds(), prices.price)?;
+        ms.unrealized_pnl(prices.price)?
+    };
+
+    Ok(AccountingResponse {
+        cash_flow: ms.cash_flow,
+        unrealized_pnl,
+    })
+}
+
+/// Value a position as if it was moved to `new_size`, i.e. the returned unrealized PnL is what
+/// would be realized by that order (fees included).
+#[allow(clippy::too_many_arguments)]
+fn perp_position(
+    deps: Deps,
+    env: &Env,
+    config: &Config<Addr>,
+    params: &Params,
+    denom: String,
+    position: Position,
+    mut ms: MarketState,
+    prices: &MarketPrices,
+    new_size: Int128,
+) -> ContractResult<PerpPosition> {
+    let perp_params = params.query_perp_params(&deps.querier, &denom)?;
+
+    ms.accrue_funding(env.block.time.seconds(), prices.price)?;
+
+    let unrealized_pnl = compute_pnl(
+        Some(&position),
+        new_size,
+        prices.price,
+        ms.funding.last_funding_accrued_per_unit_in_base_denom,
+        perp_params.opening_fee_rate,
+        perp_params.closing_fee_rate,
+    )?;
+
+    Ok(PerpPosition {
+        denom,
+        base_denom: config.base_denom.clone(),
+        size: position.size,
+        entry_price: position.entry_price,
+        current_price: prices.price,
+        denom_price: prices.denom_price,
+        entry_accrued_funding_per_unit_in_base_denom: position
+            .entry_accrued_funding_per_unit_in_base_denom,
+        unrealized_pnl,
+        realized_pnl: position.realized_pnl,
+    })
+}
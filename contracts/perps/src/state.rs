@@ -0,0 +1,28 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map};
+use mars_owner::Owner;
+use mars_types::perps::{Config, MarketState, PnlAmounts, Position, UnlockState, VaultState};
+
+pub const OWNER: Owner = Owner::new("owner");
+
+pub const CONFIG: Item<Config<Addr>> = Item::new("config");
+
+pub const VAULT_STATE: Item<VaultState> = Item::new("vault_state");
+
+// denom => market state
+pub const MARKET_STATES: Map<&str, MarketState> = Map::new("market_states");
+
+// (account_id, denom) => position
+pub const POSITIONS: Map<(&str, &str), Position> = Map::new("positions");
+
+// (account_id, denom) => realized PnL, kept after the position is closed
+pub const REALIZED_PNL: Map<(&str, &str), PnlAmounts> = Map::new("realized_pnls");
+
+// account_id => profit realized but not paid out, because the contract was short on liquidity
+pub const UNPAID_PROFITS: Map<&str, Uint128> = Map::new("unpaid_profits");
+
+// vault user id (account id or wallet address) => shares
+pub const DEPOSIT_SHARES: Map<&str, Uint128> = Map::new("deposit_shares");
+
+// vault user id (account id or wallet address) => unlocks
+pub const UNLOCKS: Map<&str, Vec<UnlockState>> = Map::new("unlocks");
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{
+    Addr, Decimal, Deps, Int128, QuerierWrapper, SignedDecimal, StdResult, Uint128,
+};
+use mars_types::{
+    adapters::oracle::Oracle,
+    address_provider::{self, MarsAddressType},
+    oracle::ActionKind,
+    perps::Config,
+};
+
+use crate::error::{ContractError, ContractResult};
+
+/// Prices of a perp denom
+pub struct MarketPrices {
+    /// Price denominated in the oracle base denom
+    pub denom_price: Decimal,
+    /// Price denominated in the perps base denom
+    pub price: Decimal,
+}
+
+pub fn query_prices(
+    querier: &QuerierWrapper,
+    oracle: &Oracle,
+    denom: &str,
+    base_denom: &str,
+    action: ActionKind,
+) -> ContractResult<MarketPrices> {
+    let denom_price = oracle.query_price(querier, denom, action.clone())?.price;
+    let base_denom_price = oracle.query_price(querier, base_denom, action)?.price;
+    Ok(MarketPrices {
+        denom_price,
+        price: denom_price.checked_div(base_denom_price)?,
+    })
+}
+
+pub fn query_contract_addrs(
+    deps: Deps,
+    config: &Config<Addr>,
+    contracts: Vec<MarsAddressType>,
+) -> StdResult<HashMap<MarsAddressType, Addr>> {
+    address_provider::helpers::query_contract_addrs(deps, &config.address_provider, contracts)
+}
+
+/// Assert that the sender is the expected protocol contract
+pub fn assert_sender_is_contract(
+    deps: Deps,
+    config: &Config<Addr>,
+    sender: &Addr,
+    contract: MarsAddressType,
+) -> ContractResult<()> {
+    let expected =
+        address_provider::helpers::query_contract_addr(deps, &config.address_provider, contract)?;
+    if sender != expected {
+        return Err(ContractError::SenderIsNotExpectedContract {
+            expected: contract.to_string(),
+            actual: sender.to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub fn uint_to_int(value: Uint128) -> ContractResult<Int128> {
+    Ok(Int128::try_from(value)?)
+}
+
+/// Negated value of an unsigned amount, e.g. a fee paid by the trader
+pub fn negate(value: Uint128) -> ContractResult<Int128> {
+    Ok(Int128::zero().checked_sub(uint_to_int(value)?)?)
+}
+
+pub fn abs(value: Int128) -> Uint128 {
+    Uint128::new(value.i128().unsigned_abs())
+}
+
+pub fn int_to_signed_decimal(value: Int128) -> ContractResult<SignedDecimal> {
+    Ok(SignedDecimal::checked_from_ratio(value, Int128::new(1))?)
+}
+
+pub fn decimal_to_signed_decimal(value: Decimal) -> ContractResult<SignedDecimal> {
+    Ok(SignedDecimal::try_from(value)?)
+}
+
+/// Value of the given size (in perp denom units) at the given price
+pub fn position_value(size: Int128, price: Decimal) -> ContractResult<Uint128> {
+    Ok(abs(size).checked_mul_floor(price)?)
+}
+
+pub fn int_to_uint(value: Int128) -> ContractResult<Uint128> {
+    Ok(Uint128::try_from(value)?)
+}
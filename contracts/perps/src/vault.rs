@@ -0,0 +1,351 @@
+use cosmwasm_std::{
+    coins, Addr, BankMsg, Decimal, Deps, DepsMut, Env, Int128, MessageInfo, Order, Response,
+    Uint128,
+};
+use mars_types::{
+    adapters::oracle::Oracle,
+    address_provider::{self, MarsAddressType},
+    oracle::ActionKind,
+    perps::{Config, UnlockState, VaultDeposit, VaultPositionResponse, VaultResponse, VaultUnlock},
+};
+
+use crate::{
+    error::{ContractError, ContractResult},
+    market::MarketStateExt,
+    state::{CONFIG, DEPOSIT_SHARES, MARKET_STATES, UNLOCKS, VAULT_STATE},
+    utils::{int_to_uint, query_prices},
+};
+
+/// Number of shares minted per unit of base denom for the first deposit
+pub const DEFAULT_SHARES_PER_AMOUNT: Uint128 = Uint128::new(1_000_000);
+
+/// Compute the value of the vault.
+///
+/// The liquidity of the vault consists of the net deposits plus the realized cash flows of all
+/// markets. Since the vault is the counterparty of all traders, the unrealized PnL of open
+/// positions is a liability of the vault and is subtracted from the liquidity to get the value
+/// used for share accounting.
+pub fn compute_vault(
+    deps: Deps,
+    env: &Env,
+    config: &Config<Addr>,
+    oracle: &Oracle,
+    action: ActionKind,
+) -> ContractResult<VaultResponse> {
+    let vault_state = VAULT_STATE.load(deps.storage)?;
+    let current_time = env.block.time.seconds();
+
+    let mut total_cash_flow = Int128::zero();
+    let mut total_unrealized_pnl = Int128::zero();
+    for item in MARKET_STATES.range(deps.storage, None, None, Order::Ascending) {
+        let (denom, mut ms) = item?;
+
+        total_cash_flow = total_cash_flow.checked_add(ms.cash_flow.total()?)?;
+
+        if ms.long_oi.is_zero() && ms.short_oi.is_zero() {
+            continue;
+        }
+
+        let prices =
+            query_prices(&deps.querier, oracle, &denom, &config.base_denom, action.clone())?;
+        ms.accrue_funding(current_time, prices.price)?;
+        total_unrealized_pnl =
+            total_unrealized_pnl.checked_add(ms.unrealized_pnl(prices.price)?.pnl)?;
+    }
+
+    let total_liquidity = vault_state.total_balance.checked_add(total_cash_flow)?;
+    let total_value =
+        int_to_uint(total_liquidity.checked_sub(total_unrealized_pnl)?.max(Int128::zero()))?;
+
+    let share_price = if vault_state.total_shares.is_zero() {
+        None
+    } else {
+        Some(Decimal::checked_from_ratio(total_value, vault_state.total_shares)?)
+    };
+
+    Ok(VaultResponse {
+        total_balance: vault_state.total_balance,
+        total_shares: vault_state.total_shares,
+        total_liquidity,
+        total_unrealized_pnl,
+        total_value,
+        share_price,
+        collateralization_ratio: collateralization_ratio(total_liquidity, total_unrealized_pnl)?,
+    })
+}
+
+/// Ratio between the vault liquidity and the unrealized profits of traders.
+/// None if traders are not in profit.
+pub fn collateralization_ratio(
+    total_liquidity: Int128,
+    total_unrealized_pnl: Int128,
+) -> ContractResult<Option<Decimal>> {
+    if total_unrealized_pnl <= Int128::zero() {
+        return Ok(None);
+    }
+
+    let liquidity = int_to_uint(total_liquidity.max(Int128::zero()))?;
+    Ok(Some(Decimal::checked_from_ratio(liquidity, int_to_uint(total_unrealized_pnl)?)?))
+}
+
+/// Vault deposits made through the Credit Manager are tracked by account id, other deposits by
+/// the depositor's address.
+fn vault_user_id(
+    deps: Deps,
+    config: &Config<Addr>,
+    sender: &Addr,
+    account_id: Option<String>,
+) -> ContractResult<String> {
+    match account_id {
+        Some(account_id) => {
+            let credit_manager = address_provider::helpers::query_contract_addr(
+                deps,
+                &config.address_provider,
+                MarsAddressType::CreditManager,
+            )?;
+            if sender != credit_manager {
+                return Err(ContractError::AccountIdNotAllowed);
+            }
+            Ok(account_id)
+        }
+        None => Ok(sender.to_string()),
+    }
+}
+
+pub fn query_oracle(deps: Deps, config: &Config<Addr>) -> ContractResult<Oracle> {
+    let oracle_addr = address_provider::helpers::query_contract_addr(
+        deps,
+        &config.address_provider,
+        MarsAddressType::Oracle,
+    )?;
+    Ok(Oracle::new(oracle_addr))
+}
+
+pub fn deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: Option<String>,
+    max_shares_receivable: Option<Uint128>,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let user_id = vault_user_id(deps.as_ref(), &config, &info.sender, account_id)?;
+    let amount = cw_utils::must_pay(&info, &config.base_denom)?;
+
+    let oracle = query_oracle(deps.as_ref(), &config)?;
+    let vault = compute_vault(deps.as_ref(), &env, &config, &oracle, ActionKind::Default)?;
+
+    let shares = if vault.total_shares.is_zero() {
+        amount.checked_mul(DEFAULT_SHARES_PER_AMOUNT)?
+    } else {
+        if vault.total_value.is_zero() {
+            return Err(ContractError::VaultInsolvent);
+        }
+        vault.total_shares.checked_multiply_ratio(amount, vault.total_value)?
+    };
+
+    if let Some(max_shares_receivable) = max_shares_receivable {
+        if shares > max_shares_receivable {
+            return Err(ContractError::MaximumSharesExceeded {
+                shares,
+                max_shares_receivable,
+            });
+        }
+    }
+
+    VAULT_STATE.update(deps.storage, |mut vs| -> ContractResult<_> {
+        vs.total_balance = vs.total_balance.checked_add(Int128::try_from(amount)?)?;
+        vs.total_shares = vs.total_shares.checked_add(shares)?;
+        Ok(vs)
+    })?;
+
+    DEPOSIT_SHARES.update(deps.storage, &user_id, |current| -> ContractResult<_> {
+        Ok(current.unwrap_or_default().checked_add(shares)?)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("user_id", user_id)
+        .add_attribute("amount", amount)
+        .add_attribute("shares", shares))
+}
+
+pub fn unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: Option<String>,
+    shares: Uint128,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let user_id = vault_user_id(deps.as_ref(), &config, &info.sender, account_id)?;
+
+    if shares.is_zero() {
+        return Err(ContractError::ZeroShares);
+    }
+
+    let available = DEPOSIT_SHARES.may_load(deps.storage, &user_id)?.unwrap_or_default();
+    if shares > available {
+        return Err(ContractError::InsufficientShares {
+            available,
+            requested: shares,
+        });
+    }
+
+    let mut unlocks = UNLOCKS.may_load(deps.storage, &user_id)?.unwrap_or_default();
+    if unlocks.len() >= config.max_unlocks as usize {
+        return Err(ContractError::MaxUnlocksReached {
+            max_unlocks: config.max_unlocks,
+        });
+    }
+
+    let remaining = available - shares;
+    if remaining.is_zero() {
+        DEPOSIT_SHARES.remove(deps.storage, &user_id);
+    } else {
+        DEPOSIT_SHARES.save(deps.storage, &user_id, &remaining)?;
+    }
+
+    let current_time = env.block.time.seconds();
+    let cooldown_end = current_time + config.cooldown_period;
+    unlocks.push(UnlockState {
+        created_at: current_time,
+        cooldown_end,
+        shares,
+    });
+    UNLOCKS.save(deps.storage, &user_id, &unlocks)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "unlock")
+        .add_attribute("user_id", user_id)
+        .add_attribute("shares", shares)
+        .add_attribute("created_at", current_time.to_string())
+        .add_attribute("cooldown_end", cooldown_end.to_string()))
+}
+
+pub fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    account_id: Option<String>,
+    min_receive: Option<Uint128>,
+) -> ContractResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if !config.vault_withdraw_enabled {
+        return Err(ContractError::VaultWithdrawDisabled);
+    }
+
+    let user_id = vault_user_id(deps.as_ref(), &config, &info.sender, account_id)?;
+
+    let current_time = env.block.time.seconds();
+    let unlocks = UNLOCKS.may_load(deps.storage, &user_id)?.unwrap_or_default();
+    let (unlocked, unlocking): (Vec<_>, Vec<_>) =
+        unlocks.into_iter().partition(|unlock| unlock.cooldown_end <= current_time);
+
+    if unlocked.is_empty() {
+        return Err(ContractError::UnlockedPositionsNotFound);
+    }
+
+    let shares = unlocked.iter().map(|unlock| unlock.shares).sum::<Uint128>();
+
+    let oracle = query_oracle(deps.as_ref(), &config)?;
+    let vault = compute_vault(deps.as_ref(), &env, &config, &oracle, ActionKind::Default)?;
+    let amount = vault.total_value.checked_multiply_ratio(shares, vault.total_shares)?;
+
+    if let Some(min_receive) = min_receive {
+        if amount < min_receive {
+            return Err(ContractError::MinimumReceiveExceeded {
+                min_receive,
+                received: amount,
+            });
+        }
+    }
+
+    // the vault has to stay sufficiently collateralized to cover traders' profits
+    let liquidity_after = vault.total_liquidity.checked_sub(Int128::try_from(amount)?)?;
+    if let Some(cr) = collateralization_ratio(liquidity_after, vault.total_unrealized_pnl)? {
+        if cr < config.target_vault_collateralization_ratio {
+            return Err(ContractError::VaultUndercollateralized {
+                current: cr,
+                target: config.target_vault_collateralization_ratio,
+            });
+        }
+    }
+
+    VAULT_STATE.update(deps.storage, |mut vs| -> ContractResult<_> {
+        vs.total_balance = vs.total_balance.checked_sub(Int128::try_from(amount)?)?;
+        vs.total_shares = vs.total_shares.checked_sub(shares)?;
+        Ok(vs)
+    })?;
+
+    if unlocking.is_empty() {
+        UNLOCKS.remove(deps.storage, &user_id);
+    } else {
+        UNLOCKS.save(deps.storage, &user_id, &unlocking)?;
+    }
+
+    let mut response = Response::new();
+    if !amount.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: coins(amount.u128(), &config.base_denom),
+        });
+    }
+
+    Ok(response
+        .add_attribute("action", "withdraw")
+        .add_attribute("user_id", user_id)
+        .add_attribute("shares", shares)
+        .add_attribute("amount", amount))
+}
+
+pub fn query_vault_position(
+    deps: Deps,
+    env: Env,
+    user_address: String,
+    account_id: Option<String>,
+) -> ContractResult<Option<VaultPositionResponse>> {
+    let config = CONFIG.load(deps.storage)?;
+    let user_id = account_id.unwrap_or(user_address);
+
+    let deposit_shares = DEPOSIT_SHARES.may_load(deps.storage, &user_id)?;
+    let unlocks = UNLOCKS.may_load(deps.storage, &user_id)?;
+    if deposit_shares.is_none() && unlocks.is_none() {
+        return Ok(None);
+    }
+
+    let oracle = query_oracle(deps, &config)?;
+    let vault = compute_vault(deps, &env, &config, &oracle, ActionKind::Default)?;
+    let shares_to_amount = |shares: Uint128| -> ContractResult<Uint128> {
+        if vault.total_shares.is_zero() {
+            return Ok(Uint128::zero());
+        }
+        Ok(vault.total_value.checked_multiply_ratio(shares, vault.total_shares)?)
+    };
+
+    let shares = deposit_shares.unwrap_or_default();
+    let deposit = VaultDeposit {
+        shares,
+        amount: shares_to_amount(shares)?,
+    };
+
+    let unlocks = unlocks
+        .unwrap_or_default()
+        .into_iter()
+        .map(|unlock| {
+            Ok(VaultUnlock {
+                created_at: unlock.created_at,
+                cooldown_end: unlock.cooldown_end,
+                shares: unlock.shares,
+                amount: shares_to_amount(unlock.shares)?,
+            })
+        })
+        .collect::<ContractResult<Vec<_>>>()?;
+
+    Ok(Some(VaultPositionResponse {
+        denom: config.base_denom,
+        deposit,
+        unlocks,
+    }))
+}
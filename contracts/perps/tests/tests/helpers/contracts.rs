@@ -0,0 +1,20 @@
+use cosmwasm_std::Empty;
+use cw_multi_test::{Contract, ContractWrapper};
+
+pub fn mock_oracle_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mars_oracle_osmosis::contract::entry::execute,
+        mars_oracle_osmosis::contract::entry::instantiate,
+        mars_oracle_osmosis::contract::entry::query,
+    );
+    Box::new(contract)
+}
+
+pub fn mock_perps_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mars_perps::contract::execute,
+        mars_perps::contract::instantiate,
+        mars_perps::contract::query,
+    );
+    Box::new(contract)
+}
@@ -479,6 +479,18 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_unpaid_profit(&self, account_id: &str) -> Uint128 {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.perps.clone(),
+                &perps::QueryMsg::UnpaidProfit {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_opening_fee(&self, denom: &str, size: Int128) -> TradingFee {
         self.app
             .wrap()
@@ -619,6 +631,7 @@ impl MockEnvBuilder {
                     owner: self.deployer.clone().to_string(),
                     risk_manager: None,
                     address_provider: address_provider.to_string(),
+                    target_health_factor: Decimal::percent(105),
                     max_perp_params: 40,
                 },
                 &[],
@@ -0,0 +1,55 @@
+use cosmwasm_std::{Decimal, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Uint128};
+use cw_multi_test::{Contract, ContractWrapper};
+pub use mars_testing::integration::mock_contracts::{
+    mock_address_provider_contract, mock_incentives_contract,
+};
+use mars_types::params::PerpParams;
+
+pub use self::{assertions::*, mock_env::*};
+
+mod assertions;
+mod contracts;
+mod mock_env;
+
+/// Params without fees or limits getting in the way, tests override what they need
+pub fn default_perp_params(denom: &str) -> PerpParams {
+    PerpParams {
+        denom: denom.to_string(),
+        enabled: true,
+        max_net_oi_value: Uint128::new(1_000_000_000),
+        max_long_oi_value: Uint128::new(1_000_000_000),
+        max_short_oi_value: Uint128::new(1_000_000_000),
+        closing_fee_rate: Decimal::zero(),
+        opening_fee_rate: Decimal::zero(),
+        min_position_value: Uint128::zero(),
+        max_position_value: None,
+        max_loan_to_value: Decimal::percent(85),
+        liquidation_threshold: Decimal::percent(90),
+        max_funding_velocity: Decimal::from_ratio(3u128, 1u128),
+        skew_scale: Uint128::new(1_000_000),
+    }
+}
+
+pub fn mock_params_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mars_params::contract::execute,
+        mars_params::contract::instantiate,
+        mars_params::contract::query,
+    );
+    Box::new(contract)
+}
+
+/// The perps contract only cares about the address of the Credit Manager (and that it can receive
+/// funds), so a contract accepting any message is enough.
+pub fn mock_credit_manager_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        |_: Deps, _: Env, _: Empty| -> StdResult<cosmwasm_std::Binary> { Ok(Default::default()) },
+    );
+    Box::new(contract)
+}
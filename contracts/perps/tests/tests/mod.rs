@@ -0,0 +1,5 @@
+mod helpers;
+
+mod test_markets;
+mod test_positions;
+mod test_vault;
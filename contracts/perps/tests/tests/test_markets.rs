@@ -0,0 +1,77 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use mars_perps::error::ContractError;
+use mars_types::params::{EmergencyUpdate, PerpParams, PerpParamsUpdate, PerpsEmergencyUpdate};
+
+use super::helpers::{assert_err, default_perp_params, MockEnv};
+
+#[test]
+fn params_update_creates_market() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.owner.clone();
+
+    mock.update_perp_params(
+        &owner,
+        PerpParamsUpdate::AddOrUpdate {
+            params: default_perp_params("uatom"),
+        },
+    );
+
+    let ms = mock.query_market_state("uatom");
+    assert!(ms.enabled);
+    assert_eq!(ms.long_oi, Uint128::zero());
+    assert_eq!(ms.short_oi, Uint128::zero());
+    assert_eq!(ms.funding.skew_scale, Uint128::new(1_000_000));
+
+    // updating the params updates the funding parameters of the market
+    mock.update_perp_params(
+        &owner,
+        PerpParamsUpdate::AddOrUpdate {
+            params: PerpParams {
+                max_funding_velocity: Decimal::percent(50),
+                skew_scale: Uint128::new(2_000_000),
+                ..default_perp_params("uatom")
+            },
+        },
+    );
+
+    let ms = mock.query_market_state("uatom");
+    assert_eq!(ms.funding.max_funding_velocity, Decimal::percent(50));
+    assert_eq!(ms.funding.skew_scale, Uint128::new(2_000_000));
+}
+
+#[test]
+fn only_params_contract_can_update_market() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.owner.clone();
+
+    let res = mock.update_market(&owner, default_perp_params("uatom"));
+    assert_err(
+        res,
+        ContractError::SenderIsNotExpectedContract {
+            expected: "params".to_string(),
+            actual: owner.to_string(),
+        },
+    );
+}
+
+#[test]
+fn emergency_owner_can_disable_trading() {
+    let mut mock = MockEnv::new().emergency_owner("emergency_owner").build().unwrap();
+    let owner = mock.owner.clone();
+
+    mock.update_perp_params(
+        &owner,
+        PerpParamsUpdate::AddOrUpdate {
+            params: default_perp_params("uatom"),
+        },
+    );
+
+    mock.emergency_params_update(
+        &Addr::unchecked("emergency_owner"),
+        EmergencyUpdate::Perps(PerpsEmergencyUpdate::DisableTrading("uatom".to_string())),
+    )
+    .unwrap();
+
+    assert!(!mock.query_perp_params("uatom").enabled);
+    assert!(!mock.query_market_state("uatom").enabled);
+}
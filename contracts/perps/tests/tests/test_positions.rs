@@ -0,0 +1,252 @@
+use cosmwasm_std::{coin, Addr, Decimal, Int128, Uint128};
+use mars_perps::error::ContractError;
+use mars_types::params::{PerpParams, PerpParamsUpdate};
+
+use super::helpers::{assert_err, default_perp_params, MockEnv};
+
+fn setup(params: PerpParams) -> MockEnv {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.owner.clone();
+    let depositor = Addr::unchecked("depositor");
+    let cm = mock.credit_manager.clone();
+
+    mock.set_price(&owner, "uusdc", Decimal::one()).unwrap();
+    mock.set_price(&owner, &params.denom, Decimal::from_ratio(10u128, 1u128)).unwrap();
+    mock.update_perp_params(
+        &owner,
+        PerpParamsUpdate::AddOrUpdate {
+            params,
+        },
+    );
+
+    mock.fund_account(&depositor, &[coin(1_000_000, "uusdc")]);
+    mock.deposit_to_vault(&depositor, None, None, &[coin(1_000_000, "uusdc")]).unwrap();
+    mock.fund_account(&cm, &[coin(1_000_000, "uusdc")]);
+
+    mock
+}
+
+#[test]
+fn only_credit_manager_can_execute_orders() {
+    let mut mock = setup(default_perp_params("uatom"));
+    let user = Addr::unchecked("user");
+
+    let res = mock.execute_perp_order(&user, "1", "uatom", Int128::new(100), None, &[]);
+    assert_err(
+        res,
+        ContractError::SenderIsNotExpectedContract {
+            expected: "credit_manager".to_string(),
+            actual: user.to_string(),
+        },
+    );
+}
+
+#[test]
+fn close_position_with_profit() {
+    let mut mock = setup(default_perp_params("uatom"));
+    let owner = mock.owner.clone();
+    let cm = mock.credit_manager.clone();
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]).unwrap();
+
+    let ms = mock.query_market_state("uatom");
+    assert_eq!(ms.long_oi, Uint128::new(100));
+    assert_eq!(ms.short_oi, Uint128::zero());
+
+    mock.set_price(&owner, "uatom", Decimal::from_ratio(12u128, 1u128)).unwrap();
+
+    let position = mock.query_position("1", "uatom").position.unwrap();
+    assert_eq!(position.size, Int128::new(100));
+    assert_eq!(position.entry_price, Decimal::from_ratio(10u128, 1u128));
+    assert_eq!(position.current_price, Decimal::from_ratio(12u128, 1u128));
+    assert_eq!(position.unrealized_pnl.price_pnl, Int128::new(200));
+    assert_eq!(position.unrealized_pnl.pnl, Int128::new(200));
+
+    let vault = mock.query_vault();
+    assert_eq!(vault.total_unrealized_pnl, Int128::new(200));
+    assert_eq!(vault.total_value, Uint128::new(999_800));
+    assert_eq!(vault.collateralization_ratio, Some(Decimal::from_ratio(1_000_000u128, 200u128)));
+
+    let balance_before = mock.query_balance(&cm, "uusdc").amount;
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-100), None, &[]).unwrap();
+    let balance_after = mock.query_balance(&cm, "uusdc").amount;
+    assert_eq!(balance_after - balance_before, Uint128::new(200));
+
+    assert!(mock.query_position("1", "uatom").position.is_none());
+    let realized_pnl = mock.query_realized_pnl_by_account_and_market("1", "uatom");
+    assert_eq!(realized_pnl.pnl, Int128::new(200));
+
+    let accounting = mock.query_market_accounting("uatom");
+    assert_eq!(accounting.cash_flow.price_pnl, Int128::new(-200));
+    assert_eq!(mock.query_vault().total_liquidity, Int128::new(999_800));
+}
+
+#[test]
+fn profit_is_capped_at_available_liquidity() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let owner = mock.owner.clone();
+    let depositor = Addr::unchecked("depositor");
+    let cm = mock.credit_manager.clone();
+
+    mock.set_price(&owner, "uusdc", Decimal::one()).unwrap();
+    mock.set_price(&owner, "uatom", Decimal::from_ratio(10u128, 1u128)).unwrap();
+    mock.update_perp_params(
+        &owner,
+        PerpParamsUpdate::AddOrUpdate {
+            params: default_perp_params("uatom"),
+        },
+    );
+
+    mock.fund_account(&depositor, &[coin(100, "uusdc")]);
+    mock.deposit_to_vault(&depositor, None, None, &[coin(100, "uusdc")]).unwrap();
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]).unwrap();
+    mock.set_price(&owner, "uatom", Decimal::from_ratio(12u128, 1u128)).unwrap();
+
+    // The profit of 200 exceeds the 100 held by the vault, closing still succeeds
+    let balance_before = mock.query_balance(&cm, "uusdc").amount;
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-100), None, &[]).unwrap();
+    let balance_after = mock.query_balance(&cm, "uusdc").amount;
+    assert_eq!(balance_after - balance_before, Uint128::new(100));
+
+    let attr = res
+        .events
+        .iter()
+        .flat_map(|event| event.attributes.iter())
+        .find(|attr| attr.key == "unpaid_profit")
+        .unwrap();
+    assert_eq!(attr.value, "100");
+
+    assert!(mock.query_position("1", "uatom").position.is_none());
+    assert_eq!(mock.query_balance(&mock.perps, "uusdc").amount, Uint128::zero());
+
+    // The rest is owed to the account, the vault doesn't keep it
+    assert_eq!(mock.query_unpaid_profit("1"), Uint128::new(100));
+    let realized_pnl = mock.query_realized_pnl_by_account_and_market("1", "uatom");
+    assert_eq!(realized_pnl.price_pnl, Int128::new(100));
+    assert_eq!(realized_pnl.pnl, Int128::new(100));
+    assert_eq!(mock.query_vault().total_liquidity, Int128::new(-100));
+}
+
+#[test]
+fn loss_has_to_be_paid() {
+    let mut mock = setup(default_perp_params("uatom"));
+    let owner = mock.owner.clone();
+    let cm = mock.credit_manager.clone();
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]).unwrap();
+    mock.set_price(&owner, "uatom", Decimal::from_ratio(8u128, 1u128)).unwrap();
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-100), None, &[]);
+    assert_err(
+        res,
+        ContractError::InvalidPayment {
+            denom: "uusdc".to_string(),
+            expected: Uint128::new(200),
+            received: Uint128::zero(),
+        },
+    );
+
+    mock.close_all_positions(&cm, "1", &[coin(200, "uusdc")]).unwrap();
+
+    assert!(mock.query_position("1", "uatom").position.is_none());
+    let accounting = mock.query_market_accounting("uatom");
+    assert_eq!(accounting.cash_flow.price_pnl, Int128::new(200));
+    assert_eq!(mock.query_vault().total_liquidity, Int128::new(1_000_200));
+}
+
+#[test]
+fn opening_fee_is_charged() {
+    let mut mock = setup(PerpParams {
+        opening_fee_rate: Decimal::percent(1),
+        ..default_perp_params("uatom")
+    });
+    let cm = mock.credit_manager.clone();
+
+    let fee = mock.query_opening_fee("uatom", Int128::new(-100));
+    assert_eq!(fee.rate, Decimal::percent(1));
+    assert_eq!(fee.fee, coin(10, "uusdc"));
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-100), None, &[]);
+    assert_err(
+        res,
+        ContractError::InvalidPayment {
+            denom: "uusdc".to_string(),
+            expected: Uint128::new(10),
+            received: Uint128::zero(),
+        },
+    );
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-100), None, &[coin(10, "uusdc")])
+        .unwrap();
+
+    let ms = mock.query_market_state("uatom");
+    assert_eq!(ms.short_oi, Uint128::new(100));
+    assert_eq!(ms.cash_flow.opening_fee, Int128::new(10));
+}
+
+#[test]
+fn reduce_only_order_cannot_increase_position() {
+    let mut mock = setup(default_perp_params("uatom"));
+    let cm = mock.credit_manager.clone();
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), Some(true), &[]);
+    assert_err(
+        res,
+        ContractError::IllegalReduceOnlyOrder {
+            current: Int128::zero(),
+            order: Int128::new(100),
+        },
+    );
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]).unwrap();
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-150), Some(true), &[]);
+    assert_err(
+        res,
+        ContractError::IllegalReduceOnlyOrder {
+            current: Int128::new(100),
+            order: Int128::new(-150),
+        },
+    );
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(-50), Some(true), &[]).unwrap();
+    assert_eq!(mock.query_position("1", "uatom").position.unwrap().size, Int128::new(50));
+}
+
+#[test]
+fn open_interest_is_capped() {
+    let mut mock = setup(PerpParams {
+        max_long_oi_value: Uint128::new(1_000),
+        ..default_perp_params("uatom")
+    });
+    let cm = mock.credit_manager.clone();
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(101), None, &[]);
+    assert_err(
+        res,
+        ContractError::LongOpenInterestReached {
+            max: Uint128::new(1_000),
+            found: Uint128::new(1_010),
+        },
+    );
+
+    mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]).unwrap();
+}
+
+#[test]
+fn cannot_open_position_in_disabled_market() {
+    let mut mock = setup(PerpParams {
+        enabled: false,
+        ..default_perp_params("uatom")
+    });
+    let cm = mock.credit_manager.clone();
+
+    let res = mock.execute_perp_order(&cm, "1", "uatom", Int128::new(100), None, &[]);
+    assert_err(
+        res,
+        ContractError::MarketDisabled {
+            denom: "uatom".to_string(),
+        },
+    );
+}
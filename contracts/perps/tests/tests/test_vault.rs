@@ -0,0 +1,134 @@
+use cosmwasm_std::{coin, Addr, Decimal, Uint128};
+use mars_perps::{error::ContractError, vault::DEFAULT_SHARES_PER_AMOUNT};
+
+use super::helpers::{assert_err, MockEnv, ONE_HOUR_SEC};
+
+#[test]
+fn deposit_mints_shares() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+    mock.fund_account(&depositor, &[coin(1_000_000, "uusdc")]);
+
+    mock.deposit_to_vault(&depositor, None, None, &[coin(1_000_000, "uusdc")]).unwrap();
+
+    let vault = mock.query_vault();
+    assert_eq!(vault.total_balance.i128(), 1_000_000);
+    assert_eq!(vault.total_shares, Uint128::new(1_000_000) * DEFAULT_SHARES_PER_AMOUNT);
+    assert_eq!(vault.total_value, Uint128::new(1_000_000));
+    assert_eq!(vault.collateralization_ratio, None);
+
+    let position = mock.query_vault_position(depositor.as_str(), None).unwrap();
+    assert_eq!(position.deposit.shares, vault.total_shares);
+    assert_eq!(position.deposit.amount, Uint128::new(1_000_000));
+    assert!(position.unlocks.is_empty());
+}
+
+#[test]
+fn deposit_fails_if_too_many_shares_minted() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+    mock.fund_account(&depositor, &[coin(1_000, "uusdc")]);
+
+    let res =
+        mock.deposit_to_vault(&depositor, None, Some(Uint128::one()), &[coin(1_000, "uusdc")]);
+    assert_err(
+        res,
+        ContractError::MaximumSharesExceeded {
+            shares: Uint128::new(1_000) * DEFAULT_SHARES_PER_AMOUNT,
+            max_shares_receivable: Uint128::one(),
+        },
+    );
+}
+
+#[test]
+fn only_credit_manager_can_deposit_on_behalf_of_account() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+    mock.fund_account(&depositor, &[coin(1_000, "uusdc")]);
+
+    let res = mock.deposit_to_vault(&depositor, Some("1"), None, &[coin(1_000, "uusdc")]);
+    assert_err(res, ContractError::AccountIdNotAllowed);
+
+    let cm = mock.credit_manager.clone();
+    mock.fund_account(&cm, &[coin(1_000, "uusdc")]);
+    mock.deposit_to_vault(&cm, Some("1"), None, &[coin(1_000, "uusdc")]).unwrap();
+
+    let position = mock.query_cm_vault_position("1").unwrap();
+    assert_eq!(position.deposit.amount, Uint128::new(1_000));
+}
+
+#[test]
+fn withdraw_after_cooldown() {
+    let mut mock = MockEnv::new().build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+    mock.fund_account(&depositor, &[coin(1_000, "uusdc")]);
+    mock.deposit_to_vault(&depositor, None, None, &[coin(1_000, "uusdc")]).unwrap();
+
+    let shares = mock.query_vault_position(depositor.as_str(), None).unwrap().deposit.shares;
+
+    // can't unlock more shares than deposited
+    let res = mock.unlock_from_vault(&depositor, None, shares + Uint128::one());
+    assert_err(
+        res,
+        ContractError::InsufficientShares {
+            available: shares,
+            requested: shares + Uint128::one(),
+        },
+    );
+
+    let half = shares.multiply_ratio(1u128, 2u128);
+    mock.unlock_from_vault(&depositor, None, half).unwrap();
+
+    // cooldown period has not passed yet
+    let res = mock.withdraw_from_vault(&depositor, None, None);
+    assert_err(res, ContractError::UnlockedPositionsNotFound);
+
+    mock.increment_by_time(ONE_HOUR_SEC);
+
+    let res = mock.withdraw_from_vault(&depositor, None, Some(Uint128::new(501)));
+    assert_err(
+        res,
+        ContractError::MinimumReceiveExceeded {
+            min_receive: Uint128::new(501),
+            received: Uint128::new(500),
+        },
+    );
+
+    mock.withdraw_from_vault(&depositor, None, Some(Uint128::new(500))).unwrap();
+
+    assert_eq!(mock.query_balance(&depositor, "uusdc").amount, Uint128::new(500));
+    let position = mock.query_vault_position(depositor.as_str(), None).unwrap();
+    assert_eq!(position.deposit.shares, shares - half);
+    assert!(position.unlocks.is_empty());
+
+    let vault = mock.query_vault();
+    assert_eq!(vault.total_balance.i128(), 500);
+    assert_eq!(vault.share_price, Some(Decimal::from_ratio(1u128, DEFAULT_SHARES_PER_AMOUNT)));
+}
+
+#[test]
+fn cannot_exceed_max_unlocks() {
+    let mut mock = MockEnv::new().max_unlocks(2).build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+    mock.fund_account(&depositor, &[coin(1_000, "uusdc")]);
+    mock.deposit_to_vault(&depositor, None, None, &[coin(1_000, "uusdc")]).unwrap();
+
+    mock.unlock_from_vault(&depositor, None, Uint128::new(10)).unwrap();
+    mock.unlock_from_vault(&depositor, None, Uint128::new(10)).unwrap();
+    let res = mock.unlock_from_vault(&depositor, None, Uint128::new(10));
+    assert_err(
+        res,
+        ContractError::MaxUnlocksReached {
+            max_unlocks: 2,
+        },
+    );
+}
+
+#[test]
+fn cannot_withdraw_if_disabled() {
+    let mut mock = MockEnv::new().withdraw_enabled(false).build().unwrap();
+    let depositor = Addr::unchecked("depositor");
+
+    let res = mock.withdraw_from_vault(&depositor, None, None);
+    assert_err(res, ContractError::VaultWithdrawDisabled);
+}
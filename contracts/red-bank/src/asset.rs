@@ -47,9 +47,10 @@ pub fn create_market(
     let InitOrUpdateAssetParams {
         reserve_factor,
         interest_rate_model,
+        flash_loan_fee_rate,
     } = params;
 
-    // All fields should be available
+    // All fields should be available (except the optional flash loan fee rate)
     let available = reserve_factor.is_some() && interest_rate_model.is_some();
 
     if !available {
@@ -67,6 +68,7 @@ pub fn create_market(
         collateral_total_scaled: Uint128::zero(),
         debt_total_scaled: Uint128::zero(),
         interest_rate_model: interest_rate_model.unwrap(),
+        flash_loan_fee_rate: flash_loan_fee_rate.unwrap_or_default(),
     };
 
     new_market.validate()?;
@@ -93,6 +95,7 @@ pub fn update_asset(
             let InitOrUpdateAssetParams {
                 reserve_factor,
                 interest_rate_model,
+                flash_loan_fee_rate,
             } = params;
 
             // If reserve factor or interest rates are updated we update indexes with
@@ -128,6 +131,7 @@ pub fn update_asset(
             let mut updated_market = Market {
                 reserve_factor: reserve_factor.unwrap_or(market.reserve_factor),
                 interest_rate_model: interest_rate_model.unwrap_or(market.interest_rate_model),
+                flash_loan_fee_rate: flash_loan_fee_rate.unwrap_or(market.flash_loan_fee_rate),
                 ..market
             };
 
@@ -10,6 +10,10 @@ use crate::{
     health::assert_below_max_ltv_after_borrow,
     helpers::query_asset_params,
     interest_rates::{apply_accumulated_interests, update_interest_rates},
+    isolation::{
+        assert_isolated_borrow, clear_stale_isolated_collateral, increase_isolated_debt,
+        query_isolated_collateral,
+    },
     state::{CONFIG, MARKETS},
     user::User,
 };
@@ -74,8 +78,16 @@ pub fn borrow(
         });
     }
 
+    // Total debt includes Credit Manager borrows, so the cap is enforced across both products
+    if debt_balance_before.checked_add(borrow_amount)? > asset_params.borrow_cap {
+        return Err(ContractError::BorrowCapExceeded {
+            denom,
+        });
+    }
+
     // Check if user can borrow specified amount
     let mut uncollateralized_debt = false;
+    let mut isolated_collateral = None;
     if info.sender != credit_manager_addr {
         if !assert_below_max_ltv_after_borrow(
             &deps.as_ref(),
@@ -89,6 +101,27 @@ pub fn borrow(
         )? {
             return Err(ContractError::BorrowAmountExceedsGivenCollateral {});
         }
+
+        clear_stale_isolated_collateral(
+            deps.storage,
+            &deps.querier,
+            borrower.address(),
+            params_addr,
+        )?;
+        isolated_collateral =
+            query_isolated_collateral(deps.as_ref(), borrower.address(), params_addr)?;
+        if let Some((collateral_denom, isolation)) = &isolated_collateral {
+            assert_isolated_borrow(
+                deps.as_ref(),
+                &env,
+                borrower.address(),
+                oracle_addr,
+                collateral_denom,
+                isolation,
+                &denom,
+                borrow_amount,
+            )?;
+        }
     } else {
         uncollateralized_debt = true;
     }
@@ -109,6 +142,15 @@ pub fn borrow(
         get_scaled_debt_amount(borrow_amount, &borrow_market, env.block.time.seconds())?;
 
     borrow_market.increase_debt(borrow_amount_scaled)?;
+    if let Some((collateral_denom, _)) = isolated_collateral {
+        increase_isolated_debt(
+            deps.storage,
+            borrower.address(),
+            &collateral_denom,
+            &denom,
+            borrow_amount_scaled,
+        )?;
+    }
     borrower.increase_debt(deps.storage, &denom, borrow_amount_scaled, uncollateralized_debt)?;
 
     response = update_interest_rates(&env, &mut borrow_market, response)?;
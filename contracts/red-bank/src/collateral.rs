@@ -8,6 +8,7 @@ use mars_types::{
 use crate::{
     error::ContractError,
     health::get_health_and_positions,
+    isolation::query_isolated_collateral,
     state::{COLLATERALS, CONFIG},
     user::User,
 };
@@ -38,19 +39,24 @@ pub fn update_asset_collateral_status(
     collateral.enabled = enable;
     COLLATERALS.save(deps.storage, (&user_id_key, &denom), &collateral)?;
 
+    let config = CONFIG.load(deps.storage)?;
+
+    let addresses = address_provider::helpers::query_contract_addrs(
+        deps.as_ref(),
+        &config.address_provider,
+        vec![MarsAddressType::Oracle, MarsAddressType::Params],
+    )?;
+    let oracle_addr = &addresses[&MarsAddressType::Oracle];
+    let params_addr = &addresses[&MarsAddressType::Params];
+
+    // isolated collateral has to be the only enabled collateral of the user
+    if !previously_enabled && enable {
+        query_isolated_collateral(deps.as_ref(), user.address(), params_addr)?;
+    }
+
     // if the collateral was previously enabled, but is not disabled, it is necessary to ensure the
     // user is not liquidatable after disabling
     if previously_enabled && !enable {
-        let config = CONFIG.load(deps.storage)?;
-
-        let addresses = address_provider::helpers::query_contract_addrs(
-            deps.as_ref(),
-            &config.address_provider,
-            vec![MarsAddressType::Oracle, MarsAddressType::Params],
-        )?;
-        let oracle_addr = &addresses[&MarsAddressType::Oracle];
-        let params_addr = &addresses[&MarsAddressType::Params];
-
         let (health, _) = get_health_and_positions(
             &deps.as_ref(),
             &env,
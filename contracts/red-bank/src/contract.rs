@@ -1,11 +1,15 @@
 use cosmwasm_std::{
     entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
 };
+use cw2::get_contract_version;
 use mars_types::red_bank::{ExecuteMsg, InstantiateMsg, QueryMsg};
 
 use crate::{
-    asset, borrow, collateral, config, deposit, error::ContractError, instantiate, liquidate,
-    migrations, query, repay, state::MIGRATION_GUARD, withdraw,
+    asset, borrow, collateral, config, deposit,
+    error::ContractError,
+    flash_loan, instantiate, liquidate, migrations, pyth, query, repay,
+    state::{FLASH_LOAN_GUARD, MIGRATION_GUARD},
+    withdraw,
 };
 
 pub const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
@@ -46,6 +50,7 @@ pub fn execute(
             on_behalf_of,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             let sent_coin = cw_utils::one_coin(&info)?;
             deposit::deposit(
                 deps,
@@ -65,6 +70,7 @@ pub fn execute(
             liquidation_related,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             cw_utils::nonpayable(&info)?;
             withdraw::withdraw(
                 deps,
@@ -83,6 +89,7 @@ pub fn execute(
             recipient,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             cw_utils::nonpayable(&info)?;
             borrow::borrow(deps, env, info, denom, amount, recipient)
         }
@@ -90,6 +97,7 @@ pub fn execute(
             on_behalf_of,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             let sent_coin = cw_utils::one_coin(&info)?;
             repay::repay(deps, env, info, on_behalf_of, sent_coin.denom, sent_coin.amount)
         }
@@ -99,6 +107,7 @@ pub fn execute(
             recipient,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             let user_addr = deps.api.addr_validate(&user)?;
             let sent_coin = cw_utils::one_coin(&info)?;
             liquidate::liquidate(
@@ -117,9 +126,23 @@ pub fn execute(
             enable,
         } => {
             MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            FLASH_LOAN_GUARD.assert_unlocked(deps.storage)?;
             cw_utils::nonpayable(&info)?;
             collateral::update_asset_collateral_status(deps, env, info, denom, enable)
         }
+        ExecuteMsg::FlashLoan {
+            coins,
+            callback,
+        } => {
+            MIGRATION_GUARD.assert_unlocked(deps.storage)?;
+            cw_utils::nonpayable(&info)?;
+            flash_loan::flash_loan(deps, env, info, coins, callback)
+        }
+        ExecuteMsg::RepayFlashLoan {} => flash_loan::repay_flash_loan(deps, info),
+        ExecuteMsg::FinalizeFlashLoan {} => flash_loan::finalize_flash_loan(deps, env, info),
+        ExecuteMsg::UpdatePythPrices {
+            vaa_data,
+        } => pyth::update_pyth_prices(deps.as_ref(), info, vaa_data),
     }
 }
 
@@ -239,11 +262,35 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractErro
             denom,
             amount_scaled,
         } => to_json_binary(&query::query_underlying_debt_amount(deps, env, denom, amount_scaled)?),
+        QueryMsg::EstimateLiquidation {
+            user,
+            collateral_denom,
+            debt_denom,
+            debt_amount,
+        } => to_json_binary(&liquidate::query_estimate_liquidation(
+            deps,
+            env,
+            user,
+            collateral_denom,
+            debt_denom,
+            debt_amount,
+        )?),
     };
     res.map_err(Into::into)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
-    migrations::v2_1_0::migrate(deps)
+pub fn migrate(mut deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
+    let from_version = get_contract_version(deps.storage)?.version;
+
+    // Contracts on v2.1.0 only need the latest step, older ones go through v2.1.0 first
+    if from_version != "2.1.0" {
+        migrations::v2_1_0::migrate(deps.branch())?;
+    }
+    migrations::v2_2_0::migrate(deps)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
 }
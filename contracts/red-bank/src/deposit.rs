@@ -1,15 +1,17 @@
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128};
 use mars_interest_rate::get_scaled_liquidity_amount;
 use mars_types::{
     address_provider::{self, MarsAddressType},
     error::MarsError,
+    keys::{UserId, UserIdKey},
 };
 
 use crate::{
     error::ContractError,
     helpers::{query_asset_params, query_total_deposit},
     interest_rates::{apply_accumulated_interests, update_interest_rates},
-    state::{CONFIG, MARKETS},
+    isolation::enable_collateral_on_deposit,
+    state::{COLLATERALS, CONFIG, MARKETS},
     user::User,
 };
 
@@ -98,6 +100,19 @@ pub fn deposit(
     let deposit_amount_scaled =
         get_scaled_liquidity_amount(deposit_amount, &market, env.block.time.seconds())?;
 
+    // New collateral positions are enabled by default, unless that would mix isolated collateral
+    // of a red-bank user with other collateral. Such collateral has to be enabled explicitly.
+    let user_id = UserId::credit_manager(user.address().clone(), "".to_string());
+    let user_id_key: UserIdKey = user_id.try_into()?;
+    let disable_collateral = account_id.is_none()
+        && !COLLATERALS.has(deps.storage, (&user_id_key, &denom))
+        && !enable_collateral_on_deposit(
+            deps.as_ref(),
+            user.address(),
+            params_addr,
+            &asset_params,
+        )?;
+
     response = user.increase_collateral(
         deps.storage,
         &market,
@@ -107,6 +122,14 @@ pub fn deposit(
         account_id,
     )?;
 
+    if disable_collateral {
+        COLLATERALS.update(deps.storage, (&user_id_key, &denom), |opt| -> StdResult<_> {
+            let mut collateral = opt.ok_or_else(|| StdError::not_found("Collateral"))?;
+            collateral.enabled = false;
+            Ok(collateral)
+        })?;
+    }
+
     market.increase_collateral(deposit_amount_scaled)?;
 
     response = update_interest_rates(&env, &mut market, response)?;
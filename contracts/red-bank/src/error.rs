@@ -1,5 +1,6 @@
 use cosmwasm_std::{
-    CheckedFromRatioError, CheckedMultiplyFractionError, DivideByZeroError, OverflowError, StdError,
+    CheckedFromRatioError, CheckedMultiplyFractionError, DivideByZeroError, OverflowError,
+    StdError, Uint128,
 };
 use cw_utils::PaymentError;
 use mars_health::error::HealthError;
@@ -78,6 +79,11 @@ pub enum ContractError {
         denom: String,
     },
 
+    #[error("Borrow Cap exceeded for {denom:?}")]
+    BorrowCapExceeded {
+        denom: String,
+    },
+
     #[error("Cannot have 0 as liquidity index")]
     InvalidLiquidityIndex {},
 
@@ -154,4 +160,47 @@ pub enum ContractError {
 
     #[error("Cannot liquidate credit manager (use credit-manager contract liquidate function)")]
     CannotLiquidateCreditManager {},
+
+    #[error("Flash loan requires at least one coin")]
+    EmptyFlashLoan {},
+
+    #[error("Flash loan amount must be greater than 0 and less or equal available liquidity (asset: {denom:?})")]
+    InvalidFlashLoanAmount {
+        denom: String,
+    },
+
+    #[error("Flash loan of {denom:?} not repaid, outstanding amount: {outstanding}")]
+    FlashLoanNotRepaid {
+        denom: String,
+        outstanding: Uint128,
+    },
+
+    #[error("Flash loan repayment of {denom:?} exceeds outstanding amount")]
+    InvalidFlashLoanRepayment {
+        denom: String,
+    },
+
+    #[error(
+        "Isolated collateral {denom:?} has to be the only enabled collateral to borrow against it"
+    )]
+    IsolatedCollateralNotSole {
+        denom: String,
+    },
+
+    #[error("{denom:?} can't be borrowed against isolated collateral {collateral:?}")]
+    BorrowNotAllowedInIsolation {
+        collateral: String,
+        denom: String,
+    },
+
+    #[error("Debt ceiling of {debt_ceiling} for isolated collateral {collateral:?} exceeded")]
+    IsolatedDebtCeilingExceeded {
+        collateral: String,
+        debt_ceiling: Uint128,
+    },
+
+    #[error("Invalid Pyth price update: {reason}")]
+    InvalidPythPriceUpdate {
+        reason: String,
+    },
 }
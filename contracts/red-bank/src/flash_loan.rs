@@ -0,0 +1,220 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Coin, Decimal, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+};
+use mars_interest_rate::{
+    compute_scaled_amount, get_underlying_debt_amount, get_underlying_liquidity_amount,
+    ScalingOperation,
+};
+use mars_types::{
+    address_provider::{self, MarsAddressType},
+    error::MarsError,
+    red_bank::{ExecuteMsg, FlashLoan},
+};
+
+use crate::{
+    error::ContractError,
+    interest_rates::{apply_accumulated_interests, update_interest_rates},
+    state::{CONFIG, FLASH_LOANS, FLASH_LOAN_GUARD, MARKETS},
+    user::User,
+};
+
+/// Send the coins to the caller together with its callback message. The loan is finalized by a
+/// message to this contract, executed after the callback.
+pub fn flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    coins: Vec<Coin>,
+    callback: Binary,
+) -> Result<Response, ContractError> {
+    if coins.is_empty() {
+        return Err(ContractError::EmptyFlashLoan {});
+    }
+
+    let mut loans: Vec<FlashLoan> = vec![];
+    for coin in coins.iter() {
+        if loans.iter().any(|loan| loan.denom == coin.denom) {
+            return Err(ContractError::InvalidFlashLoanAmount {
+                denom: coin.denom.clone(),
+            });
+        }
+
+        let market = MARKETS
+            .may_load(deps.storage, &coin.denom)?
+            .ok_or(ContractError::AssetNotInitialized {})?;
+
+        let total_collateral = get_underlying_liquidity_amount(
+            market.collateral_total_scaled,
+            &market,
+            env.block.time.seconds(),
+        )?;
+        let total_debt = get_underlying_debt_amount(
+            market.debt_total_scaled,
+            &market,
+            env.block.time.seconds(),
+        )?;
+
+        // Cannot lend zero amount or more than available liquidity
+        let available_liquidity = total_collateral.checked_sub(total_debt)?;
+        if coin.amount.is_zero() || coin.amount > available_liquidity {
+            return Err(ContractError::InvalidFlashLoanAmount {
+                denom: coin.denom.clone(),
+            });
+        }
+
+        loans.push(FlashLoan {
+            denom: coin.denom.clone(),
+            amount: coin.amount,
+            fee: coin.amount.checked_mul_ceil(market.flash_loan_fee_rate)?,
+            repaid: Uint128::zero(),
+        });
+    }
+
+    let mut response = Response::new();
+    for loan in loans.iter() {
+        response = response
+            .add_attribute("denom", loan.denom.clone())
+            .add_attribute("amount", loan.amount)
+            .add_attribute("fee", loan.fee);
+    }
+
+    // Only one flash loan can be active at a time
+    FLASH_LOAN_GUARD.try_lock(deps.storage)?;
+    FLASH_LOANS.save(deps.storage, &loans)?;
+
+    let callback_msg = WasmMsg::Execute {
+        contract_addr: info.sender.to_string(),
+        msg: callback,
+        funds: coins,
+    };
+    let finalize_msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_json_binary(&ExecuteMsg::FinalizeFlashLoan {})?,
+        funds: vec![],
+    };
+
+    Ok(response
+        .add_message(callback_msg)
+        .add_message(finalize_msg)
+        .add_attribute("action", "flash_loan")
+        .add_attribute("receiver", info.sender))
+}
+
+/// Return coins of the active flash loan
+pub fn repay_flash_loan(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    FLASH_LOAN_GUARD.assert_locked(deps.storage)?;
+
+    let mut loans = FLASH_LOANS.load(deps.storage)?;
+    for coin in info.funds.iter() {
+        let loan = loans.iter_mut().find(|loan| loan.denom == coin.denom).ok_or_else(|| {
+            ContractError::InvalidFlashLoanRepayment {
+                denom: coin.denom.clone(),
+            }
+        })?;
+        if coin.amount > loan.outstanding() {
+            return Err(ContractError::InvalidFlashLoanRepayment {
+                denom: coin.denom.clone(),
+            });
+        }
+        loan.repaid = loan.repaid.checked_add(coin.amount)?;
+    }
+    FLASH_LOANS.save(deps.storage, &loans)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "repay_flash_loan")
+        .add_attribute("sender", info.sender))
+}
+
+/// Assert the active flash loan was repaid in full. The fees are split by the reserve factor the
+/// same way interest is: the protocol part is added as collateral of the rewards collector, the
+/// rest accrues to depositors through the liquidity index.
+pub fn finalize_flash_loan(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    if info.sender != env.contract.address {
+        return Err(MarsError::Unauthorized {}.into());
+    }
+
+    let loans = FLASH_LOANS.load(deps.storage)?;
+    if let Some(loan) = loans.iter().find(|loan| !loan.outstanding().is_zero()) {
+        return Err(ContractError::FlashLoanNotRepaid {
+            denom: loan.denom.clone(),
+            outstanding: loan.outstanding(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let addresses = address_provider::helpers::query_contract_addrs(
+        deps.as_ref(),
+        &config.address_provider,
+        vec![MarsAddressType::Incentives, MarsAddressType::RewardsCollector],
+    )?;
+    let rewards_collector_addr = &addresses[&MarsAddressType::RewardsCollector];
+    let incentives_addr = &addresses[&MarsAddressType::Incentives];
+
+    let mut response = Response::new();
+    for loan in loans.iter().filter(|loan| !loan.fee.is_zero()) {
+        let mut market = MARKETS.load(deps.storage, &loan.denom)?;
+
+        response = apply_accumulated_interests(
+            deps.storage,
+            &env,
+            &mut market,
+            rewards_collector_addr,
+            incentives_addr,
+            response,
+        )?;
+
+        // The depositors' part of the fee is added to the liquidity index, the rest goes to the
+        // rewards collector
+        let protocol_fee = loan.fee * market.reserve_factor;
+        let depositors_fee = loan.fee - protocol_fee;
+        let total_collateral = get_underlying_liquidity_amount(
+            market.collateral_total_scaled,
+            &market,
+            env.block.time.seconds(),
+        )?;
+        let protocol_fee = if total_collateral.is_zero() {
+            loan.fee
+        } else {
+            market.liquidity_index = market.liquidity_index.checked_mul(Decimal::from_ratio(
+                total_collateral.checked_add(depositors_fee)?,
+                total_collateral,
+            ))?;
+            protocol_fee
+        };
+
+        let fee_amount_scaled = compute_scaled_amount(
+            protocol_fee,
+            market.liquidity_index,
+            ScalingOperation::Truncate,
+        )?;
+        if !fee_amount_scaled.is_zero() {
+            response = User(rewards_collector_addr).increase_collateral(
+                deps.storage,
+                &market,
+                fee_amount_scaled,
+                incentives_addr,
+                response,
+                None,
+            )?;
+            market.increase_collateral(fee_amount_scaled)?;
+        }
+
+        response = update_interest_rates(&env, &mut market, response)?;
+        MARKETS.save(deps.storage, &loan.denom, &market)?;
+
+        response = response
+            .add_attribute("denom", loan.denom.clone())
+            .add_attribute("fee", loan.fee)
+            .add_attribute("protocol_fee", protocol_fee)
+            .add_attribute("protocol_fee_scaled", fee_amount_scaled);
+    }
+
+    FLASH_LOANS.remove(deps.storage);
+    FLASH_LOAN_GUARD.try_unlock(deps.storage)?;
+
+    Ok(response.add_attribute("action", "finalize_flash_loan"))
+}
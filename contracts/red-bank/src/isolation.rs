@@ -0,0 +1,259 @@
+use cosmwasm_std::{Addr, Deps, Env, Order, QuerierWrapper, StdResult, Storage, Uint128};
+use mars_interest_rate::get_underlying_debt_amount;
+use mars_types::{
+    keys::{UserId, UserIdKey},
+    oracle,
+    params::{AssetParams, IsolationParams},
+};
+
+use crate::{
+    error::ContractError,
+    helpers::query_asset_params,
+    state::{COLLATERALS, DEBTS, ISOLATED_DEBTS, MARKETS, USER_ISOLATED_COLLATERAL},
+    user::User,
+};
+
+/// Returns the isolated collateral (with its isolation params) backing the user's borrows, if any.
+///
+/// A user already in isolation mode stays with the isolated collateral recorded for its debt
+/// until all debt is repaid, unless the asset isn't isolated anymore. Otherwise an isolated asset
+/// enabled as collateral puts the user into isolation mode. Either way, the isolated collateral
+/// has to be the only enabled collateral.
+pub fn query_isolated_collateral(
+    deps: Deps,
+    user_addr: &Addr,
+    params_addr: &Addr,
+) -> Result<Option<(String, IsolationParams)>, ContractError> {
+    let user_id = UserId::credit_manager(user_addr.clone(), "".to_string());
+    let user_id_key: UserIdKey = user_id.try_into()?;
+
+    let enabled_denoms = COLLATERALS
+        .prefix(&user_id_key)
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter_map(|item| match item {
+            Ok((denom, collateral)) if collateral.enabled => Some(Ok(denom)),
+            Ok(_) => None,
+            Err(err) => Some(Err(err)),
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if let Some(denom) = USER_ISOLATED_COLLATERAL.may_load(deps.storage, user_addr)? {
+        let params = query_asset_params(&deps.querier, params_addr, &denom)?;
+        if let Some(isolation) = params.red_bank.isolation {
+            if enabled_denoms.iter().any(|enabled_denom| *enabled_denom != denom) {
+                return Err(ContractError::IsolatedCollateralNotSole {
+                    denom,
+                });
+            }
+            return Ok(Some((denom, isolation)));
+        }
+    }
+
+    let mut isolated_collateral = None;
+    for denom in enabled_denoms.iter() {
+        let params = query_asset_params(&deps.querier, params_addr, denom)?;
+        if let Some(isolation) = params.red_bank.isolation {
+            if enabled_denoms.len() > 1 {
+                return Err(ContractError::IsolatedCollateralNotSole {
+                    denom: denom.clone(),
+                });
+            }
+            isolated_collateral = Some((denom.clone(), isolation));
+        }
+    }
+
+    Ok(isolated_collateral)
+}
+
+/// Whether a new collateral position created by a deposit is enabled. Isolated assets have to be
+/// the only enabled collateral of a user, so they aren't enabled by default, and neither are other
+/// assets deposited by a user with isolated collateral.
+pub fn enable_collateral_on_deposit(
+    deps: Deps,
+    user_addr: &Addr,
+    params_addr: &Addr,
+    asset_params: &AssetParams,
+) -> Result<bool, ContractError> {
+    if asset_params.red_bank.isolation.is_some() {
+        return Ok(false);
+    }
+
+    match query_isolated_collateral(deps, user_addr, params_addr) {
+        Ok(isolated_collateral) => Ok(isolated_collateral.is_none()),
+        Err(ContractError::IsolatedCollateralNotSole {
+            ..
+        }) => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Forget the isolated collateral recorded for the user's debt if the asset isn't isolated
+/// anymore. The user's debt is removed from the debt backed by it.
+pub fn clear_stale_isolated_collateral(
+    store: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    user_addr: &Addr,
+    params_addr: &Addr,
+) -> Result<(), ContractError> {
+    let Some(collateral_denom) = USER_ISOLATED_COLLATERAL.may_load(store, user_addr)? else {
+        return Ok(());
+    };
+
+    let params = query_asset_params(querier, params_addr, &collateral_denom)?;
+    if params.red_bank.isolation.is_some() {
+        return Ok(());
+    }
+
+    let debts = DEBTS
+        .prefix(user_addr)
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (debt_denom, debt) in debts {
+        remove_isolated_debt(store, &collateral_denom, &debt_denom, debt.amount_scaled)?;
+    }
+    USER_ISOLATED_COLLATERAL.remove(store, user_addr);
+
+    Ok(())
+}
+
+/// Check that the denom can be borrowed against the isolated collateral and that the value of all
+/// debt backed by it stays within the debt ceiling after the borrow.
+#[allow(clippy::too_many_arguments)]
+pub fn assert_isolated_borrow(
+    deps: Deps,
+    env: &Env,
+    user_addr: &Addr,
+    oracle_addr: &Addr,
+    collateral_denom: &str,
+    isolation: &IsolationParams,
+    denom: &str,
+    borrow_amount: Uint128,
+) -> Result<(), ContractError> {
+    let assert_borrowable = |debt_denom: &str| {
+        if !isolation.borrowable_denoms.iter().any(|d| d == debt_denom) {
+            return Err(ContractError::BorrowNotAllowedInIsolation {
+                collateral: collateral_denom.to_string(),
+                denom: debt_denom.to_string(),
+            });
+        }
+        Ok(())
+    };
+
+    assert_borrowable(denom)?;
+
+    // The existing debt of a user entering isolation mode will be backed by the isolated collateral
+    let entering_isolation = !USER_ISOLATED_COLLATERAL.has(deps.storage, user_addr);
+    if entering_isolation {
+        for debt_denom in DEBTS.prefix(user_addr).keys(deps.storage, None, None, Order::Ascending) {
+            assert_borrowable(&debt_denom?)?;
+        }
+    }
+
+    let block_time = env.block.time.seconds();
+    let mut total_debt_value = Uint128::zero();
+    for debt_denom in isolation.borrowable_denoms.iter() {
+        let mut amount_scaled = ISOLATED_DEBTS
+            .may_load(deps.storage, (collateral_denom, debt_denom.as_str()))?
+            .unwrap_or_default();
+        if entering_isolation {
+            amount_scaled = amount_scaled
+                .checked_add(User(user_addr).debt_amount_scaled(deps.storage, debt_denom)?)?;
+        }
+        if amount_scaled.is_zero() && debt_denom != denom {
+            continue;
+        }
+
+        let market = MARKETS.load(deps.storage, debt_denom)?;
+        let mut debt_amount = get_underlying_debt_amount(amount_scaled, &market, block_time)?;
+        if debt_denom == denom {
+            debt_amount = debt_amount.checked_add(borrow_amount)?;
+        }
+
+        let price = oracle::helpers::query_price(&deps.querier, oracle_addr, debt_denom)?;
+        total_debt_value = total_debt_value.checked_add(debt_amount.checked_mul_ceil(price)?)?;
+    }
+
+    if total_debt_value > isolation.debt_ceiling {
+        return Err(ContractError::IsolatedDebtCeilingExceeded {
+            collateral: collateral_denom.to_string(),
+            debt_ceiling: isolation.debt_ceiling,
+        });
+    }
+
+    Ok(())
+}
+
+/// Add a borrow to the debt backed by the isolated collateral. Must be called before the user's
+/// debt is increased, so that the existing debt of a user entering isolation mode is added as well.
+pub fn increase_isolated_debt(
+    store: &mut dyn Storage,
+    user_addr: &Addr,
+    collateral_denom: &str,
+    denom: &str,
+    amount_scaled: Uint128,
+) -> StdResult<()> {
+    if !USER_ISOLATED_COLLATERAL.has(store, user_addr) {
+        let debts = DEBTS
+            .prefix(user_addr)
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (debt_denom, debt) in debts {
+            add_isolated_debt(store, collateral_denom, &debt_denom, debt.amount_scaled)?;
+        }
+        USER_ISOLATED_COLLATERAL.save(store, user_addr, &collateral_denom.to_string())?;
+    }
+
+    add_isolated_debt(store, collateral_denom, denom, amount_scaled)
+}
+
+/// Remove repaid (or liquidated) debt of a user in isolation mode from the debt backed by its
+/// isolated collateral. The user leaves isolation mode once all debt is repaid.
+pub fn decrease_isolated_debt(
+    store: &mut dyn Storage,
+    user_addr: &Addr,
+    denom: &str,
+    amount_scaled: Uint128,
+) -> StdResult<()> {
+    let collateral_denom = match USER_ISOLATED_COLLATERAL.may_load(store, user_addr)? {
+        Some(collateral_denom) => collateral_denom,
+        None => return Ok(()),
+    };
+
+    remove_isolated_debt(store, &collateral_denom, denom, amount_scaled)?;
+
+    if !User(user_addr).is_borrowing(store) {
+        USER_ISOLATED_COLLATERAL.remove(store, user_addr);
+    }
+
+    Ok(())
+}
+
+fn add_isolated_debt(
+    store: &mut dyn Storage,
+    collateral_denom: &str,
+    denom: &str,
+    amount_scaled: Uint128,
+) -> StdResult<()> {
+    ISOLATED_DEBTS.update(store, (collateral_denom, denom), |opt| -> StdResult<_> {
+        Ok(opt.unwrap_or_default().checked_add(amount_scaled)?)
+    })?;
+    Ok(())
+}
+
+fn remove_isolated_debt(
+    store: &mut dyn Storage,
+    collateral_denom: &str,
+    denom: &str,
+    amount_scaled: Uint128,
+) -> StdResult<()> {
+    let isolated_debt = ISOLATED_DEBTS
+        .may_load(store, (collateral_denom, denom))?
+        .unwrap_or_default()
+        .saturating_sub(amount_scaled);
+    if isolated_debt.is_zero() {
+        ISOLATED_DEBTS.remove(store, (collateral_denom, denom));
+    } else {
+        ISOLATED_DEBTS.save(store, (collateral_denom, denom), &isolated_debt)?;
+    }
+    Ok(())
+}
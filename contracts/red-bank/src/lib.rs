@@ -6,11 +6,14 @@ pub mod config;
 pub mod contract;
 pub mod deposit;
 pub mod error;
+pub mod flash_loan;
 pub mod health;
 pub mod instantiate;
 pub mod interest_rates;
+pub mod isolation;
 pub mod liquidate;
 pub mod migrations;
+pub mod pyth;
 pub mod query;
 pub mod repay;
 pub mod state;
@@ -1,18 +1,22 @@
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, Uint128};
+use std::collections::HashMap;
+
+use cosmwasm_std::{coin, Addr, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
 use mars_interest_rate::{
     get_scaled_debt_amount, get_scaled_liquidity_amount, get_underlying_debt_amount,
     get_underlying_liquidity_amount,
 };
-use mars_liquidation::liquidation::calculate_liquidation_amounts;
+use mars_liquidation::liquidation::{calculate_liquidation, LiquidationAmounts};
 use mars_types::{
     address_provider::{self, MarsAddressType},
     keys::{UserId, UserIdKey},
+    liquidation::EstimateLiquidationResponse,
+    red_bank::{Debt, Market, Position},
 };
 use mars_utils::helpers::{build_send_asset_msg, option_string_to_addr};
 
 use crate::{
     error::ContractError,
-    health::get_health_and_positions,
+    health::{compute_position_health, get_health_and_positions},
     helpers::{query_asset_params, query_target_health_factor},
     interest_rates::{apply_accumulated_interests, update_interest_rates},
     state::{COLLATERALS, CONFIG, DEBTS, MARKETS},
@@ -64,91 +68,34 @@ pub fn liquidate(
         return Err(ContractError::CannotLiquidateSelf {});
     }
 
-    // Cannot liquidate credit manager users. They have own liquidation logic in credit-manager contract.
-    if liquidatee_addr == credit_manager_addr {
-        return Err(ContractError::CannotLiquidateCreditManager {});
-    };
-
-    let user_id = UserId::credit_manager(liquidatee_addr.clone(), "".to_string());
-    let user_id_key: UserIdKey = user_id.try_into()?;
-
-    // check if the user has enabled the collateral asset as collateral
-    let user_collateral = COLLATERALS
-        .may_load(deps.storage, (&user_id_key, &collateral_denom))?
-        .ok_or(ContractError::CannotLiquidateWhenNoCollateralBalance {})?;
-    if !user_collateral.enabled {
-        return Err(ContractError::CannotLiquidateWhenCollateralUnset {
-            denom: collateral_denom,
-        });
-    }
-
-    // check if user has outstanding debt in the deposited asset that needs to be repayed
-    let user_debt = DEBTS
-        .may_load(deps.storage, (&liquidatee_addr, &debt_denom))?
-        .ok_or(ContractError::CannotLiquidateWhenNoDebtBalance {})?;
-
-    // check if user has available collateral in specified collateral asset to be liquidated
-    let collateral_market = MARKETS.load(deps.storage, &collateral_denom)?;
-
-    // 2. Compute health factor
-    let (health, assets_positions) = get_health_and_positions(
-        &deps.as_ref(),
+    // 2. Compute debt to repay and collateral to liquidate
+    let LiquidationContext {
+        user_debt,
+        collateral_market,
+        debt_market,
+        user_debt_amount,
+        amounts,
+        ..
+    } = compute_liquidation(
+        deps.as_ref(),
         &env,
         &liquidatee_addr,
-        "",
+        &collateral_denom,
+        &debt_denom,
+        sent_debt_amount,
         oracle_addr,
         params_addr,
-        true,
-    )?;
-
-    if !health.is_liquidatable() {
-        return Err(ContractError::CannotLiquidateHealthyPosition {});
-    }
-
-    let debt_market = if debt_denom != collateral_denom {
-        MARKETS.load(deps.storage, &debt_denom)?
-    } else {
-        collateral_market.clone()
-    };
-
-    // 3. Compute debt to repay and collateral to liquidate
-    let collateral_price = assets_positions
-        .get(&collateral_denom)
-        .ok_or(ContractError::CannotLiquidateWhenNoCollateralBalance {})?
-        .asset_price;
-    let debt_price = assets_positions
-        .get(&debt_denom)
-        .ok_or(ContractError::CannotLiquidateWhenNoDebtBalance {})?
-        .asset_price;
-
-    let mut response = Response::new();
-
-    let user_debt_amount =
-        get_underlying_debt_amount(user_debt.amount_scaled, &debt_market, block_time)?;
-
-    let collateral_params = query_asset_params(&deps.querier, params_addr, &collateral_denom)?;
-    let target_health_factor = query_target_health_factor(&deps.querier, params_addr)?;
-
-    let user_collateral_amount = get_underlying_liquidity_amount(
-        user_collateral.amount_scaled,
-        &collateral_market,
-        block_time,
+        credit_manager_addr,
     )?;
-    let (
+    let LiquidationAmounts {
         debt_amount_to_repay,
         collateral_amount_to_liquidate,
         collateral_amount_received_by_liquidator,
-    ) = calculate_liquidation_amounts(
-        user_collateral_amount,
-        collateral_price,
-        &collateral_params,
-        user_debt_amount,
-        sent_debt_amount,
-        debt_price,
-        target_health_factor,
-        &health,
-    )?;
-    let protocol_fee = collateral_amount_to_liquidate - collateral_amount_received_by_liquidator;
+        protocol_fee_amount: protocol_fee,
+        ..
+    } = amounts;
+
+    let mut response = Response::new();
 
     let refund_amount = sent_debt_amount - debt_amount_to_repay;
 
@@ -167,7 +114,7 @@ pub fn liquidate(
     let protocol_fee_scaled =
         get_scaled_liquidity_amount(protocol_fee, &collateral_market, block_time)?;
 
-    // 4. Transfer collateral shares from the user to the liquidator and rewards-collector (protocol fee)
+    // 3. Transfer collateral shares from the user to the liquidator and rewards-collector (protocol fee)
     response = liquidatee.decrease_collateral(
         deps.storage,
         &collateral_market,
@@ -195,7 +142,7 @@ pub fn liquidate(
         )?;
     }
 
-    // 5. Reduce the user's debt shares
+    // 4. Reduce the user's debt shares
     let user_debt_amount_after = user_debt_amount.checked_sub(debt_amount_to_repay)?;
     let user_debt_amount_scaled_after =
         get_scaled_debt_amount(user_debt_amount_after, &debt_market, block_time)?;
@@ -209,7 +156,7 @@ pub fn liquidate(
     let market_debt_total_scaled_after =
         debt_market.debt_total_scaled.checked_sub(debt_amount_scaled_delta)?;
 
-    // 6. Update markets
+    // 5. Update markets
     let mut debt_market_after = debt_market;
     response = apply_accumulated_interests(
         deps.storage,
@@ -223,7 +170,7 @@ pub fn liquidate(
     response = update_interest_rates(&env, &mut debt_market_after, response)?;
     MARKETS.save(deps.storage, &debt_denom, &debt_market_after)?;
 
-    // 7. Build response
+    // 6. Build response
     // refund sent amount in excess of actual debt amount to liquidate
     if !refund_amount.is_zero() {
         response =
@@ -242,3 +189,171 @@ pub fn liquidate(
         .add_attribute("debt_amount", debt_amount_to_repay)
         .add_attribute("debt_amount_scaled", debt_amount_scaled_delta))
 }
+
+/// Returns the outcome of the liquidation without executing it
+pub fn query_estimate_liquidation(
+    deps: Deps,
+    env: Env,
+    user: String,
+    collateral_denom: String,
+    debt_denom: String,
+    debt_amount: Uint128,
+) -> Result<EstimateLiquidationResponse, ContractError> {
+    let liquidatee_addr = deps.api.addr_validate(&user)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let addresses = address_provider::helpers::query_contract_addrs(
+        deps,
+        &config.address_provider,
+        vec![MarsAddressType::Oracle, MarsAddressType::Params, MarsAddressType::CreditManager],
+    )?;
+
+    let LiquidationContext {
+        mut positions,
+        amounts,
+        ..
+    } = compute_liquidation(
+        deps,
+        &env,
+        &liquidatee_addr,
+        &collateral_denom,
+        &debt_denom,
+        debt_amount,
+        &addresses[&MarsAddressType::Oracle],
+        &addresses[&MarsAddressType::Params],
+        &addresses[&MarsAddressType::CreditManager],
+    )?;
+
+    // Both positions exist, they have been checked by `compute_liquidation`
+    if let Some(p) = positions.get_mut(&collateral_denom) {
+        p.collateral_amount =
+            p.collateral_amount.checked_sub(amounts.collateral_amount_to_liquidate)?;
+    }
+    if let Some(p) = positions.get_mut(&debt_denom) {
+        p.debt_amount = p.debt_amount.checked_sub(amounts.debt_amount_to_repay)?;
+    }
+    let health_after = compute_position_health(&positions)?;
+
+    Ok(EstimateLiquidationResponse {
+        debt_repaid: coin(amounts.debt_amount_to_repay.u128(), &debt_denom),
+        collateral_liquidated: coin(
+            amounts.collateral_amount_to_liquidate.u128(),
+            &collateral_denom,
+        ),
+        collateral_received: coin(
+            amounts.collateral_amount_received_by_liquidator.u128(),
+            &collateral_denom,
+        ),
+        protocol_fee: coin(amounts.protocol_fee_amount.u128(), &collateral_denom),
+        liquidation_bonus: amounts.liquidation_bonus,
+        health_factor_after: health_after.liquidation_health_factor,
+    })
+}
+
+struct LiquidationContext {
+    user_debt: Debt,
+    collateral_market: Market,
+    debt_market: Market,
+    user_debt_amount: Uint128,
+    /// Positions of the liquidatee before the liquidation, with liquidation prices
+    positions: HashMap<String, Position>,
+    amounts: LiquidationAmounts,
+}
+
+/// Validates the liquidation of the user and computes the debt to repay and the collateral to
+/// liquidate. Checks depending on the liquidator are left to the caller.
+#[allow(clippy::too_many_arguments)]
+fn compute_liquidation(
+    deps: Deps,
+    env: &Env,
+    liquidatee_addr: &Addr,
+    collateral_denom: &str,
+    debt_denom: &str,
+    debt_amount: Uint128,
+    oracle_addr: &Addr,
+    params_addr: &Addr,
+    credit_manager_addr: &Addr,
+) -> Result<LiquidationContext, ContractError> {
+    let block_time = env.block.time.seconds();
+
+    // Cannot liquidate credit manager users. They have own liquidation logic in credit-manager contract.
+    if liquidatee_addr == credit_manager_addr {
+        return Err(ContractError::CannotLiquidateCreditManager {});
+    };
+
+    let user_id = UserId::credit_manager(liquidatee_addr.clone(), "".to_string());
+    let user_id_key: UserIdKey = user_id.try_into()?;
+
+    // check if the user has enabled the collateral asset as collateral
+    let user_collateral = COLLATERALS
+        .may_load(deps.storage, (&user_id_key, collateral_denom))?
+        .ok_or(ContractError::CannotLiquidateWhenNoCollateralBalance {})?;
+    if !user_collateral.enabled {
+        return Err(ContractError::CannotLiquidateWhenCollateralUnset {
+            denom: collateral_denom.to_string(),
+        });
+    }
+
+    // check if user has outstanding debt in the deposited asset that needs to be repayed
+    let user_debt = DEBTS
+        .may_load(deps.storage, (liquidatee_addr, debt_denom))?
+        .ok_or(ContractError::CannotLiquidateWhenNoDebtBalance {})?;
+
+    // check if user has available collateral in specified collateral asset to be liquidated
+    let collateral_market = MARKETS.load(deps.storage, collateral_denom)?;
+
+    // Compute health factor
+    let (health, positions) =
+        get_health_and_positions(&deps, env, liquidatee_addr, "", oracle_addr, params_addr, true)?;
+
+    if !health.is_liquidatable() {
+        return Err(ContractError::CannotLiquidateHealthyPosition {});
+    }
+
+    let debt_market = if debt_denom != collateral_denom {
+        MARKETS.load(deps.storage, debt_denom)?
+    } else {
+        collateral_market.clone()
+    };
+
+    // Compute debt to repay and collateral to liquidate
+    let collateral_price = positions
+        .get(collateral_denom)
+        .ok_or(ContractError::CannotLiquidateWhenNoCollateralBalance {})?
+        .asset_price;
+    let debt_price = positions
+        .get(debt_denom)
+        .ok_or(ContractError::CannotLiquidateWhenNoDebtBalance {})?
+        .asset_price;
+
+    let user_debt_amount =
+        get_underlying_debt_amount(user_debt.amount_scaled, &debt_market, block_time)?;
+
+    let collateral_params = query_asset_params(&deps.querier, params_addr, collateral_denom)?;
+    let target_health_factor = query_target_health_factor(&deps.querier, params_addr)?;
+
+    let user_collateral_amount = get_underlying_liquidity_amount(
+        user_collateral.amount_scaled,
+        &collateral_market,
+        block_time,
+    )?;
+    let amounts = calculate_liquidation(
+        user_collateral_amount,
+        collateral_price,
+        &collateral_params,
+        user_debt_amount,
+        debt_amount,
+        debt_price,
+        target_health_factor,
+        &health,
+    )?;
+
+    Ok(LiquidationContext {
+        user_debt,
+        collateral_market,
+        debt_market,
+        user_debt_amount,
+        positions,
+        amounts,
+    })
+}
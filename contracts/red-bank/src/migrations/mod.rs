@@ -1 +1,2 @@
 pub mod v2_1_0;
+pub mod v2_2_0;
@@ -1,12 +1,10 @@
-use cosmwasm_std::{DepsMut, Response};
+use cosmwasm_std::DepsMut;
 use cw2::{assert_contract_version, set_contract_version};
 
-use crate::{
-    contract::{CONTRACT_NAME, CONTRACT_VERSION},
-    error::ContractError,
-};
+use crate::{contract::CONTRACT_NAME, error::ContractError};
 
 const FROM_VERSION: &str = "2.0.1";
+const TO_VERSION: &str = "2.1.0";
 
 pub mod v1_state {
     use cosmwasm_std::{Addr, DepsMut, Uint128};
@@ -20,17 +18,14 @@ pub mod v1_state {
     }
 }
 
-pub fn migrate(mut deps: DepsMut) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut) -> Result<(), ContractError> {
     // Make sure we're migrating the correct contract and from the correct version
     assert_contract_version(deps.storage, &format!("crates.io:{CONTRACT_NAME}"), FROM_VERSION)?;
 
     // Clear old state
     v1_state::clear_state(&mut deps);
 
-    set_contract_version(deps.storage, format!("crates.io:{CONTRACT_NAME}"), CONTRACT_VERSION)?;
+    set_contract_version(deps.storage, format!("crates.io:{CONTRACT_NAME}"), TO_VERSION)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "migrate")
-        .add_attribute("from_version", FROM_VERSION)
-        .add_attribute("to_version", CONTRACT_VERSION))
+    Ok(())
 }
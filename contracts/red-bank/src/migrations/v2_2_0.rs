@@ -0,0 +1,72 @@
+use cosmwasm_std::{DepsMut, Order, StdResult};
+use cw2::{assert_contract_version, set_contract_version};
+use mars_types::red_bank::{InterestRateModel, Market};
+
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    error::ContractError,
+    state::MARKETS,
+};
+
+const FROM_VERSION: &str = "2.1.0";
+
+pub mod v2_1_0_state {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Decimal, Uint128};
+    use cw_storage_plus::Map;
+    use mars_types::red_bank::LinearInterestRateModel;
+
+    pub const MARKETS: Map<&str, Market> = Map::new("markets");
+
+    /// Market with the interest rate model stored as the plain linear model
+    #[cw_serde]
+    pub struct Market {
+        pub denom: String,
+        pub reserve_factor: Decimal,
+        pub interest_rate_model: LinearInterestRateModel,
+        pub borrow_index: Decimal,
+        pub liquidity_index: Decimal,
+        pub borrow_rate: Decimal,
+        pub liquidity_rate: Decimal,
+        pub indexes_last_updated: u64,
+        pub collateral_total_scaled: Uint128,
+        pub debt_total_scaled: Uint128,
+        #[serde(default)]
+        pub flash_loan_fee_rate: Decimal,
+    }
+}
+
+pub fn migrate(deps: DepsMut) -> Result<(), ContractError> {
+    // Make sure we're migrating the correct contract and from the correct version
+    assert_contract_version(deps.storage, &format!("crates.io:{CONTRACT_NAME}"), FROM_VERSION)?;
+
+    // Wrap the interest rate model of existing markets into the linear variant. Markets already
+    // stored with the new model (e.g. initialized after the code was uploaded) are left untouched.
+    let denoms =
+        MARKETS.keys(deps.storage, None, None, Order::Ascending).collect::<StdResult<Vec<_>>>()?;
+    for denom in denoms {
+        if MARKETS.load(deps.storage, &denom).is_ok() {
+            continue;
+        }
+
+        let old_market = v2_1_0_state::MARKETS.load(deps.storage, &denom)?;
+        let market = Market {
+            denom: old_market.denom,
+            reserve_factor: old_market.reserve_factor,
+            interest_rate_model: InterestRateModel::Linear(old_market.interest_rate_model),
+            borrow_index: old_market.borrow_index,
+            liquidity_index: old_market.liquidity_index,
+            borrow_rate: old_market.borrow_rate,
+            liquidity_rate: old_market.liquidity_rate,
+            indexes_last_updated: old_market.indexes_last_updated,
+            collateral_total_scaled: old_market.collateral_total_scaled,
+            debt_total_scaled: old_market.debt_total_scaled,
+            flash_loan_fee_rate: old_market.flash_loan_fee_rate,
+        };
+        MARKETS.save(deps.storage, &denom, &market)?;
+    }
+
+    set_contract_version(deps.storage, format!("crates.io:{CONTRACT_NAME}"), CONTRACT_VERSION)?;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use cosmwasm_std::{Binary, Deps, MessageInfo, Response};
+use mars_types::{
+    adapters::pyth::Pyth,
+    address_provider::{self, MarsAddressType},
+};
+
+use crate::{error::ContractError, state::CONFIG};
+
+/// Forward the VAAs to the Pyth contract, paying the update fee with the coins sent. Meant to be
+/// executed ahead of the other messages of a transaction, so they are not blocked by stale prices.
+pub fn update_pyth_prices(
+    deps: Deps,
+    info: MessageInfo,
+    vaa_data: Vec<Binary>,
+) -> Result<Response, ContractError> {
+    if vaa_data.is_empty() {
+        return Err(ContractError::InvalidPythPriceUpdate {
+            reason: "at least one VAA is required".to_string(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let pyth_addr = address_provider::helpers::query_contract_addr(
+        deps,
+        &config.address_provider,
+        MarsAddressType::Pyth,
+    )?;
+    let pyth = Pyth::new(pyth_addr);
+
+    let fee = pyth.query_update_fee(&deps.querier, &vaa_data)?;
+    if fee.amount.is_zero() {
+        cw_utils::nonpayable(&info)?;
+    } else {
+        let received = cw_utils::must_pay(&info, &fee.denom)?;
+        if received != fee.amount {
+            return Err(ContractError::InvalidPythPriceUpdate {
+                reason: format!("update fee of {fee} required, received: {received}"),
+            });
+        }
+    }
+
+    Ok(Response::new()
+        .add_message(pyth.update_price_feeds_msg(vaa_data, fee.clone())?)
+        .add_attribute("action", "update_pyth_prices")
+        .add_attribute("update_fee", fee.to_string()))
+}
@@ -1,9 +1,9 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Uint128};
 use cw_storage_plus::{Item, Map};
 use mars_owner::Owner;
 use mars_types::{
     keys::UserIdKey,
-    red_bank::{Collateral, Config, Debt, Market},
+    red_bank::{Collateral, Config, Debt, FlashLoan, Market},
 };
 use mars_utils::guard::Guard;
 
@@ -15,3 +15,13 @@ pub const DEBTS: Map<(&Addr, &str), Debt> = Map::new("debts");
 
 /// Used to mark the contract as locked during migrations
 pub const MIGRATION_GUARD: Guard = Guard::new("guard");
+
+/// Used to mark a flash loan as active until it is finalized
+pub const FLASH_LOAN_GUARD: Guard = Guard::new("flash_loan_guard");
+/// Coins lent out by the active flash loan
+pub const FLASH_LOANS: Item<Vec<FlashLoan>> = Item::new("flash_loans");
+
+/// Scaled debt backed by isolated collateral: (isolated collateral denom, debt denom) -> scaled amount
+pub const ISOLATED_DEBTS: Map<(&str, &str), Uint128> = Map::new("isolated_debts");
+/// Isolated collateral denom backing all debt of a user in isolation mode
+pub const USER_ISOLATED_COLLATERAL: Map<&Addr, String> = Map::new("user_isolated_collateral");
@@ -7,7 +7,10 @@ use mars_types::{
     red_bank::{Collateral, Debt, Market},
 };
 
-use crate::state::{COLLATERALS, DEBTS};
+use crate::{
+    isolation::decrease_isolated_debt,
+    state::{COLLATERALS, DEBTS},
+};
 
 /// A helper class providing an intuitive API for managing user positions in the contract store.
 ///
@@ -222,7 +225,7 @@ impl<'a> User<'a> {
     }
 
     /// Decrease a user's debt shares by the specified amount. If reduced to zero, delete the debt
-    /// position from contract storage.
+    /// position from contract storage. Debt backed by isolated collateral is decreased as well.
     ///
     /// This may be invoked if a user makes a repayment, or gets liquidated.
     pub fn decrease_debt(
@@ -241,6 +244,8 @@ impl<'a> User<'a> {
             DEBTS.save(store, (self.0, denom), &debt)?;
         }
 
+        decrease_isolated_debt(store, self.0, denom, amount_scaled)?;
+
         Ok(())
     }
 }
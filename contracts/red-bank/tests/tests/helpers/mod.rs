@@ -133,6 +133,7 @@ pub fn th_default_asset_params() -> AssetParams {
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value: Decimal::zero(),
         liquidation_threshold: Decimal::one(),
@@ -144,6 +145,7 @@ pub fn th_default_asset_params() -> AssetParams {
         },
         protocol_liquidation_fee: Decimal::percent(2u64),
         deposit_cap: Uint128::MAX,
+        borrow_cap: Uint128::MAX,
     }
 }
 
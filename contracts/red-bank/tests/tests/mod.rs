@@ -4,12 +4,16 @@ mod test_admin;
 mod test_borrow;
 mod test_credit_accounts;
 mod test_deposit;
+mod test_flash_loan;
 mod test_health;
 mod test_inflated_collateral;
+mod test_isolation;
 mod test_liquidate;
 mod test_migration_v2;
+mod test_migration_v2_2_0;
 mod test_misc;
 mod test_payment;
 mod test_query;
 mod test_update_owner;
+mod test_update_pyth_prices;
 mod test_withdraw;
@@ -13,7 +13,7 @@ use mars_types::{
     keys::{UserId, UserIdKey},
     red_bank::{
         ConfigResponse, CreateOrUpdateConfig, ExecuteMsg, InitOrUpdateAssetParams, InstantiateMsg,
-        InterestRateModel, Market, QueryMsg,
+        InterestRateModel, LinearInterestRateModel, Market, QueryMsg,
     },
 };
 use mars_utils::error::ValidationError;
@@ -134,7 +134,7 @@ fn init_asset() {
     let info = mock_info("owner", &[]);
     instantiate(deps.as_mut(), env.clone(), info, msg).unwrap();
 
-    let ir_model = InterestRateModel {
+    let ir_model = LinearInterestRateModel {
         optimal_utilization_rate: Decimal::one(),
         base: Decimal::percent(5),
         slope_1: Decimal::zero(),
@@ -143,7 +143,8 @@ fn init_asset() {
 
     let params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::from_ratio(1u128, 100u128)),
-        interest_rate_model: Some(ir_model.clone()),
+        interest_rate_model: Some(InterestRateModel::Linear(ir_model.clone())),
+        flash_loan_fee_rate: None,
     };
 
     // non owner is not authorized
@@ -211,6 +212,7 @@ fn init_asset() {
         let empty_asset_params = InitOrUpdateAssetParams {
             reserve_factor: None,
             interest_rate_model: None,
+            flash_loan_fee_rate: None,
         };
         let msg = ExecuteMsg::InitAsset {
             denom: "someasset".to_string(),
@@ -247,10 +249,10 @@ fn init_asset() {
     // init asset where optimal utilization rate > 1
     {
         let invalid_asset_params = InitOrUpdateAssetParams {
-            interest_rate_model: Some(InterestRateModel {
+            interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
                 optimal_utilization_rate: Decimal::percent(110),
                 ..ir_model
-            }),
+            })),
             ..params
         };
         let msg = ExecuteMsg::InitAsset {
@@ -273,11 +275,11 @@ fn init_asset() {
     // init asset where slope_1 >= slope_2
     {
         let invalid_asset_params = InitOrUpdateAssetParams {
-            interest_rate_model: Some(InterestRateModel {
+            interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
                 slope_1: Decimal::percent(10),
                 slope_2: Decimal::percent(10),
                 ..ir_model
-            }),
+            })),
             ..params
         };
         let msg = ExecuteMsg::InitAsset {
@@ -346,7 +348,7 @@ fn update_asset() {
 
     deps.querier.set_target_health_factor(Decimal::from_ratio(1u128, 2u128));
 
-    let ir_model = InterestRateModel {
+    let ir_model = LinearInterestRateModel {
         optimal_utilization_rate: Decimal::one(),
         base: Decimal::percent(5),
         slope_1: Decimal::zero(),
@@ -355,7 +357,8 @@ fn update_asset() {
 
     let params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::from_ratio(1u128, 100u128)),
-        interest_rate_model: Some(ir_model.clone()),
+        interest_rate_model: Some(InterestRateModel::Linear(ir_model.clone())),
+        flash_loan_fee_rate: None,
     };
 
     // non owner is not authorized
@@ -393,10 +396,10 @@ fn update_asset() {
     // update asset where optimal utilization rate > 1
     {
         let invalid_asset_params = InitOrUpdateAssetParams {
-            interest_rate_model: Some(InterestRateModel {
+            interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
                 optimal_utilization_rate: Decimal::percent(110),
                 ..ir_model
-            }),
+            })),
             ..params
         };
         let msg = ExecuteMsg::UpdateAsset {
@@ -420,7 +423,8 @@ fn update_asset() {
     {
         let params = InitOrUpdateAssetParams {
             reserve_factor: Some(Decimal::from_ratio(10u128, 100u128)),
-            interest_rate_model: Some(ir_model),
+            interest_rate_model: Some(InterestRateModel::Linear(ir_model)),
+            flash_loan_fee_rate: None,
         };
         let msg = ExecuteMsg::UpdateAsset {
             denom: "someasset".to_string(),
@@ -447,6 +451,7 @@ fn update_asset() {
         let empty_asset_params = InitOrUpdateAssetParams {
             reserve_factor: None,
             interest_rate_model: None,
+            flash_loan_fee_rate: None,
         };
         let msg = ExecuteMsg::UpdateAsset {
             denom: "someasset".to_string(),
@@ -483,7 +488,7 @@ fn update_asset_with_new_interest_rate_model_params() {
 
     deps.querier.set_target_health_factor(Decimal::from_ratio(1u128, 2u128));
 
-    let ir_model = InterestRateModel {
+    let ir_model = LinearInterestRateModel {
         optimal_utilization_rate: Decimal::one(),
         base: Decimal::percent(5),
         slope_1: Decimal::zero(),
@@ -492,7 +497,8 @@ fn update_asset_with_new_interest_rate_model_params() {
 
     let params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::from_ratio(2u128, 100u128)),
-        interest_rate_model: Some(ir_model.clone()),
+        interest_rate_model: Some(InterestRateModel::Linear(ir_model.clone())),
+        flash_loan_fee_rate: None,
     };
 
     let msg = ExecuteMsg::InitAsset {
@@ -505,13 +511,13 @@ fn update_asset_with_new_interest_rate_model_params() {
 
     // Verify if IR model is saved correctly
     let market_before = MARKETS.load(&deps.storage, "someasset").unwrap();
-    assert_eq!(market_before.interest_rate_model, ir_model);
+    assert_eq!(market_before.interest_rate_model, InterestRateModel::Linear(ir_model.clone()));
 
     // new IR model has a fixed borrow rate of 69%
-    let new_ir_model = InterestRateModel {
+    let new_ir_model = InterestRateModel::Linear(LinearInterestRateModel {
         base: Decimal::percent(69),
         ..ir_model
-    };
+    });
     let asset_params_with_new_ir_model = InitOrUpdateAssetParams {
         interest_rate_model: Some(new_ir_model.clone()),
         ..params
@@ -564,12 +570,12 @@ fn update_asset_new_reserve_factor_accrues_interest_rate() {
 
     let reserve_factor = Decimal::from_ratio(1_u128, 10_u128);
 
-    let ir_model = InterestRateModel {
+    let ir_model = InterestRateModel::Linear(LinearInterestRateModel {
         optimal_utilization_rate: Decimal::from_ratio(80u128, 100u128),
         base: Decimal::zero(),
         slope_1: Decimal::from_ratio(1_u128, 2_u128),
         slope_2: Decimal::from_ratio(2_u128, 1_u128),
-    };
+    });
 
     let asset_initial_debt = Uint128::new(2_000_000_000_000);
     let debt_total_scaled =
@@ -605,6 +611,7 @@ fn update_asset_new_reserve_factor_accrues_interest_rate() {
     let params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::from_ratio(2_u128, 10_u128)),
         interest_rate_model: None,
+        flash_loan_fee_rate: None,
     };
     let msg = ExecuteMsg::UpdateAsset {
         denom: "somecoin".to_string(),
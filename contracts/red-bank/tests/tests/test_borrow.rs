@@ -1004,6 +1004,7 @@ fn cannot_borrow_if_market_not_enabled() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             ..th_default_asset_params()
         },
@@ -1026,6 +1027,70 @@ fn cannot_borrow_if_market_not_enabled() {
     );
 }
 
+#[test]
+fn cannot_borrow_above_borrow_cap() {
+    let block_time = 10_000_000;
+    let mut deps = th_setup(&[coin(1_000_000, "uosmo")]);
+
+    let market = Market {
+        collateral_total_scaled: Uint128::new(1_000_000) * SCALING_FACTOR,
+        debt_total_scaled: Uint128::new(400_000) * SCALING_FACTOR,
+        indexes_last_updated: block_time,
+        ..Default::default()
+    };
+    th_init_market(deps.as_mut(), "uosmo", &market);
+    deps.querier.set_oracle_price("uosmo", Decimal::one());
+    deps.querier.set_redbank_params(
+        "uosmo",
+        AssetParams {
+            max_loan_to_value: Decimal::percent(50),
+            borrow_cap: Uint128::new(450_000),
+            ..th_default_asset_params()
+        },
+    );
+
+    let borrower_addr = Addr::unchecked("borrower");
+    set_collateral(
+        deps.as_mut(),
+        &borrower_addr,
+        "uosmo",
+        Uint128::new(500_000) * SCALING_FACTOR,
+        true,
+    );
+
+    // Total debt would be 450_001, one above the cap
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(block_time),
+        mock_info("borrower", &[]),
+        ExecuteMsg::Borrow {
+            denom: "uosmo".to_string(),
+            amount: Uint128::new(50_001),
+            recipient: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::BorrowCapExceeded {
+            denom: "uosmo".to_string()
+        }
+    );
+
+    // Borrowing up to the cap is fine
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(block_time),
+        mock_info("borrower", &[]),
+        ExecuteMsg::Borrow {
+            denom: "uosmo".to_string(),
+            amount: Uint128::new(50_000),
+            recipient: None,
+        },
+    )
+    .unwrap();
+}
+
 #[test]
 fn borrow_and_send_funds_to_another_user() {
     let initial_liquidity = 10000000;
@@ -75,9 +75,11 @@ fn setup_test() -> TestSuite {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Uint128::new(12_000_000),
+            borrow_cap: Uint128::MAX,
         },
     );
 
@@ -189,6 +191,7 @@ fn depositing_to_disabled_market() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: true,
+                isolation: None,
             },
             ..th_default_asset_params()
         },
@@ -245,6 +248,7 @@ fn depositing_above_cap(amount_to_deposit: u128, deposit_cap: u128, exp_ok: bool
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             deposit_cap: Uint128::new(deposit_cap),
             ..th_default_asset_params()
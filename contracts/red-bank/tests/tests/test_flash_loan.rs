@@ -0,0 +1,381 @@
+use cosmwasm_std::{
+    coin,
+    testing::{mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR},
+    to_json_binary, Addr, Binary, Decimal, OwnedDeps, SubMsg, Uint128, WasmMsg,
+};
+use mars_interest_rate::{compute_scaled_amount, ScalingOperation, SCALING_FACTOR};
+use mars_red_bank::{
+    contract::execute,
+    error::ContractError,
+    state::{COLLATERALS, FLASH_LOANS, MARKETS},
+};
+use mars_testing::{mock_env_at_block_time, MarsMockQuerier};
+use mars_types::{
+    address_provider::MarsAddressType,
+    error::MarsError,
+    keys::{UserId, UserIdKey},
+    red_bank::{ExecuteMsg, FlashLoan, Market},
+};
+use mars_utils::error::GuardError;
+
+use super::helpers::{th_init_market, th_setup};
+
+const BLOCK_TIME: u64 = 10_000_000;
+
+/// Market with 600_000 uosmo of available liquidity, a 1% flash loan fee and a 10% reserve factor
+fn setup() -> OwnedDeps<MockStorage, MockApi, MarsMockQuerier> {
+    let mut deps = th_setup(&[coin(1_000_000, "uosmo")]);
+
+    let market = Market {
+        collateral_total_scaled: Uint128::new(1_000_000) * SCALING_FACTOR,
+        debt_total_scaled: Uint128::new(400_000) * SCALING_FACTOR,
+        indexes_last_updated: BLOCK_TIME,
+        flash_loan_fee_rate: Decimal::percent(1),
+        reserve_factor: Decimal::percent(10),
+        ..Default::default()
+    };
+    th_init_market(deps.as_mut(), "uosmo", &market);
+
+    deps
+}
+
+fn flash_loan_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::FlashLoan {
+        coins: vec![coin(amount, "uosmo")],
+        callback: Binary::from(b"{\"callback\":{}}"),
+    }
+}
+
+#[test]
+fn flash_loan_sends_coins_with_callback() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "receiver".to_string(),
+                msg: Binary::from(b"{\"callback\":{}}"),
+                funds: vec![coin(100_000, "uosmo")],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_json_binary(&ExecuteMsg::FinalizeFlashLoan {}).unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    let loans = FLASH_LOANS.load(&deps.storage).unwrap();
+    assert_eq!(
+        loans,
+        vec![FlashLoan {
+            denom: "uosmo".to_string(),
+            amount: Uint128::new(100_000),
+            fee: Uint128::new(1_000),
+            repaid: Uint128::zero(),
+        }]
+    );
+}
+
+#[test]
+fn cannot_flash_loan_more_than_available_liquidity() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(600_001),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidFlashLoanAmount {
+            denom: "uosmo".to_string()
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(0),
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidFlashLoanAmount {
+            denom: "uosmo".to_string()
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        ExecuteMsg::FlashLoan {
+            coins: vec![],
+            callback: Binary::default(),
+        },
+    );
+    assert_eq!(res, Err(ContractError::EmptyFlashLoan {}));
+}
+
+#[test]
+fn cannot_nest_flash_loans() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    );
+    assert_eq!(res, Err(ContractError::Guard(GuardError::Active {})));
+}
+
+#[test]
+fn cannot_use_red_bank_during_flash_loan() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    let msgs = vec![
+        (
+            vec![coin(100_000, "uosmo")],
+            ExecuteMsg::Deposit {
+                account_id: None,
+                on_behalf_of: None,
+            },
+        ),
+        (
+            vec![],
+            ExecuteMsg::Borrow {
+                denom: "uosmo".to_string(),
+                amount: Uint128::new(1_000),
+                recipient: None,
+            },
+        ),
+        (
+            vec![],
+            ExecuteMsg::Withdraw {
+                denom: "uosmo".to_string(),
+                amount: None,
+                recipient: None,
+                account_id: None,
+                liquidation_related: None,
+            },
+        ),
+        (
+            vec![coin(1_000, "uosmo")],
+            ExecuteMsg::Repay {
+                on_behalf_of: None,
+            },
+        ),
+        (
+            vec![coin(1_000, "uosmo")],
+            ExecuteMsg::Liquidate {
+                user: "user".to_string(),
+                collateral_denom: "uosmo".to_string(),
+                recipient: None,
+            },
+        ),
+    ];
+    for (funds, msg) in msgs {
+        let res = execute(
+            deps.as_mut(),
+            mock_env_at_block_time(BLOCK_TIME),
+            mock_info("receiver", &funds),
+            msg,
+        );
+        assert_eq!(res, Err(ContractError::Guard(GuardError::Active {})));
+    }
+}
+
+#[test]
+fn repay_requires_active_flash_loan() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[coin(100, "uosmo")]),
+        ExecuteMsg::RepayFlashLoan {},
+    );
+    assert_eq!(res, Err(ContractError::Guard(GuardError::Inactive {})));
+}
+
+#[test]
+fn cannot_repay_more_than_outstanding() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[coin(101_001, "uosmo")]),
+        ExecuteMsg::RepayFlashLoan {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidFlashLoanRepayment {
+            denom: "uosmo".to_string()
+        })
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[coin(100, "uatom")]),
+        ExecuteMsg::RepayFlashLoan {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::InvalidFlashLoanRepayment {
+            denom: "uatom".to_string()
+        })
+    );
+}
+
+#[test]
+fn only_contract_can_finalize_flash_loan() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        ExecuteMsg::FinalizeFlashLoan {},
+    );
+    assert_eq!(res, Err(ContractError::Mars(MarsError::Unauthorized {})));
+}
+
+#[test]
+fn finalize_fails_if_not_repaid() {
+    let mut deps = setup();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    // principal returned without the fee
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[coin(100_000, "uosmo")]),
+        ExecuteMsg::RepayFlashLoan {},
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::FinalizeFlashLoan {},
+    );
+    assert_eq!(
+        res,
+        Err(ContractError::FlashLoanNotRepaid {
+            denom: "uosmo".to_string(),
+            outstanding: Uint128::new(1_000),
+        })
+    );
+}
+
+#[test]
+fn finalize_splits_fee_by_reserve_factor() {
+    let mut deps = setup();
+    let market_before = MARKETS.load(&deps.storage, "uosmo").unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[coin(101_000, "uosmo")]),
+        ExecuteMsg::RepayFlashLoan {},
+    )
+    .unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::FinalizeFlashLoan {},
+    )
+    .unwrap();
+
+    // 900 of the fee accrue to the 1_000_000 deposited, the remaining 100 go to the rewards
+    // collector
+    let expected_liquidity_index =
+        market_before.liquidity_index * Decimal::from_ratio(1_000_900u128, 1_000_000u128);
+    let expected_fee_scaled = compute_scaled_amount(
+        Uint128::new(100),
+        expected_liquidity_index,
+        ScalingOperation::Truncate,
+    )
+    .unwrap();
+
+    let market_after = MARKETS.load(&deps.storage, "uosmo").unwrap();
+    assert_eq!(market_after.liquidity_index, expected_liquidity_index);
+    assert_eq!(
+        market_after.collateral_total_scaled,
+        market_before.collateral_total_scaled + expected_fee_scaled
+    );
+    assert_eq!(market_after.debt_total_scaled, market_before.debt_total_scaled);
+
+    let rewards_collector = Addr::unchecked(MarsAddressType::RewardsCollector.to_string());
+    let user_id = UserId::credit_manager(rewards_collector, "".to_string());
+    let user_id_key: UserIdKey = user_id.try_into().unwrap();
+    let collateral = COLLATERALS.load(&deps.storage, (&user_id_key, "uosmo")).unwrap();
+    assert_eq!(collateral.amount_scaled, expected_fee_scaled);
+
+    assert!(FLASH_LOANS.may_load(&deps.storage).unwrap().is_none());
+
+    // guard is released, a new flash loan can be taken
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("receiver", &[]),
+        flash_loan_msg(100_000),
+    )
+    .unwrap();
+}
@@ -5,7 +5,7 @@ use mars_red_bank::error::ContractError;
 use mars_testing::integration::mock_env::MockEnvBuilder;
 use mars_types::{
     params::{AssetParams, CmSettings, LiquidationBonus, RedBankSettings},
-    red_bank::{InitOrUpdateAssetParams, InterestRateModel},
+    red_bank::{InitOrUpdateAssetParams, InterestRateModel, LinearInterestRateModel},
 };
 
 use crate::tests::helpers::assert_err;
@@ -135,12 +135,13 @@ fn inflated_collateral() {
 fn atom_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(10)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(80),
             base: Decimal::percent(0),
             slope_1: Decimal::percent(20),
             slope_2: Decimal::percent(300),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -151,6 +152,7 @@ fn atom_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value: Decimal::percent(74),
         liquidation_threshold: Decimal::percent(75),
@@ -162,6 +164,7 @@ fn atom_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
         },
         protocol_liquidation_fee: Decimal::percent(25),
         deposit_cap: Uint128::from(700000000000u128),
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
@@ -169,12 +172,13 @@ fn atom_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
 fn osmo_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(10)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(60),
             base: Decimal::percent(0),
             slope_1: Decimal::percent(15),
             slope_2: Decimal::percent(300),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -185,6 +189,7 @@ fn osmo_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value: Decimal::percent(73),
         liquidation_threshold: Decimal::percent(75),
@@ -196,6 +201,7 @@ fn osmo_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
         },
         protocol_liquidation_fee: Decimal::percent(25),
         deposit_cap: Uint128::from(10000000000000u128),
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
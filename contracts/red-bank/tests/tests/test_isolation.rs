@@ -0,0 +1,288 @@
+use cosmwasm_std::{
+    coin,
+    testing::{mock_info, MockApi, MockStorage},
+    Addr, Decimal, OwnedDeps, Uint128,
+};
+use mars_interest_rate::{compute_scaled_amount, ScalingOperation, SCALING_FACTOR};
+use mars_red_bank::{
+    contract::execute,
+    error::ContractError,
+    state::{COLLATERALS, ISOLATED_DEBTS, USER_ISOLATED_COLLATERAL},
+};
+use mars_testing::{mock_env_at_block_time, MarsMockQuerier};
+use mars_types::{
+    keys::{UserId, UserIdKey},
+    params::{AssetParams, IsolationParams, RedBankSettings},
+    red_bank::{ExecuteMsg, Market},
+};
+
+use super::helpers::{set_collateral, th_default_asset_params, th_init_market, th_setup};
+
+const BLOCK_TIME: u64 = 10_000_000;
+
+/// Borrower with 10_000 ulong enabled as collateral. ulong is isolated: only uusdc can be borrowed
+/// against it, up to a debt ceiling of 1_000.
+fn setup() -> (OwnedDeps<MockStorage, MockApi, MarsMockQuerier>, Addr) {
+    let mut deps = th_setup(&[coin(1_000_000, "uusdc"), coin(1_000_000, "uosmo")]);
+
+    for denom in ["ulong", "uusdc", "uosmo"] {
+        let market = Market {
+            collateral_total_scaled: Uint128::new(1_000_000) * SCALING_FACTOR,
+            indexes_last_updated: BLOCK_TIME,
+            ..Default::default()
+        };
+        th_init_market(deps.as_mut(), denom, &market);
+        deps.querier.set_oracle_price(denom, Decimal::one());
+        deps.querier.set_total_deposit(denom, Uint128::new(1_000_000));
+        deps.querier.set_redbank_params(
+            denom,
+            AssetParams {
+                denom: denom.to_string(),
+                max_loan_to_value: Decimal::percent(50),
+                ..th_default_asset_params()
+            },
+        );
+    }
+
+    deps.querier.set_redbank_params(
+        "ulong",
+        AssetParams {
+            denom: "ulong".to_string(),
+            max_loan_to_value: Decimal::percent(50),
+            red_bank: RedBankSettings {
+                deposit_enabled: true,
+                borrow_enabled: true,
+                isolation: Some(IsolationParams {
+                    borrowable_denoms: vec!["uusdc".to_string()],
+                    debt_ceiling: Uint128::new(1_000),
+                }),
+            },
+            ..th_default_asset_params()
+        },
+    );
+
+    let borrower = Addr::unchecked("borrower");
+    set_collateral(deps.as_mut(), &borrower, "ulong", Uint128::new(10_000) * SCALING_FACTOR, true);
+
+    (deps, borrower)
+}
+
+fn borrow(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MarsMockQuerier>,
+    denom: &str,
+    amount: u128,
+) -> Result<(), ContractError> {
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("borrower", &[]),
+        ExecuteMsg::Borrow {
+            denom: denom.to_string(),
+            amount: Uint128::new(amount),
+            recipient: None,
+        },
+    )
+    .map(|_| ())
+}
+
+#[test]
+fn cannot_borrow_denom_not_whitelisted_for_isolated_collateral() {
+    let (mut deps, _) = setup();
+
+    let res = borrow(&mut deps, "uosmo", 100);
+    assert_eq!(
+        res,
+        Err(ContractError::BorrowNotAllowedInIsolation {
+            collateral: "ulong".to_string(),
+            denom: "uosmo".to_string(),
+        })
+    );
+}
+
+#[test]
+fn isolated_collateral_has_to_be_only_enabled_collateral() {
+    let (mut deps, borrower) = setup();
+    set_collateral(deps.as_mut(), &borrower, "uosmo", Uint128::new(1_000) * SCALING_FACTOR, true);
+
+    let res = borrow(&mut deps, "uusdc", 100);
+    assert_eq!(
+        res,
+        Err(ContractError::IsolatedCollateralNotSole {
+            denom: "ulong".to_string(),
+        })
+    );
+
+    // Disabling the other collateral allows borrowing against the isolated one
+    set_collateral(deps.as_mut(), &borrower, "uosmo", Uint128::new(1_000) * SCALING_FACTOR, false);
+    borrow(&mut deps, "uusdc", 100).unwrap();
+}
+
+#[test]
+fn depositing_isolated_collateral_does_not_enable_it() {
+    let (mut deps, _) = setup();
+    let depositor = Addr::unchecked("depositor");
+
+    for denom in ["ulong", "uosmo"] {
+        execute(
+            deps.as_mut(),
+            mock_env_at_block_time(BLOCK_TIME),
+            mock_info(depositor.as_str(), &[coin(1_000, denom)]),
+            ExecuteMsg::Deposit {
+                account_id: None,
+                on_behalf_of: None,
+            },
+        )
+        .unwrap();
+    }
+
+    let user_id = UserId::credit_manager(depositor, "".to_string());
+    let user_id_key: UserIdKey = user_id.try_into().unwrap();
+    let collateral = COLLATERALS.load(&deps.storage, (&user_id_key, "ulong")).unwrap();
+    assert!(!collateral.enabled);
+    let collateral = COLLATERALS.load(&deps.storage, (&user_id_key, "uosmo")).unwrap();
+    assert!(collateral.enabled);
+
+    // Isolated collateral isn't counted, so the other collateral can be borrowed against
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("depositor", &[]),
+        ExecuteMsg::Borrow {
+            denom: "uusdc".to_string(),
+            amount: Uint128::new(100),
+            recipient: None,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn cannot_enable_isolated_collateral_alongside_other_collateral() {
+    let (mut deps, borrower) = setup();
+    set_collateral(deps.as_mut(), &borrower, "uosmo", Uint128::new(1_000) * SCALING_FACTOR, false);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info(borrower.as_str(), &[]),
+        ExecuteMsg::UpdateAssetCollateralStatus {
+            denom: "uosmo".to_string(),
+            enable: true,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::IsolatedCollateralNotSole {
+            denom: "ulong".to_string(),
+        }
+    );
+}
+
+#[test]
+fn cannot_enable_other_collateral_in_isolation_mode() {
+    let (mut deps, borrower) = setup();
+
+    borrow(&mut deps, "uusdc", 100).unwrap();
+    assert_eq!(USER_ISOLATED_COLLATERAL.load(&deps.storage, &borrower).unwrap(), "ulong");
+
+    set_collateral(deps.as_mut(), &borrower, "uosmo", Uint128::new(1_000) * SCALING_FACTOR, false);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info(borrower.as_str(), &[]),
+        ExecuteMsg::UpdateAssetCollateralStatus {
+            denom: "uosmo".to_string(),
+            enable: true,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::IsolatedCollateralNotSole {
+            denom: "ulong".to_string(),
+        }
+    );
+}
+
+#[test]
+fn stale_isolated_collateral_is_cleared() {
+    let (mut deps, borrower) = setup();
+
+    borrow(&mut deps, "uusdc", 600).unwrap();
+
+    // ulong isn't isolated anymore
+    deps.querier.set_redbank_params(
+        "ulong",
+        AssetParams {
+            denom: "ulong".to_string(),
+            max_loan_to_value: Decimal::percent(50),
+            ..th_default_asset_params()
+        },
+    );
+
+    borrow(&mut deps, "uosmo", 100).unwrap();
+
+    assert!(ISOLATED_DEBTS.may_load(&deps.storage, ("ulong", "uusdc")).unwrap().is_none());
+    assert!(USER_ISOLATED_COLLATERAL.may_load(&deps.storage, &borrower).unwrap().is_none());
+}
+
+#[test]
+fn debt_ceiling_enforced() {
+    let (mut deps, borrower) = setup();
+
+    borrow(&mut deps, "uusdc", 600).unwrap();
+
+    let expected_scaled =
+        compute_scaled_amount(Uint128::new(600), Decimal::one(), ScalingOperation::Ceil).unwrap();
+    let isolated_debt = ISOLATED_DEBTS.load(&deps.storage, ("ulong", "uusdc")).unwrap();
+    assert_eq!(isolated_debt, expected_scaled);
+    let isolated_collateral = USER_ISOLATED_COLLATERAL.load(&deps.storage, &borrower).unwrap();
+    assert_eq!(isolated_collateral, "ulong");
+
+    let res = borrow(&mut deps, "uusdc", 401);
+    assert_eq!(
+        res,
+        Err(ContractError::IsolatedDebtCeilingExceeded {
+            collateral: "ulong".to_string(),
+            debt_ceiling: Uint128::new(1_000),
+        })
+    );
+
+    borrow(&mut deps, "uusdc", 400).unwrap();
+}
+
+#[test]
+fn repay_releases_debt_ceiling() {
+    let (mut deps, borrower) = setup();
+
+    borrow(&mut deps, "uusdc", 1_000).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("borrower", &[coin(400, "uusdc")]),
+        ExecuteMsg::Repay {
+            on_behalf_of: None,
+        },
+    )
+    .unwrap();
+
+    // Repaid amount can be borrowed again
+    borrow(&mut deps, "uusdc", 400).unwrap();
+
+    execute(
+        deps.as_mut(),
+        mock_env_at_block_time(BLOCK_TIME),
+        mock_info("borrower", &[coin(1_000, "uusdc")]),
+        ExecuteMsg::Repay {
+            on_behalf_of: None,
+        },
+    )
+    .unwrap();
+
+    // Fully repaid, the borrower leaves isolation mode
+    assert!(ISOLATED_DEBTS.may_load(&deps.storage, ("ulong", "uusdc")).unwrap().is_none());
+    assert!(USER_ISOLATED_COLLATERAL.may_load(&deps.storage, &borrower).unwrap().is_none());
+}
@@ -16,8 +16,8 @@ use mars_types::{
     incentives,
     params::{AssetParams, CmSettings, LiquidationBonus, RedBankSettings},
     red_bank::{
-        ExecuteMsg, InitOrUpdateAssetParams, InterestRateModel, Market, QueryMsg,
-        UserCollateralResponse, UserDebtResponse,
+        ExecuteMsg, InitOrUpdateAssetParams, InterestRateModel, LinearInterestRateModel, Market,
+        QueryMsg, UserCollateralResponse, UserDebtResponse,
     },
 };
 
@@ -286,6 +286,50 @@ fn target_health_factor_reached_after_max_debt_repayed() {
     assert_eq!(liq_threshold_hf, Decimal::from_str("1.200016765864699471").unwrap());
 }
 
+#[test]
+fn estimate_liquidation_matches_liquidation() {
+    let mut mock_env = MockEnvBuilder::new(None, Addr::unchecked("owner"))
+        .target_health_factor(Decimal::from_ratio(12u128, 10u128))
+        .build();
+
+    let red_bank = mock_env.red_bank.clone();
+    let oracle = mock_env.oracle.clone();
+
+    let (_, _, liquidatee, liquidator) = setup_env(&mut mock_env);
+
+    let err = red_bank
+        .query_estimate_liquidation(&mut mock_env, &liquidatee, "uosmo", coin(2373, "uusdc"))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::CannotLiquidateHealthyPosition {}.to_string()));
+
+    // change price to be able to liquidate
+    oracle.set_price_source_fixed(&mut mock_env, "uosmo", Decimal::from_ratio(3u128, 1u128));
+    oracle.set_price_source_fixed(&mut mock_env, "uusdc", Decimal::from_ratio(85u128, 10u128));
+
+    // same figures as in `target_health_factor_reached_after_max_debt_repayed`
+    let estimate = red_bank
+        .query_estimate_liquidation(&mut mock_env, &liquidatee, "uosmo", coin(2373, "uusdc"))
+        .unwrap();
+    assert_eq!(estimate.debt_repaid, coin(2373, "uusdc"));
+    assert_eq!(estimate.collateral_liquidated, coin(7191, "uosmo"));
+    assert_eq!(estimate.collateral_received, coin(7182, "uosmo"));
+    assert_eq!(estimate.protocol_fee, coin(9, "uosmo"));
+
+    red_bank
+        .liquidate(&mut mock_env, &liquidator, &liquidatee, "uosmo", &[coin(2373, "uusdc")])
+        .unwrap();
+
+    let liquidator_collaterals = red_bank.query_user_collaterals(&mut mock_env, &liquidator);
+    assert_eq!(liquidator_collaterals.get("uosmo").unwrap().amount.u128(), 7182);
+    let liquidatee_debts = red_bank.query_user_debts(&mut mock_env, &liquidatee);
+    assert_eq!(liquidatee_debts.get("uusdc").unwrap().amount.u128(), 627);
+
+    let liquidatee_position = red_bank.query_user_position(&mut mock_env, &liquidatee);
+    assert_eq!(estimate.health_factor_after, Some(liq_threshold_hf(&liquidatee_position)));
+}
+
 #[test]
 fn debt_amt_adjusted_to_total_debt_then_refund() {
     let mut mock_env = MockEnvBuilder::new(None, Addr::unchecked("owner"))
@@ -1299,12 +1343,13 @@ fn _default_asset_params_with(
 ) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(20)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(10),
             base: Decimal::percent(30),
             slope_1: Decimal::percent(25),
             slope_2: Decimal::percent(30),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -1315,12 +1360,14 @@ fn _default_asset_params_with(
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value,
         liquidation_threshold,
         liquidation_bonus,
         protocol_liquidation_fee: Decimal::percent(2),
         deposit_cap: Uint128::MAX,
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
@@ -0,0 +1,150 @@
+use cosmwasm_std::{attr, testing::mock_env, Decimal, Empty, Event, Uint128};
+use cw2::{ContractVersion, VersionError};
+use mars_red_bank::{
+    contract::migrate, error::ContractError, migrations::v2_2_0::v2_1_0_state, state::MARKETS,
+};
+use mars_testing::mock_dependencies;
+use mars_types::red_bank::{InterestRateModel, LinearInterestRateModel, Market};
+
+#[test]
+fn wrong_contract_name() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "contract_xyz", "2.1.0").unwrap();
+
+    let err = migrate(deps.as_mut(), mock_env(), Empty {}).unwrap_err();
+
+    assert_eq!(
+        err,
+        ContractError::Version(VersionError::WrongContract {
+            expected: "crates.io:mars-red-bank".to_string(),
+            found: "contract_xyz".to_string()
+        })
+    );
+}
+
+#[test]
+fn successful_migration() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-red-bank", "2.1.0").unwrap();
+
+    let res = migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(res.events, vec![] as Vec<Event>);
+    assert!(res.data.is_none());
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "migrate"), attr("from_version", "2.1.0"), attr("to_version", "2.2.0")]
+    );
+
+    let new_contract_version = ContractVersion {
+        contract: "crates.io:mars-red-bank".to_string(),
+        version: "2.2.0".to_string(),
+    };
+    assert_eq!(cw2::get_contract_version(deps.as_ref().storage).unwrap(), new_contract_version);
+}
+
+#[test]
+fn markets_migrated_to_linear_interest_rate_model() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-red-bank", "2.1.0").unwrap();
+
+    let ir_model = linear_ir_model();
+    let old_market = v2_1_0_state::Market {
+        denom: "uosmo".to_string(),
+        reserve_factor: Decimal::percent(10),
+        interest_rate_model: ir_model.clone(),
+        borrow_index: Decimal::percent(110),
+        liquidity_index: Decimal::percent(105),
+        borrow_rate: Decimal::percent(5),
+        liquidity_rate: Decimal::percent(3),
+        indexes_last_updated: 1_000,
+        collateral_total_scaled: Uint128::new(1_000_000),
+        debt_total_scaled: Uint128::new(500_000),
+        flash_loan_fee_rate: Decimal::zero(),
+    };
+    v2_1_0_state::MARKETS.save(deps.as_mut().storage, "uosmo", &old_market).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    let market = MARKETS.load(deps.as_ref().storage, "uosmo").unwrap();
+    assert_eq!(market.interest_rate_model, InterestRateModel::Linear(ir_model));
+    assert_eq!(market.reserve_factor, old_market.reserve_factor);
+    assert_eq!(market.borrow_index, old_market.borrow_index);
+    assert_eq!(market.liquidity_index, old_market.liquidity_index);
+    assert_eq!(market.indexes_last_updated, old_market.indexes_last_updated);
+    assert_eq!(market.collateral_total_scaled, old_market.collateral_total_scaled);
+    assert_eq!(market.debt_total_scaled, old_market.debt_total_scaled);
+}
+
+#[test]
+fn markets_with_new_interest_rate_model_are_skipped() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-red-bank", "2.1.0").unwrap();
+
+    let old_market = v2_1_0_state::Market {
+        denom: "uosmo".to_string(),
+        reserve_factor: Decimal::percent(10),
+        interest_rate_model: linear_ir_model(),
+        borrow_index: Decimal::one(),
+        liquidity_index: Decimal::one(),
+        borrow_rate: Decimal::zero(),
+        liquidity_rate: Decimal::zero(),
+        indexes_last_updated: 1_000,
+        collateral_total_scaled: Uint128::zero(),
+        debt_total_scaled: Uint128::zero(),
+        flash_loan_fee_rate: Decimal::zero(),
+    };
+    v2_1_0_state::MARKETS.save(deps.as_mut().storage, "uosmo", &old_market).unwrap();
+
+    let new_market = Market {
+        denom: "uatom".to_string(),
+        interest_rate_model: InterestRateModel::Linear(linear_ir_model()),
+        ..Default::default()
+    };
+    MARKETS.save(deps.as_mut().storage, "uatom", &new_market).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    assert_eq!(MARKETS.load(deps.as_ref().storage, "uatom").unwrap(), new_market);
+    assert_eq!(
+        MARKETS.load(deps.as_ref().storage, "uosmo").unwrap().interest_rate_model,
+        InterestRateModel::Linear(linear_ir_model())
+    );
+}
+
+#[test]
+fn migration_from_v2_0_1_converts_markets() {
+    let mut deps = mock_dependencies(&[]);
+    cw2::set_contract_version(deps.as_mut().storage, "crates.io:mars-red-bank", "2.0.1").unwrap();
+
+    let old_market = v2_1_0_state::Market {
+        denom: "uosmo".to_string(),
+        reserve_factor: Decimal::percent(10),
+        interest_rate_model: linear_ir_model(),
+        borrow_index: Decimal::one(),
+        liquidity_index: Decimal::one(),
+        borrow_rate: Decimal::zero(),
+        liquidity_rate: Decimal::zero(),
+        indexes_last_updated: 1_000,
+        collateral_total_scaled: Uint128::zero(),
+        debt_total_scaled: Uint128::zero(),
+        flash_loan_fee_rate: Decimal::zero(),
+    };
+    v2_1_0_state::MARKETS.save(deps.as_mut().storage, "uosmo", &old_market).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), Empty {}).unwrap();
+
+    let market = MARKETS.load(deps.as_ref().storage, "uosmo").unwrap();
+    assert_eq!(market.interest_rate_model, InterestRateModel::Linear(linear_ir_model()));
+    assert_eq!(cw2::get_contract_version(deps.as_ref().storage).unwrap().version, "2.2.0");
+}
+
+fn linear_ir_model() -> LinearInterestRateModel {
+    LinearInterestRateModel {
+        optimal_utilization_rate: Decimal::percent(80),
+        base: Decimal::zero(),
+        slope_1: Decimal::percent(7),
+        slope_2: Decimal::percent(45),
+    }
+}
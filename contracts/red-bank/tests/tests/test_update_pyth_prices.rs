@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{coin, to_json_binary, Addr, Binary, Decimal, Uint128};
+use mars_testing::integration::{
+    helpers::{osmo_asset_params, usdc_asset_params},
+    mock_env::{MockEnv, MockEnvBuilder},
+};
+use pyth_sdk_cw::{Price, PriceFeed, PriceIdentifier};
+
+use crate::tests::helpers::assert_err_with_str;
+
+#[test]
+fn stale_pyth_price_is_updated_through_red_bank() {
+    let owner = Addr::unchecked("owner");
+    let mut mock_env = MockEnvBuilder::new(None, owner).build();
+
+    let red_bank = mock_env.red_bank.clone();
+    let params = mock_env.params.clone();
+    let oracle = mock_env.oracle.clone();
+    let pyth = mock_env.pyth.clone();
+
+    let provider = Addr::unchecked("provider");
+    let user = Addr::unchecked("user");
+
+    // setup red-bank
+    let (market_params, asset_params) = osmo_asset_params();
+    red_bank.init_asset(&mut mock_env, &asset_params.denom, market_params);
+    params.init_params(&mut mock_env, asset_params);
+    let (market_params, asset_params) = usdc_asset_params();
+    red_bank.init_asset(&mut mock_env, &asset_params.denom, market_params);
+    params.init_params(&mut mock_env, asset_params);
+
+    // setup oracle
+    oracle.set_price_source_fixed(&mut mock_env, "uosmo", Decimal::one());
+    oracle.set_price_source_fixed(&mut mock_env, "uusdc", Decimal::one());
+
+    mock_env.fund_accounts(&[&provider, &user], 1_000_000_000_000u128, &["uosmo", "uusdc"]);
+
+    red_bank.deposit(&mut mock_env, &provider, coin(1_000_000_000, "uusdc")).unwrap();
+    red_bank.deposit(&mut mock_env, &user, coin(100_000_000, "uosmo")).unwrap();
+    red_bank.borrow(&mut mock_env, &user, "uusdc", 100).unwrap();
+
+    // switch uusdc to pyth and let its price get stale
+    oracle.set_price_source_fixed(&mut mock_env, "usd", Decimal::from_str("1000000").unwrap());
+    oracle.set_price_source_pyth(
+        &mut mock_env,
+        "uusdc",
+        pyth.to_string(),
+        Decimal::percent(10u64),
+        Decimal::percent(15u64),
+    );
+    mock_env.increment_by_time(60);
+
+    let withdraw_amt = Some(Uint128::new(1_000));
+    let res =
+        red_bank.withdraw_with_acc_id(&mut mock_env, &user, "uosmo", withdraw_amt, None, None);
+    assert_err_with_str(res, "current price publish time is too old/stale");
+
+    // the mock pyth contract doesn't charge a fee
+    let vaa = usdc_price_feed_vaa(&mock_env);
+    let res =
+        red_bank.update_pyth_prices(&mut mock_env, &user, vec![vaa.clone()], &[coin(1, "uosmo")]);
+    assert_err_with_str(res, "This message does no accept funds");
+    red_bank.update_pyth_prices(&mut mock_env, &user, vec![vaa], &[]).unwrap();
+
+    red_bank.withdraw_with_acc_id(&mut mock_env, &user, "uosmo", withdraw_amt, None, None).unwrap();
+}
+
+// The mock Pyth contract accepts JSON encoded price feeds in place of VAAs
+fn usdc_price_feed_vaa(mock_env: &MockEnv) -> Binary {
+    let price = Price {
+        price: 100000,
+        conf: 50,
+        expo: -5,
+        publish_time: mock_env.app.block_info().time.seconds() as i64,
+    };
+    let price_feed = PriceFeed::new(
+        PriceIdentifier::from_hex(
+            "61226d39beea19d334f17c2febce27e12646d84675924ebb02b9cdaea68727e3",
+        )
+        .unwrap(),
+        price,
+        price,
+    );
+    to_json_binary(&price_feed).unwrap()
+}
@@ -6,7 +6,8 @@ use cw_multi_test::AppResponse;
 use mars_types::{
     params::{AssetParams, CmSettings, LiquidationBonus, RedBankSettings},
     red_bank::{
-        InitOrUpdateAssetParams, InterestRateModel, UserHealthStatus, UserPositionResponse,
+        InitOrUpdateAssetParams, InterestRateModel, LinearInterestRateModel, UserHealthStatus,
+        UserPositionResponse,
     },
 };
 use osmosis_std::types::osmosis::{
@@ -18,12 +19,13 @@ use osmosis_test_tube::{Account, ExecuteResponse, OsmosisTestApp, Runner, Signin
 pub fn default_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(20)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(10),
             base: Decimal::percent(30),
             slope_1: Decimal::percent(25),
             slope_2: Decimal::percent(30),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -34,6 +36,7 @@ pub fn default_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParam
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value: Decimal::percent(60),
         liquidation_threshold: Decimal::percent(80),
@@ -45,6 +48,7 @@ pub fn default_asset_params(denom: &str) -> (InitOrUpdateAssetParams, AssetParam
         },
         protocol_liquidation_fee: Decimal::percent(2u64),
         deposit_cap: Uint128::MAX,
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
@@ -57,12 +61,13 @@ pub fn default_asset_params_with(
 ) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(20)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(10),
             base: Decimal::percent(30),
             slope_1: Decimal::percent(25),
             slope_2: Decimal::percent(30),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -73,12 +78,14 @@ pub fn default_asset_params_with(
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value,
         liquidation_threshold,
         liquidation_bonus,
         protocol_liquidation_fee: Decimal::percent(2u64),
         deposit_cap: Uint128::MAX,
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
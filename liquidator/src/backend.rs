@@ -0,0 +1,46 @@
+use cosmwasm_std::{Decimal, Uint128};
+use mars_types::{
+    adapters::vault::{VaultPosition, VaultPositionValue},
+    credit_manager::{CoinBalanceResponseItem, SharesResponseItem, VaultPositionResponseItem},
+    health::AccountKind,
+    params::{AssetParams, VaultConfig},
+};
+
+use crate::error::LiquidatorResult;
+
+/// Source of the protocol state the liquidator works on. Implemented by [`crate::Snapshot`] for
+/// JSON snapshots and by [`crate::ChainBackend`] for live contract queries.
+pub trait QueryBackend {
+    /// Credit manager `AllCoinBalances`, all pages
+    fn coin_balances(&self) -> LiquidatorResult<Vec<CoinBalanceResponseItem>>;
+
+    /// Credit manager `AllDebtShares`, all pages
+    fn debt_shares(&self) -> LiquidatorResult<Vec<SharesResponseItem>>;
+
+    /// Credit manager `AllVaultPositions`, all pages
+    fn vault_positions(&self) -> LiquidatorResult<Vec<VaultPositionResponseItem>>;
+
+    /// Total debt shares issued by the credit manager for the denom
+    fn total_debt_shares(&self, denom: &str) -> LiquidatorResult<Uint128>;
+
+    /// Debt of the credit manager in the red bank for the denom, shared by all credit accounts
+    fn red_bank_debt(&self, denom: &str) -> LiquidatorResult<Uint128>;
+
+    fn account_kind(&self, account_id: &str) -> LiquidatorResult<AccountKind>;
+
+    /// Liquidation price of the denom
+    fn price(&self, denom: &str) -> LiquidatorResult<Decimal>;
+
+    /// `None` if the denom is not supported by the params contract
+    fn asset_params(&self, denom: &str) -> LiquidatorResult<Option<AssetParams>>;
+
+    fn vault_config(&self, vault: &str) -> LiquidatorResult<VaultConfig>;
+
+    fn vault_position_value(
+        &self,
+        account_id: &str,
+        position: &VaultPosition,
+    ) -> LiquidatorResult<VaultPositionValue>;
+
+    fn target_health_factor(&self) -> LiquidatorResult<Decimal>;
+}
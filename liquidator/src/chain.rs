@@ -0,0 +1,154 @@
+use cosmwasm_schema::serde::de::DeserializeOwned;
+use cosmwasm_std::{Addr, Decimal, QuerierWrapper, Uint128};
+use mars_types::{
+    adapters::{
+        oracle::Oracle,
+        params::Params,
+        red_bank::RedBank,
+        vault::{VaultPosition, VaultPositionValue},
+    },
+    credit_manager::{
+        CoinBalanceResponseItem, ConfigResponse, DebtShares, QueryMsg, SharesResponseItem,
+        VaultPositionResponseItem,
+    },
+    health::AccountKind,
+    oracle::ActionKind,
+    params::{AssetParams, VaultConfig},
+};
+
+use crate::{backend::QueryBackend, error::LiquidatorResult};
+
+/// Page size used when enumerating the credit manager state
+const PAGE_LIMIT: u32 = 30;
+
+/// Queries the contracts directly. Works with any querier, including the one of a multitest app.
+pub struct ChainBackend<'a> {
+    querier: QuerierWrapper<'a>,
+    credit_manager: Addr,
+    red_bank: RedBank,
+    oracle: Oracle,
+    params: Params,
+}
+
+impl<'a> ChainBackend<'a> {
+    /// Reads the addresses of the red bank, oracle and params contracts from the credit manager
+    pub fn new(querier: QuerierWrapper<'a>, credit_manager: Addr) -> LiquidatorResult<Self> {
+        let config: ConfigResponse =
+            querier.query_wasm_smart(credit_manager.to_string(), &QueryMsg::Config {})?;
+
+        Ok(Self {
+            querier,
+            red_bank: RedBank::new(Addr::unchecked(config.red_bank), credit_manager.clone()),
+            oracle: Oracle::new(Addr::unchecked(config.oracle)),
+            params: Params::new(Addr::unchecked(config.params)),
+            credit_manager,
+        })
+    }
+
+    /// Queries all pages of an enumeration of the credit manager. `start_after` derives the
+    /// pagination key from the last item of the previous page.
+    fn paginate<T, K>(
+        &self,
+        msg: impl Fn(Option<K>) -> QueryMsg,
+        start_after: impl Fn(&T) -> K,
+    ) -> LiquidatorResult<Vec<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut items: Vec<T> = vec![];
+        loop {
+            let page: Vec<T> = self.querier.query_wasm_smart(
+                self.credit_manager.to_string(),
+                &msg(items.last().map(&start_after)),
+            )?;
+            if page.is_empty() {
+                return Ok(items);
+            }
+            items.extend(page);
+        }
+    }
+}
+
+impl<'a> QueryBackend for ChainBackend<'a> {
+    fn coin_balances(&self) -> LiquidatorResult<Vec<CoinBalanceResponseItem>> {
+        self.paginate(
+            |start_after| QueryMsg::AllCoinBalances {
+                start_after,
+                limit: Some(PAGE_LIMIT),
+            },
+            |item: &CoinBalanceResponseItem| (item.account_id.clone(), item.denom.clone()),
+        )
+    }
+
+    fn debt_shares(&self) -> LiquidatorResult<Vec<SharesResponseItem>> {
+        self.paginate(
+            |start_after| QueryMsg::AllDebtShares {
+                start_after,
+                limit: Some(PAGE_LIMIT),
+            },
+            |item: &SharesResponseItem| (item.account_id.clone(), item.denom.clone()),
+        )
+    }
+
+    fn vault_positions(&self) -> LiquidatorResult<Vec<VaultPositionResponseItem>> {
+        self.paginate(
+            |start_after| QueryMsg::AllVaultPositions {
+                start_after,
+                limit: Some(PAGE_LIMIT),
+            },
+            |item: &VaultPositionResponseItem| {
+                (item.account_id.clone(), item.position.vault.address.to_string())
+            },
+        )
+    }
+
+    fn total_debt_shares(&self, denom: &str) -> LiquidatorResult<Uint128> {
+        let res: DebtShares = self.querier.query_wasm_smart(
+            self.credit_manager.to_string(),
+            &QueryMsg::TotalDebtShares(denom.to_string()),
+        )?;
+        Ok(res.shares)
+    }
+
+    fn red_bank_debt(&self, denom: &str) -> LiquidatorResult<Uint128> {
+        Ok(self.red_bank.query_debt(&self.querier, denom)?)
+    }
+
+    fn account_kind(&self, account_id: &str) -> LiquidatorResult<AccountKind> {
+        Ok(self.querier.query_wasm_smart(
+            self.credit_manager.to_string(),
+            &QueryMsg::AccountKind {
+                account_id: account_id.to_string(),
+            },
+        )?)
+    }
+
+    fn price(&self, denom: &str) -> LiquidatorResult<Decimal> {
+        Ok(self.oracle.query_price(&self.querier, denom, ActionKind::Liquidation)?.price)
+    }
+
+    fn asset_params(&self, denom: &str) -> LiquidatorResult<Option<AssetParams>> {
+        Ok(self.params.query_asset_params(&self.querier, denom)?)
+    }
+
+    fn vault_config(&self, vault: &str) -> LiquidatorResult<VaultConfig> {
+        Ok(self.params.query_vault_config(&self.querier, &Addr::unchecked(vault))?)
+    }
+
+    fn vault_position_value(
+        &self,
+        _account_id: &str,
+        position: &VaultPosition,
+    ) -> LiquidatorResult<VaultPositionValue> {
+        Ok(self.querier.query_wasm_smart(
+            self.credit_manager.to_string(),
+            &QueryMsg::VaultPositionValue {
+                vault_position: position.clone(),
+            },
+        )?)
+    }
+
+    fn target_health_factor(&self) -> LiquidatorResult<Decimal> {
+        Ok(self.params.query_target_health_factor(&self.querier)?)
+    }
+}
@@ -0,0 +1,36 @@
+use cosmwasm_std::{CheckedFromRatioError, CheckedMultiplyFractionError, OverflowError, StdError};
+use mars_liquidation::error::LiquidationError;
+use mars_types::health::HealthError;
+use thiserror::Error;
+
+pub type LiquidatorResult<T> = Result<T, LiquidatorError>;
+
+#[derive(Error, Debug)]
+pub enum LiquidatorError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("{0}")]
+    CheckedMultiplyFraction(#[from] CheckedMultiplyFractionError),
+
+    #[error("{0}")]
+    CheckedFromRatio(#[from] CheckedFromRatioError),
+
+    #[error("{0}")]
+    Health(#[from] HealthError),
+
+    #[error("{0}")]
+    Liquidation(#[from] LiquidationError),
+
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("Missing data: {0}")]
+    MissingData(String),
+}
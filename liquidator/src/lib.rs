@@ -0,0 +1,16 @@
+//! Reference liquidation bot for credit manager accounts.
+//!
+//! The protocol state is read through a [`QueryBackend`], either from a JSON [`Snapshot`] or
+//! from the contracts via [`ChainBackend`]. Health is computed with `HealthComputer` and the
+//! liquidation amounts with `mars_liquidation`, the same way the contracts do.
+
+pub mod backend;
+pub mod chain;
+pub mod error;
+pub mod liquidation;
+pub mod snapshot;
+
+pub use backend::QueryBackend;
+pub use chain::ChainBackend;
+pub use liquidation::{find_liquidations, LiquidationCandidate, LiquidatorConfig};
+pub use snapshot::Snapshot;
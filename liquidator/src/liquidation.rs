@@ -0,0 +1,267 @@
+use std::collections::{btree_map::Entry, BTreeMap, HashMap};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use mars_health::health::Health as LiquidationHealth;
+use mars_liquidation::liquidation::calculate_liquidation_amounts;
+use mars_rover_health_computer::{DenomsData, HealthComputer, PerpsData, VaultsData};
+use mars_types::{
+    credit_manager::{Action, DebtAmount, ExecuteMsg, LiquidateRequest, Positions},
+    health::Health,
+};
+
+use crate::{backend::QueryBackend, error::LiquidatorResult};
+
+pub struct LiquidatorConfig {
+    /// Credit account executing the liquidations
+    pub liquidator_account_id: String,
+    /// Liquidations with a lower expected profit (in the base denom of the oracle) are skipped
+    pub min_profit: Uint128,
+    /// Slippage allowed when swapping the seized collateral to the debt denom
+    pub slippage: Decimal,
+}
+
+#[cw_serde]
+pub struct LiquidationCandidate {
+    pub account_id: String,
+    pub liquidation_health_factor: Decimal,
+    /// Debt repaid by the liquidator
+    pub debt_coin: Coin,
+    /// Collateral received by the liquidator, the protocol fee deducted
+    pub collateral_received: Coin,
+    /// Value of the received collateral in excess of the repaid debt, in the base denom of the
+    /// oracle
+    pub expected_profit: Uint128,
+    /// Flash liquidation to be sent to the credit manager
+    pub msg: ExecuteMsg,
+}
+
+/// Builds the positions of all credit accounts holding coins, debts or vault positions.
+/// Lends, staked Astroport LPs and perp positions are not part of the enumerated state and are
+/// left out.
+pub fn load_positions(backend: &impl QueryBackend) -> LiquidatorResult<Vec<Positions>> {
+    let mut accounts: BTreeMap<String, Positions> = BTreeMap::new();
+    for item in backend.coin_balances()? {
+        account_positions(backend, &mut accounts, &item.account_id)?.deposits.push(Coin {
+            denom: item.denom,
+            amount: item.amount,
+        });
+    }
+
+    // Debt shares are converted with the debt of the credit manager in the red bank, rounded up
+    // the same way the credit manager does
+    let mut debt_ratios: HashMap<String, (Uint128, Uint128)> = HashMap::new();
+    for item in backend.debt_shares()? {
+        if !debt_ratios.contains_key(&item.denom) {
+            let ratio =
+                (backend.red_bank_debt(&item.denom)?, backend.total_debt_shares(&item.denom)?);
+            debt_ratios.insert(item.denom.clone(), ratio);
+        }
+        let (total_debt, total_shares) = debt_ratios[&item.denom];
+        let amount = total_debt.checked_mul_ceil((item.shares, total_shares))?;
+        account_positions(backend, &mut accounts, &item.account_id)?.debts.push(DebtAmount {
+            denom: item.denom,
+            shares: item.shares,
+            amount,
+        });
+    }
+
+    for item in backend.vault_positions()? {
+        account_positions(backend, &mut accounts, &item.account_id)?.vaults.push(item.position);
+    }
+
+    Ok(accounts.into_values().collect())
+}
+
+fn account_positions<'a>(
+    backend: &impl QueryBackend,
+    accounts: &'a mut BTreeMap<String, Positions>,
+    account_id: &str,
+) -> LiquidatorResult<&'a mut Positions> {
+    let positions = match accounts.entry(account_id.to_string()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(Positions {
+            account_id: account_id.to_string(),
+            account_kind: backend.account_kind(account_id)?,
+            deposits: vec![],
+            debts: vec![],
+            lends: vec![],
+            vaults: vec![],
+            staked_astro_lps: vec![],
+            perps: vec![],
+        }),
+    };
+    Ok(positions)
+}
+
+/// Computes the health of the positions with liquidation prices
+pub fn compute_health(
+    backend: &impl QueryBackend,
+    positions: Positions,
+) -> LiquidatorResult<Health> {
+    let mut denoms_data = DenomsData::default();
+    let mut vaults_data = VaultsData::default();
+
+    for position in positions.vaults.iter() {
+        let value = backend.vault_position_value(&positions.account_id, position)?;
+        let base_denom = value.base_coin.denom.clone();
+        vaults_data.vault_values.insert(position.vault.address.clone(), value);
+        let config = backend.vault_config(position.vault.address.as_str())?;
+        vaults_data.vault_configs.insert(position.vault.address.clone(), config);
+        add_denom_data(backend, &mut denoms_data, &base_denom)?;
+    }
+
+    for denom in
+        positions.deposits.iter().map(|c| &c.denom).chain(positions.debts.iter().map(|d| &d.denom))
+    {
+        add_denom_data(backend, &mut denoms_data, denom)?;
+    }
+
+    let computer = HealthComputer {
+        kind: positions.account_kind.clone(),
+        positions,
+        denoms_data,
+        vaults_data,
+        perps_data: PerpsData::default(),
+    };
+
+    Ok(computer.compute_health()?)
+}
+
+/// Unsupported denoms are skipped, as done by the health contract
+fn add_denom_data(
+    backend: &impl QueryBackend,
+    denoms_data: &mut DenomsData,
+    denom: &str,
+) -> LiquidatorResult<()> {
+    if denoms_data.params.contains_key(denom) {
+        return Ok(());
+    }
+    if let Some(params) = backend.asset_params(denom)? {
+        denoms_data.params.insert(denom.to_string(), params);
+        denoms_data.prices.insert(denom.to_string(), backend.price(denom)?);
+    }
+    Ok(())
+}
+
+/// Finds the most profitable deposit liquidation of every liquidatable account.
+/// Returns them ranked by expected profit, highest first.
+pub fn find_liquidations(
+    backend: &impl QueryBackend,
+    config: &LiquidatorConfig,
+) -> LiquidatorResult<Vec<LiquidationCandidate>> {
+    let target_health_factor = backend.target_health_factor()?;
+
+    let mut candidates = vec![];
+    for positions in load_positions(backend)? {
+        if positions.debts.is_empty() || positions.account_id == config.liquidator_account_id {
+            continue;
+        }
+
+        let health = compute_health(backend, positions.clone())?;
+        let Some(liquidation_health_factor) = health.liquidation_health_factor else {
+            continue;
+        };
+        if liquidation_health_factor >= Decimal::one() {
+            continue;
+        }
+
+        if let Some(candidate) =
+            best_liquidation(backend, config, &positions, &health, target_health_factor)?
+        {
+            candidates.push(candidate);
+        }
+    }
+
+    candidates.sort_by(|a, b| b.expected_profit.cmp(&a.expected_profit));
+
+    Ok(candidates)
+}
+
+fn best_liquidation(
+    backend: &impl QueryBackend,
+    config: &LiquidatorConfig,
+    positions: &Positions,
+    health: &Health,
+    target_health_factor: Decimal,
+) -> LiquidatorResult<Option<LiquidationCandidate>> {
+    let liquidation_health = LiquidationHealth {
+        total_debt_value: health.total_debt_value,
+        total_collateral_value: health.total_collateral_value,
+        max_ltv_adjusted_collateral: health.max_ltv_adjusted_collateral,
+        liquidation_threshold_adjusted_collateral: health.liquidation_threshold_adjusted_collateral,
+        max_ltv_health_factor: health.max_ltv_health_factor,
+        liquidation_health_factor: health.liquidation_health_factor,
+    };
+
+    let mut best: Option<LiquidationCandidate> = None;
+    for debt in positions.debts.iter() {
+        let debt_price = backend.price(&debt.denom)?;
+
+        for collateral in positions.deposits.iter() {
+            let Some(collateral_params) = backend.asset_params(&collateral.denom)? else {
+                continue;
+            };
+            let collateral_price = backend.price(&collateral.denom)?;
+
+            let (debt_to_repay, _, collateral_received) = calculate_liquidation_amounts(
+                collateral.amount,
+                collateral_price,
+                &collateral_params,
+                debt.amount,
+                debt.amount,
+                debt_price,
+                target_health_factor,
+                &liquidation_health,
+            )?;
+            if debt_to_repay.is_zero() {
+                continue;
+            }
+
+            let debt_value = debt_to_repay.checked_mul_ceil(debt_price)?;
+            let received_value = collateral_received.checked_mul_floor(collateral_price)?;
+            let expected_profit = received_value.saturating_sub(debt_value);
+            if expected_profit.is_zero() || expected_profit < config.min_profit {
+                continue;
+            }
+            if best.as_ref().is_some_and(|b| b.expected_profit >= expected_profit) {
+                continue;
+            }
+
+            let debt_coin = Coin {
+                denom: debt.denom.clone(),
+                amount: debt_to_repay,
+            };
+            // Profit in the debt denom once the collateral is swapped. The slippage applies to the
+            // whole swapped collateral, not only to the profit.
+            let min_profit = received_value
+                .checked_mul_floor(Decimal::one() - config.slippage)?
+                .saturating_sub(debt_value)
+                .checked_div_floor(debt_price)?;
+
+            best = Some(LiquidationCandidate {
+                account_id: positions.account_id.clone(),
+                liquidation_health_factor: health.liquidation_health_factor.unwrap_or_default(),
+                debt_coin: debt_coin.clone(),
+                collateral_received: Coin {
+                    denom: collateral.denom.clone(),
+                    amount: collateral_received,
+                },
+                expected_profit,
+                msg: ExecuteMsg::UpdateCreditAccount {
+                    account_id: Some(config.liquidator_account_id.clone()),
+                    account_kind: None,
+                    actions: vec![Action::FlashLiquidate {
+                        liquidatee_account_id: positions.account_id.clone(),
+                        debt_coin,
+                        request: LiquidateRequest::Deposit(collateral.denom.clone()),
+                        route: None,
+                        min_profit,
+                    }],
+                },
+            });
+        }
+    }
+
+    Ok(best)
+}
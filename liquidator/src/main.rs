@@ -0,0 +1,51 @@
+use std::{env, process::ExitCode, str::FromStr};
+
+use cosmwasm_std::{Decimal, Uint128};
+use mars_liquidator::{find_liquidations, LiquidatorConfig, Snapshot};
+
+const USAGE: &str =
+    "Usage: mars-liquidator <snapshot.json> <liquidator-account-id> [min-profit] [slippage]";
+
+/// Prints the profitable liquidations of the snapshot as JSON, ranked by expected profit
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let (Some(snapshot_path), Some(liquidator_account_id)) = (args.first(), args.get(1)) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let min_profit = match args.get(2).map(|s| Uint128::from_str(s)).transpose() {
+        Ok(min_profit) => min_profit.unwrap_or_default(),
+        Err(err) => {
+            eprintln!("Invalid min profit: {err}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let slippage = match args.get(3).map(|s| Decimal::from_str(s)).transpose() {
+        Ok(slippage) => slippage.unwrap_or(Decimal::percent(1)),
+        Err(err) => {
+            eprintln!("Invalid slippage: {err}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = LiquidatorConfig {
+        liquidator_account_id: liquidator_account_id.clone(),
+        min_profit,
+        slippage,
+    };
+
+    let result = Snapshot::from_file(snapshot_path)
+        .and_then(|snapshot| find_liquidations(&snapshot, &config))
+        .and_then(|candidates| Ok(serde_json::to_string_pretty(&candidates)?));
+
+    match result {
+        Ok(json) => {
+            println!("{json}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
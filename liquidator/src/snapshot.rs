@@ -0,0 +1,132 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use mars_types::{
+    adapters::vault::{VaultPosition, VaultPositionValue},
+    credit_manager::{
+        CoinBalanceResponseItem, DebtShares, SharesResponseItem, VaultPositionResponseItem,
+    },
+    health::AccountKind,
+    params::{AssetParams, VaultConfig},
+};
+
+use crate::{
+    backend::QueryBackend,
+    error::{LiquidatorError, LiquidatorResult},
+};
+
+#[cw_serde]
+pub struct VaultValueItem {
+    pub account_id: String,
+    pub vault: String,
+    pub value: VaultPositionValue,
+}
+
+/// Protocol state dumped to JSON, e.g. from the responses of the listed queries
+#[cw_serde]
+#[derive(Default)]
+pub struct Snapshot {
+    /// Credit manager `AllCoinBalances`
+    pub coin_balances: Vec<CoinBalanceResponseItem>,
+    /// Credit manager `AllDebtShares`
+    pub debt_shares: Vec<SharesResponseItem>,
+    /// Credit manager `AllTotalDebtShares`
+    pub total_debt_shares: Vec<DebtShares>,
+    /// Credit manager `AllVaultPositions`
+    pub vault_positions: Vec<VaultPositionResponseItem>,
+    /// Credit manager `VaultPositionValue` of each vault position
+    pub vault_values: Vec<VaultValueItem>,
+    /// Red bank `UserDebt` of the credit manager for each denom
+    pub red_bank_debts: Vec<Coin>,
+    /// Accounts missing from the map are of the default kind
+    pub account_kinds: HashMap<String, AccountKind>,
+    /// Oracle liquidation prices
+    pub prices: HashMap<String, Decimal>,
+    /// Params `AllAssetParams`
+    pub asset_params: Vec<AssetParams>,
+    /// Params `AllVaultConfigsV2`
+    pub vault_configs: Vec<VaultConfig>,
+    /// Params `TargetHealthFactor`
+    pub target_health_factor: Decimal,
+}
+
+impl Snapshot {
+    pub fn from_file(path: impl AsRef<Path>) -> LiquidatorResult<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+impl QueryBackend for Snapshot {
+    fn coin_balances(&self) -> LiquidatorResult<Vec<CoinBalanceResponseItem>> {
+        Ok(self.coin_balances.clone())
+    }
+
+    fn debt_shares(&self) -> LiquidatorResult<Vec<SharesResponseItem>> {
+        Ok(self.debt_shares.clone())
+    }
+
+    fn vault_positions(&self) -> LiquidatorResult<Vec<VaultPositionResponseItem>> {
+        Ok(self.vault_positions.clone())
+    }
+
+    fn total_debt_shares(&self, denom: &str) -> LiquidatorResult<Uint128> {
+        self.total_debt_shares
+            .iter()
+            .find(|s| s.denom == denom)
+            .map(|s| s.shares)
+            .ok_or_else(|| LiquidatorError::MissingData(format!("total debt shares of {denom}")))
+    }
+
+    fn red_bank_debt(&self, denom: &str) -> LiquidatorResult<Uint128> {
+        self.red_bank_debts
+            .iter()
+            .find(|c| c.denom == denom)
+            .map(|c| c.amount)
+            .ok_or_else(|| LiquidatorError::MissingData(format!("red bank debt of {denom}")))
+    }
+
+    fn account_kind(&self, account_id: &str) -> LiquidatorResult<AccountKind> {
+        Ok(self.account_kinds.get(account_id).cloned().unwrap_or(AccountKind::Default))
+    }
+
+    fn price(&self, denom: &str) -> LiquidatorResult<Decimal> {
+        self.prices
+            .get(denom)
+            .copied()
+            .ok_or_else(|| LiquidatorError::MissingData(format!("price of {denom}")))
+    }
+
+    fn asset_params(&self, denom: &str) -> LiquidatorResult<Option<AssetParams>> {
+        Ok(self.asset_params.iter().find(|p| p.denom == denom).cloned())
+    }
+
+    fn vault_config(&self, vault: &str) -> LiquidatorResult<VaultConfig> {
+        self.vault_configs
+            .iter()
+            .find(|c| c.addr == vault)
+            .cloned()
+            .ok_or_else(|| LiquidatorError::MissingData(format!("config of vault {vault}")))
+    }
+
+    fn vault_position_value(
+        &self,
+        account_id: &str,
+        position: &VaultPosition,
+    ) -> LiquidatorResult<VaultPositionValue> {
+        self.vault_values
+            .iter()
+            .find(|v| v.account_id == account_id && v.vault == position.vault.address.as_str())
+            .map(|v| v.value.clone())
+            .ok_or_else(|| {
+                LiquidatorError::MissingData(format!(
+                    "value of vault {} for account {account_id}",
+                    position.vault.address
+                ))
+            })
+    }
+
+    fn target_health_factor(&self) -> LiquidatorResult<Decimal> {
+        Ok(self.target_health_factor)
+    }
+}
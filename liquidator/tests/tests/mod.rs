@@ -0,0 +1,3 @@
+pub use mars_testing::multitest::helpers;
+
+mod test_find_liquidations;
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{coin, coins, testing::MockApi, Addr, Coin, Decimal, Uint128};
+use mars_liquidator::{
+    find_liquidations, liquidation::load_positions, ChainBackend, LiquidatorConfig, Snapshot,
+};
+use mars_mock_oracle::msg::CoinPrice;
+use mars_types::{
+    credit_manager::{
+        Action::{Borrow, Deposit, FlashLiquidate, Withdraw},
+        CoinBalanceResponseItem, DebtShares, ExecuteMsg, LiquidateRequest, SharesResponseItem,
+    },
+    health::AccountKind,
+    oracle::ActionKind,
+    params::AssetParamsUnchecked,
+};
+
+use super::helpers::{uatom_info, uosmo_info, AccountToFund, MockEnv};
+
+#[test]
+fn healthy_accounts_are_not_liquidated() {
+    let (mock, _) = setup();
+
+    let backend = ChainBackend::new(mock.app.wrap(), mock.rover.clone()).unwrap();
+    let candidates = find_liquidations(&backend, &default_config()).unwrap();
+    assert!(candidates.is_empty());
+}
+
+#[test]
+fn unhealthy_account_is_ranked_with_flash_liquidation() {
+    let (mut mock, account_id) = setup();
+
+    mock.price_change(CoinPrice {
+        pricing: ActionKind::Liquidation,
+        denom: uatom_info().denom,
+        price: Decimal::from_atomics(2u128, 0).unwrap(),
+    });
+
+    let backend = ChainBackend::new(mock.app.wrap(), mock.rover.clone()).unwrap();
+
+    // The debt includes the extra unit added by the mock red bank on borrow
+    let positions = load_positions(&backend).unwrap();
+    assert_eq!(positions.len(), 1);
+    assert_eq!(positions[0].account_id, account_id);
+    assert_eq!(positions[0].debts[0].amount, Uint128::new(51));
+
+    let candidates = find_liquidations(&backend, &default_config()).unwrap();
+    assert_eq!(candidates.len(), 1);
+
+    let candidate = &candidates[0];
+    assert_eq!(candidate.account_id, account_id);
+    assert!(candidate.liquidation_health_factor < Decimal::one());
+    assert_eq!(candidate.debt_coin.denom, "uatom");
+    assert!(!candidate.debt_coin.amount.is_zero());
+    assert_eq!(candidate.collateral_received.denom, "uosmo");
+    assert!(!candidate.expected_profit.is_zero());
+
+    let ExecuteMsg::UpdateCreditAccount {
+        account_id: Some(liquidator_account_id),
+        actions,
+        ..
+    } = &candidate.msg
+    else {
+        panic!("unexpected message: {:?}", candidate.msg);
+    };
+    assert_eq!(liquidator_account_id, "liquidator");
+    let [FlashLiquidate {
+        liquidatee_account_id,
+        debt_coin,
+        request,
+        route,
+        ..
+    }] = actions.as_slice()
+    else {
+        panic!("unexpected actions: {actions:?}");
+    };
+    assert_eq!(liquidatee_account_id, &account_id);
+    assert_eq!(debt_coin, &candidate.debt_coin);
+    assert_eq!(request, &LiquidateRequest::Deposit("uosmo".to_string()));
+    assert_eq!(route, &None);
+
+    // Raising the minimum profit filters the liquidation out
+    let config = LiquidatorConfig {
+        min_profit: candidate.expected_profit + Uint128::one(),
+        ..default_config()
+    };
+    assert!(find_liquidations(&backend, &config).unwrap().is_empty());
+}
+
+#[test]
+fn candidates_are_ranked_by_profit() {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let account = |account_id: &str, osmo: u128, atom_debt: u128| {
+        (
+            CoinBalanceResponseItem {
+                account_id: account_id.to_string(),
+                denom: uosmo_info.denom.clone(),
+                amount: Uint128::new(osmo),
+            },
+            SharesResponseItem {
+                account_id: account_id.to_string(),
+                denom: uatom_info.denom.clone(),
+                shares: Uint128::new(atom_debt),
+            },
+        )
+    };
+    let osmo_price = uosmo_info.price;
+    let atom_price = uatom_info.price;
+    let (small_balance, small_debt) = account("1", 1_000, 200);
+    let (large_balance, large_debt) = account("2", 10_000, 2_000);
+    let (healthy_balance, healthy_debt) = account("3", 10_000, 500);
+
+    let snapshot = Snapshot {
+        coin_balances: vec![small_balance, large_balance, healthy_balance],
+        debt_shares: vec![small_debt, large_debt, healthy_debt],
+        total_debt_shares: vec![DebtShares {
+            denom: uatom_info.denom.clone(),
+            shares: Uint128::new(2_700),
+        }],
+        red_bank_debts: vec![coin(2_700, &uatom_info.denom)],
+        account_kinds: HashMap::from([("2".to_string(), AccountKind::Default)]),
+        prices: HashMap::from([
+            (uosmo_info.denom.clone(), uosmo_info.price),
+            (uatom_info.denom.clone(), uatom_info.price),
+        ]),
+        asset_params: [uosmo_info, uatom_info]
+            .into_iter()
+            .map(|info| AssetParamsUnchecked::from(info).check(&MockApi::default()).unwrap())
+            .collect(),
+        target_health_factor: Decimal::from_ratio(12u128, 10u128),
+        ..Default::default()
+    };
+
+    let candidates = find_liquidations(&snapshot, &default_config()).unwrap();
+    assert_eq!(
+        candidates.iter().map(|c| c.account_id.as_str()).collect::<Vec<_>>(),
+        vec!["2", "1"]
+    );
+    assert!(candidates[0].expected_profit > candidates[1].expected_profit);
+
+    // The slippage is taken off the value of the received collateral
+    let candidate = &candidates[0];
+    let ExecuteMsg::UpdateCreditAccount {
+        actions,
+        ..
+    } = &candidate.msg
+    else {
+        panic!("unexpected message: {:?}", candidate.msg);
+    };
+    let [FlashLiquidate {
+        min_profit,
+        ..
+    }] = actions.as_slice()
+    else {
+        panic!("unexpected actions: {actions:?}");
+    };
+    let received_value =
+        candidate.collateral_received.amount.checked_mul_floor(osmo_price).unwrap();
+    let debt_value = candidate.debt_coin.amount.checked_mul_ceil(atom_price).unwrap();
+    let expected_min_profit = received_value
+        .checked_mul_floor(Decimal::percent(99))
+        .unwrap()
+        .saturating_sub(debt_value)
+        .checked_div_floor(atom_price)
+        .unwrap();
+    assert_eq!(*min_profit, expected_min_profit);
+}
+
+fn default_config() -> LiquidatorConfig {
+    LiquidatorConfig {
+        liquidator_account_id: "liquidator".to_string(),
+        min_profit: Uint128::zero(),
+        slippage: Decimal::percent(1),
+    }
+}
+
+fn setup() -> (MockEnv, String) {
+    let uosmo_info = uosmo_info();
+    let uatom_info = uatom_info();
+
+    let user = Addr::unchecked("user");
+    let mut mock = MockEnv::new()
+        .set_params(&[uosmo_info.clone(), uatom_info.clone()])
+        .fund_account(AccountToFund {
+            addr: user.clone(),
+            funds: coins(300, uosmo_info.denom.clone()),
+        })
+        .build()
+        .unwrap();
+    let account_id = mock.create_credit_account(&user).unwrap();
+
+    mock.update_credit_account(
+        &account_id,
+        &user,
+        vec![
+            Deposit(uosmo_info.to_coin(300)),
+            Borrow(uatom_info.to_coin(50)),
+            Withdraw(uatom_info.to_action_coin(50)),
+        ],
+        &[Coin::new(300, uosmo_info.denom)],
+    )
+    .unwrap();
+
+    (mock, account_id)
+}
@@ -2,8 +2,8 @@ use std::str::FromStr;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    coin, from_json, to_json_binary, Decimal, Empty, QuerierWrapper, QueryRequest, StdError,
-    StdResult, Uint128, WasmQuery,
+    coin, from_json, to_json_binary, Decimal, Decimal256, Empty, QuerierWrapper, QueryRequest,
+    StdError, StdResult, Uint128, Uint256, WasmQuery,
 };
 use osmosis_std::{
     shim::{Duration, Timestamp},
@@ -324,6 +324,74 @@ pub fn query_cosmwasm_pool_out_amount(
     Uint128::from_str(&amount_str)
 }
 
+/// Lowest tick a concentrated liquidity position can be created at (price of 10^-12)
+pub const MIN_INITIALIZED_TICK: i64 = -108_000_000;
+
+/// Highest tick a concentrated liquidity position can be created at (price of 10^38)
+pub const MAX_TICK: i64 = 342_000_000;
+
+/// Exponent of the additive price increment between two ticks at the price of one
+const EXPONENT_AT_PRICE_ONE: i64 = -6;
+
+/// Number of ticks it takes for the price to grow by a power of ten
+const GEOMETRIC_EXPONENT_INCREMENT_DISTANCE_IN_TICKS: i64 = 9_000_000;
+
+/// Query an Osmosis concentrated liquidity pool, fails if the pool is of a different type
+pub fn query_concentrated_liquidity_pool(
+    querier: &QuerierWrapper,
+    pool_id: u64,
+) -> StdResult<ConcentratedLiquidityPool> {
+    match query_pool(querier, pool_id)? {
+        Pool::ConcentratedLiquidity(pool) => Ok(pool),
+        _ => Err(StdError::generic_err(format!(
+            "pool {pool_id} is not a concentrated liquidity pool"
+        ))),
+    }
+}
+
+/// Convert a concentrated liquidity tick to the price of token0 denominated in token1.
+///
+/// Every 9_000_000 ticks the price grows by a power of ten, within that range every tick adds the
+/// same increment to the price (10^-6 for ticks between price 1 and 10).
+///
+/// https://github.com/osmosis-labs/osmosis/tree/main/x/concentrated-liquidity#tick-to-price-conversion
+pub fn tick_to_price(tick: i64) -> StdResult<Decimal256> {
+    if !(MIN_INITIALIZED_TICK..=MAX_TICK).contains(&tick) {
+        return Err(StdError::generic_err(format!(
+            "tick {tick} out of range [{MIN_INITIALIZED_TICK}, {MAX_TICK}]"
+        )));
+    }
+
+    let geometric_exponent_delta = tick / GEOMETRIC_EXPONENT_INCREMENT_DISTANCE_IN_TICKS;
+    let mut exponent_at_current_tick = EXPONENT_AT_PRICE_ONE + geometric_exponent_delta;
+    if tick < 0 {
+        // Below the price of one the increments are ten times smaller
+        exponent_at_current_tick -= 1;
+    }
+    let num_additive_ticks =
+        tick - geometric_exponent_delta * GEOMETRIC_EXPONENT_INCREMENT_DISTANCE_IN_TICKS;
+
+    let base_price = pow10(geometric_exponent_delta, 1)?;
+    let additive_price = pow10(exponent_at_current_tick, num_additive_ticks.unsigned_abs())?;
+    let price = if num_additive_ticks < 0 {
+        base_price.checked_sub(additive_price)?
+    } else {
+        base_price.checked_add(additive_price)?
+    };
+    Ok(price)
+}
+
+/// `multiplier * 10^exponent` without losing precision on the negative exponents which can't be
+/// represented by Decimal256 on their own
+fn pow10(exponent: i64, multiplier: u64) -> StdResult<Decimal256> {
+    let ten = Uint256::from(10u8);
+    let numerator =
+        Uint256::from(multiplier).checked_mul(ten.checked_pow(exponent.max(0) as u32)?)?;
+    let denominator = ten.checked_pow((-exponent).max(0) as u32)?;
+    Decimal256::checked_from_ratio(numerator, denominator)
+        .map_err(|e| StdError::generic_err(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::to_json_vec;
@@ -577,4 +645,24 @@ mod tests {
         assert_eq!(cosmwasm_pool.pool_id, pool.get_pool_id());
         assert_eq!(Vec::<String>::new(), pool.get_pool_denoms());
     }
+
+    #[test]
+    fn converting_tick_to_price() {
+        let cases = [
+            (MIN_INITIALIZED_TICK, "0.000000000001"),
+            (-99_999_999, "0.000000000009000001"),
+            (-1, "0.9999999"),
+            (0, "1"),
+            (1, "1.000001"),
+            (9_000_000, "10"),
+            (102_311_912, "431191200000"),
+            (MAX_TICK, "100000000000000000000000000000000000000"),
+        ];
+        for (tick, price) in cases {
+            assert_eq!(tick_to_price(tick).unwrap(), Decimal256::from_str(price).unwrap());
+        }
+
+        tick_to_price(MIN_INITIALIZED_TICK - 1).unwrap_err();
+        tick_to_price(MAX_TICK + 1).unwrap_err();
+    }
 }
@@ -0,0 +1,628 @@
+use std::cmp::min;
+
+use cosmwasm_std::{Addr, Coin, Decimal, Int128, Uint128};
+use mars_types::{
+    adapters::vault::{
+        UnlockingChange, UpdateType, Vault, VaultError, VaultPosition, VaultPositionUpdate,
+        VaultUnchecked, VaultUnlockingPosition,
+    },
+    credit_manager::{Action, ActionAmount, ActionCoin, DebtAmount, Positions},
+    health::{
+        HealthError::{
+            self, BelowMinReceive, DenomNotPresent, MissingAmount, MissingPrice, UnsupportedAction,
+        },
+        HealthResult,
+    },
+    perps::PerpPosition,
+    swapper::SwapperRoute,
+};
+
+use crate::HealthComputer;
+
+/// Outcome of executing a perp order
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerpOrderEstimate {
+    /// PnL realized by the order in the perps base denom, a loss is negative. Profits are capped
+    /// at the amount the perps contract is able to pay out.
+    pub realized_pnl: Int128,
+    pub base_denom: String,
+    /// Position left after the order, `None` if it is closed
+    pub position: Option<PerpPosition>,
+}
+
+/// Provides the amounts that can't be derived from the positions alone when applying actions:
+/// debt shares, swap outputs, LP tokens received for providing liquidity, vault shares, perp
+/// orders and trigger order fees.
+/// The estimates not every implementation is able to provide default to `UnsupportedAction`.
+pub trait ActionsEstimator {
+    type Error: From<HealthError>;
+
+    /// Debt shares added to the account when borrowing `coin`
+    fn debt_shares_to_add(&self, coin: &Coin) -> Result<Uint128, Self::Error>;
+
+    /// Fee taken by the credit manager from the coin swapped
+    fn swap_fee(&self) -> Result<Decimal, Self::Error>;
+
+    /// Amount of `denom_out` received for swapping `coin_in` (swap fee already deducted)
+    fn estimate_exact_in_swap(
+        &self,
+        coin_in: &Coin,
+        denom_out: &str,
+        route: &Option<SwapperRoute>,
+    ) -> Result<Uint128, Self::Error>;
+
+    /// Amount of `lp_token_out` received for providing `coins_in` as liquidity
+    fn estimate_provide_liquidity(
+        &self,
+        coins_in: &[Coin],
+        lp_token_out: &str,
+        slippage: Decimal,
+    ) -> Result<Uint128, Self::Error>;
+
+    /// Coins received for withdrawing `lp_token` from its pool
+    fn estimate_withdraw_liquidity(
+        &self,
+        _lp_token: &Coin,
+        _slippage: Decimal,
+    ) -> Result<Vec<Coin>, Self::Error> {
+        Err(UnsupportedAction("withdraw_liquidity".to_string()).into())
+    }
+
+    /// Update of the vault position when depositing `coin` into the vault
+    fn estimate_enter_vault(
+        &self,
+        _vault: &VaultUnchecked,
+        _coin: &Coin,
+    ) -> Result<VaultPositionUpdate, Self::Error> {
+        Err(UnsupportedAction("enter_vault".to_string()).into())
+    }
+
+    /// Base tokens received for redeeming `amount` of vault tokens
+    fn estimate_exit_vault(
+        &self,
+        _vault: &VaultUnchecked,
+        _amount: Uint128,
+    ) -> Result<Coin, Self::Error> {
+        Err(UnsupportedAction("exit_vault".to_string()).into())
+    }
+
+    /// Unlocking position created when requesting to unlock `amount` of vault tokens. The id is
+    /// only assigned by the vault once the request is executed.
+    fn estimate_request_vault_unlock(
+        &self,
+        _vault: &VaultUnchecked,
+        _amount: Uint128,
+    ) -> Result<VaultUnlockingPosition, Self::Error> {
+        Err(UnsupportedAction("request_vault_unlock".to_string()).into())
+    }
+
+    /// Fails if the unlocking position `id` can not be withdrawn from the vault yet
+    fn assert_vault_unlock_released(
+        &self,
+        _vault: &VaultUnchecked,
+        _id: u64,
+    ) -> Result<(), Self::Error> {
+        Err(UnsupportedAction("exit_vault_unlocked".to_string()).into())
+    }
+
+    /// Base denom withdrawn from the perps vault, i.e. all the unlocks whose cooldown has ended
+    fn estimate_withdraw_from_perp_vault(&self, _account_id: &str) -> Result<Coin, Self::Error> {
+        Err(UnsupportedAction("withdraw_from_perp_vault".to_string()).into())
+    }
+
+    /// Realized PnL and resulting position of executing a perp order
+    fn estimate_perp_order(
+        &self,
+        _account_id: &str,
+        _denom: &str,
+        _order_size: Int128,
+        _reduce_only: Option<bool>,
+    ) -> Result<PerpOrderEstimate, Self::Error> {
+        Err(UnsupportedAction("execute_perp_order".to_string()).into())
+    }
+
+    /// Keeper fee paid when creating the trigger order, refunded when deleting it
+    fn trigger_order_keeper_fee(
+        &self,
+        _account_id: &str,
+        _trigger_order_id: &str,
+    ) -> Result<Coin, Self::Error> {
+        Err(UnsupportedAction("delete_trigger_order".to_string()).into())
+    }
+}
+
+/// Applies the actions, in order, to the positions the same way the credit manager would
+/// dispatch them.
+/// Note: Liquidations can not be applied. Rewards are not known up front and are not accounted
+/// for. Perps vault deposits are not part of the positions, depositing only reduces the balance.
+pub fn apply_actions<E: ActionsEstimator>(
+    positions: &mut Positions,
+    actions: &[Action],
+    estimator: &E,
+) -> Result<(), E::Error> {
+    for action in actions {
+        apply_action(positions, action, estimator)?;
+    }
+    Ok(())
+}
+
+fn apply_action<E: ActionsEstimator>(
+    positions: &mut Positions,
+    action: &Action,
+    estimator: &E,
+) -> Result<(), E::Error> {
+    match action {
+        Action::Deposit(coin) => add_coin(&mut positions.deposits, coin)?,
+        Action::Withdraw(coin)
+        | Action::WithdrawToWallet {
+            coin,
+            ..
+        }
+        | Action::TransferToAccount {
+            coin,
+            ..
+        } => {
+            let coin = to_coin(&positions.deposits, coin);
+            sub_coin(&mut positions.deposits, &coin)?;
+        }
+        Action::Borrow(coin) => {
+            if coin.amount.is_zero() {
+                return Err(MissingAmount(coin.denom.clone()).into());
+            }
+            let shares = estimator.debt_shares_to_add(coin)?;
+            add_debt(&mut positions.debts, coin, shares)?;
+            add_coin(&mut positions.deposits, coin)?;
+        }
+        Action::Lend(coin) => {
+            let coin = to_coin(&positions.deposits, coin);
+            sub_coin(&mut positions.deposits, &coin)?;
+            add_coin(&mut positions.lends, &coin)?;
+        }
+        Action::Reclaim(coin) => {
+            // Same as the credit manager, reclaiming more than lent reclaims everything
+            let lent = coin_amount(&positions.lends, &coin.denom);
+            if lent.is_zero() {
+                return Err(DenomNotPresent(coin.denom.clone()).into());
+            }
+            let coin = Coin {
+                denom: coin.denom.clone(),
+                amount: min(lent, coin.amount.value().unwrap_or(lent)),
+            };
+            sub_coin(&mut positions.lends, &coin)?;
+            add_coin(&mut positions.deposits, &coin)?;
+        }
+        Action::Repay {
+            recipient_account_id,
+            coin,
+        } => {
+            let balance = coin_amount(&positions.deposits, &coin.denom);
+            let amount = coin.amount.value().unwrap_or(balance);
+
+            // The debt of another account is not known, the full amount is sent to it
+            let amount = match recipient_account_id {
+                Some(_) => amount,
+                None => repay_debt(&mut positions.debts, &coin.denom, amount)?,
+            };
+
+            sub_coin(
+                &mut positions.deposits,
+                &Coin {
+                    denom: coin.denom.clone(),
+                    amount,
+                },
+            )?;
+        }
+        Action::SwapExactIn {
+            coin_in,
+            denom_out,
+            min_receive,
+            route,
+        } => {
+            let coin_in = to_coin(&positions.deposits, coin_in);
+            if coin_in.amount.is_zero() {
+                return Err(MissingAmount(coin_in.denom).into());
+            }
+            sub_coin(&mut positions.deposits, &coin_in)?;
+
+            let swap_fee = estimator.swap_fee()?;
+            let coin_in = deduct_fee(coin_in, swap_fee)?;
+
+            let amount_out = estimator.estimate_exact_in_swap(&coin_in, denom_out, route)?;
+            if amount_out < *min_receive {
+                return Err(BelowMinReceive {
+                    denom: denom_out.clone(),
+                    estimated: amount_out,
+                    min_receive: *min_receive,
+                }
+                .into());
+            }
+            add_coin(
+                &mut positions.deposits,
+                &Coin {
+                    denom: denom_out.clone(),
+                    amount: amount_out,
+                },
+            )?;
+        }
+        Action::ProvideLiquidity {
+            coins_in,
+            lp_token_out,
+            slippage,
+        } => {
+            let mut updated_coins_in = Vec::with_capacity(coins_in.len());
+            for coin_in in coins_in {
+                let coin_in = to_coin(&positions.deposits, coin_in);
+                sub_coin(&mut positions.deposits, &coin_in)?;
+                updated_coins_in.push(coin_in);
+            }
+
+            let amount_out =
+                estimator.estimate_provide_liquidity(&updated_coins_in, lp_token_out, *slippage)?;
+            add_coin(
+                &mut positions.deposits,
+                &Coin {
+                    denom: lp_token_out.clone(),
+                    amount: amount_out,
+                },
+            )?;
+        }
+        Action::StakeAstroLp {
+            lp_token,
+        } => {
+            let lp_token = to_coin(&positions.deposits, lp_token);
+            sub_coin(&mut positions.deposits, &lp_token)?;
+            add_coin(&mut positions.staked_astro_lps, &lp_token)?;
+        }
+        Action::UnstakeAstroLp {
+            lp_token,
+        } => {
+            let lp_token = to_coin(&positions.staked_astro_lps, lp_token);
+            sub_coin(&mut positions.staked_astro_lps, &lp_token)?;
+            add_coin(&mut positions.deposits, &lp_token)?;
+        }
+        Action::RefundAllCoinBalances {} => positions.deposits.clear(),
+        Action::CreateTriggerOrder {
+            keeper_fee,
+            ..
+        } => sub_coin(&mut positions.deposits, keeper_fee)?,
+        Action::DeleteTriggerOrder {
+            trigger_order_id,
+        } => {
+            let keeper_fee =
+                estimator.trigger_order_keeper_fee(&positions.account_id, trigger_order_id)?;
+            add_coin(&mut positions.deposits, &keeper_fee)?;
+        }
+        Action::ClaimRewards {}
+        | Action::ClaimAstroLpRewards {
+            ..
+        }
+        | Action::UnlockFromPerpVault {
+            ..
+        }
+        | Action::SetAutoDeleveragePolicy {
+            ..
+        }
+        | Action::UpdatePythPrices {
+            ..
+        } => {}
+        Action::WithdrawLiquidity {
+            lp_token,
+            slippage,
+        } => {
+            let lp_token = to_coin(&positions.deposits, lp_token);
+            if lp_token.amount.is_zero() {
+                return Err(MissingAmount(lp_token.denom).into());
+            }
+            sub_coin(&mut positions.deposits, &lp_token)?;
+
+            for coin_out in estimator.estimate_withdraw_liquidity(&lp_token, *slippage)? {
+                add_coin(&mut positions.deposits, &coin_out)?;
+            }
+        }
+        Action::EnterVault {
+            vault,
+            coin,
+        } => {
+            let coin = to_coin(&positions.deposits, coin);
+            sub_coin(&mut positions.deposits, &coin)?;
+
+            let update = estimator.estimate_enter_vault(vault, &coin)?;
+            update_vault_position(&mut positions.vaults, vault, update)?;
+        }
+        Action::ExitVault {
+            vault,
+            amount,
+        } => {
+            update_vault_position(
+                &mut positions.vaults,
+                vault,
+                VaultPositionUpdate::Unlocked(UpdateType::Decrement(*amount)),
+            )?;
+
+            let coin_out = estimator.estimate_exit_vault(vault, *amount)?;
+            add_coin(&mut positions.deposits, &coin_out)?;
+        }
+        Action::RequestVaultUnlock {
+            vault,
+            amount,
+        } => {
+            update_vault_position(
+                &mut positions.vaults,
+                vault,
+                VaultPositionUpdate::Locked(UpdateType::Decrement(*amount)),
+            )?;
+
+            let unlocking = estimator.estimate_request_vault_unlock(vault, *amount)?;
+            update_vault_position(
+                &mut positions.vaults,
+                vault,
+                VaultPositionUpdate::Unlocking(UnlockingChange::Add(unlocking)),
+            )?;
+        }
+        Action::ExitVaultUnlocked {
+            id,
+            vault,
+        } => {
+            let unlocking = positions
+                .vaults
+                .iter()
+                .find(|p| p.vault.address.as_str() == vault.address)
+                .and_then(|p| p.amount.get_unlocking_position(*id))
+                .ok_or_else(|| HealthError::from(VaultError::NoPositionMatch(id.to_string())))?;
+            estimator.assert_vault_unlock_released(vault, *id)?;
+
+            update_vault_position(
+                &mut positions.vaults,
+                vault,
+                VaultPositionUpdate::Unlocking(UnlockingChange::Decrement {
+                    id: *id,
+                    amount: unlocking.coin.amount,
+                }),
+            )?;
+            add_coin(&mut positions.deposits, &unlocking.coin)?;
+        }
+        Action::DepositToPerpVault {
+            coin,
+            ..
+        } => {
+            let coin = to_coin(&positions.deposits, coin);
+            if coin.amount.is_zero() {
+                return Err(MissingAmount(coin.denom).into());
+            }
+            sub_coin(&mut positions.deposits, &coin)?;
+        }
+        Action::WithdrawFromPerpVault {
+            min_receive,
+        } => {
+            let coin_out = estimator.estimate_withdraw_from_perp_vault(&positions.account_id)?;
+            if let Some(min_receive) = min_receive {
+                if coin_out.amount < *min_receive {
+                    return Err(BelowMinReceive {
+                        denom: coin_out.denom,
+                        estimated: coin_out.amount,
+                        min_receive: *min_receive,
+                    }
+                    .into());
+                }
+            }
+            add_coin(&mut positions.deposits, &coin_out)?;
+        }
+        Action::ExecutePerpOrder {
+            denom,
+            order_size,
+            reduce_only,
+        } => {
+            let estimate = estimator.estimate_perp_order(
+                &positions.account_id,
+                denom,
+                *order_size,
+                *reduce_only,
+            )?;
+
+            let pnl_coin = Coin {
+                denom: estimate.base_denom,
+                amount: Uint128::new(estimate.realized_pnl.i128().unsigned_abs()),
+            };
+            if estimate.realized_pnl < Int128::zero() {
+                sub_coin(&mut positions.deposits, &pnl_coin)?;
+            } else {
+                add_coin(&mut positions.deposits, &pnl_coin)?;
+            }
+
+            positions.perps.retain(|p| p.denom != *denom);
+            positions.perps.extend(estimate.position);
+        }
+        Action::Liquidate {
+            ..
+        } => return Err(UnsupportedAction("liquidate".to_string()).into()),
+        Action::FlashLiquidate {
+            ..
+        } => return Err(UnsupportedAction("flash_liquidate".to_string()).into()),
+    }
+    Ok(())
+}
+
+/// Same as the credit manager, a position is created on its first update and removed once empty
+fn update_vault_position(
+    vaults: &mut Vec<VaultPosition>,
+    vault: &VaultUnchecked,
+    update: VaultPositionUpdate,
+) -> HealthResult<()> {
+    let index = match vaults.iter().position(|p| p.vault.address.as_str() == vault.address) {
+        Some(index) => index,
+        None => {
+            vaults.push(VaultPosition {
+                vault: Vault::new(Addr::unchecked(&vault.address)),
+                amount: update.default_amount(),
+            });
+            vaults.len() - 1
+        }
+    };
+
+    vaults[index].amount.update(update)?;
+    if vaults[index].amount.is_empty() {
+        vaults.remove(index);
+    }
+    Ok(())
+}
+
+fn deduct_fee(coin: Coin, fee_rate: Decimal) -> HealthResult<Coin> {
+    let fee = coin.amount.checked_mul_floor(fee_rate)?;
+    Ok(Coin {
+        denom: coin.denom,
+        amount: coin.amount.checked_sub(fee)?,
+    })
+}
+
+fn add_debt(debts: &mut Vec<DebtAmount>, coin: &Coin, shares: Uint128) -> HealthResult<()> {
+    match debts.iter_mut().find(|debt| debt.denom == coin.denom) {
+        Some(debt) => {
+            debt.shares = debt.shares.checked_add(shares)?;
+            debt.amount = debt.amount.checked_add(coin.amount)?;
+        }
+        None => debts.push(DebtAmount {
+            denom: coin.denom.clone(),
+            shares,
+            amount: coin.amount,
+        }),
+    }
+    Ok(())
+}
+
+/// Reduces the debt of `denom` by up to `amount`, returning the amount actually repaid. Debt
+/// shares are reduced proportionally.
+fn repay_debt(debts: &mut Vec<DebtAmount>, denom: &str, amount: Uint128) -> HealthResult<Uint128> {
+    let index = debts
+        .iter()
+        .position(|debt| debt.denom == denom)
+        .ok_or(DenomNotPresent(denom.to_string()))?;
+
+    let debt = &mut debts[index];
+    let amount = min(debt.amount, amount);
+    if amount == debt.amount {
+        debts.remove(index);
+    } else {
+        debt.shares = debt.shares.checked_sub(debt.shares.multiply_ratio(amount, debt.amount))?;
+        debt.amount = debt.amount.checked_sub(amount)?;
+    }
+
+    Ok(amount)
+}
+
+pub fn coin_amount(coins: &[Coin], denom: &str) -> Uint128 {
+    coins.iter().find(|coin| coin.denom == denom).map(|coin| coin.amount).unwrap_or_default()
+}
+
+/// Resolves `ActionAmount::AccountBalance` to the amount held in `coins`
+pub fn to_coin(coins: &[Coin], coin: &ActionCoin) -> Coin {
+    Coin {
+        denom: coin.denom.clone(),
+        amount: match coin.amount {
+            ActionAmount::Exact(amount) => amount,
+            ActionAmount::AccountBalance => coin_amount(coins, &coin.denom),
+        },
+    }
+}
+
+pub fn add_coin(coins: &mut Vec<Coin>, coin: &Coin) -> HealthResult<()> {
+    if coin.amount.is_zero() {
+        return Ok(());
+    }
+
+    match coins.iter_mut().find(|c| c.denom == coin.denom) {
+        Some(c) => c.amount = c.amount.checked_add(coin.amount)?,
+        None => coins.push(coin.clone()),
+    }
+    Ok(())
+}
+
+pub fn sub_coin(coins: &mut Vec<Coin>, coin: &Coin) -> HealthResult<()> {
+    if coin.amount.is_zero() {
+        return Ok(());
+    }
+
+    let c = coins
+        .iter_mut()
+        .find(|c| c.denom == coin.denom)
+        .ok_or(DenomNotPresent(coin.denom.clone()))?;
+    c.amount = c.amount.checked_sub(coin.amount)?;
+    coins.retain(|c| !c.amount.is_zero());
+    Ok(())
+}
+
+/// Estimates swaps and provided liquidity using the prices injected into the health computer.
+/// Debt shares are not known and are left as is.
+struct PriceEstimator<'a> {
+    computer: &'a HealthComputer,
+    swap_fee: Decimal,
+}
+
+impl ActionsEstimator for PriceEstimator<'_> {
+    type Error = HealthError;
+
+    fn debt_shares_to_add(&self, _coin: &Coin) -> HealthResult<Uint128> {
+        Ok(Uint128::zero())
+    }
+
+    fn swap_fee(&self) -> HealthResult<Decimal> {
+        Ok(self.swap_fee)
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        coin_in: &Coin,
+        denom_out: &str,
+        _route: &Option<SwapperRoute>,
+    ) -> HealthResult<Uint128> {
+        let price_in = self.get_price(&coin_in.denom)?;
+        let price_out = self.get_price(denom_out)?;
+        Ok(coin_in.amount.checked_mul_floor(price_in.checked_div(price_out)?)?)
+    }
+
+    fn estimate_provide_liquidity(
+        &self,
+        coins_in: &[Coin],
+        lp_token_out: &str,
+        slippage: Decimal,
+    ) -> HealthResult<Uint128> {
+        let mut value_in = Uint128::zero();
+        for coin_in in coins_in {
+            value_in = value_in
+                .checked_add(coin_in.amount.checked_mul_floor(self.get_price(&coin_in.denom)?)?)?;
+        }
+
+        // Same as the minimum amount of LP tokens the credit manager accepts
+        let lp_price = self.get_price(lp_token_out)?;
+        Ok(value_in
+            .checked_div_floor(lp_price)?
+            .checked_mul_floor(Decimal::one().checked_sub(slippage)?)?)
+    }
+}
+
+impl PriceEstimator<'_> {
+    fn get_price(&self, denom: &str) -> HealthResult<Decimal> {
+        Ok(*self.computer.denoms_data.prices.get(denom).ok_or(MissingPrice(denom.to_string()))?)
+    }
+}
+
+impl HealthComputer {
+    /// Returns a copy of the health computer with the actions applied, in order, to its positions.
+    /// Amounts received from swaps (after `swap_fee`) and from providing liquidity are estimated
+    /// using the injected prices.
+    /// Note: Only the amounts of the debts are updated, their shares are left as is. Actions
+    /// depending on the state of other contracts (vaults, perps, withdrawing liquidity, deleting
+    /// trigger orders) are not supported.
+    pub fn apply_actions(
+        &self,
+        actions: &[Action],
+        swap_fee: Decimal,
+    ) -> HealthResult<HealthComputer> {
+        let mut c = self.clone();
+        let estimator = PriceEstimator {
+            computer: self,
+            swap_fee,
+        };
+        apply_actions(&mut c.positions, actions, &estimator)?;
+        Ok(c)
+    }
+}
@@ -4,7 +4,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use mars_types::{
     adapters::vault::VaultPositionValue,
-    params::{AssetParams, VaultConfig},
+    params::{AssetParams, PerpParams, VaultConfig},
 };
 
 /// Used as storage when trying to compute Health
@@ -31,3 +31,10 @@ pub struct VaultsData {
     pub vault_values: HashMap<Addr, VaultPositionValue>,
     pub vault_configs: HashMap<Addr, VaultConfig>,
 }
+
+#[cw_serde]
+#[derive(Default)]
+pub struct PerpsData {
+    /// Params of the perp markets the account holds positions in
+    pub params: HashMap<String, PerpParams>,
+}
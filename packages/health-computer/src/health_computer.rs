@@ -1,23 +1,24 @@
 use std::cmp::min;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Coin, Decimal, Fraction, Uint128};
+use cosmwasm_std::{Coin, Decimal, Fraction, Int128, Uint128};
 use mars_types::{
     credit_manager::Positions,
     health::{
         AccountKind, BorrowTarget, Health,
         HealthError::{
-            MissingAmount, MissingHLSParams, MissingParams, MissingPrice, MissingVaultConfig,
-            MissingVaultValues,
+            MissingAmount, MissingHLSParams, MissingParams, MissingPerpParams, MissingPrice,
+            MissingVaultConfig, MissingVaultValues,
         },
         HealthResult, LiquidationPriceKind, SwapKind,
     },
     params::{AssetParams, CmSettings, HlsAssetType, VaultConfig},
+    perps::PerpPosition,
 };
 #[cfg(feature = "javascript")]
 use tsify::Tsify;
 
-use crate::{CollateralValue, DenomsData, VaultsData};
+use crate::{CollateralValue, DenomsData, PerpsData, VaultsData};
 
 /// `HealthComputer` is a shared struct with the frontend that gets compiled to wasm.
 /// For this reason, it uses a dependency-injection-like pattern where all required data is needed up front.
@@ -29,6 +30,7 @@ pub struct HealthComputer {
     pub positions: Positions,
     pub denoms_data: DenomsData,
     pub vaults_data: VaultsData,
+    pub perps_data: PerpsData,
 }
 
 impl HealthComputer {
@@ -83,9 +85,11 @@ impl HealthComputer {
             .get(withdraw_denom)
             .ok_or(MissingParams(withdraw_denom.to_string()))?;
 
-        // If no debt or coin is blacklisted (meaning does not contribute to max ltv hf),
-        // the total amount deposited can be withdrawn
-        if self.positions.debts.is_empty() || !params.credit_manager.whitelisted {
+        // If no debt (perp positions included) or coin is blacklisted (meaning does not contribute
+        // to max ltv hf), the total amount deposited can be withdrawn
+        if (self.positions.debts.is_empty() && self.positions.perps.is_empty())
+            || !params.credit_manager.whitelisted
+        {
             return Ok(withdraw_coin.amount);
         }
 
@@ -149,7 +153,10 @@ impl HealthComputer {
         let from_coin = self.get_coin_from_deposits_and_lends(from_denom)?;
 
         // If no debt the total amount deposited can be swapped (only for default swaps)
-        if kind == &SwapKind::Default && self.positions.debts.is_empty() {
+        if kind == &SwapKind::Default
+            && self.positions.debts.is_empty()
+            && self.positions.perps.is_empty()
+        {
             return Ok(from_coin.amount);
         }
 
@@ -413,6 +420,61 @@ impl HealthComputer {
         Ok(max_borrow_amount)
     }
 
+    /// The max this account can borrow of `debt_denom`, swapping it into `collateral_denom` and
+    /// depositing the output again and again (a leverage loop), and maintain max_ltv >= 1.
+    /// Note: This is an estimate. Guarantees to leave account healthy, but in edge cases,
+    /// due to rounding, it may be slightly too conservative.
+    pub fn max_leverage_estimate(
+        &self,
+        collateral_denom: &str,
+        debt_denom: &str,
+        slippage: Decimal,
+    ) -> HealthResult<Uint128> {
+        let total_max_ltv_adjusted_value =
+            self.total_collateral_value()?.max_ltv_adjusted_collateral;
+        let debt_value = self.total_debt_value()?;
+
+        let debt_params =
+            self.denoms_data.params.get(debt_denom).ok_or(MissingParams(debt_denom.to_string()))?;
+
+        // Zero borrowable if unhealthy or not whitelisted
+        if debt_value >= total_max_ltv_adjusted_value || !debt_params.credit_manager.whitelisted {
+            return Ok(Uint128::zero());
+        }
+
+        // Takes the HLS max LTV into account and is zero if the collateral has been de-listed
+        let collateral_max_ltv = self.get_coin_max_ltv(collateral_denom)?;
+
+        let debt_denom_price = self
+            .denoms_data
+            .prices
+            .get(debt_denom)
+            .cloned()
+            .ok_or(MissingPrice(debt_denom.to_string()))?;
+
+        // Each loop borrows the remaining headroom, whose swapped value is deposited and adds
+        // headroom of its own:
+        //      borrow_n+1 = borrow_n * (1 - slippage) * collateral_max_ltv
+        // The loop converges to the sum of the geometric series:
+        //      total_borrow = borrow_0 / (1 - (1 - slippage) * collateral_max_ltv)
+        //          where: borrow_0 = (max ltv adjusted value - debt value) / debt_denom_price
+        // Which comes down to borrowing it all in a single swap:
+        //      1 = (max ltv adjusted value + (total_borrow * debt_denom_price * (1 - slippage) * collateral_max_ltv)) / (debt value + (total_borrow * debt_denom_price))
+        // As for the other estimates, the - 1 errs on the side of being more conservative.
+        let collateral_ltv_slippage_corrected =
+            collateral_max_ltv.checked_mul(Decimal::one().checked_sub(slippage)?)?;
+        let max_borrow_amount = total_max_ltv_adjusted_value
+            .checked_sub(debt_value)?
+            .checked_sub(Uint128::one())?
+            .checked_div_floor(
+                Decimal::one()
+                    .checked_sub(collateral_ltv_slippage_corrected)?
+                    .checked_mul(debt_denom_price)?,
+            )?;
+
+        Ok(max_borrow_amount)
+    }
+
     pub fn liquidation_price(
         &self,
         denom: &str,
@@ -487,6 +549,7 @@ impl HealthComputer {
             let debt_value = debt.amount.checked_mul_ceil(*coin_price)?;
             total = total.checked_add(debt_value)?;
         }
+        total = total.checked_add(self.perps_debt_value()?)?;
         Ok(total)
     }
 
@@ -495,23 +558,27 @@ impl HealthComputer {
         let lends = self.coins_value(&self.positions.lends)?;
         let vaults = self.vaults_value()?;
         let staked_lp = self.coins_value(&self.positions.staked_astro_lps)?;
+        let perps = self.perps_value()?;
 
         Ok(CollateralValue {
             total_collateral_value: deposits
                 .total_collateral_value
                 .checked_add(vaults.total_collateral_value)?
                 .checked_add(lends.total_collateral_value)?
-                .checked_add(staked_lp.total_collateral_value)?,
+                .checked_add(staked_lp.total_collateral_value)?
+                .checked_add(perps.total_collateral_value)?,
             max_ltv_adjusted_collateral: deposits
                 .max_ltv_adjusted_collateral
                 .checked_add(vaults.max_ltv_adjusted_collateral)?
                 .checked_add(lends.max_ltv_adjusted_collateral)?
-                .checked_add(staked_lp.max_ltv_adjusted_collateral)?,
+                .checked_add(staked_lp.max_ltv_adjusted_collateral)?
+                .checked_add(perps.max_ltv_adjusted_collateral)?,
             liquidation_threshold_adjusted_collateral: deposits
                 .liquidation_threshold_adjusted_collateral
                 .checked_add(vaults.liquidation_threshold_adjusted_collateral)?
                 .checked_add(lends.liquidation_threshold_adjusted_collateral)?
-                .checked_add(staked_lp.liquidation_threshold_adjusted_collateral)?,
+                .checked_add(staked_lp.liquidation_threshold_adjusted_collateral)?
+                .checked_add(perps.liquidation_threshold_adjusted_collateral)?,
         })
     }
 
@@ -706,6 +773,77 @@ impl HealthComputer {
         })
     }
 
+    /// A perp position counts its full value as debt (see `perps_debt_value`) and its value
+    /// weighted by the perp's max LTV / liquidation threshold as collateral. The difference is the
+    /// margin the rest of the account has to provide.
+    /// Unrealized profits are valued as a deposit of the perps base denom.
+    fn perps_value(&self) -> HealthResult<CollateralValue> {
+        let mut total_collateral_value = Uint128::zero();
+        let mut max_ltv_adjusted_collateral = Uint128::zero();
+        let mut liquidation_threshold_adjusted_collateral = Uint128::zero();
+
+        for p in &self.positions.perps {
+            // Step 1: Calculate position values
+            let params =
+                self.perps_data.params.get(&p.denom).ok_or(MissingPerpParams(p.denom.clone()))?;
+            let price =
+                self.denoms_data.prices.get(&p.denom).ok_or(MissingPrice(p.denom.clone()))?;
+            let position_value = abs_size(p).checked_mul_floor(*price)?;
+
+            total_collateral_value = total_collateral_value.checked_add(position_value)?;
+            max_ltv_adjusted_collateral = position_value
+                .checked_mul_floor(params.max_loan_to_value)?
+                .checked_add(max_ltv_adjusted_collateral)?;
+            liquidation_threshold_adjusted_collateral = position_value
+                .checked_mul_floor(params.liquidation_threshold)?
+                .checked_add(liquidation_threshold_adjusted_collateral)?;
+
+            // Step 2: Calculate unrealized profit values
+            if p.unrealized_pnl.pnl > Int128::zero() {
+                let res = self.coins_value(&[Coin {
+                    denom: p.base_denom.clone(),
+                    amount: Uint128::new(p.unrealized_pnl.pnl.i128().unsigned_abs()),
+                }])?;
+                total_collateral_value =
+                    total_collateral_value.checked_add(res.total_collateral_value)?;
+                max_ltv_adjusted_collateral =
+                    max_ltv_adjusted_collateral.checked_add(res.max_ltv_adjusted_collateral)?;
+                liquidation_threshold_adjusted_collateral =
+                    liquidation_threshold_adjusted_collateral
+                        .checked_add(res.liquidation_threshold_adjusted_collateral)?;
+            }
+        }
+
+        Ok(CollateralValue {
+            total_collateral_value,
+            max_ltv_adjusted_collateral,
+            liquidation_threshold_adjusted_collateral,
+        })
+    }
+
+    /// Full value of the perp positions plus their unrealized losses
+    fn perps_debt_value(&self) -> HealthResult<Uint128> {
+        let mut total = Uint128::zero();
+        for p in &self.positions.perps {
+            let price =
+                self.denoms_data.prices.get(&p.denom).ok_or(MissingPrice(p.denom.clone()))?;
+            let position_value = abs_size(p).checked_mul_ceil(*price)?;
+            total = total.checked_add(position_value)?;
+
+            if p.unrealized_pnl.pnl < Int128::zero() {
+                let base_denom_price = self
+                    .denoms_data
+                    .prices
+                    .get(&p.base_denom)
+                    .ok_or(MissingPrice(p.base_denom.clone()))?;
+                let loss_value = Uint128::new(p.unrealized_pnl.pnl.i128().unsigned_abs())
+                    .checked_mul_ceil(*base_denom_price)?;
+                total = total.checked_add(loss_value)?;
+            }
+        }
+        Ok(total)
+    }
+
     fn get_coin_max_ltv(&self, denom: &str) -> HealthResult<Decimal> {
         let params = self.denoms_data.params.get(denom).ok_or(MissingParams(denom.to_string()))?;
 
@@ -741,3 +879,7 @@ impl HealthComputer {
         })
     }
 }
+
+fn abs_size(position: &PerpPosition) -> Uint128 {
+    Uint128::new(position.size.i128().unsigned_abs())
+}
@@ -1,5 +1,5 @@
 use mars_types::health::{
-    BorrowTarget, HealthValuesResponse, LiquidationPriceKind, Slippage, SwapKind,
+    ActionsToApply, BorrowTarget, HealthValuesResponse, LiquidationPriceKind, Slippage, SwapKind,
 };
 use wasm_bindgen::prelude::*;
 
@@ -42,6 +42,18 @@ pub fn max_swap_estimate_js(
         .to_string()
 }
 
+#[wasm_bindgen]
+pub fn max_leverage_estimate_js(
+    c: HealthComputer,
+    collateral_denom: String,
+    debt_denom: String,
+    slippage: Slippage,
+) -> String {
+    c.max_leverage_estimate(&collateral_denom, &debt_denom, slippage.as_decimal())
+        .unwrap()
+        .to_string()
+}
+
 #[wasm_bindgen]
 pub fn liquidation_price_js(
     c: HealthComputer,
@@ -50,3 +62,13 @@ pub fn liquidation_price_js(
 ) -> String {
     c.liquidation_price(&denom, &kind).unwrap().to_string()
 }
+
+/// Returns the health computer with the actions applied to its positions
+#[wasm_bindgen]
+pub fn simulate_actions_js(
+    c: HealthComputer,
+    actions: ActionsToApply,
+) -> Result<HealthComputer, JsError> {
+    c.apply_actions(&actions.actions, actions.swap_fee)
+        .map_err(|err| JsError::new(&err.to_string()))
+}
@@ -1,6 +1,7 @@
+mod actions;
 mod data_types;
 mod health_computer;
-pub use self::{data_types::*, health_computer::*};
+pub use self::{actions::*, data_types::*, health_computer::*};
 
 #[cfg(feature = "javascript")]
 mod javascript;
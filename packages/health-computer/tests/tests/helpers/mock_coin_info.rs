@@ -35,9 +35,11 @@ pub fn umars_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -64,9 +66,11 @@ pub fn udai_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -93,9 +97,11 @@ pub fn uluna_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -134,9 +140,11 @@ pub fn ustars_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -163,9 +171,11 @@ pub fn ujuno_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -198,9 +208,11 @@ pub fn uatom_info() -> CoinInfo {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             protocol_liquidation_fee: Decimal::percent(2u64),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     }
 }
@@ -67,6 +67,7 @@ fn random_coin_info() -> impl Strategy<Value = AssetParams> {
                 red_bank: RedBankSettings {
                     deposit_enabled: true,
                     borrow_enabled: true,
+                    isolation: None,
                 },
                 max_loan_to_value,
                 liquidation_threshold,
@@ -78,6 +79,7 @@ fn random_coin_info() -> impl Strategy<Value = AssetParams> {
                 },
                 protocol_liquidation_fee: Default::default(),
                 deposit_cap: Default::default(),
+                borrow_cap: Default::default(),
             }
         },
     )
@@ -308,9 +310,11 @@ pub fn random_health_computer() -> impl Strategy<Value = HealthComputer> {
                         lends,
                         vaults,
                         staked_astro_lps,
+                        perps: vec![],
                     },
                     denoms_data: denoms_data.clone(),
                     vaults_data: vaults_data.clone(),
+                    perps_data: Default::default(),
                 }
             })
     })
@@ -1,5 +1,6 @@
 mod helpers;
 
+mod test_apply_actions;
 mod test_health_scenarios;
 mod test_hls;
 mod test_input_validation;
@@ -8,8 +9,10 @@ mod test_max_borrow_prop;
 mod test_max_borrow_validation;
 mod test_max_borrow_vault;
 mod test_max_borrow_wallet;
+mod test_max_leverage;
 mod test_max_swap;
 mod test_max_swap_prop;
 mod test_max_swap_validation;
 mod test_max_withdraw;
 mod test_max_withdraw_prop_test;
+mod test_perps;
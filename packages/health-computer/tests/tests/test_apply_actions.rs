@@ -0,0 +1,345 @@
+use cosmwasm_std::{coin, Coin, Decimal, Int128, SignedDecimal, Uint128};
+use mars_rover_health_computer::{
+    apply_actions, ActionsEstimator, DenomsData, HealthComputer, PerpOrderEstimate,
+};
+use mars_types::{
+    adapters::vault::VaultUnchecked,
+    credit_manager::{
+        Action::{
+            Borrow, DeleteTriggerOrder, Deposit, EnterVault, ExecutePerpOrder, Lend,
+            ProvideLiquidity, Reclaim, Repay, SwapExactIn, Withdraw,
+        },
+        ActionAmount, ActionCoin, DebtAmount, Positions,
+    },
+    health::{AccountKind, HealthError, HealthResult},
+    perps::PerpPosition,
+    swapper::SwapperRoute,
+};
+
+use super::helpers::{udai_info, umars_info, CoinInfo};
+
+fn health_computer(coins: &[CoinInfo], deposits: Vec<Coin>) -> HealthComputer {
+    HealthComputer {
+        kind: AccountKind::Default,
+        positions: Positions {
+            account_id: "123".to_string(),
+            account_kind: AccountKind::Default,
+            deposits,
+            debts: vec![],
+            lends: vec![],
+            vaults: vec![],
+            staked_astro_lps: vec![],
+            perps: vec![],
+        },
+        denoms_data: DenomsData {
+            prices: coins.iter().map(|c| (c.denom.clone(), c.price)).collect(),
+            params: coins.iter().map(|c| (c.denom.clone(), c.params.clone())).collect(),
+        },
+        vaults_data: Default::default(),
+        perps_data: Default::default(),
+    }
+}
+
+/// Returns the same perp order estimate and keeper fee for every order
+struct FixedEstimator {
+    perp_order: PerpOrderEstimate,
+    keeper_fee: Coin,
+}
+
+impl ActionsEstimator for FixedEstimator {
+    type Error = HealthError;
+
+    fn debt_shares_to_add(&self, _coin: &Coin) -> HealthResult<Uint128> {
+        Ok(Uint128::zero())
+    }
+
+    fn swap_fee(&self) -> HealthResult<Decimal> {
+        Ok(Decimal::zero())
+    }
+
+    fn estimate_exact_in_swap(
+        &self,
+        _coin_in: &Coin,
+        _denom_out: &str,
+        _route: &Option<SwapperRoute>,
+    ) -> HealthResult<Uint128> {
+        unimplemented!()
+    }
+
+    fn estimate_provide_liquidity(
+        &self,
+        _coins_in: &[Coin],
+        _lp_token_out: &str,
+        _slippage: Decimal,
+    ) -> HealthResult<Uint128> {
+        unimplemented!()
+    }
+
+    fn estimate_perp_order(
+        &self,
+        _account_id: &str,
+        _denom: &str,
+        _order_size: Int128,
+        _reduce_only: Option<bool>,
+    ) -> HealthResult<PerpOrderEstimate> {
+        Ok(self.perp_order.clone())
+    }
+
+    fn trigger_order_keeper_fee(
+        &self,
+        _account_id: &str,
+        _trigger_order_id: &str,
+    ) -> HealthResult<Coin> {
+        Ok(self.keeper_fee.clone())
+    }
+}
+
+fn account_balance(denom: &str) -> ActionCoin {
+    ActionCoin {
+        denom: denom.to_string(),
+        amount: ActionAmount::AccountBalance,
+    }
+}
+
+#[test]
+fn leverage_loop() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let h = health_computer(&[umars.clone(), udai.clone()], vec![]);
+
+    let res = h
+        .apply_actions(
+            &[
+                Deposit(coin(1000, &umars.denom)),
+                Borrow(coin(3000, &udai.denom)),
+                SwapExactIn {
+                    coin_in: account_balance(&udai.denom),
+                    denom_out: umars.denom.clone(),
+                    min_receive: Uint128::one(),
+                    route: None,
+                },
+                Lend(ActionCoin {
+                    denom: umars.denom.clone(),
+                    amount: ActionAmount::Exact(Uint128::new(500)),
+                }),
+            ],
+            Decimal::zero(),
+        )
+        .unwrap();
+
+    // 3000 udai swapped at oracle prices: 3000 * 0.313451 / 1
+    assert_eq!(res.positions.deposits, vec![coin(1440, &umars.denom)]);
+    assert_eq!(res.positions.lends, vec![coin(500, &umars.denom)]);
+    assert_eq!(
+        res.positions.debts,
+        vec![DebtAmount {
+            denom: udai.denom.clone(),
+            shares: Uint128::zero(),
+            amount: Uint128::new(3000),
+        }]
+    );
+
+    // Original positions are untouched
+    assert!(h.positions.deposits.is_empty());
+    assert_eq!(h.compute_health().unwrap().max_ltv_health_factor, None);
+
+    let health = res.compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(1940));
+    assert_eq!(health.total_debt_value, Uint128::new(941));
+    assert!(health.max_ltv_health_factor.unwrap() > Decimal::one());
+}
+
+#[test]
+fn repay_reclaim_and_withdraw() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let mut h = health_computer(&[umars.clone(), udai.clone()], vec![coin(1000, &udai.denom)]);
+    h.positions.lends = vec![coin(200, &umars.denom)];
+    h.positions.debts = vec![DebtAmount {
+        denom: udai.denom.clone(),
+        shares: Uint128::new(400_000_000),
+        amount: Uint128::new(400),
+    }];
+
+    let res = h
+        .apply_actions(
+            &[
+                Repay {
+                    recipient_account_id: None,
+                    coin: account_balance(&udai.denom),
+                },
+                Reclaim(account_balance(&umars.denom)),
+                Withdraw(ActionCoin {
+                    denom: umars.denom.clone(),
+                    amount: ActionAmount::Exact(Uint128::new(50)),
+                }),
+            ],
+            Decimal::zero(),
+        )
+        .unwrap();
+
+    // Repaying the account balance is capped by the debt
+    assert!(res.positions.debts.is_empty());
+    assert_eq!(res.positions.deposits, vec![coin(600, &udai.denom), coin(150, &umars.denom)]);
+    assert!(res.positions.lends.is_empty());
+}
+
+#[test]
+fn provide_liquidity_valued_with_slippage() {
+    let umars = umars_info();
+    let mut lp = umars_info();
+    lp.denom = "umars_lp".to_string();
+    lp.price = Decimal::from_atomics(2u128, 0).unwrap();
+    lp.params.denom = lp.denom.clone();
+    let h = health_computer(&[umars.clone(), lp.clone()], vec![coin(1000, &umars.denom)]);
+
+    let res = h
+        .apply_actions(
+            &[ProvideLiquidity {
+                coins_in: vec![account_balance(&umars.denom)],
+                lp_token_out: lp.denom.clone(),
+                slippage: Decimal::percent(10),
+            }],
+            Decimal::zero(),
+        )
+        .unwrap();
+
+    assert_eq!(res.positions.deposits, vec![coin(450, &lp.denom)]);
+}
+
+#[test]
+fn swap_fee_deducted_and_min_receive_enforced() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let h = health_computer(&[umars.clone(), udai.clone()], vec![coin(3000, &udai.denom)]);
+
+    let swap = |min_receive: u128| SwapExactIn {
+        coin_in: account_balance(&udai.denom),
+        denom_out: umars.denom.clone(),
+        min_receive: Uint128::new(min_receive),
+        route: None,
+    };
+
+    // 1% fee: 2970 udai swapped at oracle prices, 2970 * 0.313451 / 1
+    let res = h.apply_actions(&[swap(930)], Decimal::percent(1)).unwrap();
+    assert_eq!(res.positions.deposits, vec![coin(930, &umars.denom)]);
+
+    let err = h.apply_actions(&[swap(931)], Decimal::percent(1)).unwrap_err();
+    assert_eq!(
+        err,
+        HealthError::BelowMinReceive {
+            denom: umars.denom.clone(),
+            estimated: Uint128::new(930),
+            min_receive: Uint128::new(931),
+        }
+    );
+}
+
+#[test]
+fn invalid_actions() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let h = health_computer(&[umars.clone(), udai.clone()], vec![coin(1000, &umars.denom)]);
+
+    let err = h
+        .apply_actions(
+            &[Withdraw(ActionCoin {
+                denom: umars.denom.clone(),
+                amount: ActionAmount::Exact(Uint128::new(1001)),
+            })],
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, HealthError::Overflow(_)));
+
+    let err = h
+        .apply_actions(
+            &[Repay {
+                recipient_account_id: None,
+                coin: account_balance(&umars.denom),
+            }],
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(err, HealthError::DenomNotPresent(umars.denom.clone()));
+
+    let err = h
+        .apply_actions(
+            &[SwapExactIn {
+                coin_in: account_balance(&umars.denom),
+                denom_out: "uatom".to_string(),
+                min_receive: Uint128::one(),
+                route: None,
+            }],
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(err, HealthError::MissingPrice("uatom".to_string()));
+
+    let err = h
+        .apply_actions(
+            &[EnterVault {
+                vault: VaultUnchecked::new("vault".to_string()),
+                coin: account_balance(&umars.denom),
+            }],
+            Decimal::zero(),
+        )
+        .unwrap_err();
+    assert_eq!(err, HealthError::UnsupportedAction("enter_vault".to_string()));
+}
+
+#[test]
+fn perp_orders_and_trigger_order_refunds_use_the_estimator() {
+    let umars = umars_info();
+    let mut positions = health_computer(&[umars.clone()], vec![coin(1000, &umars.denom)]).positions;
+
+    let eth_price = Decimal::from_atomics(2000u128, 0).unwrap();
+    let perp_position = PerpPosition {
+        denom: "ueth".to_string(),
+        base_denom: umars.denom.clone(),
+        size: Int128::new(100),
+        entry_price: eth_price,
+        current_price: eth_price,
+        denom_price: eth_price,
+        entry_accrued_funding_per_unit_in_base_denom: SignedDecimal::zero(),
+        unrealized_pnl: Default::default(),
+        realized_pnl: Default::default(),
+    };
+    let mut estimator = FixedEstimator {
+        perp_order: PerpOrderEstimate {
+            realized_pnl: Int128::new(-40),
+            base_denom: umars.denom.clone(),
+            position: Some(perp_position.clone()),
+        },
+        keeper_fee: coin(10, &umars.denom),
+    };
+
+    let open_order = ExecutePerpOrder {
+        denom: "ueth".to_string(),
+        order_size: Int128::new(100),
+        reduce_only: None,
+    };
+    let delete_order = DeleteTriggerOrder {
+        trigger_order_id: "1".to_string(),
+    };
+    apply_actions(&mut positions, &[open_order, delete_order], &estimator).unwrap();
+
+    // The loss is paid from the deposits and the keeper fee is refunded
+    assert_eq!(positions.deposits, vec![coin(970, &umars.denom)]);
+    assert_eq!(positions.perps, vec![perp_position]);
+
+    estimator.perp_order = PerpOrderEstimate {
+        realized_pnl: Int128::new(25),
+        base_denom: umars.denom.clone(),
+        position: None,
+    };
+    let close_order = ExecutePerpOrder {
+        denom: "ueth".to_string(),
+        order_size: Int128::new(-100),
+        reduce_only: Some(true),
+    };
+    apply_actions(&mut positions, &[close_order], &estimator).unwrap();
+
+    assert_eq!(positions.deposits, vec![coin(995, &umars.denom)]);
+    assert!(positions.perps.is_empty());
+}
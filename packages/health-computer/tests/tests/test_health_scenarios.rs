@@ -48,9 +48,11 @@ fn only_assets_with_no_debts() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -116,9 +118,11 @@ fn terra_ragnarok() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data: vaults_data.clone(),
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -178,9 +182,11 @@ fn terra_ragnarok() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -248,9 +254,11 @@ fn ltv_and_lqdt_adjusted_values() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -371,9 +379,11 @@ fn debt_value() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -476,9 +486,11 @@ fn above_max_ltv_below_liq_threshold() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -541,9 +553,11 @@ fn liquidatable() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -608,9 +622,11 @@ fn rover_whitelist_influences_max_ltv() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -702,9 +718,11 @@ fn unlocked_vault() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -799,9 +817,11 @@ fn locked_vault() {
                 }),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -905,9 +925,11 @@ fn locked_vault_with_unlocking_positions() {
                 }),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -999,9 +1021,11 @@ fn vault_is_not_whitelisted() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -1111,9 +1135,11 @@ fn vault_base_token_is_not_whitelisted() {
                 }),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -1172,9 +1198,11 @@ fn lent_coins_used_as_collateral() {
             lends: vec![coin(10, udai.denom), coin(2, uluna.denom)],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -1235,9 +1263,11 @@ fn allowed_lent_coins_influence_max_ltv() {
             lends: vec![coin(10, udai.denom), coin(2, uluna.denom)],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -42,9 +42,11 @@ fn hls_deposit() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -130,9 +132,11 @@ fn hls_vault() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -181,9 +185,11 @@ fn hls_on_blacklisted_asset() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -264,9 +270,11 @@ fn hls_on_blacklisted_vault() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
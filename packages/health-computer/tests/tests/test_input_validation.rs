@@ -53,9 +53,11 @@ fn missing_price_data() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.compute_health().unwrap_err();
@@ -102,9 +104,11 @@ fn missing_params() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     // If asset params is missing for a denom (in params contract), both price and params will be missing in denoms_data.
@@ -182,9 +186,11 @@ fn missing_market_data_for_vault_base_token() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::one())),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.compute_health().unwrap_err();
@@ -229,9 +235,11 @@ fn missing_vault_value() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::one())),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.compute_health().unwrap_err();
@@ -280,9 +288,11 @@ fn missing_vault_config() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::one())),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.compute_health().unwrap_err();
@@ -318,9 +328,11 @@ fn missing_hls_params() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.compute_health().unwrap_err();
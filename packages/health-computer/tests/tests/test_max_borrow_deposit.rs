@@ -34,9 +34,11 @@ fn max_borrow_deposit_offset_good() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount =
@@ -69,9 +71,11 @@ fn max_borrow_deposit_offset_margin_of_error() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount =
@@ -52,9 +52,11 @@ fn missing_borrow_denom_price_data() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError =
@@ -101,9 +103,11 @@ fn missing_borrow_denom_params() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError =
@@ -153,9 +157,11 @@ fn cannot_borrow_when_unhealthy() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_withdraw_amount =
@@ -225,9 +231,11 @@ fn hls_influences_max_borrow() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_before = h.max_borrow_amount_estimate(&ustars.denom, &BorrowTarget::Deposit).unwrap();
@@ -37,9 +37,11 @@ fn max_borrow_vault_offset_good() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount = h
@@ -83,9 +85,11 @@ fn max_borrow_vault_offset_margin_of_error() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount = h
@@ -33,9 +33,11 @@ fn max_borrow_wallet_offset_good() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount =
@@ -67,9 +69,11 @@ fn max_borrow_wallet_offset_margin_of_error() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_borrow_amount =
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{coin, Decimal, Uint128};
+use mars_rover_health_computer::{DenomsData, HealthComputer};
+use mars_types::{
+    credit_manager::{
+        Action::{Borrow, SwapExactIn},
+        ActionAmount, ActionCoin, Positions,
+    },
+    health::AccountKind,
+    params::HlsParams,
+};
+
+use super::helpers::{udai_info, ujuno_info, umars_info, CoinInfo};
+
+fn health_computer(
+    kind: AccountKind,
+    coins: &[&CoinInfo],
+    deposit: (u128, &str),
+) -> HealthComputer {
+    HealthComputer {
+        kind: kind.clone(),
+        positions: Positions {
+            account_id: "123".to_string(),
+            account_kind: kind,
+            deposits: vec![coin(deposit.0, deposit.1)],
+            debts: vec![],
+            lends: vec![],
+            vaults: vec![],
+            staked_astro_lps: vec![],
+            perps: vec![],
+        },
+        denoms_data: DenomsData {
+            prices: HashMap::from_iter(coins.iter().map(|c| (c.denom.clone(), c.price))),
+            params: HashMap::from_iter(coins.iter().map(|c| (c.denom.clone(), c.params.clone()))),
+        },
+        vaults_data: Default::default(),
+        perps_data: Default::default(),
+    }
+}
+
+/// Borrows `amount` of `debt_denom` and swaps all of it into `collateral_denom`
+fn leverage(
+    h: &HealthComputer,
+    collateral_denom: &str,
+    debt_denom: &str,
+    amount: Uint128,
+) -> HealthComputer {
+    h.apply_actions(
+        &[
+            Borrow(coin(amount.u128(), debt_denom)),
+            SwapExactIn {
+                coin_in: ActionCoin {
+                    denom: debt_denom.to_string(),
+                    amount: ActionAmount::AccountBalance,
+                },
+                denom_out: collateral_denom.to_string(),
+                min_receive: Uint128::one(),
+                route: None,
+            },
+        ],
+        Decimal::zero(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn max_leverage_converges_to_max_ltv() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let h = health_computer(AccountKind::Default, &[&umars, &udai], (1000, &umars.denom));
+
+    let max_borrow = h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::zero()).unwrap();
+    assert_eq!(max_borrow, Uint128::new(12745));
+
+    // Max ltv of 0.8 for the collateral allows 1 / (1 - 0.8) = 5x the value borrowed in a single
+    // loop, the loop stopping right at a health factor of 1
+    let health = leverage(&h, &umars.denom, &udai.denom, max_borrow).compute_health().unwrap();
+    assert_eq!(health.max_ltv_health_factor, Some(Decimal::one()));
+
+    let health = leverage(&h, &umars.denom, &udai.denom, max_borrow + Uint128::new(10))
+        .compute_health()
+        .unwrap();
+    assert!(health.max_ltv_health_factor.unwrap() < Decimal::one());
+}
+
+#[test]
+fn max_leverage_accounts_for_slippage() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let h = health_computer(AccountKind::Default, &[&umars, &udai], (1000, &umars.denom));
+
+    let no_slippage = h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::zero()).unwrap();
+    let max_borrow =
+        h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::percent(5)).unwrap();
+    assert!(max_borrow < no_slippage);
+
+    // Leaves the account healthy even if the swap is executed with the max slippage
+    let max_ltv_adjusted_collateral = Uint128::new(1000)
+        .checked_add(max_borrow.checked_mul_floor(udai.price * Decimal::percent(95)).unwrap())
+        .unwrap()
+        .checked_mul_floor(umars.params.max_loan_to_value)
+        .unwrap();
+    let debt_value = max_borrow.checked_mul_ceil(udai.price).unwrap();
+    assert!(max_ltv_adjusted_collateral >= debt_value);
+}
+
+#[test]
+fn max_leverage_for_hls_account() {
+    let mut umars = umars_info();
+    umars.params.credit_manager.hls = Some(HlsParams {
+        max_loan_to_value: Decimal::percent(90),
+        liquidation_threshold: Decimal::percent(95),
+        correlations: vec![],
+    });
+    let udai = udai_info();
+
+    let h = health_computer(AccountKind::Default, &[&umars, &udai], (1000, &umars.denom));
+    let default_max_borrow =
+        h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::zero()).unwrap();
+    assert_eq!(default_max_borrow, Uint128::new(12745));
+
+    // HLS max ltv of 0.9 instead of 0.8
+    let h =
+        health_computer(AccountKind::HighLeveredStrategy, &[&umars, &udai], (1000, &umars.denom));
+    let hls_max_borrow =
+        h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::zero()).unwrap();
+    assert_eq!(hls_max_borrow, Uint128::new(28680));
+
+    let health = leverage(&h, &umars.denom, &udai.denom, hls_max_borrow).compute_health().unwrap();
+    assert_eq!(health.max_ltv_health_factor, Some(Decimal::one()));
+}
+
+#[test]
+fn max_leverage_is_zero_for_blacklisted_debt() {
+    let umars = umars_info();
+    let mut udai = udai_info();
+    udai.params.credit_manager.whitelisted = false;
+    let h = health_computer(AccountKind::Default, &[&umars, &udai], (1000, &umars.denom));
+
+    let max_borrow = h.max_leverage_estimate(&umars.denom, &udai.denom, Decimal::zero()).unwrap();
+    assert_eq!(max_borrow, Uint128::zero());
+}
+
+#[test]
+fn max_leverage_into_blacklisted_collateral() {
+    let umars = umars_info();
+    let udai = udai_info();
+    let mut ujuno = ujuno_info();
+    ujuno.params.credit_manager.whitelisted = false;
+    let h = health_computer(AccountKind::Default, &[&umars, &udai, &ujuno], (1000, &umars.denom));
+
+    // Swapped output doesn't count as collateral, same as borrowing to the wallet
+    let max_borrow = h.max_leverage_estimate(&ujuno.denom, &udai.denom, Decimal::zero()).unwrap();
+    let max_borrow_to_wallet = Uint128::new(799).checked_div_floor(udai.price).unwrap();
+    assert_eq!(max_borrow, max_borrow_to_wallet);
+}
@@ -53,9 +53,11 @@ fn missing_price_data() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.max_withdraw_amount_estimate(&udai.denom).unwrap_err();
@@ -102,9 +104,11 @@ fn missing_params() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let err: HealthError = h.max_withdraw_amount_estimate(&umars.denom).unwrap_err();
@@ -134,9 +138,11 @@ fn deposit_not_present() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_withdraw_amount = h.max_withdraw_amount_estimate("xyz").unwrap();
@@ -190,9 +196,11 @@ fn blacklisted_assets_should_be_able_be_fully_withdrawn() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -246,9 +254,11 @@ fn zero_when_unhealthy() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let health = h.compute_health().unwrap();
@@ -283,9 +293,11 @@ fn no_debts() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_withdraw_amount = h.max_withdraw_amount_estimate(&ustars.denom).unwrap();
@@ -328,9 +340,11 @@ fn should_allow_max_withdraw() {
             lends: vec![],
             vaults: vec![],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     // Max when debt value is smaller than collateral value - withdraw denom value
@@ -399,9 +413,11 @@ fn hls_with_max_withdraw() {
                 amount: VaultPositionAmount::Unlocked(VaultAmount::new(Uint128::new(5264))),
             }],
             staked_astro_lps: vec![],
+            perps: vec![],
         },
         denoms_data,
         vaults_data,
+        perps_data: Default::default(),
     };
 
     let max_before = h.max_withdraw_amount_estimate(&ustars.denom).unwrap();
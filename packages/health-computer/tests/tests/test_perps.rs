@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use cosmwasm_std::{coin, Decimal, Int128, SignedDecimal, Uint128};
+use mars_rover_health_computer::{DenomsData, HealthComputer, PerpsData};
+use mars_types::{
+    credit_manager::Positions,
+    health::{AccountKind, BorrowTarget, HealthError},
+    params::PerpParams,
+    perps::{PerpPosition, PnlAmounts},
+};
+
+use super::helpers::umars_info;
+
+#[test]
+fn perp_position_requires_margin() {
+    let h = perps_health_computer(Int128::zero());
+
+    let health = h.compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(1200));
+    assert_eq!(health.total_debt_value, Uint128::new(200));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(980));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(1030));
+    assert_eq!(health.max_ltv_health_factor, Some(Decimal::from_ratio(980u128, 200u128)));
+    assert_eq!(health.liquidation_health_factor, Some(Decimal::from_ratio(1030u128, 200u128)));
+}
+
+#[test]
+fn unrealized_profit_counts_as_collateral() {
+    let h = perps_health_computer(Int128::new(50));
+
+    let health = h.compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(1250));
+    assert_eq!(health.total_debt_value, Uint128::new(200));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(1020));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(1072));
+}
+
+#[test]
+fn unrealized_loss_counts_as_debt() {
+    let h = perps_health_computer(Int128::new(-50));
+
+    let health = h.compute_health().unwrap();
+    assert_eq!(health.total_collateral_value, Uint128::new(1200));
+    assert_eq!(health.total_debt_value, Uint128::new(250));
+    assert_eq!(health.max_ltv_adjusted_collateral, Uint128::new(980));
+    assert_eq!(health.liquidation_threshold_adjusted_collateral, Uint128::new(1030));
+}
+
+#[test]
+fn max_withdraw_with_perp_position() {
+    let h = perps_health_computer(Int128::zero());
+
+    // (980 - 200 - 1) / (1 * 0.8)
+    let max_withdraw = h.max_withdraw_amount_estimate("umars").unwrap();
+    assert_eq!(max_withdraw, Uint128::new(973));
+}
+
+#[test]
+fn max_borrow_with_perp_position() {
+    let h = perps_health_computer(Int128::new(-50));
+
+    // (980 - 250 - 1) / 1
+    let max_borrow = h.max_borrow_amount_estimate("umars", &BorrowTarget::Wallet).unwrap();
+    assert_eq!(max_borrow, Uint128::new(729));
+}
+
+#[test]
+fn missing_perp_params() {
+    let mut h = perps_health_computer(Int128::zero());
+    h.perps_data = Default::default();
+
+    let err: HealthError = h.compute_health().unwrap_err();
+    assert_eq!(err, HealthError::MissingPerpParams("ueth".to_string()));
+}
+
+/// Account with 1000 umars deposited and a short ueth position worth 200 umars
+fn perps_health_computer(pnl: Int128) -> HealthComputer {
+    let umars = umars_info();
+    let eth_price = Decimal::from_atomics(2u128, 0).unwrap();
+
+    let denoms_data = DenomsData {
+        prices: HashMap::from([
+            (umars.denom.clone(), umars.price),
+            ("ueth".to_string(), eth_price),
+        ]),
+        params: HashMap::from([(umars.denom.clone(), umars.params.clone())]),
+    };
+
+    let perps_data = PerpsData {
+        params: HashMap::from([("ueth".to_string(), perp_params("ueth"))]),
+    };
+
+    HealthComputer {
+        kind: AccountKind::Default,
+        positions: Positions {
+            account_id: "123".to_string(),
+            account_kind: AccountKind::Default,
+            deposits: vec![coin(1000, &umars.denom)],
+            debts: vec![],
+            lends: vec![],
+            vaults: vec![],
+            staked_astro_lps: vec![],
+            perps: vec![PerpPosition {
+                denom: "ueth".to_string(),
+                base_denom: umars.denom,
+                size: Int128::new(-100),
+                entry_price: eth_price,
+                current_price: eth_price,
+                denom_price: eth_price,
+                entry_accrued_funding_per_unit_in_base_denom: SignedDecimal::zero(),
+                unrealized_pnl: PnlAmounts {
+                    price_pnl: pnl,
+                    pnl,
+                    ..Default::default()
+                },
+                realized_pnl: Default::default(),
+            }],
+        },
+        denoms_data,
+        vaults_data: Default::default(),
+        perps_data,
+    }
+}
+
+fn perp_params(denom: &str) -> PerpParams {
+    PerpParams {
+        denom: denom.to_string(),
+        enabled: true,
+        max_net_oi_value: Uint128::new(1_000_000),
+        max_long_oi_value: Uint128::new(1_000_000),
+        max_short_oi_value: Uint128::new(1_000_000),
+        closing_fee_rate: Decimal::zero(),
+        opening_fee_rate: Decimal::zero(),
+        min_position_value: Uint128::zero(),
+        max_position_value: None,
+        max_loan_to_value: Decimal::percent(90),
+        liquidation_threshold: Decimal::percent(95),
+        max_funding_velocity: Decimal::from_atomics(3u128, 0).unwrap(),
+        skew_scale: Uint128::new(1_000_000),
+    }
+}
@@ -138,6 +138,7 @@ fn mock_setup() -> MarsMockQuerier {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(50u128, 2).unwrap(),
             liquidation_threshold: Decimal::from_atomics(55u128, 2).unwrap(),
@@ -149,6 +150,7 @@ fn mock_setup() -> MarsMockQuerier {
             },
             protocol_liquidation_fee: Decimal::zero(),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     );
     let atom_market = Market {
@@ -167,6 +169,7 @@ fn mock_setup() -> MarsMockQuerier {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(70u128, 2).unwrap(),
             liquidation_threshold: Decimal::from_atomics(75u128, 2).unwrap(),
@@ -178,6 +181,7 @@ fn mock_setup() -> MarsMockQuerier {
             },
             protocol_liquidation_fee: Decimal::zero(),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     );
 
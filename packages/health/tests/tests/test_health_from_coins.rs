@@ -32,6 +32,7 @@ fn health_success_from_coins() {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(50u128, 2).unwrap(),
             liquidation_threshold: Decimal::from_atomics(55u128, 2).unwrap(),
@@ -43,6 +44,7 @@ fn health_success_from_coins() {
             },
             protocol_liquidation_fee: Decimal::zero(),
             deposit_cap: Uint128::MAX,
+            borrow_cap: Uint128::MAX,
         },
     );
     let atom_market = Market {
@@ -61,6 +63,7 @@ fn health_success_from_coins() {
             red_bank: RedBankSettings {
                 deposit_enabled: true,
                 borrow_enabled: true,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(70u128, 2).unwrap(),
             liquidation_threshold: Decimal::from_atomics(75u128, 2).unwrap(),
@@ -72,6 +75,7 @@ fn health_success_from_coins() {
             },
             protocol_liquidation_fee: Decimal::zero(),
             deposit_cap: Uint128::MAX,
+            borrow_cap: Uint128::MAX,
         },
     );
 
@@ -129,6 +133,7 @@ fn health_error_from_coins() {
             red_bank: RedBankSettings {
                 deposit_enabled: false,
                 borrow_enabled: false,
+                isolation: None,
             },
             max_loan_to_value: Decimal::from_atomics(50u128, 2).unwrap(),
             liquidation_threshold: Decimal::from_atomics(55u128, 2).unwrap(),
@@ -140,6 +145,7 @@ fn health_error_from_coins() {
             },
             protocol_liquidation_fee: Decimal::zero(),
             deposit_cap: Default::default(),
+            borrow_cap: Default::default(),
         },
     );
 
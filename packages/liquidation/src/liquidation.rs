@@ -9,6 +9,50 @@ use mars_types::params::AssetParams;
 
 use crate::error::LiquidationError;
 
+/// Outcome of a liquidation, see [`calculate_liquidation`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiquidationAmounts {
+    pub debt_amount_to_repay: Uint128,
+    /// Collateral taken from the liquidatee, liquidation bonus included
+    pub collateral_amount_to_liquidate: Uint128,
+    /// Collateral to liquidate less the protocol fee
+    pub collateral_amount_received_by_liquidator: Uint128,
+    /// Part of the liquidation bonus charged by the protocol, in collateral
+    pub protocol_fee_amount: Uint128,
+    pub liquidation_bonus: Decimal,
+}
+
+/// Same as [`calculate_liquidation`], returning
+/// (debt to repay, collateral to liquidate, collateral received by liquidator)
+#[allow(clippy::too_many_arguments)]
+pub fn calculate_liquidation_amounts(
+    collateral_amount: Uint128,
+    collateral_price: Decimal,
+    collateral_params: &AssetParams,
+    debt_amount: Uint128,
+    debt_requested_to_repay: Uint128,
+    debt_price: Decimal,
+    target_health_factor: Decimal,
+    health: &Health,
+) -> Result<(Uint128, Uint128, Uint128), LiquidationError> {
+    let amounts = calculate_liquidation(
+        collateral_amount,
+        collateral_price,
+        collateral_params,
+        debt_amount,
+        debt_requested_to_repay,
+        debt_price,
+        target_health_factor,
+        health,
+    )?;
+
+    Ok((
+        amounts.debt_amount_to_repay,
+        amounts.collateral_amount_to_liquidate,
+        amounts.collateral_amount_received_by_liquidator,
+    ))
+}
+
 /// Within this new system, the close factor (CF) will be determined dynamically using a parameter
 /// known as the Target Health Factor (THF). The THF determines the ideal HF a position should be left
 /// at immediately after the position has been liquidated. The CF, in turn, is a result of this parameter:
@@ -32,7 +76,7 @@ use crate::error::LiquidationError;
 /// - The liquidator receives 90% of the LB.
 /// - The remaining 10% is sent to the protocol as PLF.
 #[allow(clippy::too_many_arguments)]
-pub fn calculate_liquidation_amounts(
+pub fn calculate_liquidation(
     collateral_amount: Uint128,
     collateral_price: Decimal,
     collateral_params: &AssetParams,
@@ -41,7 +85,7 @@ pub fn calculate_liquidation_amounts(
     debt_price: Decimal,
     target_health_factor: Decimal,
     health: &Health,
-) -> Result<(Uint128, Uint128, Uint128), LiquidationError> {
+) -> Result<LiquidationAmounts, LiquidationError> {
     // if health.liquidatable == true, save to unwrap
     let liquidation_health_factor = health.liquidation_health_factor.unwrap();
 
@@ -119,11 +163,13 @@ pub fn calculate_liquidation_amounts(
     let collateral_amount_received_by_liquidator =
         collateral_amount_to_liquidate - protocol_fee_amount;
 
-    Ok((
+    Ok(LiquidationAmounts {
         debt_amount_to_repay,
         collateral_amount_to_liquidate,
         collateral_amount_received_by_liquidator,
-    ))
+        protocol_fee_amount,
+        liquidation_bonus,
+    })
 }
 
 /// The LB will depend on the Health Factor and a couple other parameters as follows:
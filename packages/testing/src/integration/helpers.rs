@@ -3,7 +3,7 @@ use std::str::FromStr;
 use cosmwasm_std::{Decimal, Uint128};
 use mars_types::{
     params::{AssetParams, CmSettings, LiquidationBonus, RedBankSettings},
-    red_bank::{InitOrUpdateAssetParams, InterestRateModel},
+    red_bank::{InitOrUpdateAssetParams, InterestRateModel, LinearInterestRateModel},
 };
 
 pub fn osmo_asset_params() -> (InitOrUpdateAssetParams, AssetParams) {
@@ -21,12 +21,13 @@ pub fn default_asset_params_with(
 ) -> (InitOrUpdateAssetParams, AssetParams) {
     let market_params = InitOrUpdateAssetParams {
         reserve_factor: Some(Decimal::percent(20)),
-        interest_rate_model: Some(InterestRateModel {
+        interest_rate_model: Some(InterestRateModel::Linear(LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(10),
             base: Decimal::percent(30),
             slope_1: Decimal::percent(25),
             slope_2: Decimal::percent(30),
-        }),
+        })),
+        flash_loan_fee_rate: None,
     };
     let asset_params = AssetParams {
         denom: denom.to_string(),
@@ -37,6 +38,7 @@ pub fn default_asset_params_with(
         red_bank: RedBankSettings {
             deposit_enabled: true,
             borrow_enabled: true,
+            isolation: None,
         },
         max_loan_to_value,
         liquidation_threshold,
@@ -48,6 +50,7 @@ pub fn default_asset_params_with(
         },
         protocol_liquidation_fee: Decimal::percent(2),
         deposit_cap: Uint128::MAX,
+        borrow_cap: Uint128::MAX,
     };
     (market_params, asset_params)
 }
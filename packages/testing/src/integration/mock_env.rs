@@ -4,7 +4,7 @@ use std::{collections::HashMap, default::Default, mem::take, str::FromStr};
 
 use anyhow::Result as AnyResult;
 use astroport_v5::incentives::InputSchedule;
-use cosmwasm_std::{coin, Addr, Coin, Decimal, Empty, StdResult, Uint128};
+use cosmwasm_std::{coin, Addr, Binary, Coin, Decimal, Empty, StdResult, Uint128};
 use cw_multi_test::{App, AppResponse, BankSudo, BasicApp, Executor, SudoMsg};
 use cw_paginate::PaginationResponse;
 use mars_oracle_osmosis::OsmosisPriceSourceUnchecked;
@@ -12,6 +12,7 @@ use mars_types::{
     address_provider::{self, MarsAddressType},
     credit_manager::ActionCoin,
     incentives,
+    liquidation::EstimateLiquidationResponse,
     oracle::{
         self,
         ActionKind::{Default as ActionDefault, Liquidation},
@@ -484,6 +485,23 @@ impl RedBank {
         )
     }
 
+    pub fn update_pyth_prices(
+        &self,
+        env: &mut MockEnv,
+        sender: &Addr,
+        vaa_data: Vec<Binary>,
+        fee: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        env.app.execute_contract(
+            sender.clone(),
+            self.contract_addr.clone(),
+            &red_bank::ExecuteMsg::UpdatePythPrices {
+                vaa_data,
+            },
+            fee,
+        )
+    }
+
     pub fn repay(&self, env: &mut MockEnv, sender: &Addr, coin: Coin) -> AnyResult<AppResponse> {
         env.app.execute_contract(
             sender.clone(),
@@ -567,6 +585,24 @@ impl RedBank {
         )
     }
 
+    pub fn query_estimate_liquidation(
+        &self,
+        env: &mut MockEnv,
+        user: &Addr,
+        collateral_denom: &str,
+        debt_coin: Coin,
+    ) -> StdResult<EstimateLiquidationResponse> {
+        env.app.wrap().query_wasm_smart(
+            self.contract_addr.clone(),
+            &red_bank::QueryMsg::EstimateLiquidation {
+                user: user.to_string(),
+                collateral_denom: collateral_denom.to_string(),
+                debt_denom: debt_coin.denom,
+                debt_amount: debt_coin.amount,
+            },
+        )
+    }
+
     pub fn query_market(&self, env: &mut MockEnv, denom: &str) -> Market {
         env.app
             .wrap()
@@ -956,6 +992,7 @@ impl MockEnvBuilder {
             MarsAddressType::AstroportIncentives,
             &astroport_incentives_addr,
         );
+        self.update_address_provider(&address_provider_addr, MarsAddressType::Pyth, &pyth_addr);
 
         MockEnv {
             app: take(&mut self.app),
@@ -1098,8 +1135,10 @@ impl MockEnvBuilder {
                 self.owner.clone(),
                 &mars_types::params::InstantiateMsg {
                     owner: self.owner.to_string(),
+                    risk_manager: None,
                     address_provider: address_provider_addr.to_string(),
                     target_health_factor: self.target_health_factor,
+                    max_perp_params: 40,
                 },
                 &[],
                 "params",
@@ -118,3 +118,12 @@ pub fn mock_managed_vault_contract() -> Box<dyn Contract<Empty>> {
     );
     Box::new(contract)
 }
+
+pub fn mock_pyth_contract() -> Box<dyn Contract<Empty>> {
+    let contract = ContractWrapper::new(
+        mars_mock_pyth::contract::execute,
+        mars_mock_pyth::contract::instantiate,
+        mars_mock_pyth::contract::query,
+    );
+    Box::new(contract)
+}
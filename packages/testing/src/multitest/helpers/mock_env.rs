@@ -37,10 +37,12 @@ use mars_types::{
     },
     address_provider::{self, MarsAddressType},
     credit_manager::{
-        Account, Action, CallbackMsg, CoinBalanceResponseItem, ConfigResponse, ConfigUpdates,
-        DebtShares, ExecuteMsg, InstantiateMsg, Positions,
+        Account, AccountStats, Action, AutoDeleveragePolicy, AutoDeleveragePolicyResponse,
+        CallbackMsg, CoinBalanceResponseItem, ConfigResponse, ConfigUpdates, DebtShares,
+        Delegation, DelegationResponse, ExecuteMsg, InstantiateMsg, LiquidateRequest, Positions,
         QueryMsg::{self, EstimateProvideLiquidity, VaultPositionValue},
-        SharesResponseItem, VaultBinding, VaultPositionResponseItem, VaultUtilizationResponse,
+        SharesResponseItem, SimulateActionsResponse, TriggerOrderResponse, VaultBinding,
+        VaultPositionResponseItem, VaultUtilizationResponse,
     },
     health::{
         AccountKind, ExecuteMsg::UpdateConfig, HealthValuesResponse,
@@ -51,6 +53,7 @@ use mars_types::{
         QueryMsg::{StakedAstroLpPosition, StakedAstroLpRewards, UserUnclaimedRewards},
         StakedLpPositionResponse,
     },
+    liquidation::EstimateLiquidationResponse,
     oracle::{ActionKind, PriceResponse, QueryMsg::Price as OraclePrice},
     params::{
         AssetParams,
@@ -217,6 +220,22 @@ impl MockEnv {
         )
     }
 
+    pub fn update_credit_accounts(
+        &mut self,
+        sender: &Addr,
+        updates: Vec<(String, Vec<Action>)>,
+        send_funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::UpdateCreditAccounts {
+                updates,
+            },
+            send_funds,
+        )
+    }
+
     pub fn invoke_callback(
         &mut self,
         sender: &Addr,
@@ -246,6 +265,70 @@ impl MockEnv {
         )
     }
 
+    pub fn execute_trigger_order(
+        &mut self,
+        keeper: &Addr,
+        account_id: &str,
+        trigger_order_id: &str,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            keeper.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::ExecuteTriggerOrder {
+                account_id: account_id.to_string(),
+                trigger_order_id: trigger_order_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn auto_deleverage(&mut self, keeper: &Addr, account_id: &str) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            keeper.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::AutoDeleverage {
+                account_id: account_id.to_string(),
+            },
+            &[],
+        )
+    }
+
+    pub fn grant_delegation(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+        delegate: &Addr,
+        delegation: Delegation,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::GrantDelegation {
+                account_id: account_id.to_string(),
+                delegate: delegate.to_string(),
+                delegation,
+            },
+            &[],
+        )
+    }
+
+    pub fn revoke_delegation(
+        &mut self,
+        sender: &Addr,
+        account_id: &str,
+        delegate: &Addr,
+    ) -> AnyResult<AppResponse> {
+        self.app.execute_contract(
+            sender.clone(),
+            self.rover.clone(),
+            &ExecuteMsg::RevokeDelegation {
+                account_id: account_id.to_string(),
+                delegate: delegate.to_string(),
+            },
+            &[],
+        )
+    }
+
     pub fn update_config(
         &mut self,
         sender: &Addr,
@@ -456,6 +539,30 @@ impl MockEnv {
     // Queries
     //--------------------------------------------------------------------------------------------------
 
+    pub fn query_last_borrow_height(&self, account_id: &str) -> Option<u64> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::LastBorrowHeight {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_account_stats(&self, account_id: &str) -> AccountStats {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AccountStats {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_positions(&self, account_id: &str) -> Positions {
         self.app
             .wrap()
@@ -468,6 +575,137 @@ impl MockEnv {
             .unwrap()
     }
 
+    pub fn query_account_trigger_orders(
+        &self,
+        account_id: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> PaginationResponse<TriggerOrderResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AccountTriggerOrders {
+                    account_id: account_id.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_all_trigger_orders(
+        &self,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    ) -> PaginationResponse<TriggerOrderResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AllTriggerOrders {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn simulate_actions(
+        &self,
+        account_id: &str,
+        actions: Vec<Action>,
+    ) -> StdResult<SimulateActionsResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::SimulateActions {
+                account_id: account_id.to_string(),
+                actions,
+            },
+        )
+    }
+
+    pub fn estimate_liquidation(
+        &self,
+        liquidatee_account_id: &str,
+        debt_coin: Coin,
+        request: LiquidateRequest<VaultUnchecked>,
+    ) -> StdResult<EstimateLiquidationResponse> {
+        self.app.wrap().query_wasm_smart(
+            self.rover.clone(),
+            &QueryMsg::EstimateLiquidation {
+                account_id: liquidatee_account_id.to_string(),
+                debt_coin,
+                request,
+            },
+        )
+    }
+
+    pub fn query_auto_deleverage_policy(&self, account_id: &str) -> Option<AutoDeleveragePolicy> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AutoDeleveragePolicy {
+                    account_id: account_id.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_all_auto_deleverage_policies(
+        &self,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> PaginationResponse<AutoDeleveragePolicyResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AllAutoDeleveragePolicies {
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_delegation(
+        &self,
+        account_id: &str,
+        delegate: &Addr,
+    ) -> Option<DelegationResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::Delegation {
+                    account_id: account_id.to_string(),
+                    delegate: delegate.to_string(),
+                },
+            )
+            .unwrap()
+    }
+
+    pub fn query_account_delegations(
+        &self,
+        account_id: &str,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> PaginationResponse<DelegationResponse> {
+        self.app
+            .wrap()
+            .query_wasm_smart(
+                self.rover.clone(),
+                &QueryMsg::AccountDelegations {
+                    account_id: account_id.to_string(),
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap()
+    }
+
     pub fn query_health(
         &self,
         account_id: &str,
@@ -1035,6 +1273,7 @@ impl MockEnvBuilder {
                         params: InitOrUpdateAssetParams {
                             reserve_factor: Some(Decimal::zero()),
                             interest_rate_model: Some(InterestRateModel::default()),
+                            flash_loan_fee_rate: None,
                         },
                     },
                     &[],
@@ -1236,10 +1475,12 @@ impl MockEnvBuilder {
                 owner.clone(),
                 &ParamsInstantiateMsg {
                     owner: owner.to_string(),
+                    risk_manager: None,
                     address_provider: address_provider.into(),
                     target_health_factor: self
                         .target_health_factor
                         .unwrap_or(Decimal::from_str("1.2").unwrap()),
+                    max_perp_params: 40,
                 },
                 &[],
                 "mock-params-contract",
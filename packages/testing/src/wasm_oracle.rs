@@ -272,6 +272,33 @@ impl<'a> WasmOracleTestRobot<'a> {
         self.wasm().execute(&self.mars_oracle_contract_addr, &msg, &[], signer).unwrap();
         self
     }
+
+    pub fn set_max_twap_snapshots(
+        &self,
+        pair_address: &str,
+        max_snapshots: u32,
+        signer: &SigningAccount,
+    ) -> &Self {
+        let msg = &mars_types::oracle::ExecuteMsg::<Empty, WasmOracleCustomExecuteMsg>::Custom(
+            WasmOracleCustomExecuteMsg::SetMaxTwapSnapshots {
+                pair_address: pair_address.to_string(),
+                max_snapshots,
+            },
+        );
+        self.wasm().execute(&self.mars_oracle_contract_addr, &msg, &[], signer).unwrap();
+        self
+    }
+
+    pub fn query_twap_snapshot_coverage(
+        &self,
+    ) -> Vec<mars_types::oracle::TwapSnapshotCoverageResponse> {
+        let msg = &mars_types::oracle::QueryMsg::TwapSnapshotCoverage {
+            start_after: None,
+            limit: None,
+        };
+        self.wasm().query(&self.mars_oracle_contract_addr, &msg).unwrap()
+    }
+
     pub fn query_price_via_simulation(&self, pair_addr: &str, denom: &str) -> Decimal {
         let decimals = self.query_native_coin_registry(denom).unwrap();
         let one: Uint128 = Uint128::from(10u128.pow(decimals as u32));
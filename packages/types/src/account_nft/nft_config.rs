@@ -1,11 +1,26 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Uint128};
 
+/// Checks made before a credit account is transferred (`TransferNft` / `SendNft`)
+#[cw_serde]
+#[derive(Default)]
+pub struct TransferPolicy {
+    /// Block transfers of accounts that can be liquidated
+    pub block_liquidatable: bool,
+    /// Block transfers for this many blocks after the account last borrowed.
+    /// Zero disables the check.
+    pub borrow_cooldown_blocks: u64,
+    /// Emit a `credit_account_transfer` event with the debts of the account when it is transferred
+    pub emit_debt_snapshot: bool,
+}
+
 #[cw_serde]
 pub struct NftConfigBase<T> {
     pub max_value_for_burn: Uint128,
     pub health_contract_addr: Option<T>,
     pub credit_manager_contract_addr: Option<T>,
+    #[serde(default)]
+    pub transfer_policy: TransferPolicy,
 }
 
 pub type NftConfig = NftConfigBase<Addr>;
@@ -17,6 +32,7 @@ impl From<NftConfig> for UncheckedNftConfig {
             max_value_for_burn: config.max_value_for_burn,
             health_contract_addr: config.health_contract_addr.map(Into::into),
             credit_manager_contract_addr: config.credit_manager_contract_addr.map(Into::into),
+            transfer_policy: config.transfer_policy,
         }
     }
 }
@@ -26,4 +42,5 @@ pub struct NftConfigUpdates {
     pub max_value_for_burn: Option<Uint128>,
     pub health_contract_addr: Option<String>,
     pub credit_manager_contract_addr: Option<String>,
+    pub transfer_policy: Option<TransferPolicy>,
 }
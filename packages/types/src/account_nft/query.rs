@@ -4,6 +4,11 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Empty, StdError};
 use cw721_base::QueryMsg as ParentQueryMsg;
 
+use crate::{
+    credit_manager::Positions,
+    health::{AccountKind, HealthValuesResponse},
+};
+
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -16,6 +21,13 @@ pub enum QueryMsg {
     #[returns(String)]
     NextId {},
 
+    /// Positions and health of a credit account, along with the reason it can't be transferred
+    /// at the moment (if any). Meant for buyers of accounts.
+    #[returns(AccountSummaryResponse)]
+    AccountSummary {
+        token_id: String,
+    },
+
     //--------------------------------------------------------------------------------------------------
     // Base cw721 messages
     //--------------------------------------------------------------------------------------------------
@@ -101,6 +113,16 @@ pub enum QueryMsg {
     Ownership {},
 }
 
+#[cw_serde]
+pub struct AccountSummaryResponse {
+    pub owner: String,
+    pub kind: AccountKind,
+    pub positions: Positions,
+    pub health: HealthValuesResponse,
+    /// Why the account can't be transferred under the current transfer policy, `None` if it can
+    pub transfer_blocked_reason: Option<String>,
+}
+
 impl TryInto<ParentQueryMsg<Empty>> for QueryMsg {
     type Error = StdError;
 
@@ -2,7 +2,9 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Api, Decimal, QuerierWrapper, StdResult};
 use cw_paginate::PaginationResponse;
 
-use crate::params::{AssetParams, QueryMsg, TotalDepositResponse, VaultConfig};
+use crate::params::{
+    AssetParams, PerpParams, QueryMsg, TotalBorrowResponse, TotalDepositResponse, VaultConfig,
+};
 
 #[cw_serde]
 pub struct ParamsBase<T>(T);
@@ -46,6 +48,19 @@ impl Params {
         )
     }
 
+    pub fn query_perp_params(
+        &self,
+        querier: &QuerierWrapper,
+        denom: &str,
+    ) -> StdResult<PerpParams> {
+        querier.query_wasm_smart(
+            self.address().to_string(),
+            &QueryMsg::PerpParams {
+                denom: denom.to_string(),
+            },
+        )
+    }
+
     pub fn query_total_deposit(
         &self,
         querier: &QuerierWrapper,
@@ -59,6 +74,19 @@ impl Params {
         )
     }
 
+    pub fn query_total_borrow(
+        &self,
+        querier: &QuerierWrapper,
+        denom: &str,
+    ) -> StdResult<TotalBorrowResponse> {
+        querier.query_wasm_smart(
+            self.address().to_string(),
+            &QueryMsg::TotalBorrow {
+                denom: denom.to_string(),
+            },
+        )
+    }
+
     pub fn query_vault_config(
         &self,
         querier: &QuerierWrapper,
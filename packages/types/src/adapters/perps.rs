@@ -0,0 +1,196 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Coin, CosmosMsg, Int128, QuerierWrapper, StdResult, Uint128, WasmMsg,
+};
+
+use crate::{
+    oracle::ActionKind,
+    perps::{
+        Config, ExecuteMsg, PositionResponse, PositionsByAccountResponse, QueryMsg, TradingFee,
+        VaultPositionResponse,
+    },
+};
+
+#[cw_serde]
+pub struct PerpsBase<T>(T);
+
+impl<T> PerpsBase<T> {
+    pub fn new(address: T) -> PerpsBase<T> {
+        PerpsBase(address)
+    }
+
+    pub fn address(&self) -> &T {
+        &self.0
+    }
+}
+
+pub type PerpsUnchecked = PerpsBase<String>;
+pub type Perps = PerpsBase<Addr>;
+
+impl From<Perps> for PerpsUnchecked {
+    fn from(perps: Perps) -> Self {
+        Self(perps.address().to_string())
+    }
+}
+
+impl PerpsUnchecked {
+    pub fn check(&self, api: &dyn Api) -> StdResult<Perps> {
+        Ok(PerpsBase::new(api.addr_validate(self.address())?))
+    }
+}
+
+impl Perps {
+    /// Generate a msg for depositing base denom into the counterparty vault on behalf of an account
+    pub fn deposit_msg(
+        &self,
+        account_id: &str,
+        coin: &Coin,
+        max_shares_receivable: Option<Uint128>,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().into(),
+            msg: to_json_binary(&ExecuteMsg::Deposit {
+                account_id: Some(account_id.to_string()),
+                max_shares_receivable,
+            })?,
+            funds: vec![coin.clone()],
+        }))
+    }
+
+    /// Generate a msg for unlocking vault shares of an account
+    pub fn unlock_msg(&self, account_id: &str, shares: Uint128) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().into(),
+            msg: to_json_binary(&ExecuteMsg::Unlock {
+                account_id: Some(account_id.to_string()),
+                shares,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    /// Generate a msg for withdrawing the unlocked vault shares of an account
+    pub fn withdraw_msg(
+        &self,
+        account_id: &str,
+        min_receive: Option<Uint128>,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().into(),
+            msg: to_json_binary(&ExecuteMsg::Withdraw {
+                account_id: Some(account_id.to_string()),
+                min_receive,
+            })?,
+            funds: vec![],
+        }))
+    }
+
+    /// Generate a msg for executing a perp order. `funds` has to cover the realized loss (if any).
+    pub fn execute_perp_order_msg(
+        &self,
+        account_id: &str,
+        denom: &str,
+        size: Int128,
+        reduce_only: Option<bool>,
+        funds: Vec<Coin>,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().into(),
+            msg: to_json_binary(&ExecuteMsg::ExecuteOrder {
+                account_id: account_id.to_string(),
+                denom: denom.to_string(),
+                size,
+                reduce_only,
+            })?,
+            funds,
+        }))
+    }
+
+    /// Generate a msg for closing all perp positions of an account.
+    /// `funds` has to cover the realized loss (if any).
+    pub fn close_all_msg(
+        &self,
+        account_id: &str,
+        funds: Vec<Coin>,
+        action: ActionKind,
+    ) -> StdResult<CosmosMsg> {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().into(),
+            msg: to_json_binary(&ExecuteMsg::CloseAllPositions {
+                account_id: account_id.to_string(),
+                action: Some(action),
+            })?,
+            funds,
+        }))
+    }
+
+    pub fn query_config(&self, querier: &QuerierWrapper) -> StdResult<Config<Addr>> {
+        querier.query_wasm_smart(self.address(), &QueryMsg::Config {})
+    }
+
+    /// Query a position. If `order_size` is provided, the returned unrealized PnL is the amount
+    /// realized by executing that order, otherwise by closing the position.
+    pub fn query_position(
+        &self,
+        querier: &QuerierWrapper,
+        account_id: &str,
+        denom: &str,
+        order_size: Option<Int128>,
+        reduce_only: Option<bool>,
+    ) -> StdResult<PositionResponse> {
+        querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::Position {
+                account_id: account_id.to_string(),
+                denom: denom.to_string(),
+                order_size,
+                reduce_only,
+            },
+        )
+    }
+
+    pub fn query_positions_by_account(
+        &self,
+        querier: &QuerierWrapper,
+        account_id: &str,
+        action: ActionKind,
+    ) -> StdResult<PositionsByAccountResponse> {
+        querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::PositionsByAccount {
+                account_id: account_id.to_string(),
+                action: Some(action),
+            },
+        )
+    }
+
+    pub fn query_opening_fee(
+        &self,
+        querier: &QuerierWrapper,
+        denom: &str,
+        size: Int128,
+    ) -> StdResult<TradingFee> {
+        querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::OpeningFee {
+                denom: denom.to_string(),
+                size,
+            },
+        )
+    }
+
+    pub fn query_vault_position(
+        &self,
+        querier: &QuerierWrapper,
+        credit_manager: &Addr,
+        account_id: &str,
+    ) -> StdResult<Option<VaultPositionResponse>> {
+        querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::VaultPosition {
+                user_address: credit_manager.to_string(),
+                account_id: Some(account_id.to_string()),
+            },
+        )
+    }
+}
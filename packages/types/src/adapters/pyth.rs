@@ -0,0 +1,66 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Api, Binary, Coin, CosmosMsg, QuerierWrapper, StdResult, WasmMsg,
+};
+
+use crate::pyth::{ExecuteMsg, QueryMsg};
+
+#[cw_serde]
+pub struct PythBase<T>(T);
+
+impl<T> PythBase<T> {
+    pub fn new(address: T) -> PythBase<T> {
+        PythBase(address)
+    }
+
+    pub fn address(&self) -> &T {
+        &self.0
+    }
+}
+
+pub type PythUnchecked = PythBase<String>;
+pub type Pyth = PythBase<Addr>;
+
+impl From<Pyth> for PythUnchecked {
+    fn from(pyth: Pyth) -> Self {
+        Self(pyth.address().to_string())
+    }
+}
+
+impl PythUnchecked {
+    pub fn check(&self, api: &dyn Api) -> StdResult<Pyth> {
+        Ok(PythBase::new(api.addr_validate(self.address())?))
+    }
+}
+
+impl Pyth {
+    /// Fee required by the Pyth contract to update the price feeds with the VAAs
+    pub fn query_update_fee(
+        &self,
+        querier: &QuerierWrapper,
+        vaa_data: &[Binary],
+    ) -> StdResult<Coin> {
+        querier.query_wasm_smart(
+            self.address(),
+            &QueryMsg::GetUpdateFee {
+                vaas: vaa_data.to_vec(),
+            },
+        )
+    }
+
+    /// Generate a msg updating the price feeds with the VAAs, paying the fee
+    pub fn update_price_feeds_msg(&self, vaa_data: Vec<Binary>, fee: Coin) -> StdResult<CosmosMsg> {
+        let funds = if fee.amount.is_zero() {
+            vec![]
+        } else {
+            vec![fee]
+        };
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.address().to_string(),
+            msg: to_json_binary(&ExecuteMsg::UpdatePriceFeeds {
+                data: vaa_data,
+            })?,
+            funds,
+        }))
+    }
+}
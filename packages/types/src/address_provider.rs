@@ -39,6 +39,10 @@ pub enum MarsAddressType {
     AstroportIncentives,
     /// The address that shall receive the revenue share given to neutron (10%)
     RevenueShare,
+    /// Perps contract
+    Perps,
+    /// Pyth oracle contract, receiving the price updates pushed by the users
+    Pyth,
 }
 
 impl fmt::Display for MarsAddressType {
@@ -56,6 +60,8 @@ impl fmt::Display for MarsAddressType {
             MarsAddressType::Swapper => "swapper",
             MarsAddressType::AstroportIncentives => "astroport_incentives",
             MarsAddressType::RevenueShare => "revenue_share",
+            MarsAddressType::Perps => "perps",
+            MarsAddressType::Pyth => "pyth",
         };
         write!(f, "{s}")
     }
@@ -78,6 +84,8 @@ impl FromStr for MarsAddressType {
             "swapper" => Ok(MarsAddressType::Swapper),
             "astroport_incentives" => Ok(MarsAddressType::AstroportIncentives),
             "revenue_share" => Ok(MarsAddressType::RevenueShare),
+            "perps" => Ok(MarsAddressType::Perps),
+            "pyth" => Ok(MarsAddressType::Pyth),
             _ => Err(StdError::parse_err(type_name::<Self>(), s)),
         }
     }
@@ -0,0 +1,25 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Uint128};
+
+/// Cumulative totals of an account since stats recording has been enabled in the config.
+/// Only aggregates are kept, one coin per denom and category.
+#[cw_serde]
+#[derive(Default)]
+pub struct AccountStats {
+    /// Coins deposited from a wallet
+    pub deposited: Vec<Coin>,
+    /// Coins withdrawn to a wallet, including refunds
+    pub withdrawn: Vec<Coin>,
+    /// Part of the repaid debt exceeding the borrowed principal
+    pub interest_paid: Vec<Coin>,
+    /// Swap fees sent to the rewards-collector
+    pub swap_fees_paid: Vec<Coin>,
+    /// Value of the collateral seized by liquidators in excess of the debt they repaid, in the
+    /// base denom of the oracle. Includes the protocol fee.
+    pub liquidation_losses: Uint128,
+    /// Incentive rewards claimed, including rewards of staked Astroport LPs
+    pub rewards_claimed: Vec<Coin>,
+    /// Outstanding borrowed principal, used to split repayments into principal and interest.
+    /// Debt taken before recording started is counted as principal in full.
+    pub debt_principal: Vec<Coin>,
+}
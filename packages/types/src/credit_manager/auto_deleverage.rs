@@ -0,0 +1,28 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Decimal;
+
+use crate::swapper::SwapperRoute;
+
+/// Protective policy of an account: once its liquidation health factor drops below
+/// `trigger_health_factor`, a keeper can sell the collateral for the debt denom and repay the
+/// debt until the health factor is back at `target_health_factor`.
+#[cw_serde]
+pub struct AutoDeleveragePolicy {
+    /// Liquidation health factor below which the policy can be executed
+    pub trigger_health_factor: Decimal,
+    /// Liquidation health factor the account is brought back to
+    pub target_health_factor: Decimal,
+    /// Deposited coin sold to repay the debt
+    pub collateral_denom: String,
+    /// Debt repaid with the swapped collateral
+    pub debt_denom: String,
+    /// Slippage allowed when swapping the collateral. Can't exceed the max slippage of the config.
+    pub slippage: Decimal,
+    pub route: Option<SwapperRoute>,
+}
+
+#[cw_serde]
+pub struct AutoDeleveragePolicyResponse {
+    pub account_id: String,
+    pub policy: AutoDeleveragePolicy,
+}
@@ -0,0 +1,188 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::Coin;
+use strum::Display;
+
+use super::{Action, ActionCoin};
+
+/// Group of actions a delegate can be allowed to dispatch on behalf of an account
+#[cw_serde]
+#[derive(Copy, Eq, Hash, Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum AccountPermission {
+    /// Deposit and UpdatePythPrices
+    Deposit,
+    /// Withdraw, WithdrawToWallet, TransferToAccount and RefundAllCoinBalances.
+    /// Withdraw sends the coins to the delegate, RefundAllCoinBalances to the account owner.
+    Withdraw,
+    Borrow,
+    /// Repay debt of the account or of other accounts of the same owner.
+    /// Repaying the debt of an account of another owner requires the Withdraw permission as well.
+    Repay,
+    /// Lend and Reclaim
+    Lend,
+    /// ClaimRewards and ClaimAstroLpRewards
+    ClaimRewards,
+    Swap,
+    /// ProvideLiquidity, WithdrawLiquidity, StakeAstroLp and UnstakeAstroLp
+    Liquidity,
+    /// EnterVault, ExitVault, RequestVaultUnlock and ExitVaultUnlocked
+    Vault,
+    /// Liquidate and FlashLiquidate
+    Liquidate,
+    /// DepositToPerpVault, UnlockFromPerpVault and WithdrawFromPerpVault
+    PerpVault,
+    ExecutePerpOrder,
+    /// CreateTriggerOrder and DeleteTriggerOrder. The actions of a new order have to be
+    /// allowed by the delegation as well.
+    TriggerOrder,
+    SetAutoDeleveragePolicy,
+}
+
+impl Action {
+    /// Permission a delegate needs to dispatch the action
+    pub fn permission(&self) -> AccountPermission {
+        match self {
+            Action::Deposit(..)
+            | Action::UpdatePythPrices {
+                ..
+            } => AccountPermission::Deposit,
+            Action::Withdraw(..)
+            | Action::WithdrawToWallet {
+                ..
+            }
+            | Action::TransferToAccount {
+                ..
+            }
+            | Action::RefundAllCoinBalances {} => AccountPermission::Withdraw,
+            Action::Borrow(..) => AccountPermission::Borrow,
+            Action::Repay {
+                ..
+            } => AccountPermission::Repay,
+            Action::Lend(..) | Action::Reclaim(..) => AccountPermission::Lend,
+            Action::ClaimRewards {}
+            | Action::ClaimAstroLpRewards {
+                ..
+            } => AccountPermission::ClaimRewards,
+            Action::SwapExactIn {
+                ..
+            } => AccountPermission::Swap,
+            Action::ProvideLiquidity {
+                ..
+            }
+            | Action::WithdrawLiquidity {
+                ..
+            }
+            | Action::StakeAstroLp {
+                ..
+            }
+            | Action::UnstakeAstroLp {
+                ..
+            } => AccountPermission::Liquidity,
+            Action::EnterVault {
+                ..
+            }
+            | Action::ExitVault {
+                ..
+            }
+            | Action::RequestVaultUnlock {
+                ..
+            }
+            | Action::ExitVaultUnlocked {
+                ..
+            } => AccountPermission::Vault,
+            Action::Liquidate {
+                ..
+            }
+            | Action::FlashLiquidate {
+                ..
+            } => AccountPermission::Liquidate,
+            Action::DepositToPerpVault {
+                ..
+            }
+            | Action::UnlockFromPerpVault {
+                ..
+            }
+            | Action::WithdrawFromPerpVault {
+                ..
+            } => AccountPermission::PerpVault,
+            Action::ExecutePerpOrder {
+                ..
+            } => AccountPermission::ExecutePerpOrder,
+            Action::CreateTriggerOrder {
+                ..
+            }
+            | Action::DeleteTriggerOrder {
+                ..
+            } => AccountPermission::TriggerOrder,
+            Action::SetAutoDeleveragePolicy {
+                ..
+            } => AccountPermission::SetAutoDeleveragePolicy,
+        }
+    }
+
+    /// Coins taken out of the account's deposits (or borrowed) by the action, which count towards
+    /// the denom limits of a delegation
+    pub fn spent_coins(&self) -> Vec<ActionCoin> {
+        match self {
+            Action::Borrow(coin) => vec![ActionCoin::from(coin)],
+            Action::Withdraw(coin)
+            | Action::WithdrawToWallet {
+                coin,
+                ..
+            }
+            | Action::TransferToAccount {
+                coin,
+                ..
+            }
+            | Action::Lend(coin)
+            | Action::Repay {
+                coin,
+                ..
+            }
+            | Action::EnterVault {
+                coin,
+                ..
+            }
+            | Action::DepositToPerpVault {
+                coin,
+                ..
+            } => vec![coin.clone()],
+            Action::SwapExactIn {
+                coin_in,
+                ..
+            } => vec![coin_in.clone()],
+            Action::ProvideLiquidity {
+                coins_in,
+                ..
+            } => coins_in.clone(),
+            Action::CreateTriggerOrder {
+                keeper_fee,
+                ..
+            } => vec![ActionCoin::from(keeper_fee)],
+            _ => vec![],
+        }
+    }
+}
+
+/// Actions a delegate is allowed to dispatch on behalf of an account through `UpdateCreditAccount`
+#[cw_serde]
+pub struct Delegation {
+    pub permissions: Vec<AccountPermission>,
+    /// Timestamp (in seconds) from which the delegation can no longer be used
+    pub expires_at: Option<u64>,
+    /// Maximum amounts of the denoms the delegate can withdraw, transfer, borrow, repay, lend,
+    /// swap, put into pools, vaults and the perp vault or pay as keeper fees, in total across all
+    /// its transactions.
+    /// Reduced as the delegation is used. Denoms without a limit can be used without restrictions.
+    pub denom_limits: Vec<Coin>,
+}
+
+#[cw_serde]
+pub struct DelegationResponse {
+    pub account_id: String,
+    pub delegate: String,
+    /// Owner of the account at the time of the grant. The delegation is inactive once the
+    /// account has been transferred to someone else.
+    pub granter: String,
+    pub delegation: Delegation,
+}
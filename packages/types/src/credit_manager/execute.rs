@@ -1,10 +1,12 @@
 use std::collections::BTreeMap;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{to_json_binary, Addr, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Int128, StdResult, Uint128, WasmMsg,
+};
 use mars_owner::OwnerUpdate;
 
-use super::ConfigUpdates;
+use super::{AutoDeleveragePolicy, Condition, ConfigUpdates, Delegation};
 use crate::{
     account_nft::NftConfigUpdates,
     adapters::vault::{Vault, VaultPositionType, VaultUnchecked},
@@ -25,11 +27,29 @@ pub enum ExecuteMsg {
         account_kind: Option<AccountKind>,
         actions: Vec<Action>,
     },
+    /// Update several credit accounts of the sender in one go: `(account_id, actions)` pairs
+    /// dispatched in order. The health of every account is asserted once all actions are executed,
+    /// so coins can be moved between the accounts with `TransferToAccount`.
+    UpdateCreditAccounts {
+        updates: Vec<(String, Vec<Action>)>,
+    },
     /// Repay debt on behalf of an account, funded from wallet. Must send exactly one coin in message funds.
     /// Allows repaying debts of assets that have been de-listed from credit manager.
     RepayFromWallet {
         account_id: String,
     },
+    /// Allow `delegate` to dispatch actions on behalf of the account, replacing any previous
+    /// delegation. Only callable by the account owner.
+    GrantDelegation {
+        account_id: String,
+        delegate: String,
+        delegation: Delegation,
+    },
+    /// Remove the delegation of `delegate`. Callable by the account owner and the delegate.
+    RevokeDelegation {
+        account_id: String,
+        delegate: String,
+    },
 
     //--------------------------------------------------------------------------------------------------
     // Privileged messages
@@ -47,6 +67,24 @@ pub enum ExecuteMsg {
     },
     /// Internal actions only callable by the contract itself
     Callback(CallbackMsg),
+    /// Credit the profit of a deleveraged perp position to the account.
+    /// Only callable by the perps contract, the profit is sent along with the message.
+    UpdateBalanceAfterDeleverage {
+        account_id: String,
+        pnl: Int128,
+    },
+    /// Execute a trigger order of an account once all its conditions are met.
+    /// Callable by anyone, the keeper fee of the order is sent to the sender.
+    ExecuteTriggerOrder {
+        account_id: String,
+        trigger_order_id: String,
+    },
+    /// Execute the auto-deleverage policy of an account whose liquidation health factor dropped
+    /// below the policy's trigger. Callable by anyone, a share of the sold collateral is sent to
+    /// the sender as keeper fee.
+    AutoDeleverage {
+        account_id: String,
+    },
 }
 
 #[cw_serde]
@@ -114,6 +152,11 @@ pub enum LiquidateRequest<T> {
     /// Pay back debt of a liquidatable credit manager account for a via liquidating an Astro LP position.
     /// LP shares are transfered from the liquidatable to the liquidator.
     StakedAstroLp(String),
+    /// Pay back debt of a liquidatable credit manager account via closing a perp position.
+    /// The realized PnL is settled with the liquidatee's perps base denom balance (a loss has to be
+    /// covered by it). The liquidator then receives base denom, similar to the `Deposit` msg.
+    /// If the debt coin amount is zero, the position is only closed.
+    Perp(String),
 }
 
 /// The list of actions that users can perform on their positions
@@ -128,6 +171,11 @@ pub enum Action {
         coin: ActionCoin,
         recipient: String,
     },
+    /// Move coin of specified denom and amount to another credit account of the same owner
+    TransferToAccount {
+        account_id: String,
+        coin: ActionCoin,
+    },
     /// Borrow coin of specified amount from Red Bank
     Borrow(Coin),
     /// Lend coin to the Red Bank
@@ -175,6 +223,18 @@ pub enum Action {
         /// Position details to be liquidated
         request: LiquidateRequest<VaultUnchecked>,
     },
+    /// Liquidate without holding the debt coin upfront. The debt coin is borrowed, the liquidatee
+    /// is liquidated with it, the seized collateral is swapped to the debt denom via `route`
+    /// and the borrowed amount is repaid, all within the same dispatch.
+    /// Fails if the liquidator's profit (in the debt denom) is below `min_profit`.
+    FlashLiquidate {
+        liquidatee_account_id: String,
+        debt_coin: Coin,
+        request: LiquidateRequest<VaultUnchecked>,
+        /// Route for swapping the seized collateral. Not used if it is in the debt denom.
+        route: Option<SwapperRoute>,
+        min_profit: Uint128,
+    },
     /// Perform a swapper with an exact-in amount. Requires slippage allowance %.
     /// If `coin_in.amount: AccountBalance`, the accounts entire balance of `coin_in.denom` will be used.
     SwapExactIn {
@@ -211,6 +271,51 @@ pub enum Action {
     },
     /// Refunds all coin balances back to user wallet
     RefundAllCoinBalances {},
+    /// Deposit base denom into the perps counterparty vault
+    DepositToPerpVault {
+        coin: ActionCoin,
+        max_receivable_shares: Option<Uint128>,
+    },
+    /// Start the cooldown period for the given amount of perps vault shares
+    UnlockFromPerpVault {
+        shares: Uint128,
+    },
+    /// Withdraw all unlocked perps vault shares whose cooldown period has ended
+    WithdrawFromPerpVault {
+        min_receive: Option<Uint128>,
+    },
+    /// Open, increase, reduce, close or flip a perp position by `order_size`.
+    /// Realized losses are paid from the account balance in the perps base denom.
+    ExecutePerpOrder {
+        denom: String,
+        order_size: Int128,
+        reduce_only: Option<bool>,
+    },
+    /// Register actions to be dispatched by a keeper once all conditions are met.
+    /// The keeper fee is taken from the account balance and refunded if the order is deleted.
+    /// Only Borrow, Lend, Reclaim, Repay (to the account itself), SwapExactIn and
+    /// ExecutePerpOrder actions are allowed.
+    CreateTriggerOrder {
+        actions: Vec<Action>,
+        conditions: Vec<Condition>,
+        keeper_fee: Coin,
+        /// Timestamp (in seconds) from which the order can no longer be executed
+        expires_at: Option<u64>,
+    },
+    /// Delete a trigger order of the account, refunding its keeper fee
+    DeleteTriggerOrder {
+        trigger_order_id: String,
+    },
+    /// Set the auto-deleverage policy of the account, `None` removes it
+    SetAutoDeleveragePolicy {
+        policy: Option<AutoDeleveragePolicy>,
+    },
+    /// Push fresh Pyth prices (VAAs) on chain before the other actions of the transaction are
+    /// dispatched, so they are not blocked by stale prices. The update fee is sent with the
+    /// message on top of the deposited coins. Can only be used in `UpdateCreditAccount`.
+    UpdatePythPrices {
+        vaa_data: Vec<Binary>,
+    },
 }
 
 /// Internal actions made by the contract with pre-validated inputs
@@ -237,12 +342,24 @@ pub enum CallbackMsg {
         account_id: String,
         coin: ActionCoin,
     },
+    /// Move coin from one account's balance to another's
+    TransferToAccount {
+        account_id: String,
+        recipient_account_id: String,
+        coin: ActionCoin,
+    },
     /// Benefactor account repays debt on behalf of recipient
     RepayForRecipient {
         benefactor_account_id: String,
         recipient_account_id: String,
         coin: ActionCoin,
     },
+    /// Repay debt with the amount of `denom` received since `previous_balance`
+    RepayReceived {
+        account_id: String,
+        denom: String,
+        previous_balance: Uint128,
+    },
     /// Lend coin to the Red Bank
     Lend {
         account_id: String,
@@ -311,6 +428,30 @@ pub enum CallbackMsg {
         debt_coin: Coin,
         request: LiquidateRequest<Vault>,
     },
+    /// Borrow the debt coin, liquidate, swap the seized collateral and repay the borrowed amount
+    FlashLiquidate {
+        liquidator_account_id: String,
+        liquidatee_account_id: String,
+        debt_coin: Coin,
+        request: LiquidateRequest<Vault>,
+        route: Option<SwapperRoute>,
+        min_profit: Uint128,
+    },
+    /// Swap the amount of `denom_in` received since `previous_balance` into `denom_out`
+    SwapLiquidatedCollateral {
+        account_id: String,
+        denom_in: String,
+        previous_balance: Uint128,
+        denom_out: String,
+        route: Option<SwapperRoute>,
+    },
+    /// Assert the balance of the debt denom, net of any debt increase, grew by at least `min_profit`
+    AssertFlashLiquidationProfit {
+        account_id: String,
+        previous_balance: Coin,
+        previous_debt: Uint128,
+        min_profit: Uint128,
+    },
     /// Perform a swapper with an exact-in amount. Requires slippage allowance %.
     /// If `coin_in.amount: AccountBalance`, the accounts entire balance of `coin_in.denom` will be used.
     SwapExactIn {
@@ -383,6 +524,52 @@ pub enum CallbackMsg {
     /// At the end of the execution of dispatched actions, this callback removes the guard
     /// and allows subsequent dispatches.
     RemoveReentrancyGuard {},
+    /// Deposit base denom into the perps counterparty vault
+    DepositToPerpVault {
+        account_id: String,
+        coin: ActionCoin,
+        max_receivable_shares: Option<Uint128>,
+    },
+    /// Unlock perps vault shares
+    UnlockFromPerpVault {
+        account_id: String,
+        shares: Uint128,
+    },
+    /// Withdraw unlocked perps vault shares
+    WithdrawFromPerpVault {
+        account_id: String,
+        min_receive: Option<Uint128>,
+    },
+    /// Execute a perp order and settle its realized PnL with the account balance
+    ExecutePerpOrder {
+        account_id: String,
+        denom: String,
+        order_size: Int128,
+        reduce_only: Option<bool>,
+    },
+    CreateTriggerOrder {
+        account_id: String,
+        actions: Vec<Action>,
+        conditions: Vec<Condition>,
+        keeper_fee: Coin,
+        expires_at: Option<u64>,
+    },
+    DeleteTriggerOrder {
+        account_id: String,
+        trigger_order_id: String,
+    },
+    SetAutoDeleveragePolicy {
+        account_id: String,
+        policy: Option<AutoDeleveragePolicy>,
+    },
+    /// Dispatch the actions of an account once the Pyth prices have been updated.
+    /// `funds` were received with the original message, net of the update fee.
+    DispatchActions {
+        account_id: String,
+        sender: Addr,
+        actions: Vec<Action>,
+        funds: Vec<Coin>,
+    },
 }
 
 impl CallbackMsg {
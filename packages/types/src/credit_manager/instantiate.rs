@@ -1,10 +1,12 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Decimal, Uint128};
 
+use super::KeeperFeeConfig;
 use crate::adapters::{
     account_nft::AccountNftUnchecked, health::HealthContractUnchecked,
     incentives::IncentivesUnchecked, oracle::OracleUnchecked, params::ParamsUnchecked,
-    red_bank::RedBankUnchecked, swapper::SwapperUnchecked, zapper::ZapperUnchecked,
+    perps::PerpsUnchecked, pyth::PythUnchecked, red_bank::RedBankUnchecked,
+    swapper::SwapperUnchecked, zapper::ZapperUnchecked,
 };
 
 #[cw_serde]
@@ -53,4 +55,12 @@ pub struct ConfigUpdates {
     /// The Mars Protocol rewards-collector contract. We collect protocol fee for its account.
     pub rewards_collector: Option<String>,
     pub swap_fee: Option<Decimal>,
+    /// The Mars Protocol perps contract. Required for perp positions and perps vault deposits.
+    pub perps: Option<PerpsUnchecked>,
+    /// Required for creating trigger orders
+    pub keeper_fee_config: Option<KeeperFeeConfig>,
+    /// Enables recording the cumulative stats of each account, see `QueryMsg::AccountStats`
+    pub account_stats_enabled: Option<bool>,
+    /// The Pyth contract. Required for the `UpdatePythPrices` action.
+    pub pyth: Option<PythUnchecked>,
 }
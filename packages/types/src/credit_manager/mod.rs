@@ -1,11 +1,19 @@
+mod account_stats;
+mod auto_deleverage;
+mod delegation;
 mod execute;
 mod instantiate;
 mod migrate;
 mod query;
 mod reply;
+mod trigger;
 
+pub use account_stats::*;
+pub use auto_deleverage::*;
+pub use delegation::*;
 pub use execute::*;
 pub use instantiate::*;
 pub use migrate::*;
 pub use query::*;
 pub use reply::*;
+pub use trigger::*;
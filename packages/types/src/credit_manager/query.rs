@@ -2,12 +2,17 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Coin, Decimal, Uint128};
 use mars_owner::OwnerResponse;
 
+use super::{
+    AccountStats, Action, AutoDeleveragePolicy, AutoDeleveragePolicyResponse, DelegationResponse,
+    KeeperFeeConfig, LiquidateRequest, TriggerOrderResponse,
+};
 use crate::{
     adapters::{
         rewards_collector::RewardsCollector,
         vault::{Vault, VaultPosition, VaultUnchecked},
     },
-    health::AccountKind,
+    health::{AccountKind, HealthValuesResponse},
+    perps::PerpPosition,
     traits::Coins,
 };
 
@@ -18,6 +23,17 @@ pub enum QueryMsg {
     AccountKind {
         account_id: String,
     },
+    /// Block height of the last borrow of the account, `None` if it never borrowed
+    #[returns(Option<u64>)]
+    LastBorrowHeight {
+        account_id: String,
+    },
+    /// Cumulative deposits, withdrawals, interest, fees, liquidation losses and rewards of the
+    /// account. Empty if stats recording is disabled in the config.
+    #[returns(AccountStats)]
+    AccountStats {
+        account_id: String,
+    },
     #[returns(Vec<Account>)]
     Accounts {
         owner: String,
@@ -98,6 +114,59 @@ pub enum QueryMsg {
     },
     #[returns(Decimal)]
     SwapFeeRate {},
+    /// Enumerate the trigger orders of an account; start_after accepts order id
+    #[returns(cw_paginate::PaginationResponse<TriggerOrderResponse>)]
+    AccountTriggerOrders {
+        account_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Enumerate all trigger orders; start_after accepts (account_id, order_id)
+    #[returns(cw_paginate::PaginationResponse<TriggerOrderResponse>)]
+    AllTriggerOrders {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<AutoDeleveragePolicy>)]
+    AutoDeleveragePolicy {
+        account_id: String,
+    },
+    /// Enumerate the auto-deleverage policies of all accounts; start_after accepts account id
+    #[returns(cw_paginate::PaginationResponse<AutoDeleveragePolicyResponse>)]
+    AllAutoDeleveragePolicies {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Active delegation of `delegate` on the account, if any
+    #[returns(Option<DelegationResponse>)]
+    Delegation {
+        account_id: String,
+        delegate: String,
+    },
+    /// Enumerate the active delegations of an account; start_after accepts delegate address
+    #[returns(cw_paginate::PaginationResponse<DelegationResponse>)]
+    AccountDelegations {
+        account_id: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Project the positions and health of an account after the actions, without executing them.
+    /// Swaps and provided liquidity are estimated by the swapper and zapper.
+    /// Only Deposit, Withdraw, WithdrawToWallet, Borrow, Repay (to the account itself), Lend,
+    /// Reclaim, SwapExactIn and ProvideLiquidity actions can be simulated.
+    #[returns(SimulateActionsResponse)]
+    SimulateActions {
+        account_id: String,
+        actions: Vec<Action>,
+    },
+    /// Estimate the liquidation of an account, without executing it. Fails the same way the
+    /// `Liquidate` action would. Perp requests can not be estimated.
+    #[returns(crate::liquidation::EstimateLiquidationResponse)]
+    EstimateLiquidation {
+        account_id: String,
+        debt_coin: Coin,
+        request: LiquidateRequest<VaultUnchecked>,
+    },
 }
 
 #[cw_serde]
@@ -161,6 +230,35 @@ pub struct Positions {
     pub lends: Vec<Coin>,
     pub vaults: Vec<VaultPosition>,
     pub staked_astro_lps: Vec<Coin>,
+    pub perps: Vec<PerpPosition>,
+}
+
+#[cw_serde]
+pub struct SimulateActionsResponse {
+    /// Positions of the account once the actions are applied
+    pub positions: Positions,
+    /// Health of the projected positions
+    pub health: HealthValuesResponse,
+    /// Checks made at the end of `UpdateCreditAccount` that would reject the actions.
+    /// Empty if the actions would go through.
+    pub rejections: Vec<ActionsRejection>,
+}
+
+#[cw_serde]
+pub enum ActionsRejection {
+    /// `AssertMaxLTV` would fail: the account ends up above max LTV and its health weakened
+    AboveMaxLtv {
+        max_ltv_health_factor: Option<Decimal>,
+    },
+    /// The total deposits of a denom would exceed its deposit cap
+    AboveDepositCap {
+        new_value: Coin,
+        maximum: Uint128,
+    },
+    /// The positions would break the rules of the HLS account kind
+    HlsRules {
+        reason: String,
+    },
 }
 
 #[cw_serde]
@@ -189,6 +287,10 @@ pub struct ConfigResponse {
     pub zapper: String,
     pub health_contract: String,
     pub rewards_collector: Option<RewardsCollector>,
+    pub perps: Option<String>,
+    pub keeper_fee_config: Option<KeeperFeeConfig>,
+    pub account_stats_enabled: bool,
+    pub pyth: Option<String>,
 }
 
 #[cw_serde]
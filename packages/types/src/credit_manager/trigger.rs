@@ -0,0 +1,76 @@
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal};
+
+use super::Action;
+
+#[cw_serde]
+pub enum Comparison {
+    GreaterThan,
+    LessThan,
+}
+
+impl Comparison {
+    /// Returns true if `value` crossed the `threshold` in the direction of the comparison
+    pub fn is_met(&self, value: Decimal, threshold: Decimal) -> bool {
+        match self {
+            Comparison::GreaterThan => value > threshold,
+            Comparison::LessThan => value < threshold,
+        }
+    }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let comparison = match self {
+            Comparison::GreaterThan => "greater_than",
+            Comparison::LessThan => "less_than",
+        };
+        write!(f, "{comparison}")
+    }
+}
+
+#[cw_serde]
+pub enum Condition {
+    /// Oracle price of the denom compared to `price`
+    OraclePrice {
+        denom: String,
+        price: Decimal,
+        comparison: Comparison,
+    },
+    /// Liquidation health factor of the account compared to `threshold`.
+    /// An account without debt has an infinite health factor.
+    HealthFactor {
+        threshold: Decimal,
+        comparison: Comparison,
+    },
+}
+
+#[cw_serde]
+pub struct TriggerOrder {
+    pub order_id: String,
+    /// Actions dispatched on behalf of the account once all conditions are met
+    pub actions: Vec<Action>,
+    pub conditions: Vec<Condition>,
+    /// Paid out to the keeper executing the order. Held by the contract until then.
+    pub keeper_fee: Coin,
+    /// Timestamp (in seconds) from which the order can no longer be executed
+    pub expires_at: Option<u64>,
+}
+
+#[cw_serde]
+pub struct TriggerOrderResponse {
+    pub account_id: String,
+    pub order: TriggerOrder,
+}
+
+#[cw_serde]
+pub struct KeeperFeeConfig {
+    /// Minimum keeper fee of a trigger order. Keeper fees have to be paid in this denom.
+    pub min_fee: Coin,
+    /// Share of the collateral sold by an auto-deleverage that is paid to the keeper.
+    /// Meant to be well below the liquidation bonus.
+    #[serde(default)]
+    pub auto_deleverage_fee_rate: Decimal,
+}
@@ -5,6 +5,8 @@ use cosmwasm_std::{Addr, Decimal};
 #[cfg(feature = "javascript")]
 use tsify::Tsify;
 
+use crate::credit_manager::Action;
+
 #[cw_serde]
 pub enum AccountKind {
     Default,
@@ -58,6 +60,16 @@ impl Slippage {
     }
 }
 
+/// Actions to apply to the positions of an account, in the order they are sent to the credit
+/// manager, along with the fee the credit manager takes on swaps
+#[cw_serde]
+#[cfg_attr(feature = "javascript", derive(Tsify))]
+#[cfg_attr(feature = "javascript", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ActionsToApply {
+    pub actions: Vec<Action>,
+    pub swap_fee: Decimal,
+}
+
 #[cw_serde]
 #[cfg_attr(feature = "javascript", derive(Tsify))]
 #[cfg_attr(feature = "javascript", tsify(into_wasm_abi, from_wasm_abi))]
@@ -1,12 +1,25 @@
-use cosmwasm_std::{CheckedFromRatioError, CheckedMultiplyFractionError, OverflowError, StdError};
+use cosmwasm_std::{
+    CheckedFromRatioError, CheckedMultiplyFractionError, OverflowError, StdError, Uint128,
+};
 use cw2::VersionError;
 use mars_owner::OwnerError;
 use thiserror::Error;
 
+use crate::adapters::vault::VaultError;
+
 pub type HealthResult<T> = Result<T, HealthError>;
 
 #[derive(Error, Debug, PartialEq)]
 pub enum HealthError {
+    #[error(
+        "Estimated amount of {denom} received {estimated} is below the minimum of {min_receive}"
+    )]
+    BelowMinReceive {
+        denom: String,
+        estimated: Uint128,
+        min_receive: Uint128,
+    },
+
     #[error("{0}")]
     CheckedFromRatio(#[from] CheckedFromRatioError),
 
@@ -30,6 +43,9 @@ pub enum HealthError {
     #[error("{0} was not provided asset params to compute health with")]
     MissingParams(String),
 
+    #[error("{0} was not provided perp params to compute health with")]
+    MissingPerpParams(String),
+
     #[error("{0} was not provided a price to compute health with")]
     MissingPrice(String),
 
@@ -48,9 +64,15 @@ pub enum HealthError {
     #[error("{0}")]
     Owner(#[from] OwnerError),
 
+    #[error("{0} action can not be applied to the positions")]
+    UnsupportedAction(String),
+
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Vault(#[from] VaultError),
+
     #[error("{0}")]
     Version(#[from] VersionError),
 }
@@ -2,7 +2,7 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use mars_owner::{OwnerResponse, OwnerUpdate};
 
 use super::AccountKind;
-use crate::oracle::ActionKind;
+use crate::{credit_manager::Positions, oracle::ActionKind};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -41,6 +41,14 @@ pub enum QueryMsg {
         kind: AccountKind,
         action: ActionKind,
     },
+    /// Returns all values that comprise health for the given positions instead of the stored ones.
+    /// Used by the credit manager to compute the health of simulated positions.
+    #[returns(super::HealthValuesResponse)]
+    HealthValuesForPositions {
+        positions: Positions,
+        kind: AccountKind,
+        action: ActionKind,
+    },
     #[returns(ConfigResponse)]
     Config {},
 }
@@ -6,8 +6,11 @@ pub mod error;
 pub mod health;
 pub mod incentives;
 pub mod keys;
+pub mod liquidation;
 pub mod oracle;
 pub mod params;
+pub mod perps;
+pub mod pyth;
 pub mod red_bank;
 pub mod rewards_collector;
 pub mod swapper;
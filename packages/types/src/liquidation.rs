@@ -0,0 +1,20 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Coin, Decimal};
+
+/// Outcome of a liquidation, as it would be executed in the current block
+#[cw_serde]
+pub struct EstimateLiquidationResponse {
+    /// Debt repaid by the liquidator. Can be less than requested, see `calculate_liquidation` in
+    /// the `mars-liquidation` package.
+    pub debt_repaid: Coin,
+    /// Collateral taken from the liquidatee, liquidation bonus included
+    pub collateral_liquidated: Coin,
+    /// Collateral received by the liquidator, the protocol fee deducted
+    pub collateral_received: Coin,
+    /// Part of the liquidation bonus charged by the protocol
+    pub protocol_fee: Coin,
+    pub liquidation_bonus: Decimal,
+    /// Liquidation health factor of the liquidatee after the liquidation. `None` if no debt is
+    /// left.
+    pub health_factor_after: Option<Decimal>,
+}
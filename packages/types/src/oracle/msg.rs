@@ -2,6 +2,8 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Decimal, Empty};
 use mars_owner::OwnerUpdate;
 
+use super::TwapSnapshotCoverageResponse;
+
 #[cw_serde]
 pub struct InstantiateMsg<C = Empty> {
     /// The contract's owner, who can update config and price sources
@@ -37,10 +39,50 @@ pub enum ExecuteMsg<T, C = Empty> {
     UpdateConfig {
         base_denom: Option<String>,
     },
+    /// Set the circuit breaker of a coin, or remove it if `None` (only callable by owner)
+    SetCircuitBreaker {
+        denom: String,
+        circuit_breaker: Option<CircuitBreaker>,
+    },
+    /// Store the current prices of the coins as their last accepted prices. Prices which moved
+    /// outside of the circuit breaker band are not accepted. Callable by anyone.
+    ///
+    /// Only coins with a circuit breaker can be updated.
+    UpdatePrices {
+        denoms: Vec<String>,
+    },
     /// Custom messages defined by the contract
     Custom(C),
 }
 
+/// Limits how fast the price of a coin can move away from its last accepted price.
+///
+/// The allowed change grows with the time elapsed since the last accepted price, so a price
+/// which moved legitimately is accepted again after a while.
+#[cw_serde]
+pub struct CircuitBreaker {
+    /// The maximum change (percentage) of the price per second elapsed since the last accepted
+    /// price. For example 0.0001 allows the price to move by 0.6% within a minute.
+    pub max_change_per_second: Decimal,
+    /// Price used for liquidations while the circuit breaker is tripped
+    pub liquidation_fallback: LiquidationFallback,
+}
+
+#[cw_serde]
+pub enum LiquidationFallback {
+    /// Use the current price of the price source, liquidations are never halted
+    CurrentPrice,
+    /// Use the last accepted price
+    LastPrice,
+}
+
+#[cw_serde]
+pub struct LastPrice {
+    pub price: Decimal,
+    /// Timestamp (in seconds) at which the price was accepted
+    pub timestamp: u64,
+}
+
 /// Differentiator for the action (liquidate, withdraw, borrow etc.) being performed.
 #[cw_serde]
 pub enum ActionKind {
@@ -88,6 +130,20 @@ pub enum QueryMsg {
         limit: Option<u32>,
         kind: Option<ActionKind>,
     },
+    /// Query a coin's circuit breaker and whether it is tripped by the current price.
+    #[returns(CircuitBreakerResponse)]
+    CircuitBreaker {
+        denom: String,
+    },
+    /// Enumerate the TWAP snapshot coverage of the coins whose price sources use snapshots, so
+    /// operators can check whether there is enough data before switching a price source.
+    ///
+    /// NOTE: Only supported by the Wasm oracle.
+    #[returns(Vec<TwapSnapshotCoverageResponse>)]
+    TwapSnapshotCoverage {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -112,6 +168,18 @@ pub struct PriceResponse {
     pub price: Decimal,
 }
 
+#[cw_serde]
+pub struct CircuitBreakerResponse {
+    pub denom: String,
+    pub circuit_breaker: Option<CircuitBreaker>,
+    pub last_price: Option<LastPrice>,
+    /// The current price of the price source, `None` if it can't be queried
+    pub current_price: Option<Decimal>,
+    /// Whether the current price moved outside of the band around the last accepted price. Prices
+    /// for `ActionKind::Default` are rejected while the circuit breaker is tripped.
+    pub tripped: bool,
+}
+
 #[cw_serde]
 pub enum MigrateMsg {
     V1_1_0ToV2_0_0(V2Updates),
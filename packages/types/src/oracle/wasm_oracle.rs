@@ -1,5 +1,5 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Decimal256, Uint128};
 
 #[cw_serde]
 pub struct WasmOracleCustomInitParams {
@@ -12,6 +12,16 @@ pub enum WasmOracleCustomExecuteMsg {
     RecordTwapSnapshots {
         denoms: Vec<String>,
     },
+    /// Set the maximum number of TWAP snapshots kept per denom priced with the pair (only
+    /// callable by owner). Snapshots are recorded at least `tolerance` seconds apart, or further
+    /// apart if needed to keep at most `max_snapshots` within `window_size + tolerance`.
+    /// Snapshots can be at most `2 * tolerance` apart for the TWAP to stay available, so the
+    /// maximum has to be at least `ceil((window_size + tolerance) / (2 * tolerance)) + 1` for
+    /// every price source using the pair.
+    SetMaxTwapSnapshots {
+        pair_address: String,
+        max_snapshots: u32,
+    },
 }
 
 #[cw_serde]
@@ -21,3 +31,55 @@ pub struct AstroportTwapSnapshot {
     /// Cumulative price of the asset retrieved by the most recent TWAP data update
     pub price_cumulative: Uint128,
 }
+
+/// State of an Astroport PCL or StableSwap pool used for pricing its liquidity token
+#[cw_serde]
+pub struct AstroportPoolSnapshot {
+    /// Timestamp at which the pool state was recorded
+    pub timestamp: u64,
+    /// Total supply of the liquidity token
+    pub total_share: Uint128,
+    /// Curve invariant (D) of the pool
+    pub curve_invariant: Decimal256,
+    /// Price scale of the pool, only set for PCL pools
+    pub price_scale: Option<Decimal>,
+}
+
+#[cw_serde]
+pub enum TwapSnapshotKind {
+    /// Cumulative price snapshots used by Astroport TWAP and LSD price sources
+    Price,
+    /// Pool state snapshots used by liquidity token price sources with TWAP
+    Pool,
+}
+
+#[cw_serde]
+pub struct TwapSnapshotGap {
+    /// Timestamp of the snapshot before the gap
+    pub start: u64,
+    /// Timestamp of the snapshot after the gap
+    pub end: u64,
+}
+
+#[cw_serde]
+pub struct TwapSnapshotCoverageResponse {
+    pub denom: String,
+    pub kind: TwapSnapshotKind,
+    /// Address of the Astroport pair the snapshots are recorded for
+    pub pair_address: String,
+    /// The size of the sliding TWAP window in seconds
+    pub window_size: u64,
+    /// The tolerance in seconds for the sliding TWAP window
+    pub tolerance: u64,
+    /// Number of stored snapshots
+    pub snapshots: u32,
+    /// Timestamp of the oldest stored snapshot
+    pub oldest_timestamp: Option<u64>,
+    /// Timestamp of the most recent stored snapshot
+    pub latest_timestamp: Option<u64>,
+    /// Periods between two consecutive snapshots longer than `2 * tolerance`. TWAP windows
+    /// starting inside of a gap have no snapshot within tolerance, so their price can't be queried.
+    pub gaps: Vec<TwapSnapshotGap>,
+    /// Whether there are enough snapshots to query the TWAP at the current block time
+    pub sufficient: bool,
+}
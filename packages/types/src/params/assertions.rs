@@ -86,3 +86,14 @@ pub(super) fn assert_max_lb_gt_min_lb(
     }
     Ok(())
 }
+
+pub(super) fn assert_isolation_borrowable_denoms(denoms: &[String]) -> Result<(), ValidationError> {
+    if denoms.is_empty() {
+        return Err(ValidationError::InvalidParam {
+            param_name: "borrowable_denoms".to_string(),
+            invalid_value: "[]".to_string(),
+            predicate: "not empty".to_string(),
+        });
+    }
+    Ok(())
+}
@@ -7,9 +7,9 @@ use mars_utils::{
 
 use super::{
     assertions::{
-        assert_hls_lqt_gt_max_ltv, assert_lb_slope_within_range, assert_lqt_gt_max_ltv,
-        assert_max_lb_gt_min_lb, assert_max_lb_within_range, assert_min_lb_within_range,
-        assert_starting_lb_within_range,
+        assert_hls_lqt_gt_max_ltv, assert_isolation_borrowable_denoms,
+        assert_lb_slope_within_range, assert_lqt_gt_max_ltv, assert_max_lb_gt_min_lb,
+        assert_max_lb_within_range, assert_min_lb_within_range, assert_starting_lb_within_range,
     },
     hls::HlsParamsBase,
 };
@@ -25,6 +25,17 @@ pub struct CmSettings<T> {
 pub struct RedBankSettings {
     pub deposit_enabled: bool,
     pub borrow_enabled: bool,
+    /// If set, the asset is isolated: as collateral it can only back borrows of a whitelisted
+    /// set of denoms, up to a debt ceiling.
+    pub isolation: Option<IsolationParams>,
+}
+
+#[cw_serde]
+pub struct IsolationParams {
+    /// Denoms which can be borrowed against the isolated asset
+    pub borrowable_denoms: Vec<String>,
+    /// Maximum value (denominated in the oracle base denom) of all debt backed by the isolated asset
+    pub debt_ceiling: Uint128,
 }
 
 /// The LB will depend on the Health Factor and a couple other parameters as follows:
@@ -71,6 +82,14 @@ pub struct AssetParamsBase<T> {
     pub liquidation_bonus: LiquidationBonus,
     pub protocol_liquidation_fee: Decimal,
     pub deposit_cap: Uint128,
+    /// Maximum amount that can be borrowed across Red Bank and Credit Manager.
+    /// Assets added before borrow caps existed are uncapped.
+    #[serde(default = "default_borrow_cap")]
+    pub borrow_cap: Uint128,
+}
+
+fn default_borrow_cap() -> Uint128 {
+    Uint128::MAX
 }
 
 pub type AssetParams = AssetParamsBase<Addr>;
@@ -90,6 +109,7 @@ impl From<AssetParams> for AssetParamsUnchecked {
             liquidation_bonus: p.liquidation_bonus,
             protocol_liquidation_fee: p.protocol_liquidation_fee,
             deposit_cap: p.deposit_cap,
+            borrow_cap: p.borrow_cap,
         }
     }
 }
@@ -111,6 +131,13 @@ impl AssetParamsUnchecked {
             assert_hls_lqt_gt_max_ltv(hls.max_loan_to_value, hls.liquidation_threshold)?;
         }
 
+        if let Some(isolation) = self.red_bank.isolation.as_ref() {
+            assert_isolation_borrowable_denoms(&isolation.borrowable_denoms)?;
+            for denom in isolation.borrowable_denoms.iter() {
+                validate_native_denom(denom)?;
+            }
+        }
+
         let hls = self.credit_manager.hls.as_ref().map(|hls| hls.check(api)).transpose()?;
 
         Ok(AssetParams {
@@ -125,6 +152,72 @@ impl AssetParamsUnchecked {
             liquidation_bonus: self.liquidation_bonus.clone(),
             protocol_liquidation_fee: self.protocol_liquidation_fee,
             deposit_cap: self.deposit_cap,
+            borrow_cap: self.borrow_cap,
         })
     }
 }
+
+/// Linear decrease of the max LTV and liquidation threshold of an asset, so that lowering them
+/// doesn't make accounts liquidatable at once.
+#[cw_serde]
+pub struct LtvRamp {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub start_max_loan_to_value: Decimal,
+    pub start_liquidation_threshold: Decimal,
+    pub target_max_loan_to_value: Decimal,
+    pub target_liquidation_threshold: Decimal,
+}
+
+impl LtvRamp {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if self.end_time <= self.start_time {
+            return Err(ValidationError::InvalidParam {
+                param_name: "end_time".to_string(),
+                invalid_value: self.end_time.to_string(),
+                predicate: format!("> {} (current time)", self.start_time),
+            });
+        }
+        if self.target_max_loan_to_value > self.start_max_loan_to_value {
+            return Err(ValidationError::InvalidParam {
+                param_name: "max_loan_to_value".to_string(),
+                invalid_value: self.target_max_loan_to_value.to_string(),
+                predicate: format!("<= {} (current max LTV)", self.start_max_loan_to_value),
+            });
+        }
+        if self.target_liquidation_threshold > self.start_liquidation_threshold {
+            return Err(ValidationError::InvalidParam {
+                param_name: "liquidation_threshold".to_string(),
+                invalid_value: self.target_liquidation_threshold.to_string(),
+                predicate: format!(
+                    "<= {} (current liquidation threshold)",
+                    self.start_liquidation_threshold
+                ),
+            });
+        }
+        assert_lqt_gt_max_ltv(self.target_max_loan_to_value, self.target_liquidation_threshold)
+    }
+
+    /// Max LTV and liquidation threshold at the given time
+    pub fn current_values(&self, current_time: u64) -> (Decimal, Decimal) {
+        if current_time >= self.end_time {
+            return (self.target_max_loan_to_value, self.target_liquidation_threshold);
+        }
+
+        let elapsed = current_time.saturating_sub(self.start_time);
+        let progress = Decimal::from_ratio(elapsed, self.end_time - self.start_time);
+        let interpolate = |start: Decimal, target: Decimal| start - (start - target) * progress;
+
+        (
+            interpolate(self.start_max_loan_to_value, self.target_max_loan_to_value),
+            interpolate(self.start_liquidation_threshold, self.target_liquidation_threshold),
+        )
+    }
+
+    /// Replace the max LTV and liquidation threshold of the params with the values at the given time
+    pub fn apply(&self, params: &mut AssetParams, current_time: u64) {
+        let (max_loan_to_value, liquidation_threshold) = self.current_values(current_time);
+        params.max_loan_to_value = max_loan_to_value;
+        params.liquidation_threshold = liquidation_threshold;
+    }
+}
@@ -2,9 +2,13 @@ mod assertions;
 mod asset;
 mod hls;
 mod msg;
+mod perp;
+mod timelock;
 mod vault;
 
 pub use asset::*;
 pub use hls::*;
 pub use msg::*;
+pub use perp::*;
+pub use timelock::*;
 pub use vault::*;
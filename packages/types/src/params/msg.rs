@@ -2,16 +2,21 @@ use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Decimal, Uint128};
 use mars_owner::OwnerUpdate;
 
-use super::{asset::AssetParamsUnchecked, vault::VaultConfigUnchecked};
+use super::{asset::AssetParamsUnchecked, perp::PerpParams, vault::VaultConfigUnchecked};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     /// Contract's owner
     pub owner: String,
+    /// Address allowed to update perp params alongside the owner
+    #[serde(default)]
+    pub risk_manager: Option<String>,
     /// Address of the address provider contract
     pub address_provider: String,
     /// Determines the ideal HF a position should be left at immediately after the position has been liquidated.
     pub target_health_factor: Decimal,
+    /// The maximum number of perps that can be created
+    pub max_perp_params: u8,
 }
 
 #[cw_serde]
@@ -19,11 +24,23 @@ pub enum ExecuteMsg {
     UpdateOwner(OwnerUpdate),
     UpdateConfig {
         address_provider: Option<String>,
+        /// Delay (in seconds) before changes to risk parameters of listed assets and vaults can
+        /// be executed
+        update_delay: Option<u64>,
     },
     UpdateTargetHealthFactor(Decimal),
     UpdateAssetParams(AssetParamsUpdate),
     UpdateVaultConfig(VaultConfigUpdate),
+    UpdatePerpParams(PerpParamsUpdate),
     EmergencyUpdate(EmergencyUpdate),
+    /// Apply a pending update once its ETA has passed. Callable by anyone.
+    ExecutePendingUpdate {
+        id: u64,
+    },
+    /// Discard a pending update. Only callable by the owner.
+    CancelPendingUpdate {
+        id: u64,
+    },
 }
 
 #[cw_serde]
@@ -46,6 +63,13 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
 
+    /// LTV ramp-down of the asset, if any. Asset params queries return the current values of the
+    /// ramp.
+    #[returns(Option<super::asset::LtvRamp>)]
+    LtvRamp {
+        denom: String,
+    },
+
     #[returns(super::vault::VaultConfig)]
     VaultConfig {
         /// Address of vault
@@ -64,6 +88,17 @@ pub enum QueryMsg {
         limit: Option<u32>,
     },
 
+    #[returns(super::perp::PerpParams)]
+    PerpParams {
+        denom: String,
+    },
+
+    #[returns(Vec<super::perp::PerpParams>)]
+    AllPerpParams {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
     #[returns(Decimal)]
     TargetHealthFactor {},
 
@@ -81,12 +116,38 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+
+    /// Compute the total amount borrowed of the given asset across Red Bank
+    /// and Credit Manager.
+    #[returns(TotalBorrowResponse)]
+    TotalBorrow {
+        denom: String,
+    },
+
+    /// Compute the total amount borrowed for paginated assets across Red Bank
+    /// and Credit Manager.
+    #[returns(cw_paginate::PaginationResponse<TotalBorrowResponse>)]
+    AllTotalBorrows {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// Risk parameter changes scheduled to take effect after the update delay
+    #[returns(cw_paginate::PaginationResponse<super::timelock::PendingUpdate>)]
+    PendingUpdates {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     /// Address provider returns addresses for all protocol contracts
     pub address_provider: String,
+    /// Address allowed to update perp params alongside the owner
+    pub risk_manager: Option<String>,
+    /// Delay (in seconds) before risk parameter changes can be executed
+    pub update_delay: u64,
 }
 
 #[cw_serde]
@@ -96,11 +157,26 @@ pub struct TotalDepositResponse {
     pub amount: Uint128,
 }
 
+#[cw_serde]
+pub struct TotalBorrowResponse {
+    pub denom: String,
+    pub cap: Uint128,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub enum AssetParamsUpdate {
     AddOrUpdate {
         params: AssetParamsUnchecked,
     },
+    /// Linearly lower the max LTV and liquidation threshold of a listed asset, starting from the
+    /// current values, until the targets are reached at `end_time`
+    RampDown {
+        denom: String,
+        max_loan_to_value: Decimal,
+        liquidation_threshold: Decimal,
+        end_time: u64,
+    },
 }
 
 #[cw_serde]
@@ -110,6 +186,13 @@ pub enum VaultConfigUpdate {
     },
 }
 
+#[cw_serde]
+pub enum PerpParamsUpdate {
+    AddOrUpdate {
+        params: PerpParams,
+    },
+}
+
 #[cw_serde]
 pub enum CmEmergencyUpdate {
     SetZeroMaxLtvOnVault(String),
@@ -122,8 +205,14 @@ pub enum RedBankEmergencyUpdate {
     DisableBorrowing(String),
 }
 
+#[cw_serde]
+pub enum PerpsEmergencyUpdate {
+    DisableTrading(String),
+}
+
 #[cw_serde]
 pub enum EmergencyUpdate {
     CreditManager(CmEmergencyUpdate),
     RedBank(RedBankEmergencyUpdate),
+    Perps(PerpsEmergencyUpdate),
 }
@@ -0,0 +1,70 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, Uint128};
+use mars_utils::{
+    error::ValidationError,
+    helpers::{decimal_param_le_one, decimal_param_lt_one, validate_native_denom},
+};
+
+use super::assertions::assert_lqt_gt_max_ltv;
+
+#[cw_serde]
+pub struct PerpParams {
+    /// Perp denomination
+    pub denom: String,
+    /// Whether the perp is enabled for trading
+    pub enabled: bool,
+    /// The maximum net open interest value (in oracle base denomination)
+    pub max_net_oi_value: Uint128,
+    /// The maximum long open interest value (in oracle base denomination)
+    pub max_long_oi_value: Uint128,
+    /// The maximum short open interest value (in oracle base denomination)
+    pub max_short_oi_value: Uint128,
+    /// The fee paid by the user to close a position (as a percent)
+    pub closing_fee_rate: Decimal,
+    /// The fee paid by the user to open a position (as a percent)
+    pub opening_fee_rate: Decimal,
+    /// The minimum value of a position (in oracle base denomination)
+    pub min_position_value: Uint128,
+    /// The maximum value of a position (in oracle base denomination)
+    pub max_position_value: Option<Uint128>,
+    /// Max loan to position value for the position
+    pub max_loan_to_value: Decimal,
+    /// LTV at which a position becomes liquidatable
+    pub liquidation_threshold: Decimal,
+    /// Determines the maximum rate at which funding can be adjusted (per day)
+    pub max_funding_velocity: Decimal,
+    /// Determines the funding rate for a given level of skew.
+    /// The lower the skew_scale the higher the funding rate.
+    pub skew_scale: Uint128,
+}
+
+impl PerpParams {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_native_denom(&self.denom)?;
+        decimal_param_lt_one(self.opening_fee_rate, "opening_fee_rate")?;
+        decimal_param_lt_one(self.closing_fee_rate, "closing_fee_rate")?;
+        decimal_param_le_one(self.max_loan_to_value, "max_loan_to_value")?;
+        decimal_param_le_one(self.liquidation_threshold, "liquidation_threshold")?;
+        assert_lqt_gt_max_ltv(self.max_loan_to_value, self.liquidation_threshold)?;
+
+        if let Some(max_position_value) = self.max_position_value {
+            if max_position_value < self.min_position_value {
+                return Err(ValidationError::InvalidParam {
+                    param_name: "max_position_value".to_string(),
+                    invalid_value: max_position_value.to_string(),
+                    predicate: format!(">= {} (min position value)", self.min_position_value),
+                });
+            }
+        }
+
+        if self.skew_scale.is_zero() {
+            return Err(ValidationError::InvalidParam {
+                param_name: "skew_scale".to_string(),
+                invalid_value: self.skew_scale.to_string(),
+                predicate: "> 0".to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
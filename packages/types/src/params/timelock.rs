@@ -0,0 +1,22 @@
+use cosmwasm_schema::cw_serde;
+
+use super::{asset::AssetParams, vault::VaultConfig};
+
+/// Risk parameter change waiting for the update delay to pass
+#[cw_serde]
+pub struct PendingUpdate {
+    pub id: u64,
+    /// Timestamp (in seconds) after which the update can be executed
+    pub eta: u64,
+    pub update: PendingUpdateKind,
+}
+
+/// Only the time-locked fields (max LTV, liquidation threshold, liquidation bonus and caps) are
+/// applied on execution. Other fields take effect when the update is submitted.
+#[cw_serde]
+pub enum PendingUpdateKind {
+    AssetParams(AssetParams),
+    VaultConfig(VaultConfig),
+    /// Lowering the update delay, which has to wait for the current delay to pass
+    UpdateDelay(u64),
+}
@@ -0,0 +1,451 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Api, Coin, Decimal, Int128, SignedDecimal, StdResult, Uint128};
+use mars_owner::OwnerUpdate;
+
+use crate::{oracle::ActionKind, params::PerpParams};
+
+//--------------------------------------------------------------------------------------------------
+// Config
+//--------------------------------------------------------------------------------------------------
+
+#[cw_serde]
+pub struct Config<T> {
+    /// Address provider returns addresses for all protocol contracts
+    pub address_provider: T,
+    /// The denom in which PnL, fees and vault deposits are settled
+    pub base_denom: String,
+    /// The minimum amount of time (in seconds) that vault shares have to stay unlocked before
+    /// they can be withdrawn
+    pub cooldown_period: u64,
+    /// The maximum number of positions that can be opened by a single account
+    pub max_positions: u8,
+    /// The percentage of opening and closing fees that go to the protocol (rewards collector)
+    pub protocol_fee_rate: Decimal,
+    /// The vault collateralization ratio below which withdrawals are blocked and profitable
+    /// positions can be deleveraged
+    pub target_vault_collateralization_ratio: Decimal,
+    /// Whether profitable positions can be deleveraged when the vault is undercollateralized
+    pub deleverage_enabled: bool,
+    /// Whether vault withdrawals are enabled
+    pub vault_withdraw_enabled: bool,
+    /// The maximum number of simultaneous unlocks a single vault depositor can have
+    pub max_unlocks: u8,
+}
+
+impl Config<String> {
+    pub fn check(self, api: &dyn Api) -> StdResult<Config<Addr>> {
+        Ok(Config {
+            address_provider: api.addr_validate(&self.address_provider)?,
+            base_denom: self.base_denom,
+            cooldown_period: self.cooldown_period,
+            max_positions: self.max_positions,
+            protocol_fee_rate: self.protocol_fee_rate,
+            target_vault_collateralization_ratio: self.target_vault_collateralization_ratio,
+            deleverage_enabled: self.deleverage_enabled,
+            vault_withdraw_enabled: self.vault_withdraw_enabled,
+            max_unlocks: self.max_unlocks,
+        })
+    }
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdates {
+    pub address_provider: Option<String>,
+    pub cooldown_period: Option<u64>,
+    pub max_positions: Option<u8>,
+    pub protocol_fee_rate: Option<Decimal>,
+    pub target_vault_collateralization_ratio: Option<Decimal>,
+    pub deleverage_enabled: Option<bool>,
+    pub vault_withdraw_enabled: Option<bool>,
+    pub max_unlocks: Option<u8>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Vault
+//--------------------------------------------------------------------------------------------------
+
+#[cw_serde]
+#[derive(Default)]
+pub struct VaultState {
+    /// Net amount of base denom deposited by depositors (deposits minus withdrawals)
+    pub total_balance: Int128,
+    /// Total number of vault shares issued
+    pub total_shares: Uint128,
+}
+
+#[cw_serde]
+pub struct VaultResponse {
+    pub total_balance: Int128,
+    pub total_shares: Uint128,
+    /// Net amount of base denom owned by the vault, taking into account the realized
+    /// cash flows of all markets
+    pub total_liquidity: Int128,
+    /// Unrealized PnL of all open positions, from the traders' perspective
+    pub total_unrealized_pnl: Int128,
+    /// Value of the vault used for share accounting, i.e. liquidity minus unrealized traders' PnL
+    pub total_value: Uint128,
+    /// Value of a single share denominated in the base denom. None if no shares have been issued.
+    pub share_price: Option<Decimal>,
+    /// Ratio between vault liquidity and unrealized traders' profits.
+    /// None if traders don't have any unrealized profits.
+    pub collateralization_ratio: Option<Decimal>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct UnlockState {
+    pub created_at: u64,
+    pub cooldown_end: u64,
+    pub shares: Uint128,
+}
+
+#[cw_serde]
+pub struct VaultDeposit {
+    pub shares: Uint128,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct VaultUnlock {
+    pub created_at: u64,
+    pub cooldown_end: u64,
+    pub shares: Uint128,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct VaultPositionResponse {
+    pub denom: String,
+    pub deposit: VaultDeposit,
+    pub unlocks: Vec<VaultUnlock>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Markets
+//--------------------------------------------------------------------------------------------------
+
+#[cw_serde]
+pub struct Funding {
+    /// Determines the maximum rate at which funding can be adjusted (per day)
+    pub max_funding_velocity: Decimal,
+    /// Determines the funding rate for a given level of skew
+    pub skew_scale: Uint128,
+    /// The current funding rate calculated on the last state change
+    pub last_funding_rate: SignedDecimal,
+    /// Accumulated funding per unit of position size, denominated in the base denom
+    pub last_funding_accrued_per_unit_in_base_denom: SignedDecimal,
+}
+
+/// Realized amounts of a market, from the vault's perspective.
+/// Positive values are inflows to the vault, negative values are outflows.
+#[cw_serde]
+#[derive(Default)]
+pub struct CashFlow {
+    pub price_pnl: Int128,
+    pub opening_fee: Int128,
+    pub closing_fee: Int128,
+    pub accrued_funding: Int128,
+    /// Part of the trading fees sent to the protocol (rewards collector)
+    pub protocol_fee: Uint128,
+}
+
+impl CashFlow {
+    /// Net amount owned by the vault
+    pub fn total(&self) -> StdResult<Int128> {
+        Ok(self
+            .price_pnl
+            .checked_add(self.opening_fee)?
+            .checked_add(self.closing_fee)?
+            .checked_add(self.accrued_funding)?)
+    }
+}
+
+#[cw_serde]
+pub struct MarketState {
+    /// Whether new positions can be opened in this market
+    pub enabled: bool,
+    /// Total long open interest (in perp denom units)
+    pub long_oi: Uint128,
+    /// Total short open interest (in perp denom units)
+    pub short_oi: Uint128,
+    pub funding: Funding,
+    /// Timestamp (in seconds) of the last funding accrual
+    pub last_updated: u64,
+    /// Sum of `size * entry_price` over all open positions
+    pub total_entry_cost: SignedDecimal,
+    /// Sum of `size * entry_accrued_funding_per_unit_in_base_denom` over all open positions
+    pub total_entry_funding: SignedDecimal,
+    /// Realized amounts, from the vault's perspective
+    pub cash_flow: CashFlow,
+}
+
+#[cw_serde]
+pub struct MarketStateResponse {
+    pub denom: String,
+    pub enabled: bool,
+    pub long_oi: Uint128,
+    pub short_oi: Uint128,
+    pub funding: Funding,
+    pub last_updated: u64,
+    pub total_entry_cost: SignedDecimal,
+    pub total_entry_funding: SignedDecimal,
+    pub cash_flow: CashFlow,
+}
+
+#[cw_serde]
+pub struct MarketResponse {
+    pub denom: String,
+    pub enabled: bool,
+    pub long_oi: Uint128,
+    /// Long open interest value (in oracle base denomination)
+    pub long_oi_value: Uint128,
+    pub short_oi: Uint128,
+    /// Short open interest value (in oracle base denomination)
+    pub short_oi_value: Uint128,
+    pub current_funding_rate: SignedDecimal,
+}
+
+#[cw_serde]
+pub struct AccountingResponse {
+    /// Realized amounts, from the vault's perspective
+    pub cash_flow: CashFlow,
+    /// Unrealized PnL of open positions, from the traders' perspective
+    pub unrealized_pnl: PnlAmounts,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Positions
+//--------------------------------------------------------------------------------------------------
+
+/// PnL breakdown, from the trader's perspective.
+/// Positive values are profits, negative values are losses (fees are always <= 0).
+#[cw_serde]
+#[derive(Default)]
+pub struct PnlAmounts {
+    pub price_pnl: Int128,
+    pub accrued_funding: Int128,
+    pub opening_fee: Int128,
+    pub closing_fee: Int128,
+    /// Sum of all the above
+    pub pnl: Int128,
+}
+
+impl PnlAmounts {
+    pub fn add(&mut self, other: &PnlAmounts) -> StdResult<()> {
+        self.price_pnl = self.price_pnl.checked_add(other.price_pnl)?;
+        self.accrued_funding = self.accrued_funding.checked_add(other.accrued_funding)?;
+        self.opening_fee = self.opening_fee.checked_add(other.opening_fee)?;
+        self.closing_fee = self.closing_fee.checked_add(other.closing_fee)?;
+        self.pnl = self.pnl.checked_add(other.pnl)?;
+        Ok(())
+    }
+}
+
+#[cw_serde]
+pub struct Position {
+    pub size: Int128,
+    /// Entry price denominated in the base denom
+    pub entry_price: Decimal,
+    pub entry_accrued_funding_per_unit_in_base_denom: SignedDecimal,
+    /// PnL realized so far by this position (accumulated over modifications)
+    pub realized_pnl: PnlAmounts,
+}
+
+/// Position with its current valuation
+#[cw_serde]
+pub struct PerpPosition {
+    pub denom: String,
+    pub base_denom: String,
+    pub size: Int128,
+    pub entry_price: Decimal,
+    /// Current price denominated in the base denom
+    pub current_price: Decimal,
+    /// Current price denominated in the oracle base denom
+    pub denom_price: Decimal,
+    pub entry_accrued_funding_per_unit_in_base_denom: SignedDecimal,
+    pub unrealized_pnl: PnlAmounts,
+    pub realized_pnl: PnlAmounts,
+}
+
+#[cw_serde]
+pub struct PositionResponse {
+    pub account_id: String,
+    pub position: Option<PerpPosition>,
+}
+
+#[cw_serde]
+pub struct PositionsByAccountResponse {
+    pub account_id: String,
+    pub positions: Vec<PerpPosition>,
+}
+
+#[cw_serde]
+pub struct TradingFee {
+    pub rate: Decimal,
+    pub fee: Coin,
+}
+
+#[cw_serde]
+pub struct PositionFeesResponse {
+    pub base_denom: String,
+    pub opening_fee: Uint128,
+    pub closing_fee: Uint128,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Messages
+//--------------------------------------------------------------------------------------------------
+
+pub type InstantiateMsg = Config<String>;
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    UpdateOwner(OwnerUpdate),
+
+    UpdateConfig {
+        updates: ConfigUpdates,
+    },
+
+    /// Deposit base denom into the counterparty vault and receive shares.
+    /// Only the Credit Manager can pass an `account_id`.
+    Deposit {
+        account_id: Option<String>,
+        /// The deposit fails if more shares than this would be minted
+        max_shares_receivable: Option<Uint128>,
+    },
+
+    /// Start the cooldown period for the given amount of shares
+    Unlock {
+        account_id: Option<String>,
+        shares: Uint128,
+    },
+
+    /// Withdraw all unlocked shares whose cooldown period has ended
+    Withdraw {
+        account_id: Option<String>,
+        min_receive: Option<Uint128>,
+    },
+
+    /// Open, increase, reduce, close or flip a position by `size`.
+    /// Only callable by the Credit Manager. If the realized PnL is a loss, it must be sent
+    /// along with the message in the base denom. Profits are sent back to the Credit Manager.
+    ExecuteOrder {
+        account_id: String,
+        denom: String,
+        size: Int128,
+        reduce_only: Option<bool>,
+    },
+
+    /// Close all positions of the account. Only callable by the Credit Manager.
+    CloseAllPositions {
+        account_id: String,
+        action: Option<ActionKind>,
+    },
+
+    /// Close a profitable position while the vault is undercollateralized.
+    /// Anyone can call it if deleveraging is enabled.
+    Deleverage {
+        account_id: String,
+        denom: String,
+    },
+
+    /// Create or update a market. Only callable by the params contract.
+    UpdateMarket {
+        params: PerpParams,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(mars_owner::OwnerResponse)]
+    Owner {},
+
+    #[returns(Config<Addr>)]
+    Config {},
+
+    #[returns(VaultResponse)]
+    Vault {
+        action: Option<ActionKind>,
+    },
+
+    #[returns(MarketStateResponse)]
+    MarketState {
+        denom: String,
+    },
+
+    #[returns(MarketResponse)]
+    Market {
+        denom: String,
+    },
+
+    #[returns(cw_paginate::PaginationResponse<MarketResponse>)]
+    Markets {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Option<VaultPositionResponse>)]
+    VaultPosition {
+        user_address: String,
+        account_id: Option<String>,
+    },
+
+    /// Query a single position. If `order_size` is provided, the returned unrealized PnL is
+    /// the amount that would be realized by executing an order of that size, including fees.
+    #[returns(PositionResponse)]
+    Position {
+        account_id: String,
+        denom: String,
+        order_size: Option<Int128>,
+        reduce_only: Option<bool>,
+    },
+
+    #[returns(Vec<PositionResponse>)]
+    Positions {
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(PositionsByAccountResponse)]
+    PositionsByAccount {
+        account_id: String,
+        action: Option<ActionKind>,
+    },
+
+    #[returns(AccountingResponse)]
+    MarketAccounting {
+        denom: String,
+    },
+
+    #[returns(AccountingResponse)]
+    TotalAccounting {},
+
+    #[returns(PnlAmounts)]
+    RealizedPnlByAccountAndMarket {
+        account_id: String,
+        denom: String,
+    },
+
+    /// Profit realized by the account which couldn't be paid out, because the contract was short
+    /// on liquidity
+    #[returns(Uint128)]
+    UnpaidProfit {
+        account_id: String,
+    },
+
+    #[returns(TradingFee)]
+    OpeningFee {
+        denom: String,
+        size: Int128,
+    },
+
+    /// Fees paid for moving the position from its current size to `new_size`
+    #[returns(PositionFeesResponse)]
+    PositionFees {
+        account_id: String,
+        denom: String,
+        new_size: Int128,
+    },
+}
@@ -0,0 +1,23 @@
+//! Subset of the Pyth contract messages used to push fresh prices on chain. The messages are
+//! JSON-compatible with the ones of `pyth-sdk-cw`.
+
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Binary, Coin};
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// Verify the VAAs and store the price updates they contain
+    UpdatePriceFeeds {
+        data: Vec<Binary>,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Fee to be paid for updating the price feeds with the VAAs
+    #[returns(Coin)]
+    GetUpdateFee {
+        vaas: Vec<Binary>,
+    },
+}
@@ -1,12 +1,78 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Decimal;
-use mars_utils::{error::ValidationError, helpers::decimal_param_le_one};
+use mars_utils::{
+    error::ValidationError,
+    helpers::{decimal_param_le_one, decimal_param_lt_one},
+};
 
 use crate::error::MarsError;
 
+#[cw_serde]
+#[derive(Eq)]
+pub enum InterestRateModel {
+    /// Single kink: the borrow rate increases slowly up to the optimal utilization rate and
+    /// sharply above it
+    Linear(LinearInterestRateModel),
+    /// Piecewise linear curve going through any number of kinks
+    MultiKink(MultiKinkInterestRateModel),
+    /// Curve shifting over time toward a target utilization rate
+    Adaptive(AdaptiveInterestRateModel),
+}
+
+impl Default for InterestRateModel {
+    fn default() -> Self {
+        InterestRateModel::Linear(LinearInterestRateModel::default())
+    }
+}
+
+impl InterestRateModel {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            InterestRateModel::Linear(model) => model.validate(),
+            InterestRateModel::MultiKink(model) => model.validate(),
+            InterestRateModel::Adaptive(model) => model.validate(),
+        }
+    }
+
+    /// Update the state of models that change over time. Has to be called before computing the
+    /// borrow rate for the new utilization rate.
+    pub fn update(
+        &mut self,
+        current_utilization_rate: Decimal,
+        current_timestamp: u64,
+    ) -> Result<(), MarsError> {
+        match self {
+            InterestRateModel::Linear(_) | InterestRateModel::MultiKink(_) => Ok(()),
+            InterestRateModel::Adaptive(model) => {
+                model.update(current_utilization_rate, current_timestamp)
+            }
+        }
+    }
+
+    pub fn get_borrow_rate(&self, current_utilization_rate: Decimal) -> Result<Decimal, MarsError> {
+        match self {
+            InterestRateModel::Linear(model) => model.get_borrow_rate(current_utilization_rate),
+            InterestRateModel::MultiKink(model) => model.get_borrow_rate(current_utilization_rate),
+            InterestRateModel::Adaptive(model) => model.get_borrow_rate(current_utilization_rate),
+        }
+    }
+
+    pub fn get_liquidity_rate(
+        &self,
+        borrow_rate: Decimal,
+        current_utilization_rate: Decimal,
+        reserve_factor: Decimal,
+    ) -> Result<Decimal, MarsError> {
+        Ok(borrow_rate
+            .checked_mul(current_utilization_rate)?
+            // This operation should not underflow as reserve_factor is checked to be <= 1
+            .checked_mul(Decimal::one() - reserve_factor)?)
+    }
+}
+
 #[cw_serde]
 #[derive(Eq, Default)]
-pub struct InterestRateModel {
+pub struct LinearInterestRateModel {
     /// Optimal utilization rate
     pub optimal_utilization_rate: Decimal,
     /// Base rate
@@ -17,7 +83,7 @@ pub struct InterestRateModel {
     pub slope_2: Decimal,
 }
 
-impl InterestRateModel {
+impl LinearInterestRateModel {
     pub fn validate(&self) -> Result<(), ValidationError> {
         decimal_param_le_one(self.optimal_utilization_rate, "optimal_utilization_rate")?;
 
@@ -55,17 +121,207 @@ impl InterestRateModel {
         };
         Ok(new_borrow_rate)
     }
+}
 
-    pub fn get_liquidity_rate(
-        &self,
-        borrow_rate: Decimal,
+#[cw_serde]
+#[derive(Eq)]
+pub struct InterestRateKink {
+    pub utilization_rate: Decimal,
+    /// Borrow rate at the kink's utilization rate
+    pub borrow_rate: Decimal,
+}
+
+#[cw_serde]
+#[derive(Eq)]
+pub struct MultiKinkInterestRateModel {
+    /// Borrow rate at 0% utilization
+    pub base: Decimal,
+    /// Kinks sorted by utilization rate. The last kink has to be at 100% utilization.
+    /// The borrow rate is interpolated linearly between two kinks.
+    pub kinks: Vec<InterestRateKink>,
+}
+
+impl MultiKinkInterestRateModel {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let last_utilization_rate =
+            self.kinks.last().map(|kink| kink.utilization_rate).unwrap_or_default();
+        if last_utilization_rate != Decimal::one() {
+            return Err(ValidationError::InvalidParam {
+                param_name: "kinks".to_string(),
+                invalid_value: last_utilization_rate.to_string(),
+                predicate: "last kink at utilization rate 1".to_string(),
+            });
+        }
+
+        let mut previous = InterestRateKink {
+            utilization_rate: Decimal::zero(),
+            borrow_rate: self.base,
+        };
+        for kink in self.kinks.iter() {
+            if kink.utilization_rate <= previous.utilization_rate {
+                return Err(ValidationError::InvalidParam {
+                    param_name: "kink_utilization_rate".to_string(),
+                    invalid_value: kink.utilization_rate.to_string(),
+                    predicate: format!("> {}", previous.utilization_rate),
+                });
+            }
+            if kink.borrow_rate < previous.borrow_rate {
+                return Err(ValidationError::InvalidParam {
+                    param_name: "kink_borrow_rate".to_string(),
+                    invalid_value: kink.borrow_rate.to_string(),
+                    predicate: format!(">= {}", previous.borrow_rate),
+                });
+            }
+            previous = kink.clone();
+        }
+
+        Ok(())
+    }
+
+    pub fn get_borrow_rate(&self, current_utilization_rate: Decimal) -> Result<Decimal, MarsError> {
+        let mut previous_utilization_rate = Decimal::zero();
+        let mut previous_borrow_rate = self.base;
+
+        for kink in self.kinks.iter() {
+            if current_utilization_rate <= kink.utilization_rate {
+                let segment_progress = (current_utilization_rate - previous_utilization_rate)
+                    .checked_div(kink.utilization_rate - previous_utilization_rate)?;
+                let borrow_rate = previous_borrow_rate.checked_add(
+                    (kink.borrow_rate - previous_borrow_rate).checked_mul(segment_progress)?,
+                )?;
+                return Ok(borrow_rate);
+            }
+            previous_utilization_rate = kink.utilization_rate;
+            previous_borrow_rate = kink.borrow_rate;
+        }
+
+        Ok(previous_borrow_rate)
+    }
+}
+
+/// Interest rate model in the style of Morpho's AdaptiveCurveIRM.
+///
+/// The borrow rate follows a curve around `rate_at_target`: `rate_at_target / curve_steepness` at
+/// 0% utilization, `rate_at_target` at the target utilization and
+/// `rate_at_target * curve_steepness` at 100% utilization. On each update, `rate_at_target` moves
+/// up while the utilization was above the target and down while it was below, proportionally to
+/// the distance from the target and the time elapsed.
+#[cw_serde]
+#[derive(Eq)]
+pub struct AdaptiveInterestRateModel {
+    pub target_utilization_rate: Decimal,
+    pub curve_steepness: Decimal,
+    /// Relative change of `rate_at_target` per second at 0% or 100% utilization
+    pub adjustment_speed: Decimal,
+    pub min_rate_at_target: Decimal,
+    pub max_rate_at_target: Decimal,
+    /// Borrow rate at the target utilization, adjusted on each update
+    pub rate_at_target: Decimal,
+    /// Utilization rate set on the last update, valid until the next one
+    #[serde(default)]
+    pub utilization_rate: Decimal,
+    /// Timestamp of the last update
+    #[serde(default)]
+    pub last_updated: u64,
+}
+
+impl AdaptiveInterestRateModel {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        decimal_param_lt_one(self.target_utilization_rate, "target_utilization_rate")?;
+        if self.target_utilization_rate.is_zero() {
+            return Err(ValidationError::InvalidParam {
+                param_name: "target_utilization_rate".to_string(),
+                invalid_value: self.target_utilization_rate.to_string(),
+                predicate: "> 0".to_string(),
+            });
+        }
+
+        if self.curve_steepness < Decimal::one() {
+            return Err(ValidationError::InvalidParam {
+                param_name: "curve_steepness".to_string(),
+                invalid_value: self.curve_steepness.to_string(),
+                predicate: ">= 1".to_string(),
+            });
+        }
+
+        if self.min_rate_at_target > self.max_rate_at_target {
+            return Err(ValidationError::InvalidParam {
+                param_name: "min_rate_at_target".to_string(),
+                invalid_value: self.min_rate_at_target.to_string(),
+                predicate: format!("<= {}", self.max_rate_at_target),
+            });
+        }
+
+        if self.rate_at_target < self.min_rate_at_target
+            || self.rate_at_target > self.max_rate_at_target
+        {
+            return Err(ValidationError::InvalidParam {
+                param_name: "rate_at_target".to_string(),
+                invalid_value: self.rate_at_target.to_string(),
+                predicate: format!("[{}, {}]", self.min_rate_at_target, self.max_rate_at_target),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Adjust `rate_at_target` for the time elapsed at the previous utilization rate and record the
+    /// current one.
+    ///
+    /// The exponential adjustment of AdaptiveCurveIRM is approximated by multiplying (or dividing)
+    /// `rate_at_target` by `1 + adjustment_speed * error * time_elapsed`.
+    pub fn update(
+        &mut self,
         current_utilization_rate: Decimal,
-        reserve_factor: Decimal,
-    ) -> Result<Decimal, MarsError> {
-        Ok(borrow_rate
-            .checked_mul(current_utilization_rate)?
-            // This operation should not underflow as reserve_factor is checked to be <= 1
-            .checked_mul(Decimal::one() - reserve_factor)?)
+        current_timestamp: u64,
+    ) -> Result<(), MarsError> {
+        if self.last_updated > 0 && current_timestamp > self.last_updated {
+            let time_elapsed = Decimal::from_ratio(current_timestamp - self.last_updated, 1u128);
+            let (error, above_target) = self.utilization_error(self.utilization_rate)?;
+
+            let factor = Decimal::one().checked_add(
+                self.adjustment_speed.checked_mul(error)?.checked_mul(time_elapsed)?,
+            )?;
+            let rate_at_target = if above_target {
+                self.rate_at_target.checked_mul(factor)?
+            } else {
+                self.rate_at_target.checked_div(factor)?
+            };
+
+            self.rate_at_target =
+                rate_at_target.clamp(self.min_rate_at_target, self.max_rate_at_target);
+        }
+
+        self.utilization_rate = current_utilization_rate;
+        self.last_updated = current_timestamp;
+
+        Ok(())
+    }
+
+    pub fn get_borrow_rate(&self, current_utilization_rate: Decimal) -> Result<Decimal, MarsError> {
+        let (error, above_target) = self.utilization_error(current_utilization_rate)?;
+
+        let curve_factor = if above_target {
+            Decimal::one()
+                .checked_add((self.curve_steepness - Decimal::one()).checked_mul(error)?)?
+        } else {
+            let below_steepness =
+                Decimal::one() - Decimal::one().checked_div(self.curve_steepness)?;
+            Decimal::one() - below_steepness.checked_mul(error)?
+        };
+
+        Ok(self.rate_at_target.checked_mul(curve_factor)?)
+    }
+
+    /// Distance of the utilization rate from the target, normalized to [0, 1], and whether it is
+    /// above the target
+    fn utilization_error(&self, utilization_rate: Decimal) -> Result<(Decimal, bool), MarsError> {
+        let target = self.target_utilization_rate;
+        if utilization_rate > target {
+            Ok(((utilization_rate - target).checked_div(Decimal::one() - target)?, true))
+        } else {
+            Ok(((target - utilization_rate).checked_div(target)?, false))
+        }
     }
 }
 
@@ -79,7 +335,7 @@ mod tests {
         let optimal_utilization_rate = Decimal::percent(80);
         let reserve_factor = Decimal::percent(20);
 
-        let model = InterestRateModel {
+        let model = LinearInterestRateModel {
             optimal_utilization_rate,
             base: Decimal::zero(),
             slope_1: Decimal::percent(7),
@@ -90,7 +346,7 @@ mod tests {
             borrow_rate: Decimal::percent(10),
             liquidity_rate: Decimal::zero(),
             reserve_factor,
-            interest_rate_model: model.clone(),
+            interest_rate_model: InterestRateModel::Linear(model.clone()),
             ..Default::default()
         };
 
@@ -111,7 +367,7 @@ mod tests {
 
     #[test]
     fn interest_rates_calculation() {
-        let model = InterestRateModel {
+        let model = LinearInterestRateModel {
             optimal_utilization_rate: Decimal::percent(80),
             base: Decimal::zero(),
             slope_1: Decimal::percent(7),
@@ -155,7 +411,7 @@ mod tests {
 
         // current utilization rate == 100% and optimal utilization rate == 100%
         {
-            let model = InterestRateModel {
+            let model = LinearInterestRateModel {
                 optimal_utilization_rate: Decimal::percent(100),
                 base: Decimal::zero(),
                 slope_1: Decimal::percent(7),
@@ -170,7 +426,7 @@ mod tests {
 
         // current utilization rate == 0% and optimal utilization rate == 0%
         {
-            let model = InterestRateModel {
+            let model = LinearInterestRateModel {
                 optimal_utilization_rate: Decimal::percent(0),
                 base: Decimal::percent(2),
                 slope_1: Decimal::percent(7),
@@ -185,7 +441,7 @@ mod tests {
 
         // current utilization rate == 20% and optimal utilization rate == 0%
         {
-            let model = InterestRateModel {
+            let model = LinearInterestRateModel {
                 optimal_utilization_rate: Decimal::percent(0),
                 base: Decimal::percent(2),
                 slope_1: Decimal::percent(1),
@@ -201,4 +457,198 @@ mod tests {
             assert_eq!(new_borrow_rate, expected_borrow_rate);
         }
     }
+
+    fn multi_kink_model() -> MultiKinkInterestRateModel {
+        MultiKinkInterestRateModel {
+            base: Decimal::percent(1),
+            kinks: vec![
+                InterestRateKink {
+                    utilization_rate: Decimal::percent(50),
+                    borrow_rate: Decimal::percent(5),
+                },
+                InterestRateKink {
+                    utilization_rate: Decimal::percent(80),
+                    borrow_rate: Decimal::percent(20),
+                },
+                InterestRateKink {
+                    utilization_rate: Decimal::one(),
+                    borrow_rate: Decimal::percent(100),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn multi_kink_validation() {
+        multi_kink_model().validate().unwrap();
+
+        let mut model = multi_kink_model();
+        model.kinks.pop();
+        let err = model.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "kinks".to_string(),
+                invalid_value: "0.8".to_string(),
+                predicate: "last kink at utilization rate 1".to_string(),
+            }
+        );
+
+        let mut model = multi_kink_model();
+        model.kinks[1].utilization_rate = Decimal::percent(50);
+        let err = model.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "kink_utilization_rate".to_string(),
+                invalid_value: "0.5".to_string(),
+                predicate: "> 0.5".to_string(),
+            }
+        );
+
+        let mut model = multi_kink_model();
+        model.kinks[0].borrow_rate = Decimal::permille(5);
+        let err = model.validate().unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "kink_borrow_rate".to_string(),
+                invalid_value: "0.005".to_string(),
+                predicate: ">= 0.01".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn multi_kink_interest_rates_calculation() {
+        let model = InterestRateModel::MultiKink(multi_kink_model());
+
+        assert_eq!(model.get_borrow_rate(Decimal::zero()).unwrap(), Decimal::percent(1));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(25)).unwrap(), Decimal::percent(3));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(50)).unwrap(), Decimal::percent(5));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(65)).unwrap(), Decimal::permille(125));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(80)).unwrap(), Decimal::percent(20));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(90)).unwrap(), Decimal::percent(60));
+        assert_eq!(model.get_borrow_rate(Decimal::one()).unwrap(), Decimal::percent(100));
+    }
+
+    fn adaptive_model() -> AdaptiveInterestRateModel {
+        AdaptiveInterestRateModel {
+            target_utilization_rate: Decimal::percent(90),
+            curve_steepness: Decimal::percent(400),
+            adjustment_speed: Decimal::from_ratio(1u128, 1_000_000u128),
+            min_rate_at_target: Decimal::percent(1),
+            max_rate_at_target: Decimal::percent(200),
+            rate_at_target: Decimal::percent(4),
+            utilization_rate: Decimal::zero(),
+            last_updated: 0,
+        }
+    }
+
+    #[test]
+    fn adaptive_validation() {
+        adaptive_model().validate().unwrap();
+
+        let err = AdaptiveInterestRateModel {
+            target_utilization_rate: Decimal::one(),
+            ..adaptive_model()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "target_utilization_rate".to_string(),
+                invalid_value: "1".to_string(),
+                predicate: "< 1".to_string(),
+            }
+        );
+
+        let err = AdaptiveInterestRateModel {
+            curve_steepness: Decimal::percent(50),
+            ..adaptive_model()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "curve_steepness".to_string(),
+                invalid_value: "0.5".to_string(),
+                predicate: ">= 1".to_string(),
+            }
+        );
+
+        let err = AdaptiveInterestRateModel {
+            rate_at_target: Decimal::percent(300),
+            ..adaptive_model()
+        }
+        .validate()
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::InvalidParam {
+                param_name: "rate_at_target".to_string(),
+                invalid_value: "3".to_string(),
+                predicate: "[0.01, 2]".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn adaptive_interest_rates_calculation() {
+        let model = InterestRateModel::Adaptive(adaptive_model());
+
+        // rate_at_target / curve_steepness at 0%, rate_at_target at the target and
+        // rate_at_target * curve_steepness at 100%
+        assert_eq!(model.get_borrow_rate(Decimal::zero()).unwrap(), Decimal::percent(1));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(45)).unwrap(), Decimal::permille(25));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(90)).unwrap(), Decimal::percent(4));
+        assert_eq!(model.get_borrow_rate(Decimal::percent(95)).unwrap(), Decimal::percent(10));
+        assert_eq!(model.get_borrow_rate(Decimal::one()).unwrap(), Decimal::percent(16));
+    }
+
+    #[test]
+    fn adaptive_rate_at_target_adjusts_over_time() {
+        let mut model = adaptive_model();
+
+        // first update only records the utilization rate
+        model.update(Decimal::one(), 1_000).unwrap();
+        assert_eq!(model.rate_at_target, Decimal::percent(4));
+        assert_eq!(model.utilization_rate, Decimal::one());
+        assert_eq!(model.last_updated, 1_000);
+
+        // 100_000 seconds at 100% utilization: factor = 1 + 0.000001 * 1 * 100_000 = 1.1
+        model.update(Decimal::percent(45), 101_000).unwrap();
+        assert_eq!(model.rate_at_target, Decimal::permille(44));
+
+        // 100_000 seconds at 45% utilization (error 0.5): factor = 1.05
+        model.update(Decimal::percent(90), 201_000).unwrap();
+        assert_eq!(model.rate_at_target, Decimal::permille(44) / Decimal::percent(105));
+
+        // no adjustment at the target utilization
+        let rate_at_target = model.rate_at_target;
+        model.update(Decimal::percent(90), 301_000).unwrap();
+        assert_eq!(model.rate_at_target, rate_at_target);
+
+        // clamped to the max rate at target
+        model.update(Decimal::one(), 401_000).unwrap();
+        model.update(Decimal::one(), 1_000_000_000).unwrap();
+        assert_eq!(model.rate_at_target, model.max_rate_at_target);
+    }
+
+    #[test]
+    fn adaptive_model_updated_with_market() {
+        let mut market = Market {
+            interest_rate_model: InterestRateModel::Adaptive(adaptive_model()),
+            indexes_last_updated: 1_000,
+            ..Default::default()
+        };
+        market.update_interest_rates(Decimal::one()).unwrap();
+        assert_eq!(market.borrow_rate, Decimal::percent(16));
+
+        market.indexes_last_updated = 101_000;
+        market.update_interest_rates(Decimal::one()).unwrap();
+        assert_eq!(market.borrow_rate, Decimal::permille(44) * Decimal::percent(400));
+    }
 }
@@ -29,6 +29,11 @@ pub struct Market {
     pub collateral_total_scaled: Uint128,
     /// Total debt scaled for the market's currency
     pub debt_total_scaled: Uint128,
+
+    /// Portion of a flash loaned amount charged as fee, split between depositors and the protocol
+    /// by the reserve factor
+    #[serde(default)]
+    pub flash_loan_fee_rate: Decimal,
 }
 
 impl Default for Market {
@@ -44,6 +49,7 @@ impl Default for Market {
             collateral_total_scaled: Uint128::zero(),
             debt_total_scaled: Uint128::zero(),
             interest_rate_model: InterestRateModel::default(),
+            flash_loan_fee_rate: Decimal::zero(),
         }
     }
 }
@@ -51,13 +57,17 @@ impl Default for Market {
 impl Market {
     pub fn validate(&self) -> Result<(), ValidationError> {
         decimal_param_lt_one(self.reserve_factor, "reserve_factor")?;
+        decimal_param_lt_one(self.flash_loan_fee_rate, "flash_loan_fee_rate")?;
 
         self.interest_rate_model.validate()?;
 
         Ok(())
     }
 
+    /// Has to be called after the indexes are updated to the current time, so that models changing
+    /// over time (adaptive) are updated with the right timestamp
     pub fn update_interest_rates(&mut self, current_utilization_rate: Decimal) -> StdResult<()> {
+        self.interest_rate_model.update(current_utilization_rate, self.indexes_last_updated)?;
         self.borrow_rate = self.interest_rate_model.get_borrow_rate(current_utilization_rate)?;
 
         self.liquidity_rate = self.interest_rate_model.get_liquidity_rate(
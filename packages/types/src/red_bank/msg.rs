@@ -1,5 +1,5 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Binary, Coin, Decimal, Uint128};
 use mars_owner::OwnerUpdate;
 
 use crate::red_bank::InterestRateModel;
@@ -102,6 +102,30 @@ pub enum ExecuteMsg {
         /// Option to enable (true) / disable (false) asset as collateral
         enable: bool,
     },
+
+    /// Lend out coins without collateral. The coins are sent to the caller (a contract) together
+    /// with the `callback` message. The principal plus the market's flash loan fee has to be
+    /// returned through `RepayFlashLoan` within the same transaction.
+    FlashLoan {
+        /// Coins to borrow
+        coins: Vec<Coin>,
+        /// Message executed on the caller's contract once the coins are sent
+        callback: Binary,
+    },
+
+    /// Return flash loaned coins. Coins used to repay must be sent in the transaction this call
+    /// is made.
+    RepayFlashLoan {},
+
+    /// Assert that the flash loan was repaid and distribute the fees (only callable by the
+    /// contract itself)
+    FinalizeFlashLoan {},
+
+    /// Push fresh Pyth prices (VAAs) to the Pyth contract. The update fee has to be sent with
+    /// the message, see the Pyth contract's `GetUpdateFee` query.
+    UpdatePythPrices {
+        vaa_data: Vec<Binary>,
+    },
 }
 
 #[cw_serde]
@@ -116,6 +140,9 @@ pub struct InitOrUpdateAssetParams {
 
     /// Interest rate strategy to calculate borrow_rate and liquidity_rate
     pub interest_rate_model: Option<InterestRateModel>,
+
+    /// Portion of a flash loaned amount charged as fee. Defaults to zero when initializing.
+    pub flash_loan_fee_rate: Option<Decimal>,
 }
 
 /// Migrate from V1 to V2, only owner can call
@@ -217,6 +244,16 @@ pub enum QueryMsg {
         account_id: Option<String>,
     },
 
+    /// Estimate the liquidation of a user repaying up to `debt_amount`, without executing it.
+    /// Fails the same way the `Liquidate` execute message would.
+    #[returns(crate::liquidation::EstimateLiquidationResponse)]
+    EstimateLiquidation {
+        user: String,
+        collateral_denom: String,
+        debt_denom: String,
+        debt_amount: Uint128,
+    },
+
     /// Get liquidity scaled amount for a given underlying asset amount.
     /// (i.e: how much scaled collateral is added if the given amount is deposited)
     #[returns(Uint128)]
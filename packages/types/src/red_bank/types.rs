@@ -36,6 +36,25 @@ pub struct Debt {
     pub uncollateralized: bool,
 }
 
+/// Coin lent out by an active flash loan
+#[cw_serde]
+pub struct FlashLoan {
+    pub denom: String,
+    /// Amount sent to the flash loan receiver
+    pub amount: Uint128,
+    /// Fee owed on top of the lent amount
+    pub fee: Uint128,
+    /// Amount returned so far
+    pub repaid: Uint128,
+}
+
+impl FlashLoan {
+    /// Amount still to be returned, including the fee
+    pub fn outstanding(&self) -> Uint128 {
+        self.amount.saturating_add(self.fee).saturating_sub(self.repaid)
+    }
+}
+
 #[cw_serde]
 pub enum UserHealthStatus {
     NotBorrowing,